@@ -0,0 +1,67 @@
+//! Compare reading a TCP stream into buffers from a `BufPool` against
+//! allocating a new `Vec` for every read.
+
+use std::time::Instant;
+
+use monoio::{
+    buf::{BufPool, IoBuf},
+    io::{AsyncReadRent, AsyncWriteRentExt},
+    net::{TcpListener, TcpStream},
+};
+
+const TOTAL: usize = 1024 * 1024 * 1024;
+const CHUNK: usize = 64 * 1024;
+
+#[monoio::main(entries = 512, timer_enabled = false)]
+async fn main() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    monoio::spawn(async move {
+        let data = vec![b'x'; CHUNK];
+        loop {
+            let (mut conn, _) = listener.accept().await.unwrap();
+            let data = data.clone();
+            monoio::spawn(async move {
+                let mut buf = data;
+                let mut sent = 0;
+                while sent < TOTAL {
+                    let (res, b) = conn.write_all(buf).await;
+                    res.unwrap();
+                    buf = b;
+                    sent += buf.bytes_init();
+                }
+            });
+        }
+    });
+
+    let mut stream = TcpStream::connect(addr).await.unwrap();
+    let begin = Instant::now();
+    let mut received = 0;
+    while received < TOTAL {
+        let (res, buf) = stream.read(Vec::with_capacity(CHUNK)).await;
+        received += res.unwrap();
+        drop(buf);
+    }
+    report("vec per read", begin);
+
+    let pool = BufPool::new(CHUNK, 4);
+    println!("pool registered: {}", pool.is_registered());
+    let mut stream = TcpStream::connect(addr).await.unwrap();
+    let begin = Instant::now();
+    let mut received = 0;
+    while received < TOTAL {
+        let (res, buf) = stream.read(pool.get().await).await;
+        received += res.unwrap();
+        drop(buf);
+    }
+    report("pooled", begin);
+}
+
+fn report(name: &str, begin: Instant) {
+    let elapsed = begin.elapsed();
+    let mb = TOTAL as f64 / 1024.0 / 1024.0;
+    println!(
+        "{name}: {mb} MiB in {elapsed:?}, {:.2} MiB/s",
+        mb / elapsed.as_secs_f64()
+    );
+}
@@ -0,0 +1,52 @@
+//! Write a file with direct IO in 4 KiB aligned blocks, then read it back,
+//! e.g. `cargo run --example direct-io /var/tmp/direct.bin`.
+//!
+//! The file must be on a file system supporting `O_DIRECT`, tmpfs does not
+//! support it on older kernels.
+
+use monoio::{buf::AlignedBuf, fs::OpenOptions};
+
+const BLOCK: usize = 4096;
+const BLOCKS: usize = 256;
+
+#[monoio::main]
+async fn main() {
+    let path = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| "direct_io.bin".to_string());
+    let file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .direct(true)
+        .open(&path)
+        .await
+        .expect("unable to open the file with O_DIRECT");
+
+    let mut buf = AlignedBuf::new(BLOCK, BLOCK);
+    for i in 0..BLOCKS {
+        buf.clear();
+        buf.put_slice(&[i as u8; BLOCK]);
+        let (res, b) = file.write_at(buf, (i * BLOCK) as u64).await;
+        assert_eq!(res.unwrap(), BLOCK);
+        buf = b;
+    }
+    file.sync_all().await.unwrap();
+    println!("wrote {} bytes to {path}", BLOCKS * BLOCK);
+
+    for i in 0..BLOCKS {
+        let (res, b) = file.read_at(buf, (i * BLOCK) as u64).await;
+        assert_eq!(res.unwrap(), BLOCK);
+        assert!(b.iter().all(|&x| x == i as u8));
+        buf = b;
+    }
+    println!("read back and verified {BLOCKS} blocks");
+
+    // A misaligned offset is rejected before it is submitted.
+    let (res, _) = file.read_at(buf, 100).await;
+    println!("read at offset 100: {:?}", res.map_err(|e| e.kind()));
+
+    file.close().await.unwrap();
+    std::fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,63 @@
+//! A thread-per-core echo server.
+//!
+//! Every thread runs its own runtime with its own listener bound to the same
+//! address with `SO_REUSEPORT`, so the kernel shards the connections across
+//! the threads and nothing is shared between them.
+//!
+//! Run the example and `nc 127.0.0.1 50002` in other shells.
+
+use monoio::{
+    io::{AsyncReadRent, AsyncWriteRentExt},
+    net::{ListenerOpts, TcpListener, TcpStream},
+};
+
+const ADDR: &str = "127.0.0.1:50002";
+
+fn main() {
+    let cores = std::thread::available_parallelism().map_or(1, |n| n.get());
+    let threads: Vec<_> = (0..cores)
+        .map(|core| std::thread::spawn(move || serve(core)))
+        .collect();
+    for thread in threads {
+        thread.join().unwrap();
+    }
+}
+
+fn serve(core: usize) {
+    // Pinning is best-effort, e.g. it is not supported on macOS.
+    let _ = monoio::utils::bind_to_cpu_set(Some(core));
+    let mut rt = monoio::RuntimeBuilder::<monoio::FusionDriver>::new()
+        .build()
+        .unwrap();
+    rt.block_on(async move {
+        let opts = ListenerOpts::new().reuse_port(true).backlog(1024);
+        let listener = TcpListener::bind_with_config(ADDR, &opts).unwrap();
+        println!("core {core} listening on {ADDR}");
+        loop {
+            match listener.accept().await {
+                Ok((stream, addr)) => {
+                    println!("core {core} accepted a connection from {addr}");
+                    monoio::spawn(echo(stream));
+                }
+                Err(e) => {
+                    println!("core {core} failed to accept: {e}");
+                    return;
+                }
+            }
+        }
+    });
+}
+
+async fn echo(mut stream: TcpStream) -> std::io::Result<()> {
+    let mut buf: Vec<u8> = Vec::with_capacity(8 * 1024);
+    let mut res;
+    loop {
+        (res, buf) = stream.read(buf).await;
+        if res? == 0 {
+            return Ok(());
+        }
+        (res, buf) = stream.write_all(buf).await;
+        res?;
+        buf.clear();
+    }
+}
@@ -0,0 +1,76 @@
+//! Ping a host with ICMP echo requests, e.g. `cargo run --example ping
+//! 127.0.0.1`.
+//!
+//! It uses an ICMP datagram socket if the group of the process is allowed by
+//! `net.ipv4.ping_group_range`, and a raw socket otherwise, which needs root
+//! or `CAP_NET_RAW`.
+
+use std::{
+    net::{IpAddr, Ipv4Addr},
+    time::{Duration, Instant},
+};
+
+use monoio::net::IcmpSocket;
+
+const COUNT: u16 = 4;
+
+#[monoio::main(timer_enabled = true)]
+async fn main() {
+    let host: IpAddr = std::env::args()
+        .nth(1)
+        .map(|host| host.parse().expect("invalid address"))
+        .unwrap_or(Ipv4Addr::LOCALHOST.into());
+    let IpAddr::V4(_) = host else {
+        panic!("only IPv4 is supported by the example");
+    };
+    let (socket, raw) = match IcmpSocket::new_v4() {
+        Ok(socket) => (socket, false),
+        Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => (
+            IcmpSocket::new_raw_v4().expect("raw socket requires root or CAP_NET_RAW"),
+            true,
+        ),
+        Err(e) => panic!("create ICMP socket failed: {e}"),
+    };
+
+    for seq in 0..COUNT {
+        let begin = Instant::now();
+        socket.send_to(echo_request(seq), host).await.0.unwrap();
+        loop {
+            let (res, buf) = socket.recv_from(vec![0; 1024]).await;
+            let (n, from) = res.unwrap();
+            // A raw socket receives the IP header and all the ICMP messages.
+            let offset = if raw {
+                usize::from(buf[0] & 0x0f) * 4
+            } else {
+                0
+            };
+            let msg = &buf[offset..n];
+            if from == host && msg[0] == 0 && msg[6..8] == seq.to_be_bytes() {
+                println!(
+                    "{} bytes from {from}: icmp_seq={seq} time={:?}",
+                    msg.len(),
+                    begin.elapsed()
+                );
+                break;
+            }
+        }
+        monoio::time::sleep(Duration::from_secs(1)).await;
+    }
+}
+
+fn echo_request(seq: u16) -> Vec<u8> {
+    let mut msg = vec![8, 0, 0, 0];
+    // The kernel replaces the identifier of a datagram socket with its port.
+    msg.extend_from_slice(&std::process::id().to_be_bytes()[2..]);
+    msg.extend_from_slice(&seq.to_be_bytes());
+    msg.extend_from_slice(&[0; 56]);
+    let mut sum = msg
+        .chunks(2)
+        .map(|c| u32::from(u16::from_be_bytes([c[0], c[1]])))
+        .sum::<u32>();
+    while sum > 0xffff {
+        sum = (sum & 0xffff) + (sum >> 16);
+    }
+    msg[2..4].copy_from_slice(&(!(sum as u16)).to_be_bytes());
+    msg
+}
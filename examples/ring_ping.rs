@@ -0,0 +1,29 @@
+//! Compare the round trip of a `Nop` op on rings entered with the registered
+//! ring fd against the ones entered with the fd.
+
+use std::time::{Duration, Instant};
+
+use monoio::{IoUringDriver, RuntimeBuilder};
+
+const PINGS: u32 = 1_000_000;
+
+fn main() {
+    for registered in [false, true] {
+        let mut rt = RuntimeBuilder::<IoUringDriver>::new()
+            .uring_register_ring_fd(registered)
+            .build()
+            .unwrap();
+        let (elapsed, rtt) = rt.block_on(async {
+            let begin = Instant::now();
+            let mut rtt = Duration::ZERO;
+            for _ in 0..PINGS {
+                rtt += monoio::utils::ring_ping().await.unwrap();
+            }
+            (begin.elapsed(), rtt)
+        });
+        println!(
+            "registered ring fd {registered}: {PINGS} pings in {elapsed:?}, {:?} per ping",
+            rtt / PINGS
+        );
+    }
+}
@@ -0,0 +1,75 @@
+//! Compare sending a file to a TCP stream with `File::send_to` against reading
+//! it into a buffer and writing the buffer.
+
+use std::time::Instant;
+
+use monoio::{
+    buf::IoBuf,
+    fs::File,
+    io::{AsyncReadRent, AsyncWriteRentExt},
+    net::{TcpListener, TcpStream},
+};
+
+const FILE_SIZE: usize = 64 * 1024 * 1024;
+const ROUNDS: usize = 16;
+const CHUNK: usize = 64 * 1024;
+
+#[monoio::main(entries = 512, timer_enabled = false)]
+async fn main() {
+    let path = std::env::temp_dir().join("monoio-sendfile-example");
+    std::fs::write(&path, vec![b'x'; FILE_SIZE]).unwrap();
+    let file = File::open(&path).await.unwrap();
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    monoio::spawn(async move {
+        loop {
+            let (mut conn, _) = listener.accept().await.unwrap();
+            monoio::spawn(async move {
+                let mut buf = vec![0; CHUNK];
+                loop {
+                    let (res, b) = conn.read(buf).await;
+                    buf = b;
+                    if res.unwrap_or(0) == 0 {
+                        break;
+                    }
+                }
+            });
+        }
+    });
+
+    let mut stream = TcpStream::connect(addr).await.unwrap();
+    let begin = Instant::now();
+    for _ in 0..ROUNDS {
+        let n = file.send_to(&mut stream, 0, FILE_SIZE).await.unwrap();
+        assert_eq!(n, FILE_SIZE);
+    }
+    report("send_to", begin);
+
+    let mut stream = TcpStream::connect(addr).await.unwrap();
+    let begin = Instant::now();
+    let mut buf = Vec::with_capacity(CHUNK);
+    for _ in 0..ROUNDS {
+        let mut pos = 0;
+        while pos < FILE_SIZE {
+            let (res, b) = file.read_at(buf, pos as u64).await;
+            let n = res.unwrap();
+            let (res, slice) = stream.write_all(b.slice(0..n)).await;
+            res.unwrap();
+            buf = slice.into_inner();
+            pos += n;
+        }
+    }
+    report("read + write", begin);
+
+    let _ = std::fs::remove_file(&path);
+}
+
+fn report(name: &str, begin: Instant) {
+    let elapsed = begin.elapsed();
+    let mb = (FILE_SIZE * ROUNDS) as f64 / 1024.0 / 1024.0;
+    println!(
+        "{name}: {mb} MiB in {elapsed:?}, {:.2} MiB/s",
+        mb / elapsed.as_secs_f64()
+    );
+}
@@ -0,0 +1,80 @@
+//! An example TCP proxy which mirrors the inbound traffic into a file with tee
+//! and splice, the data is never copied to userspace.
+//!
+//! Run a server on TARGET_ADDRESS, then connect to LISTEN_ADDRESS.
+
+use monoio::{
+    fs::File,
+    io::{
+        as_fd::{AsReadFd, AsWriteFd},
+        new_pipe, zero_copy, Pipe, Splitable,
+    },
+    net::{TcpListener, TcpStream},
+};
+
+const LISTEN_ADDRESS: &str = "127.0.0.1:50005";
+const TARGET_ADDRESS: &str = "127.0.0.1:50006";
+const LOG_PATH: &str = "tee.log";
+const PIPE_SIZE: u32 = 64 * 1024;
+
+#[monoio::main(entries = 512, timer_enabled = false)]
+async fn main() {
+    let listener = TcpListener::bind(LISTEN_ADDRESS)
+        .unwrap_or_else(|_| panic!("[Server] Unable to bind to {LISTEN_ADDRESS}"));
+    loop {
+        let Ok((in_conn, _addr)) = listener.accept().await else {
+            eprintln!("accept connection failed");
+            return;
+        };
+        let Ok(out_conn) = TcpStream::connect(TARGET_ADDRESS).await else {
+            eprintln!("dial outbound connection failed");
+            continue;
+        };
+        let Ok(log) = File::create(LOG_PATH).await else {
+            eprintln!("create log file failed");
+            continue;
+        };
+        monoio::spawn(async move {
+            let (mut in_r, mut in_w) = in_conn.into_split();
+            let (mut out_r, mut out_w) = out_conn.into_split();
+            let (res, _) = monoio::join!(
+                tee_one_direction(&mut in_r, &mut out_w, log),
+                zero_copy(&mut out_r, &mut in_w),
+            );
+            match res {
+                Ok(n) => println!("relay finished, {n} bytes mirrored to {LOG_PATH}"),
+                Err(e) => eprintln!("relay failed: {e}"),
+            }
+        });
+    }
+}
+
+/// Forward the data from `from` to `to`, and duplicate it into `log`.
+async fn tee_one_direction<FROM: AsReadFd, TO: AsWriteFd>(
+    from: &mut FROM,
+    to: &mut TO,
+    mut log: File,
+) -> std::io::Result<u64> {
+    let (mut pr, mut pw) = new_pipe()?;
+    let (mut log_r, mut log_w) = new_pipe()?;
+    let mut mirrored = 0;
+    loop {
+        let n = pw.splice_from(from, PIPE_SIZE).await?;
+        if n == 0 {
+            return Ok(mirrored);
+        }
+        // The log pipe is drained every round, so it has enough room for all
+        // the data in the forwarding pipe.
+        let teed = pr.tee_to(&mut log_w, n).await?;
+        drain(&mut log_r, &mut log, teed).await?;
+        drain(&mut pr, to, n).await?;
+        mirrored += teed as u64;
+    }
+}
+
+async fn drain<T: AsWriteFd>(pipe: &mut Pipe, to: &mut T, mut len: u32) -> std::io::Result<()> {
+    while len > 0 {
+        len -= pipe.splice_to(to, len).await?;
+    }
+    Ok(())
+}
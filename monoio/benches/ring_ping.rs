@@ -0,0 +1,35 @@
+//! Round trip of each driver measured with `ring_ping`, run with
+//! `cargo bench --bench ring_ping`.
+
+use std::time::{Duration, Instant};
+
+const PINGS: u32 = 100_000;
+
+fn bench<D: monoio::Buildable + monoio::Driver>(name: &str) {
+    let mut rt = match D::build(monoio::RuntimeBuilder::<D>::new()) {
+        Ok(rt) => rt,
+        Err(e) => {
+            println!("{name}: skipped, {e}");
+            return;
+        }
+    };
+    let (elapsed, rtt) = rt.block_on(async {
+        let begin = Instant::now();
+        let mut rtt = Duration::ZERO;
+        for _ in 0..PINGS {
+            rtt += monoio::utils::ring_ping().await.unwrap();
+        }
+        (begin.elapsed(), rtt)
+    });
+    println!(
+        "{name}: {PINGS} pings in {elapsed:?}, {:?} per ping",
+        rtt / PINGS
+    );
+}
+
+fn main() {
+    #[cfg(feature = "legacy")]
+    bench::<monoio::LegacyDriver>("legacy");
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    bench::<monoio::IoUringDriver>("uring");
+}
@@ -118,6 +118,12 @@ where
     join
 }
 
+/// Returns true if a thread pool is attached to the current runtime.
+pub(crate) fn has_thread_pool() -> bool {
+    crate::runtime::CURRENT
+        .with(|inner| matches!(inner.blocking_handle, BlockingHandle::Attached(_)))
+}
+
 /// DefaultThreadPool is a simple wrapped `threadpool::ThreadPool` that implement
 /// `monoio::blocking::ThreadPool`. You may use this implementation, or you can use your own thread
 /// pool implementation.
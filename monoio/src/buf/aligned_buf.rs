@@ -0,0 +1,151 @@
+use std::{
+    alloc::{self, Layout},
+    fmt,
+    ops::{Deref, DerefMut},
+    ptr::NonNull,
+};
+
+use super::{IoBuf, IoBufMut};
+
+/// A heap buffer whose address is aligned to a given power of two, e.g. for
+/// the direct IO of files opened with
+/// [`OpenOptions::direct`](crate::fs::OpenOptions::direct), which requires the
+/// memory to be aligned to the logical block size of the device.
+///
+/// The memory is zeroed on allocation. It derefs to the initialized part like
+/// [`PooledBuf`](super::PooledBuf).
+pub struct AlignedBuf {
+    ptr: NonNull<u8>,
+    layout: Layout,
+    len: usize,
+}
+
+impl AlignedBuf {
+    /// Allocate a buffer of `capacity` bytes aligned to `align`.
+    ///
+    /// # Panics
+    /// Panics if `capacity` is zero, `align` is not a power of two, or
+    /// `capacity` overflows when rounded up to `align`.
+    pub fn new(capacity: usize, align: usize) -> Self {
+        assert!(capacity != 0, "AlignedBuf capacity must not be zero");
+        let layout = Layout::from_size_align(capacity, align).expect("invalid AlignedBuf layout");
+        // Safety: the size of the layout is not zero.
+        let ptr = unsafe { alloc::alloc_zeroed(layout) };
+        let ptr = NonNull::new(ptr).unwrap_or_else(|| alloc::handle_alloc_error(layout));
+        Self {
+            ptr,
+            layout,
+            len: 0,
+        }
+    }
+
+    /// Total length of the buffer.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.layout.size()
+    }
+
+    /// Alignment of the address of the buffer.
+    #[inline]
+    pub fn alignment(&self) -> usize {
+        self.layout.align()
+    }
+
+    /// Clear the buffer, the capacity is kept.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    /// Set the length of the initialized part. The memory is zeroed on
+    /// allocation, so it is always initialized.
+    ///
+    /// # Panics
+    /// Panics if `len` is larger than the capacity.
+    #[inline]
+    pub fn set_len(&mut self, len: usize) {
+        assert!(len <= self.capacity(), "AlignedBuf capacity exceeded");
+        self.len = len;
+    }
+
+    /// Append data to the buffer.
+    ///
+    /// # Panics
+    /// Panics if there is not enough remaining capacity.
+    pub fn put_slice(&mut self, src: &[u8]) {
+        assert!(
+            self.capacity() - self.len >= src.len(),
+            "AlignedBuf capacity exceeded"
+        );
+        unsafe {
+            std::ptr::copy_nonoverlapping(src.as_ptr(), self.ptr.as_ptr().add(self.len), src.len());
+        }
+        self.len += src.len();
+    }
+}
+
+// Safety: the buffer owns the memory like a `Vec<u8>`.
+unsafe impl Send for AlignedBuf {}
+unsafe impl Sync for AlignedBuf {}
+
+unsafe impl IoBuf for AlignedBuf {
+    #[inline]
+    fn read_ptr(&self) -> *const u8 {
+        self.ptr.as_ptr()
+    }
+
+    #[inline]
+    fn bytes_init(&self) -> usize {
+        self.len
+    }
+}
+
+unsafe impl IoBufMut for AlignedBuf {
+    #[inline]
+    fn write_ptr(&mut self) -> *mut u8 {
+        self.ptr.as_ptr()
+    }
+
+    #[inline]
+    fn bytes_total(&mut self) -> usize {
+        self.capacity()
+    }
+
+    #[inline]
+    unsafe fn set_init(&mut self, pos: usize) {
+        self.len = pos;
+    }
+}
+
+impl Deref for AlignedBuf {
+    type Target = [u8];
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        unsafe { std::slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl DerefMut for AlignedBuf {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        unsafe { std::slice::from_raw_parts_mut(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl fmt::Debug for AlignedBuf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AlignedBuf")
+            .field("len", &self.len)
+            .field("capacity", &self.capacity())
+            .field("alignment", &self.alignment())
+            .finish()
+    }
+}
+
+impl Drop for AlignedBuf {
+    fn drop(&mut self) {
+        // Safety: it is allocated with the same layout.
+        unsafe { alloc::dealloc(self.ptr.as_ptr(), self.layout) };
+    }
+}
@@ -0,0 +1,336 @@
+use std::{
+    cell::RefCell,
+    collections::VecDeque,
+    fmt,
+    future::poll_fn,
+    ops::{Deref, DerefMut},
+    ptr::NonNull,
+    rc::Rc,
+    task::{Poll, Waker},
+};
+
+use super::{IoBuf, IoBufMut};
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+use crate::driver;
+
+/// A pool of fixed-size buffers which are recycled instead of allocated for
+/// every read.
+///
+/// It works on all drivers. When created in an io_uring runtime, the buffers
+/// are also registered to the ring if there is no other registered set, see
+/// [`BufPool::is_registered`]. A buffer goes back to the pool when dropped.
+///
+/// It is cheap to clone, the clones refer to the same pool.
+#[derive(Clone)]
+pub struct BufPool {
+    inner: Rc<PoolInner>,
+}
+
+pub(super) struct PoolInner {
+    storage: Storage,
+    count: u16,
+    buf_len: usize,
+    state: RefCell<PoolState>,
+}
+
+pub(super) enum Storage {
+    // Leaked from a boxed slice, freed when the pool is dropped.
+    Heap(NonNull<[u8]>),
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    Registered(driver::FixedBufs),
+}
+
+struct PoolState {
+    free: Vec<u16>,
+    // Tasks waiting in `get`, in FIFO order, one slot for each.
+    waiters: VecDeque<(u64, Waker)>,
+    next_waiter: u64,
+}
+
+impl BufPool {
+    /// Create a pool of `count` buffers with `buf_size` bytes each.
+    ///
+    /// In an io_uring runtime the buffers are registered to the ring when
+    /// possible, otherwise they are plain heap memory.
+    pub fn new(buf_size: usize, count: u16) -> Self {
+        #[cfg(all(target_os = "linux", feature = "iouring"))]
+        if driver::CURRENT.is_set() {
+            if let Ok(bufs) = driver::FixedBufs::new(count, buf_size) {
+                return Self::with_storage(Storage::Registered(bufs), count, buf_size);
+            }
+        }
+        let len = (count as usize)
+            .checked_mul(buf_size)
+            .expect("BufPool size overflow");
+        let bufs = NonNull::from(Box::leak(vec![0; len].into_boxed_slice()));
+        Self::with_storage(Storage::Heap(bufs), count, buf_size)
+    }
+
+    pub(super) fn with_storage(storage: Storage, count: u16, buf_len: usize) -> Self {
+        Self {
+            inner: Rc::new(PoolInner {
+                storage,
+                count,
+                buf_len,
+                state: RefCell::new(PoolState {
+                    free: (0..count).rev().collect(),
+                    waiters: VecDeque::new(),
+                    next_waiter: 0,
+                }),
+            }),
+        }
+    }
+
+    /// Take a buffer from the pool, return None if all buffers are in use.
+    pub fn try_get(&self) -> Option<PooledBuf> {
+        let index = self.inner.state.borrow_mut().free.pop()?;
+        Some(PooledBuf {
+            pool: self.inner.clone(),
+            index,
+            len: 0,
+        })
+    }
+
+    /// Take a buffer from the pool, wait until one is given back if all
+    /// buffers are in use.
+    pub async fn get(&self) -> PooledBuf {
+        let mut waiter = Waiter {
+            pool: &self.inner,
+            key: None,
+        };
+        poll_fn(|cx| {
+            let mut state = self.inner.state.borrow_mut();
+            if let Some(key) = waiter.key {
+                if let Some((_, waker)) = state.waiters.iter_mut().find(|(k, _)| *k == key) {
+                    // Not notified yet, only update the waker in the slot.
+                    if !waker.will_wake(cx.waker()) {
+                        *waker = cx.waker().clone();
+                    }
+                    return Poll::Pending;
+                }
+            }
+            match state.free.pop() {
+                Some(index) => {
+                    waiter.key = None;
+                    Poll::Ready(PooledBuf {
+                        pool: self.inner.clone(),
+                        index,
+                        len: 0,
+                    })
+                }
+                None => {
+                    match waiter.key {
+                        // Notified but the buffer is taken by `try_get`, wait
+                        // again ahead of the others.
+                        Some(key) => state.waiters.push_front((key, cx.waker().clone())),
+                        None => {
+                            let key = state.next_waiter;
+                            state.next_waiter += 1;
+                            state.waiters.push_back((key, cx.waker().clone()));
+                            waiter.key = Some(key);
+                        }
+                    }
+                    Poll::Pending
+                }
+            }
+        })
+        .await
+    }
+
+    /// Number of buffers in the pool.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.inner.count as usize
+    }
+
+    /// Length of each buffer.
+    #[inline]
+    pub fn buf_len(&self) -> usize {
+        self.inner.buf_len
+    }
+
+    /// Number of buffers not in use.
+    #[inline]
+    pub fn available(&self) -> usize {
+        self.inner.state.borrow().free.len()
+    }
+
+    /// Whether the buffers are registered to the io_uring driver.
+    #[inline]
+    pub fn is_registered(&self) -> bool {
+        !matches!(self.inner.storage, Storage::Heap(_))
+    }
+}
+
+impl fmt::Debug for BufPool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BufPool")
+            .field("capacity", &self.capacity())
+            .field("buf_len", &self.buf_len())
+            .field("available", &self.available())
+            .field("registered", &self.is_registered())
+            .finish()
+    }
+}
+
+impl PoolInner {
+    #[inline]
+    fn buf_ptr(&self, index: u16) -> *mut u8 {
+        match &self.storage {
+            Storage::Heap(bufs) => {
+                debug_assert!(index < self.count);
+                unsafe { (bufs.as_ptr() as *mut u8).add(index as usize * self.buf_len) }
+            }
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            Storage::Registered(bufs) => bufs.buf_ptr(index),
+        }
+    }
+}
+
+impl PoolState {
+    // Wake the first waiter if there is a free buffer for it.
+    fn notify_one(&mut self) {
+        if self.free.is_empty() {
+            return;
+        }
+        if let Some((_, waker)) = self.waiters.pop_front() {
+            waker.wake();
+        }
+    }
+}
+
+// Slot of a pending `BufPool::get`.
+struct Waiter<'a> {
+    pool: &'a PoolInner,
+    key: Option<u64>,
+}
+
+impl Drop for Waiter<'_> {
+    fn drop(&mut self) {
+        let Some(key) = self.key else {
+            return;
+        };
+        let mut state = self.pool.state.borrow_mut();
+        match state.waiters.iter().position(|(k, _)| *k == key) {
+            Some(pos) => {
+                state.waiters.remove(pos);
+            }
+            // Notified but gone before taking the buffer, pass it on.
+            None => state.notify_one(),
+        }
+    }
+}
+
+impl Drop for PoolInner {
+    fn drop(&mut self) {
+        #[allow(irrefutable_let_patterns)]
+        if let Storage::Heap(bufs) = self.storage {
+            unsafe { drop(Box::from_raw(bufs.as_ptr())) };
+        }
+    }
+}
+
+/// A buffer taken from [`BufPool`].
+///
+/// It derefs to the initialized part, and goes back to the pool on drop.
+pub struct PooledBuf {
+    pool: Rc<PoolInner>,
+    pub(super) index: u16,
+    len: usize,
+}
+
+impl PooledBuf {
+    /// Total length of the buffer.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.pool.buf_len
+    }
+
+    /// Clear the buffer, the capacity is kept.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    /// Append data to the buffer.
+    ///
+    /// # Panics
+    /// Panics if there is not enough remaining capacity.
+    pub fn put_slice(&mut self, src: &[u8]) {
+        assert!(
+            self.capacity() - self.len >= src.len(),
+            "PooledBuf capacity exceeded"
+        );
+        unsafe {
+            std::ptr::copy_nonoverlapping(
+                src.as_ptr(),
+                self.pool.buf_ptr(self.index).add(self.len),
+                src.len(),
+            );
+        }
+        self.len += src.len();
+    }
+}
+
+unsafe impl IoBuf for PooledBuf {
+    #[inline]
+    fn read_ptr(&self) -> *const u8 {
+        self.pool.buf_ptr(self.index)
+    }
+
+    #[inline]
+    fn bytes_init(&self) -> usize {
+        self.len
+    }
+}
+
+unsafe impl IoBufMut for PooledBuf {
+    #[inline]
+    fn write_ptr(&mut self) -> *mut u8 {
+        self.pool.buf_ptr(self.index)
+    }
+
+    #[inline]
+    fn bytes_total(&mut self) -> usize {
+        self.capacity()
+    }
+
+    #[inline]
+    unsafe fn set_init(&mut self, pos: usize) {
+        self.len = pos;
+    }
+}
+
+impl Deref for PooledBuf {
+    type Target = [u8];
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        unsafe { std::slice::from_raw_parts(self.read_ptr(), self.len) }
+    }
+}
+
+impl DerefMut for PooledBuf {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        unsafe { std::slice::from_raw_parts_mut(self.write_ptr(), self.len) }
+    }
+}
+
+impl fmt::Debug for PooledBuf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PooledBuf")
+            .field("index", &self.index)
+            .field("len", &self.len)
+            .field("capacity", &self.capacity())
+            .finish()
+    }
+}
+
+impl Drop for PooledBuf {
+    fn drop(&mut self) {
+        let mut state = self.pool.state.borrow_mut();
+        state.free.push(self.index);
+        state.notify_one();
+    }
+}
@@ -0,0 +1,98 @@
+use std::{fmt, io, ops::Deref, rc::Rc};
+
+use crate::driver;
+
+/// A ring of fixed-size buffers registered to the io_uring driver of current
+/// thread(requires kernel 5.19+).
+///
+/// Reads issued with the ring do not carry a buffer, the kernel picks one from
+/// the ring when the data arrives. The picked buffer is returned as a
+/// [`ProvidedBuf`] and given back to the ring when it is dropped.
+///
+/// It is cheap to clone, the clones refer to the same ring.
+#[derive(Clone)]
+pub struct BufRing {
+    inner: Rc<driver::BufRing>,
+}
+
+impl BufRing {
+    /// Create a ring of `entries` buffers with `buf_len` bytes each and
+    /// register it to the current runtime.
+    ///
+    /// `entries` must be a power of 2 and not larger than 32768. It must be
+    /// called in an io_uring runtime, an `Unsupported` error is returned on
+    /// legacy driver.
+    pub fn new(entries: u16, buf_len: usize) -> io::Result<Self> {
+        Ok(Self {
+            inner: Rc::new(driver::BufRing::new(entries, buf_len)?),
+        })
+    }
+
+    /// Length of each buffer in the ring.
+    #[inline]
+    pub fn buf_len(&self) -> usize {
+        self.inner.buf_len()
+    }
+
+    #[inline]
+    pub(crate) fn inner(&self) -> &Rc<driver::BufRing> {
+        &self.inner
+    }
+}
+
+impl fmt::Debug for BufRing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BufRing")
+            .field("bgid", &self.inner.bgid())
+            .field("buf_len", &self.inner.buf_len())
+            .finish()
+    }
+}
+
+/// A buffer picked by the kernel from a [`BufRing`].
+///
+/// It derefs to the received data, and the buffer is given back to the ring
+/// on drop.
+pub struct ProvidedBuf {
+    ring: Rc<driver::BufRing>,
+    // None if no buffer is consumed(e.g. EOF)
+    bid: Option<u16>,
+    len: usize,
+}
+
+impl ProvidedBuf {
+    pub(crate) fn new(ring: Rc<driver::BufRing>, bid: Option<u16>, len: usize) -> Self {
+        Self { ring, bid, len }
+    }
+}
+
+impl Deref for ProvidedBuf {
+    type Target = [u8];
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        match self.bid {
+            // Safety: the kernel wrote `len` bytes to the buffer and it is not
+            // recycled until drop.
+            Some(bid) => unsafe { self.ring.buf(bid, self.len) },
+            None => &[],
+        }
+    }
+}
+
+impl fmt::Debug for ProvidedBuf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProvidedBuf")
+            .field("bid", &self.bid)
+            .field("len", &self.len)
+            .finish()
+    }
+}
+
+impl Drop for ProvidedBuf {
+    fn drop(&mut self) {
+        if let Some(bid) = self.bid {
+            self.ring.recycle(bid);
+        }
+    }
+}
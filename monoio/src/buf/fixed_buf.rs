@@ -0,0 +1,167 @@
+use std::{
+    fmt, io,
+    ops::{Deref, DerefMut},
+};
+
+use super::{
+    buf_pool::{BufPool, PooledBuf, Storage},
+    IoBuf, IoBufMut,
+};
+use crate::driver;
+
+/// A pool of buffers registered to the io_uring driver of current thread.
+///
+/// Buffers taken from the pool can be used with fixed ops like
+/// [`File::read_fixed_at`](crate::fs::File::read_fixed_at) and
+/// [`TcpStream::write_fixed`](crate::net::TcpStream::write_fixed), which skip
+/// mapping the buffer for every op. A buffer goes back to the pool when
+/// dropped.
+///
+/// Only one pool can be registered to a runtime at a time. It is cheap to
+/// clone, the clones refer to the same pool.
+#[derive(Clone)]
+pub struct FixedBufPool {
+    pool: BufPool,
+}
+
+impl FixedBufPool {
+    /// Create a pool of `count` buffers with `buf_len` bytes each and register
+    /// it to the current runtime.
+    ///
+    /// It must be called in an io_uring runtime, an `Unsupported` error is
+    /// returned on legacy driver.
+    pub fn new(count: u16, buf_len: usize) -> io::Result<Self> {
+        let bufs = driver::FixedBufs::new(count, buf_len)?;
+        Ok(Self {
+            pool: BufPool::with_storage(Storage::Registered(bufs), count, buf_len),
+        })
+    }
+
+    /// Take a buffer from the pool, return None if all buffers are in use.
+    #[inline]
+    pub fn try_get(&self) -> Option<FixedBuf> {
+        self.pool.try_get().map(|buf| FixedBuf { buf })
+    }
+
+    /// Take a buffer from the pool, wait until one is given back if all
+    /// buffers are in use.
+    #[inline]
+    pub async fn get(&self) -> FixedBuf {
+        FixedBuf {
+            buf: self.pool.get().await,
+        }
+    }
+
+    /// Number of buffers in the pool.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.pool.capacity()
+    }
+
+    /// Length of each buffer.
+    #[inline]
+    pub fn buf_len(&self) -> usize {
+        self.pool.buf_len()
+    }
+}
+
+impl fmt::Debug for FixedBufPool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FixedBufPool")
+            .field("capacity", &self.capacity())
+            .field("buf_len", &self.buf_len())
+            .field("available", &self.pool.available())
+            .finish()
+    }
+}
+
+/// A buffer taken from [`FixedBufPool`].
+///
+/// It derefs to the initialized part, and goes back to the pool on drop.
+pub struct FixedBuf {
+    buf: PooledBuf,
+}
+
+impl FixedBuf {
+    /// Index of the buffer in the registered buffers.
+    #[inline]
+    pub(crate) fn buf_index(&self) -> u16 {
+        self.buf.index
+    }
+
+    /// Total length of the buffer.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.buf.capacity()
+    }
+
+    /// Clear the buffer, the capacity is kept.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.buf.clear();
+    }
+
+    /// Append data to the buffer.
+    ///
+    /// # Panics
+    /// Panics if there is not enough remaining capacity.
+    #[inline]
+    pub fn put_slice(&mut self, src: &[u8]) {
+        self.buf.put_slice(src);
+    }
+}
+
+unsafe impl IoBuf for FixedBuf {
+    #[inline]
+    fn read_ptr(&self) -> *const u8 {
+        self.buf.read_ptr()
+    }
+
+    #[inline]
+    fn bytes_init(&self) -> usize {
+        self.buf.bytes_init()
+    }
+}
+
+unsafe impl IoBufMut for FixedBuf {
+    #[inline]
+    fn write_ptr(&mut self) -> *mut u8 {
+        self.buf.write_ptr()
+    }
+
+    #[inline]
+    fn bytes_total(&mut self) -> usize {
+        self.buf.bytes_total()
+    }
+
+    #[inline]
+    unsafe fn set_init(&mut self, pos: usize) {
+        self.buf.set_init(pos);
+    }
+}
+
+impl Deref for FixedBuf {
+    type Target = [u8];
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.buf
+    }
+}
+
+impl DerefMut for FixedBuf {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.buf
+    }
+}
+
+impl fmt::Debug for FixedBuf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FixedBuf")
+            .field("index", &self.buf.index)
+            .field("len", &self.buf.bytes_init())
+            .field("capacity", &self.capacity())
+            .finish()
+    }
+}
@@ -238,17 +238,12 @@ unsafe impl IoVecBufMut for VecBuf {
     }
 
     unsafe fn set_init(&mut self, mut len: usize) {
-        for (idx, iovec) in self.iovecs.iter_mut().enumerate() {
-            if iovec.iov_len <= len {
-                // set_init all
-                self.raw[idx].set_len(iovec.iov_len);
-                len -= iovec.iov_len;
-            } else {
-                if len > 0 {
-                    self.raw[idx].set_len(len);
-                }
-                break;
-            }
+        // The buffers are filled in order, the ones after the last written
+        // byte hold no data.
+        for (raw, iovec) in self.raw.iter_mut().zip(self.iovecs.iter()) {
+            let n = iovec.iov_len.min(len);
+            raw.set_len(n);
+            len -= n;
         }
     }
 }
@@ -264,17 +259,12 @@ unsafe impl IoVecBufMut for VecBuf {
     }
 
     unsafe fn set_init(&mut self, mut len: usize) {
-        for (idx, wsabuf) in self.wsabufs.iter_mut().enumerate() {
-            if wsabuf.len as usize <= len {
-                // set_init all
-                self.raw[idx].set_len(wsabuf.len as _);
-                len -= wsabuf.len as usize;
-            } else {
-                if len > 0 {
-                    self.raw[idx].set_len(len);
-                }
-                break;
-            }
+        // The buffers are filled in order, the ones after the last written
+        // byte hold no data.
+        for (raw, wsabuf) in self.raw.iter_mut().zip(self.wsabufs.iter()) {
+            let n = (wsabuf.len as usize).min(len);
+            raw.set_len(n);
+            len -= n;
         }
     }
 }
@@ -18,6 +18,22 @@ pub use slice::{IoVecWrapper, IoVecWrapperMut, Slice, SliceMut};
 mod raw_buf;
 pub use raw_buf::{RawBuf, RawBufVectored};
 
+mod buf_pool;
+pub use buf_pool::{BufPool, PooledBuf};
+
+mod aligned_buf;
+pub use aligned_buf::AlignedBuf;
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+mod buf_ring;
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+pub use buf_ring::{BufRing, ProvidedBuf};
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+mod fixed_buf;
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+pub use fixed_buf::{FixedBuf, FixedBufPool};
+
 mod vec_wrapper;
 pub(crate) use vec_wrapper::{read_vec_meta, write_vec_meta};
 
@@ -1,9 +1,14 @@
 use std::{io, marker::PhantomData};
-
 #[cfg(all(target_os = "linux", feature = "iouring"))]
-use crate::driver::IoUringDriver;
+use std::{
+    os::unix::io::{AsRawFd, FromRawFd, OwnedFd, RawFd},
+    sync::{Arc, Mutex},
+};
+
 #[cfg(all(unix, feature = "legacy"))]
 use crate::driver::LegacyDriver;
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+use crate::driver::{AllowedOp, IoUringDriver};
 #[cfg(all(unix, any(feature = "legacy", feature = "iouring")))]
 use crate::utils::thread_id::gen_id;
 use crate::{
@@ -22,6 +27,52 @@ pub struct RuntimeBuilder<D> {
     #[cfg(all(target_os = "linux", feature = "iouring"))]
     urb: io_uring::Builder,
 
+    // zero copy threshold
+    #[cfg(all(target_os = "linux", feature = "iouring", feature = "zero-copy"))]
+    zero_copy_threshold: Option<usize>,
+
+    // registered file table capacity
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    register_files: Option<u32>,
+
+    // fire timers with uring timeout ops
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    precise_timers: bool,
+
+    // kernel side submission polling
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    sqpoll_idle: Option<u32>,
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    sqpoll_cpu: Option<u32>,
+
+    // setup COOP_TASKRUN, SINGLE_ISSUER and DEFER_TASKRUN if supported
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    taskrun_flags: bool,
+
+    // completion queue entries
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    cq_entries: Option<u32>,
+
+    // opcodes treated as unsupported
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    disabled_opcodes: Vec<u8>,
+
+    // ring to share the async workqueue with
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    attach_wq: Option<WqDonor>,
+
+    // opcodes allowed in the ring
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    uring_restrictions: Option<Vec<AllowedOp>>,
+
+    // NAPI busy poll timeout in microseconds and prefer busy poll
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    napi: Option<(u32, bool)>,
+
+    // enter the ring with the registered ring fd
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    register_ring_fd: bool,
+
     // blocking handle
     #[cfg(feature = "sync")]
     blocking_handle: crate::blocking::BlockingHandle,
@@ -48,6 +99,30 @@ impl<T> RuntimeBuilder<T> {
 
             #[cfg(all(target_os = "linux", feature = "iouring"))]
             urb: io_uring::IoUring::builder(),
+            #[cfg(all(target_os = "linux", feature = "iouring", feature = "zero-copy"))]
+            zero_copy_threshold: None,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            register_files: None,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            precise_timers: false,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            sqpoll_idle: None,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            sqpoll_cpu: None,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            taskrun_flags: true,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            cq_entries: None,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            disabled_opcodes: Vec::new(),
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            attach_wq: None,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            uring_restrictions: None,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            napi: None,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            register_ring_fd: true,
 
             #[cfg(feature = "sync")]
             blocking_handle: crate::blocking::BlockingStrategy::Panic.into(),
@@ -115,11 +190,107 @@ impl Buildable for IoUringDriver {
         #[cfg(feature = "sync")]
         let blocking_handle = this.blocking_handle;
 
+        // The flags can not be used with SQPOLL.
+        let taskrun = this.taskrun_flags && this.sqpoll_idle.is_none();
+        let mut urb = this.urb;
+        if let Some(cq_entries) = this.cq_entries {
+            let sq_entries = this.entries.unwrap_or(IoUringDriver::DEFAULT_ENTRIES);
+            if !cq_entries.is_power_of_two() || cq_entries < sq_entries {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!(
+                        "io_uring cq entries {cq_entries} must be a power of two and no less \
+                         than sq entries {sq_entries}"
+                    ),
+                ));
+            }
+            urb.setup_cqsize(cq_entries);
+        }
+        if let Some(idle) = this.sqpoll_idle {
+            urb.setup_sqpoll(idle);
+            if let Some(cpu) = this.sqpoll_cpu {
+                urb.setup_sqpoll_cpu(cpu);
+            }
+        }
+        // The ring is enabled after the restrictions are registered.
+        if this.uring_restrictions.is_some() {
+            urb.setup_r_disabled();
+        }
+
         BUILD_THREAD_ID.set(&thread_id, || {
-            let driver = match this.entries {
-                Some(entries) => IoUringDriver::new_with_entries(&this.urb, entries)?,
-                None => IoUringDriver::new(&this.urb)?,
+            let new_driver = |urb: &io_uring::Builder| match this.entries {
+                Some(entries) => IoUringDriver::new_with_entries(urb, entries, taskrun),
+                None => IoUringDriver::new(urb, taskrun),
+            };
+            // The group lock is held until the first ring is given to it, so
+            // the rings built at the same time attach to the same one.
+            let mut group = match &this.attach_wq {
+                Some(WqDonor::Group(group)) => Some(group.donor.lock().unwrap()),
+                _ => None,
+            };
+            let donor = match (&this.attach_wq, &group) {
+                (Some(WqDonor::Fd(fd)), _) => Some(*fd),
+                (_, Some(donor)) => donor.as_ref().map(AsRawFd::as_raw_fd),
+                _ => None,
             };
+            let driver = match donor {
+                Some(fd) => {
+                    let mut attached = urb.clone();
+                    attached.setup_attach_wq(fd);
+                    match new_driver(&attached) {
+                        // The donor ring is gone, use an independent workqueue.
+                        Err(e) if matches!(e.raw_os_error(), Some(libc::EINVAL | libc::EBADF)) => {
+                            new_driver(&urb)
+                        }
+                        driver => driver,
+                    }
+                }
+                None => new_driver(&urb),
+            };
+            let driver = match driver {
+                // Before 5.11 SQPOLL requires CAP_SYS_NICE.
+                Err(e) if this.sqpoll_idle.is_some() && e.raw_os_error() == Some(libc::EPERM) => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::PermissionDenied,
+                        "io_uring SQPOLL is not permitted, it requires CAP_SYS_NICE before \
+                         kernel 5.11",
+                    ));
+                }
+                // Before 5.10 R_DISABLED is unknown.
+                Err(e)
+                    if this.uring_restrictions.is_some()
+                        && e.raw_os_error() == Some(libc::EINVAL) =>
+                {
+                    return Err(io::Error::new(
+                        io::ErrorKind::Unsupported,
+                        "io_uring restrictions are not supported, kernel 5.10+ is required",
+                    ));
+                }
+                driver => driver?,
+            };
+            if this.register_ring_fd {
+                driver.register_ring_fd();
+            }
+            if let Some(allowed) = &this.uring_restrictions {
+                driver.restrict(allowed, &this.disabled_opcodes)?;
+            }
+            if let Some(donor) = group.as_mut().filter(|donor| donor.is_none()) {
+                // Keep a dup, so the ring outlives this runtime for attaching.
+                let fd = crate::syscall!(fcntl(driver.as_raw_fd(), libc::F_DUPFD_CLOEXEC, 0))?;
+                **donor = Some(unsafe { OwnedFd::from_raw_fd(fd) });
+            }
+            drop(group);
+            #[cfg(feature = "zero-copy")]
+            if let Some(threshold) = this.zero_copy_threshold {
+                driver.set_zero_copy_threshold(threshold);
+            }
+            if let Some(capacity) = this.register_files {
+                driver.register_files(capacity);
+            }
+            if let Some((busy_poll_us, prefer_busy_poll)) = this.napi {
+                driver.register_napi(busy_poll_us, prefer_busy_poll)?;
+            }
+            driver.disable_opcodes(&this.disabled_opcodes);
             #[cfg(feature = "sync")]
             let context = crate::runtime::Context::new(blocking_handle);
             #[cfg(not(feature = "sync"))]
@@ -155,6 +326,236 @@ impl<D> RuntimeBuilder<D> {
         self.urb = urb;
         self
     }
+
+    /// Set the minimum write size to be sent with MSG_ZEROCOPY on io_uring
+    /// driver, the default value is 10 KB.
+    #[cfg(all(target_os = "linux", feature = "iouring", feature = "zero-copy"))]
+    #[must_use]
+    pub fn zero_copy_threshold(mut self, threshold: usize) -> Self {
+        self.zero_copy_threshold = Some(threshold);
+        self
+    }
+
+    /// Register a file table with `capacity` slots to the io_uring driver.
+    ///
+    /// Registered fds(e.g. with [`TcpStream::register`]) are addressed by their
+    /// slot in the table, which saves looking up the file for every op. It
+    /// requires kernel 5.19+, and fds are used as is if the table is not
+    /// available or full.
+    ///
+    /// [`TcpStream::register`]: crate::net::TcpStream::register
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    #[must_use]
+    pub fn register_files(mut self, capacity: u32) -> Self {
+        self.register_files = Some(capacity);
+        self
+    }
+
+    /// Set io_uring completion queue entries, which must be a power of two and
+    /// no less than the submission queue entries. The default size is twice
+    /// the submission queue entries.
+    ///
+    /// Multishot ops may post many completions for one submission, and the
+    /// completion queue should be large enough to hold them.
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    #[must_use]
+    pub fn uring_cq_entries(mut self, entries: u32) -> Self {
+        self.cq_entries = Some(entries);
+        self
+    }
+
+    /// Treat the io_uring opcodes(e.g. `io_uring::opcode::Statx::CODE`) as
+    /// unsupported by the kernel, which can be used to work around kernel
+    /// bugs of some ops.
+    ///
+    /// Ops with unsupported opcodes are run in the legacy way if `legacy` or
+    /// `poll-io` feature is enabled. The ops which do not wait for readiness
+    /// (e.g. file system ops) do the syscalls at once, and the others are
+    /// polled with the poller if the fd is registered to it(requires
+    /// `poll-io`).
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    #[must_use]
+    pub fn disable_uring_opcodes(mut self, codes: &[u8]) -> Self {
+        self.disabled_opcodes.extend_from_slice(codes);
+        self
+    }
+
+    /// Let a kernel thread poll the submission queue, so submitting ops does
+    /// not need `io_uring_enter` while the thread is busy. The thread sleeps
+    /// after `idle_ms` milliseconds without submissions, and it is woken up
+    /// on the next submit.
+    ///
+    /// It requires CAP_SYS_NICE before kernel 5.11, and building the runtime
+    /// fails with `PermissionDenied` without it.
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    #[must_use]
+    pub fn uring_sqpoll(mut self, idle_ms: u32) -> Self {
+        self.sqpoll_idle = Some(idle_ms);
+        self
+    }
+
+    /// Bind the submission polling thread to the given cpu, it only works
+    /// with [`uring_sqpoll`](Self::uring_sqpoll).
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    #[must_use]
+    pub fn sqpoll_cpu(mut self, cpu: u32) -> Self {
+        self.sqpoll_cpu = Some(cpu);
+        self
+    }
+
+    /// Setup the ring with `IORING_SETUP_COOP_TASKRUN`, `SINGLE_ISSUER` and
+    /// `DEFER_TASKRUN`, which save the interrupts and defer the completion
+    /// work to the time the driver enters the kernel. They are enabled by
+    /// default if the kernel supports them(6.1+), and ignored with
+    /// [`uring_sqpoll`](Self::uring_sqpoll).
+    ///
+    /// The ring can only be submitted from the thread building it with these
+    /// flags, which is always true for monoio runtimes.
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    #[must_use]
+    pub fn uring_taskrun_flags(mut self, enable: bool) -> Self {
+        self.taskrun_flags = enable;
+        self
+    }
+
+    /// Share the async workqueue(io-wq) of the io_uring instance `ring_fd`
+    /// with `IORING_SETUP_ATTACH_WQ`, so the rings do not create their own
+    /// kernel workers for the ops which can not complete inline(e.g. buffered
+    /// file io). If the ring is gone or can not be attached to, an independent
+    /// workqueue is used.
+    ///
+    /// Since kernel 5.12 the io-wq belongs to the submitting thread rather than
+    /// the ring, and attaching only shares the submission polling thread of
+    /// [`uring_sqpoll`](Self::uring_sqpoll) rings: with SQPOLL on both rings
+    /// one kernel thread polls both, and the idle time and cpu of the donor
+    /// are used. A SQPOLL ring can not attach to a ring without SQPOLL, it
+    /// gets its own polling thread then. Use [`attach_wq_group`](Self::attach_wq_group) to share
+    /// among runtimes without passing the fd around.
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    #[must_use]
+    pub fn attach_wq(mut self, ring_fd: RawFd) -> Self {
+        self.attach_wq = Some(WqDonor::Fd(ring_fd));
+        self
+    }
+
+    /// Share the async workqueue among the runtimes built with the group, see
+    /// [`attach_wq`](Self::attach_wq). The first io_uring runtime built with
+    /// the group gives its ring, and the others attach to it.
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    #[must_use]
+    pub fn attach_wq_group(mut self, group: &RuntimeGroup) -> Self {
+        self.attach_wq = Some(WqDonor::Group(group.clone()));
+        self
+    }
+
+    /// Create the io_uring instance disabled, register an allowlist of the
+    /// ops it can issue with `IORING_REGISTER_RESTRICTIONS` and then enable
+    /// it(requires kernel 5.10+). The ops used by the driver itself(e.g.
+    /// cancellation and the timeouts of park) are always allowed.
+    ///
+    /// Ops not in the allowlist fail with [`PermissionDenied`] when submitted
+    /// instead of being rejected by the kernel, unless they are given to
+    /// [`uring_restricted_fallback`](Self::uring_restricted_fallback).
+    ///
+    /// [`PermissionDenied`]: std::io::ErrorKind::PermissionDenied
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    #[must_use]
+    pub fn uring_restrictions(mut self, allowed: &[AllowedOp]) -> Self {
+        self.uring_restrictions = Some(allowed.to_vec());
+        self
+    }
+
+    /// Run the ops not allowed by [`uring_restrictions`](Self::uring_restrictions)
+    /// with syscalls in the legacy way, like
+    /// [`disable_uring_opcodes`](Self::disable_uring_opcodes). It requires
+    /// `legacy` or `poll-io` feature, otherwise the ops still fail.
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    #[must_use]
+    pub fn uring_restricted_fallback(mut self, ops: &[AllowedOp]) -> Self {
+        self.disabled_opcodes
+            .extend(ops.iter().flat_map(|op| op.opcodes()));
+        self
+    }
+
+    /// Register the ring fd to the ring itself, so entering the ring skips
+    /// looking up the fd(requires kernel 5.18+). It is enabled by default.
+    ///
+    /// The fd is still open and can be passed to
+    /// [`attach_wq`](Self::attach_wq) or used as a msg_ring target, disabling
+    /// it only saves the slot of the registered ring fds of the thread.
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    #[must_use]
+    pub fn uring_register_ring_fd(mut self, enable: bool) -> Self {
+        self.register_ring_fd = enable;
+        self
+    }
+
+    /// Busy poll the NIC queues of the sockets used by the ring with NAPI for
+    /// `busy_poll_us` microseconds when waiting for completions(requires
+    /// kernel 6.9+), which cuts the latency of waking up on packets at the
+    /// cost of cpu. It is ignored if the kernel does not support it.
+    ///
+    /// With [`uring_sqpoll`](Self::uring_sqpoll) the polling thread busy polls,
+    /// and the runtime spins on the completion queue for `busy_poll_us`
+    /// before going to sleep when it is idle.
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    #[must_use]
+    pub fn uring_napi(mut self, busy_poll_us: u32, prefer_busy_poll: bool) -> Self {
+        self.napi = Some((busy_poll_us, prefer_busy_poll));
+        self
+    }
+
+    /// Fire [`Sleep`], [`Timeout`] and [`Interval`] with io_uring timeout ops,
+    /// which have nanosecond precision, instead of the timer wheel which
+    /// rounds deadlines up to the next millisecond. Sleeps shorter than a
+    /// millisecond use the timeout ops anyway. The timer must be enabled, and
+    /// it is ignored on legacy driver.
+    ///
+    /// [`Sleep`]: crate::time::Sleep
+    /// [`Timeout`]: crate::time::Timeout
+    /// [`Interval`]: crate::time::Interval
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    #[must_use]
+    pub fn precise_timers(mut self, enable: bool) -> Self {
+        self.precise_timers = enable;
+        self
+    }
+}
+
+// ===== RuntimeGroup =====
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+enum WqDonor {
+    Fd(RawFd),
+    Group(RuntimeGroup),
+}
+
+/// A group of runtimes sharing the io_uring async workqueue, see
+/// [`RuntimeBuilder::attach_wq_group`].
+///
+/// It is cheap to clone and can be sent to the threads building the runtimes.
+/// The group holds the ring of the first runtime open until the group and
+/// its clones are dropped, so the ring can be attached to even after the
+/// runtime is dropped.
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+#[derive(Debug, Clone, Default)]
+pub struct RuntimeGroup {
+    donor: Arc<Mutex<Option<OwnedFd>>>,
+}
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+impl RuntimeGroup {
+    /// Create an empty group.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the fd of the ring which the runtimes of the group attach to.
+    /// Returns None if no io_uring runtime is built with the group yet.
+    pub fn ring_fd(&self) -> Option<RawFd> {
+        self.donor.lock().unwrap().as_ref().map(AsRawFd::as_raw_fd)
+    }
 }
 
 // ===== FusionDriver =====
@@ -172,6 +573,30 @@ impl RuntimeBuilder<FusionDriver> {
             let builder = RuntimeBuilder::<IoUringDriver> {
                 entries: self.entries,
                 urb: self.urb,
+                #[cfg(all(target_os = "linux", feature = "iouring", feature = "zero-copy"))]
+                zero_copy_threshold: self.zero_copy_threshold,
+                #[cfg(all(target_os = "linux", feature = "iouring"))]
+                register_files: self.register_files,
+                #[cfg(all(target_os = "linux", feature = "iouring"))]
+                precise_timers: self.precise_timers,
+                #[cfg(all(target_os = "linux", feature = "iouring"))]
+                sqpoll_idle: self.sqpoll_idle,
+                #[cfg(all(target_os = "linux", feature = "iouring"))]
+                sqpoll_cpu: self.sqpoll_cpu,
+                #[cfg(all(target_os = "linux", feature = "iouring"))]
+                taskrun_flags: self.taskrun_flags,
+                #[cfg(all(target_os = "linux", feature = "iouring"))]
+                cq_entries: self.cq_entries,
+                #[cfg(all(target_os = "linux", feature = "iouring"))]
+                disabled_opcodes: self.disabled_opcodes,
+                #[cfg(all(target_os = "linux", feature = "iouring"))]
+                attach_wq: self.attach_wq,
+                #[cfg(all(target_os = "linux", feature = "iouring"))]
+                uring_restrictions: self.uring_restrictions,
+                #[cfg(all(target_os = "linux", feature = "iouring"))]
+                napi: self.napi,
+                #[cfg(all(target_os = "linux", feature = "iouring"))]
+                register_ring_fd: self.register_ring_fd,
                 #[cfg(feature = "sync")]
                 blocking_handle: self.blocking_handle,
                 _mark: PhantomData,
@@ -182,6 +607,30 @@ impl RuntimeBuilder<FusionDriver> {
             let builder = RuntimeBuilder::<LegacyDriver> {
                 entries: self.entries,
                 urb: self.urb,
+                #[cfg(all(target_os = "linux", feature = "iouring", feature = "zero-copy"))]
+                zero_copy_threshold: self.zero_copy_threshold,
+                #[cfg(all(target_os = "linux", feature = "iouring"))]
+                register_files: self.register_files,
+                #[cfg(all(target_os = "linux", feature = "iouring"))]
+                precise_timers: self.precise_timers,
+                #[cfg(all(target_os = "linux", feature = "iouring"))]
+                sqpoll_idle: self.sqpoll_idle,
+                #[cfg(all(target_os = "linux", feature = "iouring"))]
+                sqpoll_cpu: self.sqpoll_cpu,
+                #[cfg(all(target_os = "linux", feature = "iouring"))]
+                taskrun_flags: self.taskrun_flags,
+                #[cfg(all(target_os = "linux", feature = "iouring"))]
+                cq_entries: self.cq_entries,
+                #[cfg(all(target_os = "linux", feature = "iouring"))]
+                disabled_opcodes: self.disabled_opcodes,
+                #[cfg(all(target_os = "linux", feature = "iouring"))]
+                attach_wq: self.attach_wq,
+                #[cfg(all(target_os = "linux", feature = "iouring"))]
+                uring_restrictions: self.uring_restrictions,
+                #[cfg(all(target_os = "linux", feature = "iouring"))]
+                napi: self.napi,
+                #[cfg(all(target_os = "linux", feature = "iouring"))]
+                register_ring_fd: self.register_ring_fd,
                 #[cfg(feature = "sync")]
                 blocking_handle: self.blocking_handle,
                 _mark: PhantomData,
@@ -209,6 +658,30 @@ impl RuntimeBuilder<FusionDriver> {
         let builder = RuntimeBuilder::<IoUringDriver> {
             entries: self.entries,
             urb: self.urb,
+            #[cfg(all(target_os = "linux", feature = "iouring", feature = "zero-copy"))]
+            zero_copy_threshold: self.zero_copy_threshold,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            register_files: self.register_files,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            precise_timers: self.precise_timers,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            sqpoll_idle: self.sqpoll_idle,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            sqpoll_cpu: self.sqpoll_cpu,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            taskrun_flags: self.taskrun_flags,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            cq_entries: self.cq_entries,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            disabled_opcodes: self.disabled_opcodes,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            attach_wq: self.attach_wq,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            uring_restrictions: self.uring_restrictions,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            napi: self.napi,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            register_ring_fd: self.register_ring_fd,
             #[cfg(feature = "sync")]
             blocking_handle: self.blocking_handle,
             _mark: PhantomData,
@@ -228,6 +701,30 @@ impl RuntimeBuilder<TimeDriver<FusionDriver>> {
             let builder = RuntimeBuilder::<TimeDriver<IoUringDriver>> {
                 entries: self.entries,
                 urb: self.urb,
+                #[cfg(all(target_os = "linux", feature = "iouring", feature = "zero-copy"))]
+                zero_copy_threshold: self.zero_copy_threshold,
+                #[cfg(all(target_os = "linux", feature = "iouring"))]
+                register_files: self.register_files,
+                #[cfg(all(target_os = "linux", feature = "iouring"))]
+                precise_timers: self.precise_timers,
+                #[cfg(all(target_os = "linux", feature = "iouring"))]
+                sqpoll_idle: self.sqpoll_idle,
+                #[cfg(all(target_os = "linux", feature = "iouring"))]
+                sqpoll_cpu: self.sqpoll_cpu,
+                #[cfg(all(target_os = "linux", feature = "iouring"))]
+                taskrun_flags: self.taskrun_flags,
+                #[cfg(all(target_os = "linux", feature = "iouring"))]
+                cq_entries: self.cq_entries,
+                #[cfg(all(target_os = "linux", feature = "iouring"))]
+                disabled_opcodes: self.disabled_opcodes,
+                #[cfg(all(target_os = "linux", feature = "iouring"))]
+                attach_wq: self.attach_wq,
+                #[cfg(all(target_os = "linux", feature = "iouring"))]
+                uring_restrictions: self.uring_restrictions,
+                #[cfg(all(target_os = "linux", feature = "iouring"))]
+                napi: self.napi,
+                #[cfg(all(target_os = "linux", feature = "iouring"))]
+                register_ring_fd: self.register_ring_fd,
                 #[cfg(feature = "sync")]
                 blocking_handle: self.blocking_handle,
                 _mark: PhantomData,
@@ -238,6 +735,30 @@ impl RuntimeBuilder<TimeDriver<FusionDriver>> {
             let builder = RuntimeBuilder::<TimeDriver<LegacyDriver>> {
                 entries: self.entries,
                 urb: self.urb,
+                #[cfg(all(target_os = "linux", feature = "iouring", feature = "zero-copy"))]
+                zero_copy_threshold: self.zero_copy_threshold,
+                #[cfg(all(target_os = "linux", feature = "iouring"))]
+                register_files: self.register_files,
+                #[cfg(all(target_os = "linux", feature = "iouring"))]
+                precise_timers: self.precise_timers,
+                #[cfg(all(target_os = "linux", feature = "iouring"))]
+                sqpoll_idle: self.sqpoll_idle,
+                #[cfg(all(target_os = "linux", feature = "iouring"))]
+                sqpoll_cpu: self.sqpoll_cpu,
+                #[cfg(all(target_os = "linux", feature = "iouring"))]
+                taskrun_flags: self.taskrun_flags,
+                #[cfg(all(target_os = "linux", feature = "iouring"))]
+                cq_entries: self.cq_entries,
+                #[cfg(all(target_os = "linux", feature = "iouring"))]
+                disabled_opcodes: self.disabled_opcodes,
+                #[cfg(all(target_os = "linux", feature = "iouring"))]
+                attach_wq: self.attach_wq,
+                #[cfg(all(target_os = "linux", feature = "iouring"))]
+                uring_restrictions: self.uring_restrictions,
+                #[cfg(all(target_os = "linux", feature = "iouring"))]
+                napi: self.napi,
+                #[cfg(all(target_os = "linux", feature = "iouring"))]
+                register_ring_fd: self.register_ring_fd,
                 #[cfg(feature = "sync")]
                 blocking_handle: self.blocking_handle,
                 _mark: PhantomData,
@@ -265,6 +786,30 @@ impl RuntimeBuilder<TimeDriver<FusionDriver>> {
         let builder = RuntimeBuilder::<TimeDriver<IoUringDriver>> {
             entries: self.entries,
             urb: self.urb,
+            #[cfg(all(target_os = "linux", feature = "iouring", feature = "zero-copy"))]
+            zero_copy_threshold: self.zero_copy_threshold,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            register_files: self.register_files,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            precise_timers: self.precise_timers,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            sqpoll_idle: self.sqpoll_idle,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            sqpoll_cpu: self.sqpoll_cpu,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            taskrun_flags: self.taskrun_flags,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            cq_entries: self.cq_entries,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            disabled_opcodes: self.disabled_opcodes,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            attach_wq: self.attach_wq,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            uring_restrictions: self.uring_restrictions,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            napi: self.napi,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            register_ring_fd: self.register_ring_fd,
             #[cfg(feature = "sync")]
             blocking_handle: self.blocking_handle,
             _mark: PhantomData,
@@ -291,6 +836,8 @@ where
 {
     /// Build the runtime
     fn build(this: RuntimeBuilder<Self>) -> io::Result<Runtime<TimeDriver<D>>> {
+        #[cfg(all(target_os = "linux", feature = "iouring"))]
+        let precise_timers = this.precise_timers;
         let Runtime {
             driver,
             mut context,
@@ -298,12 +845,38 @@ where
             entries: this.entries,
             #[cfg(all(target_os = "linux", feature = "iouring"))]
             urb: this.urb,
+            #[cfg(all(target_os = "linux", feature = "iouring", feature = "zero-copy"))]
+            zero_copy_threshold: this.zero_copy_threshold,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            register_files: this.register_files,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            precise_timers: this.precise_timers,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            sqpoll_idle: this.sqpoll_idle,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            sqpoll_cpu: this.sqpoll_cpu,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            taskrun_flags: this.taskrun_flags,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            cq_entries: this.cq_entries,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            disabled_opcodes: this.disabled_opcodes,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            attach_wq: this.attach_wq,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            uring_restrictions: this.uring_restrictions,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            napi: this.napi,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            register_ring_fd: this.register_ring_fd,
             #[cfg(feature = "sync")]
             blocking_handle: this.blocking_handle,
             _mark: PhantomData,
         })?;
 
         let timer_driver = TimeDriver::new(driver, Clock::new());
+        #[cfg(all(target_os = "linux", feature = "iouring"))]
+        timer_driver.handle.set_precise_timers(precise_timers);
         context.time_handle = Some(timer_driver.handle.clone());
         Ok(Runtime {
             driver: timer_driver,
@@ -326,6 +899,30 @@ impl<D: time_wrap::TimeWrapable> RuntimeBuilder<D> {
             entries,
             #[cfg(all(target_os = "linux", feature = "iouring"))]
             urb,
+            #[cfg(all(target_os = "linux", feature = "iouring", feature = "zero-copy"))]
+            zero_copy_threshold,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            register_files,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            precise_timers,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            sqpoll_idle,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            sqpoll_cpu,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            taskrun_flags,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            cq_entries,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            disabled_opcodes,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            attach_wq,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            uring_restrictions,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            napi,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            register_ring_fd,
             #[cfg(feature = "sync")]
             blocking_handle,
             ..
@@ -334,6 +931,30 @@ impl<D: time_wrap::TimeWrapable> RuntimeBuilder<D> {
             entries,
             #[cfg(all(target_os = "linux", feature = "iouring"))]
             urb,
+            #[cfg(all(target_os = "linux", feature = "iouring", feature = "zero-copy"))]
+            zero_copy_threshold,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            register_files,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            precise_timers,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            sqpoll_idle,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            sqpoll_cpu,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            taskrun_flags,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            cq_entries,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            disabled_opcodes,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            attach_wq,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            uring_restrictions,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            napi,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            register_ring_fd,
             #[cfg(feature = "sync")]
             blocking_handle,
             _mark: PhantomData,
@@ -42,6 +42,11 @@ pub(crate) struct LegacyInner {
     // Waker receiver
     #[cfg(feature = "sync")]
     waker_receiver: flume::Receiver<std::task::Waker>,
+
+    // Tasks to wake after the next poll, see `poll_round`.
+    poll_waiters: Vec<std::task::Waker>,
+    // Number of polls done.
+    polls: u64,
 }
 
 /// Driver with Poll-like syscall.
@@ -100,6 +105,8 @@ impl LegacyDriver {
             shared_waker,
             #[cfg(feature = "sync")]
             waker_receiver,
+            poll_waiters: Vec::new(),
+            polls: 0,
         };
         let driver = Self {
             inner: Rc::new(UnsafeCell::new(inner)),
@@ -146,7 +153,7 @@ impl LegacyDriver {
             }
         }
 
-        if !need_wait {
+        if !need_wait || !inner.poll_waiters.is_empty() {
             timeout = Some(Duration::ZERO);
         }
 
@@ -172,6 +179,8 @@ impl LegacyDriver {
             #[cfg(not(feature = "sync"))]
             inner.dispatch(token, Ready::from_mio(event));
         }
+        inner.polls += 1;
+        inner.poll_waiters.drain(..).for_each(|w| w.wake());
         Ok(())
     }
 
@@ -251,6 +260,24 @@ impl LegacyDriver {
 }
 
 impl LegacyInner {
+    /// Wait for the next poll after the first call, which is marked by
+    /// `start`. The poll does not block while there are tasks waiting for it.
+    pub(crate) fn poll_round(
+        this: &Rc<UnsafeCell<Self>>,
+        start: &mut Option<u64>,
+        cx: &mut Context<'_>,
+    ) -> Poll<()> {
+        let inner = unsafe { &mut *this.get() };
+        match *start {
+            Some(polls) if inner.polls > polls => return Poll::Ready(()),
+            // Woken spuriously before the poll.
+            Some(_) => (),
+            None => *start = Some(inner.polls),
+        }
+        inner.poll_waiters.push(cx.waker().clone());
+        Poll::Pending
+    }
+
     fn dispatch(&mut self, token: mio::Token, ready: Ready) {
         let mut sio = match self.io_dispatch.get(token.0) {
             Some(io) => io,
@@ -338,10 +365,31 @@ impl LegacyInner {
             driver: Inner::Legacy(this.clone()),
             // useless for legacy
             index: 0,
+            legacy_interest: data.legacy_interest(),
             data: Some(data),
         })
     }
 
+    /// Deregister the interest of a dropped operation, so the task is not
+    /// woken up for it.
+    pub(crate) fn drop_op(
+        this: &Rc<UnsafeCell<LegacyInner>>,
+        op_index: usize,
+        interest: Option<(ready::Direction, usize)>,
+    ) {
+        let Some((direction, index)) = interest else {
+            return;
+        };
+        if op_index == usize::MAX {
+            // already finished
+            return;
+        }
+        let inner = unsafe { &mut *this.get() };
+        if let Some(mut scheduled_io) = inner.io_dispatch.get(index) {
+            scheduled_io.as_mut().clear_waker(direction);
+        }
+    }
+
     #[cfg(feature = "sync")]
     pub(crate) fn unpark(this: &Rc<UnsafeCell<LegacyInner>>) -> waker::UnparkHandle {
         let inner = unsafe { &*this.get() };
@@ -17,7 +17,7 @@ mod legacy;
 #[cfg(all(target_os = "linux", feature = "iouring"))]
 mod uring;
 
-mod util;
+pub(crate) mod util;
 
 use std::{
     io,
@@ -32,9 +32,13 @@ pub use self::legacy::LegacyDriver;
 use self::legacy::LegacyInner;
 use self::op::{CompletionMeta, Op, OpAble};
 #[cfg(all(target_os = "linux", feature = "iouring"))]
-pub use self::uring::IoUringDriver;
+pub(crate) use self::uring::buf_ring::BufRing;
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+pub(crate) use self::uring::fixed_buf::FixedBufs;
 #[cfg(all(target_os = "linux", feature = "iouring"))]
 use self::uring::UringInner;
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+pub use self::uring::{AllowedOp, IoUringDriver};
 
 /// Unpark a runtime of another thread.
 pub(crate) mod unpark {
@@ -116,6 +120,63 @@ impl Inner {
         }
     }
 
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    fn submit_chain<T: OpAble>(&self, data: impl Iterator<Item = T>) -> io::Result<Vec<Op<T>>> {
+        match self {
+            Inner::Uring(this) => UringInner::submit_chain_with_data(this, data),
+            #[cfg(feature = "legacy")]
+            Inner::Legacy(this) => data
+                .map(|data| LegacyInner::submit_with_data(this, data))
+                .collect(),
+        }
+    }
+
+    /// Check if the op, which does not wait for readiness, would be a
+    /// blocking call of the thread.
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    #[allow(unused)]
+    fn is_blocking_op<T: OpAble>(&self, data: &mut T) -> io::Result<bool> {
+        match self {
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            Inner::Uring(this) => UringInner::is_blocking_op(this, &data.uring_op()),
+            #[cfg(feature = "legacy")]
+            Inner::Legacy(_) => Ok(true),
+            #[cfg(all(
+                not(feature = "legacy"),
+                not(all(target_os = "linux", feature = "iouring"))
+            ))]
+            _ => {
+                #[cfg(unix)]
+                util::feature_panic();
+                #[cfg(windows)]
+                unimplemented!();
+            }
+        }
+    }
+
+    #[allow(unused)]
+    fn submit_link<A: OpAble, B: OpAble>(&self, first: A, second: B) -> io::Result<(Op<A>, Op<B>)> {
+        match self {
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            Inner::Uring(this) => UringInner::submit_link_with_data(this, first, second),
+            #[cfg(feature = "legacy")]
+            Inner::Legacy(this) => Ok((
+                LegacyInner::submit_with_data(this, first)?,
+                LegacyInner::submit_with_data(this, second)?,
+            )),
+            #[cfg(all(
+                not(feature = "legacy"),
+                not(all(target_os = "linux", feature = "iouring"))
+            ))]
+            _ => {
+                #[cfg(unix)]
+                util::feature_panic();
+                #[cfg(windows)]
+                unimplemented!();
+            }
+        }
+    }
+
     #[allow(unused)]
     fn poll_op<T: OpAble>(
         &self,
@@ -127,7 +188,7 @@ impl Inner {
             #[cfg(windows)]
             _ => unimplemented!(),
             #[cfg(all(target_os = "linux", feature = "iouring"))]
-            Inner::Uring(this) => UringInner::poll_op(this, index, cx),
+            Inner::Uring(this) => UringInner::poll_op(this, data, index, cx),
             #[cfg(feature = "legacy")]
             Inner::Legacy(this) => LegacyInner::poll_op::<T>(this, data, cx),
             #[cfg(all(
@@ -164,14 +225,19 @@ impl Inner {
     }
 
     #[allow(unused)]
-    fn drop_op<T: 'static>(&self, index: usize, data: &mut Option<T>) {
+    fn drop_op<T: 'static>(
+        &self,
+        index: usize,
+        data: &mut Option<T>,
+        #[cfg(feature = "legacy")] legacy_interest: Option<(ready::Direction, usize)>,
+    ) {
         match self {
             #[cfg(windows)]
             _ => unimplemented!(),
             #[cfg(all(target_os = "linux", feature = "iouring"))]
             Inner::Uring(this) => UringInner::drop_op(this, index, data),
             #[cfg(feature = "legacy")]
-            Inner::Legacy(_) => {}
+            Inner::Legacy(this) => LegacyInner::drop_op(this, index, legacy_interest),
             #[cfg(all(
                 not(feature = "legacy"),
                 not(all(target_os = "linux", feature = "iouring"))
@@ -3,25 +3,109 @@ use std::{
     io,
     pin::Pin,
     task::{Context, Poll},
+    time::Duration,
 };
 
 use crate::driver;
 
+/// Build a uring entry with the fd of a `SharedFd`, or with its slot if the fd
+/// is registered in the file table.
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+macro_rules! uring_fd {
+    ($fd:expr, |$target:ident| $build:expr) => {
+        match $fd.fixed_index() {
+            Some(idx) => {
+                let $target = io_uring::types::Fixed(idx);
+                $build
+            }
+            None => {
+                let $target = io_uring::types::Fd($fd.raw_fd());
+                $build
+            }
+        }
+    };
+}
+
 pub(crate) mod close;
 
 mod accept;
 mod connect;
+#[cfg(target_os = "linux")]
+mod fadvise;
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+mod fallocate;
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+pub(crate) mod fd_install;
 mod fsync;
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+mod ftruncate;
+#[cfg(all(target_os = "linux", feature = "iouring", feature = "sync"))]
+mod futex;
+#[cfg(unix)]
+mod link;
+#[cfg(target_os = "linux")]
+mod madvise;
+#[cfg(unix)]
+mod mkdir;
+mod nop;
 mod open;
 mod poll;
 mod read;
 mod recv;
+#[cfg(unix)]
+mod recv_batch;
+#[cfg(unix)]
+mod rename;
 mod send;
+#[cfg(unix)]
+mod send_batch;
+#[cfg(unix)]
+mod shutdown;
+#[cfg(unix)]
+mod symlink;
+#[cfg(unix)]
+mod unlink;
 mod write;
 
 #[cfg(all(target_os = "linux", feature = "splice"))]
 mod splice;
 
+#[cfg(target_os = "linux")]
+mod statx;
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+mod timeout;
+
+#[cfg(all(unix, feature = "futures-stream"))]
+pub(crate) use accept::Accept;
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+pub(crate) use accept::AcceptMulti;
+#[cfg(target_os = "linux")]
+pub(crate) use fadvise::FADVISE_CHUNK;
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+pub(crate) use ftruncate::Ftruncate;
+#[cfg(all(target_os = "linux", feature = "iouring", feature = "sync"))]
+pub(crate) use futex::futex_wake_all;
+#[cfg(all(target_os = "linux", feature = "iouring", feature = "bytes"))]
+pub(crate) use recv::RecvMulti;
+#[cfg(unix)]
+pub(crate) use recv_batch::{recv_batch, RecvBatch};
+#[cfg(all(target_os = "linux", feature = "iouring", feature = "zero-copy"))]
+pub(crate) use send::MSG_ZEROCOPY_THRESHOLD;
+#[cfg(unix)]
+pub(crate) use send_batch::{send_batch, SendBatch};
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+pub(crate) use timeout::Timeout;
+
+/// Releases what the kernel gave to the dropped op `op` with a completion,
+/// either queued before the drop or completed after it, like an accepted fd
+/// or a buffer picked from a ring. The fds received by a recvmsg are closed
+/// when its data is dropped after this, since the op is generic.
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+pub(crate) fn close_orphan(op: &dyn std::any::Any, result: &io::Result<u32>, flags: u32) {
+    accept::close_orphan(op, result);
+    recv::recycle_orphan(op, flags);
+}
+
 /// In-flight operation
 pub(crate) struct Op<T: 'static> {
     // Driver running the operation
@@ -30,6 +114,10 @@ pub(crate) struct Op<T: 'static> {
     // Operation index in the slab(useless for legacy)
     pub(super) index: usize,
 
+    // Readiness the operation waits for(legacy only)
+    #[cfg(feature = "legacy")]
+    pub(super) legacy_interest: Option<(driver::ready::Direction, usize)>,
+
     // Per-operation data
     pub(super) data: Option<T>,
 }
@@ -49,6 +137,18 @@ pub(crate) struct CompletionMeta {
     pub(crate) flags: u32,
 }
 
+impl CompletionMeta {
+    /// Check if the kernel will post more completions for the operation.
+    /// It is only possible for multishot operations on uring driver.
+    #[inline]
+    pub(crate) fn has_more(&self) -> bool {
+        #[cfg(all(target_os = "linux", feature = "iouring"))]
+        return io_uring::cqueue::more(self.flags);
+        #[cfg(not(all(target_os = "linux", feature = "iouring")))]
+        false
+    }
+}
+
 pub(crate) trait OpAble {
     #[cfg(all(target_os = "linux", feature = "iouring"))]
     fn uring_op(&mut self) -> io_uring::squeue::Entry;
@@ -111,6 +211,45 @@ impl<T> Op<T> {
         driver::CURRENT.with(|this| this.submit_with(data))
     }
 
+    /// Submit an operation which does not wait for readiness, or run it on the
+    /// blocking thread pool where it would be a blocking call of the thread:
+    /// on the legacy driver, and on the uring driver without its opcode. See
+    /// [`asyncify`](crate::fs::asyncify).
+    #[allow(unused)]
+    pub(super) fn submit_or_asyncify(
+        mut data: T,
+    ) -> io::Result<impl Future<Output = io::Result<u32>>>
+    where
+        T: OpAble + Send + Unpin,
+    {
+        #[cfg(any(feature = "legacy", feature = "poll-io"))]
+        let (op, blocking) = match driver::CURRENT.with(|this| this.is_blocking_op(&mut data))? {
+            true => (None, Some(crate::fs::asyncify(move || data.legacy_call()))),
+            false => (Some(Op::submit_with(data)?), None),
+        };
+        #[cfg(not(any(feature = "legacy", feature = "poll-io")))]
+        let (op, blocking) = (
+            Some(Op::submit_with(data)?),
+            None::<std::future::Pending<_>>,
+        );
+        Ok(async move {
+            match (op, blocking) {
+                (Some(op), _) => op.await.meta.result,
+                (None, blocking) => blocking.unwrap().await,
+            }
+        })
+    }
+
+    /// Submit the operations linked in order, at most the size of the SQ of
+    /// them are taken from `data`. Only the uring driver links them.
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    pub(super) fn submit_chain(data: impl Iterator<Item = T>) -> io::Result<Vec<Op<T>>>
+    where
+        T: OpAble,
+    {
+        driver::CURRENT.with(|this| this.submit_chain(data))
+    }
+
     /// Try submitting an operation to uring
     #[allow(unused)]
     pub(super) fn try_submit_with(data: T) -> io::Result<Op<T>>
@@ -124,6 +263,23 @@ impl<T> Op<T> {
         }
     }
 
+    /// Poll the next completion of a multishot operation.
+    ///
+    /// Unlike polling the `Op` as a future, the operation data is kept so the
+    /// operation can be polled again until a completion without the more flag
+    /// is returned. The operation must not be polled after that.
+    pub(crate) fn poll_multishot(&mut self, cx: &mut Context<'_>) -> Poll<CompletionMeta>
+    where
+        T: OpAble,
+    {
+        let data_mut = self.data.as_mut().expect("unexpected operation state");
+        let meta = ready!(self.driver.poll_op::<T>(data_mut, self.index, cx));
+        if !meta.has_more() {
+            self.index = usize::MAX;
+        }
+        Poll::Ready(meta)
+    }
+
     pub(crate) fn op_canceller(&self) -> OpCanceller
     where
         T: OpAble,
@@ -150,6 +306,49 @@ impl<T> Op<T> {
     }
 }
 
+impl<T> Op<T>
+where
+    T: Unpin + OpAble + 'static,
+{
+    /// Submit an operation which is cancelled if it does not complete within
+    /// `timeout`, its result is `TimedOut` then.
+    ///
+    /// On uring driver the operation is linked to a `LinkTimeout`, so no timer
+    /// is involved. On legacy driver it waits with the timer of the runtime,
+    /// and its interest is deregistered when the timer fires.
+    pub(crate) async fn with_timeout(data: T, timeout: Duration) -> io::Result<Completion<T>> {
+        #[cfg(all(target_os = "linux", feature = "iouring"))]
+        if !is_legacy() {
+            let link = Link::submit_with(data, timeout::LinkTimeout::new(timeout))?;
+            let (mut complete, timer) = link.wait().await;
+            let errno = |meta: &CompletionMeta| {
+                meta.result.as_ref().err().and_then(io::Error::raw_os_error)
+            };
+            // The timeout may fire after the op completes but before it is
+            // cancelled, so only a cancelled op counts as timed out.
+            if errno(&timer.meta) == Some(libc::ETIME)
+                && errno(&complete.meta) == Some(libc::ECANCELED)
+            {
+                complete.meta.result = Err(io::ErrorKind::TimedOut.into());
+            }
+            return Ok(complete);
+        }
+
+        let mut op = Op::submit_with(data)?;
+        match crate::time::timeout(timeout, &mut op).await {
+            Ok(complete) => Ok(complete),
+            Err(_) => {
+                let data = op.data.take().expect("unexpected operation state");
+                let meta = CompletionMeta {
+                    result: Err(io::ErrorKind::TimedOut.into()),
+                    flags: 0,
+                };
+                Ok(Completion { data, meta })
+            }
+        }
+    }
+}
+
 impl<T> Future for Op<T>
 where
     T: Unpin + OpAble + 'static,
@@ -167,8 +366,47 @@ where
     }
 }
 
+/// Two operations linked in order. The second one is executed after the
+/// first one completes successfully, otherwise it completes with `ECANCELED`.
+pub(crate) struct Link<A: 'static, B: 'static> {
+    first: Op<A>,
+    second: Op<B>,
+}
+
+impl<A, B> Link<A, B>
+where
+    A: Unpin + OpAble + 'static,
+    B: Unpin + OpAble + 'static,
+{
+    pub(super) fn submit_with(first: A, second: B) -> io::Result<Self> {
+        let (first, second) = driver::CURRENT.with(|this| this.submit_link(first, second))?;
+        Ok(Self { first, second })
+    }
+
+    /// Wait for both operations to complete.
+    pub(crate) async fn wait(self) -> (Completion<A>, Completion<B>) {
+        let Link { first, mut second } = self;
+        let first = first.await;
+        // Legacy operations are executed when polled, so the second one is
+        // skipped here instead of by the kernel.
+        if first.meta.result.is_err() && second.driver.is_legacy() {
+            let data = second.data.take().expect("unexpected operation state");
+            let meta = CompletionMeta {
+                result: Err(io::Error::from_raw_os_error(libc::ECANCELED)),
+                flags: 0,
+            };
+            return (first, Completion { data, meta });
+        }
+        (first, second.await)
+    }
+}
+
 impl<T> Drop for Op<T> {
     fn drop(&mut self) {
+        #[cfg(feature = "legacy")]
+        self.driver
+            .drop_op(self.index, &mut self.data, self.legacy_interest);
+        #[cfg(not(feature = "legacy"))]
         self.driver.drop_op(self.index, &mut self.data);
     }
 }
@@ -188,6 +426,180 @@ pub fn is_legacy() -> bool {
     super::CURRENT.with(|inner| inner.is_legacy())
 }
 
+/// Get the zero copy threshold of current uring driver.
+#[cfg(all(target_os = "linux", feature = "iouring", feature = "zero-copy"))]
+#[inline]
+pub(crate) fn zero_copy_threshold() -> usize {
+    super::CURRENT.with(|inner| match inner {
+        super::Inner::Uring(this) => super::UringInner::zero_copy_threshold(this),
+        #[cfg(feature = "legacy")]
+        super::Inner::Legacy(_) => MSG_ZEROCOPY_THRESHOLD,
+    })
+}
+
+/// Get the entries of the submission and completion queues of current uring
+/// driver, which are rounded up by the kernel. Returns None on legacy driver
+/// or outside of a runtime.
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+#[inline]
+pub fn uring_entries() -> Option<(u32, u32)> {
+    if !super::CURRENT.is_set() {
+        return None;
+    }
+    super::CURRENT.with(|inner| match inner {
+        super::Inner::Uring(this) => Some(super::UringInner::entries(this)),
+        #[cfg(feature = "legacy")]
+        super::Inner::Legacy(_) => None,
+    })
+}
+
+/// Get the number of times that ops of current uring driver found the
+/// submission queue full. Those ops wait in the driver and are pushed after
+/// the next submit makes space. Returns None on legacy driver.
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+#[inline]
+pub fn uring_sq_full_events() -> Option<u64> {
+    super::CURRENT.with(|inner| match inner {
+        super::Inner::Uring(this) => Some(super::UringInner::sq_full_events(this)),
+        #[cfg(feature = "legacy")]
+        super::Inner::Legacy(_) => None,
+    })
+}
+
+/// Get the number of times that the completion queue of current uring driver
+/// overflowed. The completions are held by the kernel and flushed into the
+/// queue by the driver, so nothing is lost, but a larger queue(see
+/// [`RuntimeBuilder::uring_cq_entries`]) avoids the extra syscalls. Returns
+/// None on legacy driver.
+///
+/// [`RuntimeBuilder::uring_cq_entries`]: crate::RuntimeBuilder::uring_cq_entries
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+#[inline]
+pub fn uring_cq_overflow_events() -> Option<u64> {
+    super::CURRENT.with(|inner| match inner {
+        super::Inner::Uring(this) => Some(super::UringInner::cq_overflow_events(this)),
+        #[cfg(feature = "legacy")]
+        super::Inner::Legacy(_) => None,
+    })
+}
+
+/// Check if the opcode(e.g. `io_uring::opcode::Statx::CODE`) is supported by
+/// current uring driver. Ops with unsupported opcodes are run in the legacy
+/// way. Returns false on legacy driver.
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+#[inline]
+pub fn is_uring_op_supported(code: u8) -> bool {
+    super::CURRENT.with(|inner| match inner {
+        super::Inner::Uring(this) => super::UringInner::is_op_supported(this, code),
+        #[cfg(feature = "legacy")]
+        super::Inner::Legacy(_) => false,
+    })
+}
+
+/// Credentials registered to the io_uring driver, which the ops tagged with
+/// it run with instead of the credentials of the thread, e.g. to reopen files
+/// with the credentials of root after dropping privileges.
+///
+/// It is only valid in the runtime which registers it.
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PersonalityId(u16);
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+impl PersonalityId {
+    /// The id assigned by the kernel.
+    #[inline]
+    pub fn id(&self) -> u16 {
+        self.0
+    }
+}
+
+/// Register the credentials of the current thread to current uring driver as
+/// a personality. Fails with [`Unsupported`](io::ErrorKind::Unsupported) on
+/// legacy driver.
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+pub fn register_personality() -> io::Result<PersonalityId> {
+    super::CURRENT.with(|inner| match inner {
+        super::Inner::Uring(this) => {
+            super::UringInner::register_personality(this).map(PersonalityId)
+        }
+        #[cfg(feature = "legacy")]
+        super::Inner::Legacy(_) => Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "personalities require the uring driver",
+        )),
+    })
+}
+
+/// Unregister the personality from current uring driver. Fails with
+/// [`InvalidInput`](io::ErrorKind::InvalidInput) if it is not registered to
+/// the driver.
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+pub fn unregister_personality(personality: PersonalityId) -> io::Result<()> {
+    super::CURRENT.with(|inner| match inner {
+        super::Inner::Uring(this) => super::UringInner::unregister_personality(this, personality.0),
+        #[cfg(feature = "legacy")]
+        super::Inner::Legacy(_) => Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "personalities require the uring driver",
+        )),
+    })
+}
+
+/// Get the personality field of the sqe of the op with `code`, 0 means the
+/// credentials of the thread.
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+pub(crate) fn personality(personality: Option<PersonalityId>, code: u8) -> io::Result<u16> {
+    let Some(PersonalityId(id)) = personality else {
+        return Ok(0);
+    };
+    super::CURRENT.with(|inner| match inner {
+        super::Inner::Uring(this) => {
+            super::UringInner::check_personality(this, id, code).map(|_| id)
+        }
+        #[cfg(feature = "legacy")]
+        super::Inner::Legacy(_) => Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "personalities require the uring driver",
+        )),
+    })
+}
+
+/// Wait for the next poll of current legacy driver, see
+/// `LegacyInner::poll_round`. It is ready at once on uring driver.
+#[cfg(feature = "legacy")]
+pub(crate) fn poll_legacy_round(start: &mut Option<u64>, cx: &mut Context<'_>) -> Poll<()> {
+    super::CURRENT.with(|inner| match inner {
+        #[cfg(all(target_os = "linux", feature = "iouring"))]
+        super::Inner::Uring(_) => Poll::Ready(()),
+        super::Inner::Legacy(this) => super::LegacyInner::poll_round(this, start, cx),
+    })
+}
+
+/// Take a free slot of the registered file table of current uring driver.
+/// Returns None if the table is full or not registered, or on legacy driver.
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+pub(crate) fn alloc_fixed_slot() -> Option<u32> {
+    super::CURRENT.with(|inner| match inner {
+        super::Inner::Uring(this) => super::IoUringDriver::alloc_fixed_file(this),
+        #[cfg(feature = "legacy")]
+        super::Inner::Legacy(_) => None,
+    })
+}
+
+/// Clear the slot taken by `alloc_fixed_slot` and give it back.
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+pub(crate) fn release_fixed_slot(slot: u32) {
+    if super::CURRENT.is_set() {
+        super::CURRENT.with(|inner| {
+            #[allow(irrefutable_let_patterns)]
+            if let super::Inner::Uring(this) = inner {
+                super::IoUringDriver::unregister_fixed_file(this, slot);
+            }
+        });
+    }
+}
+
 #[derive(Debug, Eq, PartialEq, Clone, Hash)]
 pub(crate) struct OpCanceller {
     pub(super) index: usize,
@@ -29,6 +29,13 @@ pub(crate) struct Accept {
     pub(crate) addr: Box<(MaybeUninit<libc::sockaddr_storage>, libc::socklen_t)>,
     #[cfg(windows)]
     pub(crate) addr: Box<(MaybeUninit<SOCKADDR_STORAGE>, socklen_t)>,
+    // Slot of the file table to install the accepted socket into. It is
+    // given back on drop unless taken.
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    pub(crate) file_index: Option<u32>,
+    // The checked slot in the form of the sqe.
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    dest_slot: Option<types::DestinationSlot>,
 }
 
 impl Op<Accept> {
@@ -49,8 +56,98 @@ impl Op<Accept> {
         Op::submit_with(Accept {
             fd: fd.clone(),
             addr,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            file_index: None,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            dest_slot: None,
         })
     }
+
+    /// Accept a connection as a direct descriptor in the slot of the file
+    /// table(requires kernel 5.19+). It must be submitted to uring driver.
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    pub(crate) fn accept_direct(fd: &SharedFd, slot: u32) -> io::Result<Self> {
+        let addr = Box::new((
+            MaybeUninit::uninit(),
+            size_of::<libc::sockaddr_storage>() as libc::socklen_t,
+        ));
+        let mut accept = Accept {
+            fd: fd.clone(),
+            addr,
+            file_index: Some(slot),
+            dest_slot: None,
+        };
+        // The slot is given back on drop if it can not be used.
+        let dest_slot = types::DestinationSlot::try_from_slot_target(slot).map_err(|slot| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("file slot {slot} is out of range"),
+            )
+        })?;
+        accept.dest_slot = Some(dest_slot);
+        Op::submit_with(accept)
+    }
+}
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+impl Drop for Accept {
+    fn drop(&mut self) {
+        if let Some(slot) = self.file_index.take() {
+            super::release_fixed_slot(slot);
+        }
+    }
+}
+
+/// Closes the accepted fd of the dropped accept op `op`, either queued before
+/// the drop or completed after it, so the connection is not leaked. Direct
+/// accepts are not closed since the results are not fds.
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+pub(super) fn close_orphan(op: &dyn std::any::Any, result: &io::Result<u32>) {
+    let owns_fd = op.is::<AcceptMulti>()
+        || op
+            .downcast_ref::<Accept>()
+            .is_some_and(|op| op.file_index.is_none());
+    if let (true, Ok(fd)) = (owns_fd, result) {
+        unsafe { libc::close(*fd as _) };
+    }
+}
+
+/// Multishot accept(requires kernel 5.19+).
+/// The peer address is not reported by the kernel for multishot accept.
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+pub(crate) struct AcceptMulti {
+    pub(crate) fd: SharedFd,
+}
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+impl Op<AcceptMulti> {
+    /// Accept connections until canceled
+    pub(crate) fn accept_multi(fd: &SharedFd) -> io::Result<Self> {
+        Op::submit_with(AcceptMulti { fd: fd.clone() })
+    }
+}
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+impl OpAble for AcceptMulti {
+    fn uring_op(&mut self) -> io_uring::squeue::Entry {
+        opcode::AcceptMulti::new(types::Fd(self.fd.raw_fd())).build()
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    #[inline]
+    fn legacy_interest(&self) -> Option<(Direction, usize)> {
+        self.fd.registered_index().map(|idx| (Direction::Read, idx))
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    fn legacy_call(&mut self) -> io::Result<u32> {
+        syscall_u32!(accept4(
+            self.fd.as_raw_fd(),
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+            libc::SOCK_CLOEXEC | libc::SOCK_NONBLOCK,
+        ))
+    }
 }
 
 impl OpAble for Accept {
@@ -61,6 +158,7 @@ impl OpAble for Accept {
             self.addr.0.as_mut_ptr() as *mut _,
             &mut self.addr.1,
         )
+        .file_index(self.dest_slot)
         .build()
     }
 
@@ -30,14 +30,19 @@ impl Op<Connect> {
         addr: SocketAddr,
         _tfo: bool,
     ) -> io::Result<Op<Connect>> {
+        Op::submit_with(Self::connect_raw(socket, addr, _tfo))
+    }
+
+    /// Build a connect request without submitting it.
+    pub(crate) fn connect_raw(socket: SharedFd, addr: SocketAddr, _tfo: bool) -> Connect {
         let (raw_addr, raw_addr_length) = socket_addr(&addr);
-        Op::submit_with(Connect {
+        Connect {
             fd: socket,
             socket_addr: Box::new(raw_addr),
             socket_addr_len: raw_addr_length,
             #[cfg(any(target_os = "ios", target_os = "macos"))]
             tfo: _tfo,
-        })
+        }
     }
 }
 
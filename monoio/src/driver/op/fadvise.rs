@@ -0,0 +1,68 @@
+use std::io;
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+use io_uring::opcode;
+
+use super::{super::shared_fd::SharedFd, Op, OpAble};
+#[cfg(any(feature = "legacy", feature = "poll-io"))]
+use crate::driver::ready::Direction;
+
+/// Announce the access pattern of file data with posix_fadvise(2).
+pub(crate) struct Fadvise {
+    fd: SharedFd,
+    offset: libc::off64_t,
+    len: libc::off64_t,
+    advice: i32,
+}
+
+/// The length of fadvise is 32-bit on io_uring, so longer ranges are split
+/// into chunks of it.
+pub(crate) const FADVISE_CHUNK: u64 = 1 << 30;
+
+impl Op<Fadvise> {
+    pub(crate) fn fadvise(
+        fd: &SharedFd,
+        offset: u64,
+        len: u64,
+        advice: i32,
+    ) -> io::Result<Op<Fadvise>> {
+        // Both are signed in the syscall, they must not wrap to negative.
+        let too_big = |_| io::Error::new(io::ErrorKind::InvalidInput, "offset or length too big");
+        Op::submit_with(Fadvise {
+            fd: fd.clone(),
+            offset: libc::off64_t::try_from(offset).map_err(too_big)?,
+            len: libc::off64_t::try_from(len).map_err(too_big)?,
+            advice,
+        })
+    }
+}
+
+impl OpAble for Fadvise {
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    fn uring_op(&mut self) -> io_uring::squeue::Entry {
+        // The caller splits the range, so the length fits in `off_t`.
+        uring_fd!(self.fd, |fd| opcode::Fadvise::new(
+            fd,
+            self.len as libc::off_t,
+            self.advice
+        )
+        .offset(self.offset as u64)
+        .build())
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    #[inline]
+    fn legacy_interest(&self) -> Option<(Direction, usize)> {
+        None
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    fn legacy_call(&mut self) -> io::Result<u32> {
+        // posix_fadvise returns the error number instead of setting errno.
+        match unsafe { libc::posix_fadvise64(self.fd.raw_fd(), self.offset, self.len, self.advice) }
+        {
+            0 => Ok(0),
+            errno => Err(io::Error::from_raw_os_error(errno)),
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use std::io;
+
+use io_uring::opcode;
+
+use super::{super::shared_fd::SharedFd, Op, OpAble};
+#[cfg(any(feature = "legacy", feature = "poll-io"))]
+use crate::{driver::ready::Direction, syscall_u32};
+
+/// Manipulate the allocated disk space of a file with fallocate(2)(requires
+/// kernel 5.6+).
+pub(crate) struct Fallocate {
+    fd: SharedFd,
+    offset: libc::off64_t,
+    len: libc::off64_t,
+    mode: i32,
+}
+
+impl Op<Fallocate> {
+    pub(crate) fn fallocate(
+        fd: &SharedFd,
+        offset: libc::off64_t,
+        len: libc::off64_t,
+        mode: i32,
+    ) -> io::Result<Op<Fallocate>> {
+        Op::submit_with(Fallocate {
+            fd: fd.clone(),
+            offset,
+            len,
+            mode,
+        })
+    }
+}
+
+impl OpAble for Fallocate {
+    fn uring_op(&mut self) -> io_uring::squeue::Entry {
+        uring_fd!(self.fd, |fd| opcode::Fallocate::new(fd, self.len as u64)
+            .offset(self.offset as u64)
+            .mode(self.mode)
+            .build())
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    #[inline]
+    fn legacy_interest(&self) -> Option<(Direction, usize)> {
+        None
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    fn legacy_call(&mut self) -> io::Result<u32> {
+        syscall_u32!(fallocate64(
+            self.fd.raw_fd(),
+            self.mode,
+            self.offset,
+            self.len
+        ))
+    }
+}
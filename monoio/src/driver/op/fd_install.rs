@@ -0,0 +1,54 @@
+use std::io;
+
+use io_uring::squeue::Flags;
+
+use super::{super::shared_fd::SharedFd, Op, OpAble};
+#[cfg(any(feature = "legacy", feature = "poll-io"))]
+use crate::driver::ready::Direction;
+
+/// Install a regular fd for a direct descriptor(requires kernel 6.8+). The
+/// fd is created with `O_CLOEXEC`.
+pub(crate) struct FixedFdInstall {
+    #[allow(unused)]
+    fd: SharedFd,
+}
+
+impl FixedFdInstall {
+    // IORING_OP_FIXED_FD_INSTALL, which io-uring has no builder for yet.
+    pub(crate) const CODE: u8 = 54;
+}
+
+impl Op<FixedFdInstall> {
+    pub(crate) fn fixed_fd_install(fd: &SharedFd) -> io::Result<Op<FixedFdInstall>> {
+        Op::submit_with(FixedFdInstall { fd: fd.clone() })
+    }
+}
+
+impl OpAble for FixedFdInstall {
+    fn uring_op(&mut self) -> io_uring::squeue::Entry {
+        let slot = self
+            .fd
+            .fixed_index()
+            .expect("direct descriptor without slot");
+        // Patch a zeroed sqe: opcode is at offset 0 and fd at offset 4, the
+        // install flags at offset 28 are left 0.
+        let mut sqe = io_uring::opcode::Nop::new().build();
+        unsafe {
+            let raw = &mut sqe as *mut io_uring::squeue::Entry as *mut u8;
+            *raw = FixedFdInstall::CODE;
+            (raw.add(4) as *mut i32).write_unaligned(slot as i32);
+        }
+        sqe.flags(Flags::FIXED_FILE)
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    #[inline]
+    fn legacy_interest(&self) -> Option<(Direction, usize)> {
+        None
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    fn legacy_call(&mut self) -> io::Result<u32> {
+        Err(io::ErrorKind::Unsupported.into())
+    }
+}
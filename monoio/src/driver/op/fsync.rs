@@ -21,6 +21,16 @@ pub(crate) struct Fsync {
     data_sync: bool,
 }
 
+impl Fsync {
+    pub(super) fn new(fd: &SharedFd, _data_sync: bool) -> Self {
+        Fsync {
+            fd: fd.clone(),
+            #[cfg(target_os = "linux")]
+            data_sync: _data_sync,
+        }
+    }
+}
+
 impl Op<Fsync> {
     pub(crate) fn fsync(fd: &SharedFd) -> io::Result<Op<Fsync>> {
         Op::submit_with(Fsync {
@@ -42,11 +52,13 @@ impl Op<Fsync> {
 impl OpAble for Fsync {
     #[cfg(all(target_os = "linux", feature = "iouring"))]
     fn uring_op(&mut self) -> io_uring::squeue::Entry {
-        let mut opc = opcode::Fsync::new(types::Fd(self.fd.raw_fd()));
-        if self.data_sync {
-            opc = opc.flags(types::FsyncFlags::DATASYNC)
-        }
-        opc.build()
+        uring_fd!(self.fd, |fd| {
+            let mut opc = opcode::Fsync::new(fd);
+            if self.data_sync {
+                opc = opc.flags(types::FsyncFlags::DATASYNC)
+            }
+            opc.build()
+        })
     }
 
     #[cfg(any(feature = "legacy", feature = "poll-io"))]
@@ -76,3 +88,56 @@ impl OpAble for Fsync {
         syscall_u32!(fsync(self.fd.raw_fd()))
     }
 }
+
+/// Sync a file segment with disk, see sync_file_range(2).
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+pub(crate) struct SyncFileRange {
+    fd: SharedFd,
+    offset: u64,
+    len: u32,
+    flags: u32,
+}
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+impl Op<SyncFileRange> {
+    pub(crate) fn sync_file_range(
+        fd: &SharedFd,
+        offset: u64,
+        len: u32,
+        flags: u32,
+    ) -> io::Result<Op<SyncFileRange>> {
+        Op::submit_with(SyncFileRange {
+            fd: fd.clone(),
+            offset,
+            len,
+            flags,
+        })
+    }
+}
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+impl OpAble for SyncFileRange {
+    #[cfg(feature = "iouring")]
+    fn uring_op(&mut self) -> io_uring::squeue::Entry {
+        uring_fd!(self.fd, |fd| opcode::SyncFileRange::new(fd, self.len)
+            .offset(self.offset)
+            .flags(self.flags)
+            .build())
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    #[inline]
+    fn legacy_interest(&self) -> Option<(Direction, usize)> {
+        None
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    fn legacy_call(&mut self) -> io::Result<u32> {
+        syscall_u32!(sync_file_range(
+            self.fd.raw_fd(),
+            self.offset as libc::off64_t,
+            self.len as libc::off64_t,
+            self.flags as libc::c_uint
+        ))
+    }
+}
@@ -0,0 +1,57 @@
+use std::io;
+
+use io_uring::squeue::Flags;
+
+use super::{super::shared_fd::SharedFd, Op, OpAble};
+#[cfg(any(feature = "legacy", feature = "poll-io"))]
+use crate::{driver::ready::Direction, syscall_u32};
+
+/// Truncate or extend a file to `len` bytes(requires kernel 6.9+).
+pub(crate) struct Ftruncate {
+    fd: SharedFd,
+    len: libc::off64_t,
+}
+
+impl Ftruncate {
+    // IORING_OP_FTRUNCATE, which io-uring has no builder for yet.
+    pub(crate) const CODE: u8 = 55;
+}
+
+impl Op<Ftruncate> {
+    pub(crate) fn ftruncate(fd: &SharedFd, len: libc::off64_t) -> io::Result<Op<Ftruncate>> {
+        Op::submit_with(Ftruncate {
+            fd: fd.clone(),
+            len,
+        })
+    }
+}
+
+impl OpAble for Ftruncate {
+    fn uring_op(&mut self) -> io_uring::squeue::Entry {
+        let (fd, flags) = match self.fd.fixed_index() {
+            Some(slot) => (slot as i32, Flags::FIXED_FILE),
+            None => (self.fd.raw_fd(), Flags::empty()),
+        };
+        // Patch a zeroed sqe: opcode is at offset 0, fd at offset 4 and the
+        // length at offset 8, where the offset of the rw ops is.
+        let mut sqe = io_uring::opcode::Nop::new().build();
+        unsafe {
+            let raw = &mut sqe as *mut io_uring::squeue::Entry as *mut u8;
+            *raw = Ftruncate::CODE;
+            (raw.add(4) as *mut i32).write_unaligned(fd);
+            (raw.add(8) as *mut u64).write_unaligned(self.len as u64);
+        }
+        sqe.flags(flags)
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    #[inline]
+    fn legacy_interest(&self) -> Option<(Direction, usize)> {
+        None
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    fn legacy_call(&mut self) -> io::Result<u32> {
+        syscall_u32!(ftruncate64(self.fd.raw_fd(), self.len))
+    }
+}
@@ -0,0 +1,65 @@
+use std::{io, sync::atomic::AtomicU32};
+
+use io_uring::opcode;
+
+use super::{Op, OpAble};
+#[cfg(any(feature = "legacy", feature = "poll-io"))]
+use crate::driver::ready::Direction;
+
+// futex2 flags, which are not in libc for linux yet.
+const FUTEX2_SIZE_U32: u32 = 0x02;
+const FUTEX2_PRIVATE: u32 = 128;
+const FUTEX_BITSET_MATCH_ANY: u64 = u32::MAX as u64;
+
+/// Wait on a futex word until it is woken(requires kernel 6.7+). It
+/// completes with EAGAIN at once if the word does not equal to `val`.
+pub(crate) struct FutexWait {
+    // The kernel only reads the word when the wait is queued.
+    futex: *const u32,
+    val: u32,
+}
+
+impl Op<FutexWait> {
+    pub(crate) fn futex_wait(futex: &AtomicU32, val: u32) -> io::Result<Op<FutexWait>> {
+        Op::submit_with(FutexWait {
+            futex: futex.as_ptr(),
+            val,
+        })
+    }
+}
+
+impl OpAble for FutexWait {
+    fn uring_op(&mut self) -> io_uring::squeue::Entry {
+        opcode::FutexWait::new(
+            self.futex,
+            self.val as u64,
+            FUTEX_BITSET_MATCH_ANY,
+            FUTEX2_SIZE_U32 | FUTEX2_PRIVATE,
+        )
+        .build()
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    #[inline]
+    fn legacy_interest(&self) -> Option<(Direction, usize)> {
+        None
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    fn legacy_call(&mut self) -> io::Result<u32> {
+        unreachable!("futex op is only submitted to uring driver")
+    }
+}
+
+/// Wake all the waiters of a futex word, no matter they wait with the op or
+/// the syscall.
+pub(crate) fn futex_wake_all(futex: &AtomicU32) {
+    unsafe {
+        libc::syscall(
+            libc::SYS_futex,
+            futex.as_ptr(),
+            libc::FUTEX_WAKE | libc::FUTEX_PRIVATE_FLAG,
+            i32::MAX,
+        );
+    }
+}
@@ -0,0 +1,119 @@
+#[cfg(target_os = "linux")]
+use std::os::fd::FromRawFd;
+use std::{
+    ffi::CString,
+    future::Future,
+    io,
+    os::fd::{AsRawFd, OwnedFd},
+    path::Path,
+};
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+use io_uring::{opcode, types};
+
+use super::{super::shared_fd::SharedFd, Op, OpAble};
+use crate::driver::util::cstr;
+#[cfg(any(feature = "legacy", feature = "poll-io"))]
+use crate::{driver::ready::Direction, syscall_u32};
+
+/// Create a hard link(uring requires kernel 5.15+).
+pub(crate) struct HardLink {
+    // The file to link instead of `original` with `AT_EMPTY_PATH`, a
+    // duplicate which may be moved to the blocking thread pool.
+    fd: Option<OwnedFd>,
+    original: CString,
+    link: CString,
+    flags: i32,
+}
+
+impl Op<HardLink> {
+    /// Submit a request to create a hard link at `link` to `original`,
+    /// relative paths are resolved against the current working directory.
+    /// See [`Op::submit_or_asyncify`].
+    pub(crate) fn hard_link<P: AsRef<Path>, Q: AsRef<Path>>(
+        original: P,
+        link: Q,
+    ) -> io::Result<impl Future<Output = io::Result<u32>>> {
+        Self::link_with(None, original, link, 0)
+    }
+
+    /// Submit a request to create a hard link at `link` to the file of `fd`,
+    /// e.g. an unnamed one opened with `O_TMPFILE`. It requires
+    /// `CAP_DAC_READ_SEARCH`, otherwise it fails with `ENOENT`.
+    #[cfg(target_os = "linux")]
+    pub(crate) fn link_fd<Q: AsRef<Path>>(
+        fd: &SharedFd,
+        link: Q,
+    ) -> io::Result<impl Future<Output = io::Result<u32>>> {
+        let fd = unsafe { OwnedFd::from_raw_fd(fd.dup()?) };
+        Self::link_with(Some(fd), "", link, libc::AT_EMPTY_PATH)
+    }
+
+    /// Submit a request to create a hard link at `link` to the target of
+    /// `original` if it is a symbolic link, e.g. the magic links of procfs.
+    #[cfg(target_os = "linux")]
+    pub(crate) fn hard_link_follow<P: AsRef<Path>, Q: AsRef<Path>>(
+        original: P,
+        link: Q,
+    ) -> io::Result<impl Future<Output = io::Result<u32>>> {
+        Self::link_with(None, original, link, libc::AT_SYMLINK_FOLLOW)
+    }
+
+    fn link_with<P: AsRef<Path>, Q: AsRef<Path>>(
+        fd: Option<OwnedFd>,
+        original: P,
+        link: Q,
+        flags: i32,
+    ) -> io::Result<impl Future<Output = io::Result<u32>>> {
+        // Here the paths will be copied, so its safe.
+        let original = cstr(original.as_ref())?;
+        let link = cstr(link.as_ref())?;
+        Op::submit_or_asyncify(HardLink {
+            fd,
+            original,
+            link,
+            flags,
+        })
+    }
+}
+
+impl HardLink {
+    #[inline]
+    fn dir_fd(&self) -> i32 {
+        self.fd
+            .as_ref()
+            .map(|fd| fd.as_raw_fd())
+            .unwrap_or(libc::AT_FDCWD)
+    }
+}
+
+impl OpAble for HardLink {
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    fn uring_op(&mut self) -> io_uring::squeue::Entry {
+        opcode::LinkAt::new(
+            types::Fd(self.dir_fd()),
+            self.original.as_ptr(),
+            types::Fd(libc::AT_FDCWD),
+            self.link.as_ptr(),
+        )
+        .flags(self.flags)
+        .build()
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    #[inline]
+    fn legacy_interest(&self) -> Option<(Direction, usize)> {
+        None
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    fn legacy_call(&mut self) -> io::Result<u32> {
+        syscall_u32!(linkat(
+            self.dir_fd(),
+            self.original.as_ptr(),
+            libc::AT_FDCWD,
+            self.link.as_ptr(),
+            self.flags
+        ))
+    }
+}
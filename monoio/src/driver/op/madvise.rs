@@ -0,0 +1,55 @@
+use std::io;
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+use io_uring::opcode;
+
+use super::{Op, OpAble};
+use crate::buf::IoBuf;
+#[cfg(any(feature = "legacy", feature = "poll-io"))]
+use crate::{driver::ready::Direction, syscall_u32};
+
+/// Give advice about the use of memory with madvise(2).
+pub(crate) struct Madvise<T> {
+    /// Holds a strong ref to the buffer, so the memory stays mapped until the
+    /// operation completes.
+    pub(crate) buf: T,
+    advice: i32,
+}
+
+impl<T: IoBuf> Op<Madvise<T>> {
+    pub(crate) fn madvise(buf: T, advice: i32) -> io::Result<Op<Madvise<T>>> {
+        Op::submit_with(Madvise { buf, advice })
+    }
+
+    pub(crate) async fn result(self) -> crate::BufResult<(), T> {
+        let complete = self.await;
+        (complete.meta.result.map(|_| ()), complete.data.buf)
+    }
+}
+
+impl<T: IoBuf> OpAble for Madvise<T> {
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    fn uring_op(&mut self) -> io_uring::squeue::Entry {
+        opcode::Madvise::new(
+            self.buf.read_ptr() as *const libc::c_void,
+            self.buf.bytes_init() as libc::off_t,
+            self.advice,
+        )
+        .build()
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    #[inline]
+    fn legacy_interest(&self) -> Option<(Direction, usize)> {
+        None
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    fn legacy_call(&mut self) -> io::Result<u32> {
+        syscall_u32!(madvise(
+            self.buf.read_ptr() as *mut libc::c_void,
+            self.buf.bytes_init(),
+            self.advice
+        ))
+    }
+}
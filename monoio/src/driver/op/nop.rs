@@ -0,0 +1,36 @@
+use std::io;
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+use io_uring::opcode;
+
+use super::{Op, OpAble};
+#[cfg(any(feature = "legacy", feature = "poll-io"))]
+use crate::driver::ready::Direction;
+
+/// An op which does nothing but completes, for measuring the round trip of
+/// the driver.
+pub(crate) struct Nop;
+
+impl Op<Nop> {
+    pub(crate) fn nop() -> io::Result<Op<Nop>> {
+        Op::submit_with(Nop)
+    }
+}
+
+impl OpAble for Nop {
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    fn uring_op(&mut self) -> io_uring::squeue::Entry {
+        opcode::Nop::new().build()
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    #[inline]
+    fn legacy_interest(&self) -> Option<(Direction, usize)> {
+        None
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    fn legacy_call(&mut self) -> io::Result<u32> {
+        Ok(0)
+    }
+}
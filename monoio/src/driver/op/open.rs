@@ -8,6 +8,8 @@ use windows_sys::Win32::{Foundation::INVALID_HANDLE_VALUE, Storage::FileSystem::
 use super::{Op, OpAble};
 #[cfg(any(feature = "legacy", feature = "poll-io"))]
 use crate::driver::ready::Direction;
+#[cfg(unix)]
+use crate::driver::shared_fd::SharedFd;
 #[cfg(windows)]
 use crate::syscall;
 #[cfg(all(unix, any(feature = "legacy", feature = "poll-io")))]
@@ -18,26 +20,103 @@ use crate::{driver::util::cstr, fs::OpenOptions};
 pub(crate) struct Open {
     pub(crate) path: CString,
     #[cfg(unix)]
+    dir: Option<SharedFd>,
+    #[cfg(unix)]
     flags: i32,
     #[cfg(unix)]
     mode: libc::mode_t,
+    // Set when there are resolve flags, then openat2 is used. It's boxed since
+    // the kernel reads it after the op is submitted.
+    #[cfg(target_os = "linux")]
+    how: Option<Box<OpenHow>>,
+    // Credentials to run with, 0 for the ones of the thread.
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    personality: u16,
     #[cfg(windows)]
     opts: OpenOptions,
 }
 
+/// `struct open_how` of openat2(2).
+#[cfg(target_os = "linux")]
+#[repr(C)]
+struct OpenHow {
+    flags: u64,
+    mode: u64,
+    resolve: u64,
+}
+
 impl Op<Open> {
     #[cfg(unix)]
     /// Submit a request to open a file.
     pub(crate) fn open<P: AsRef<Path>>(path: P, options: &OpenOptions) -> io::Result<Op<Open>> {
+        Self::open_at(None, path, options)
+    }
+
+    #[cfg(unix)]
+    /// Submit a request to open a file relative to the directory `dir`, or the
+    /// current working directory if it's `None`.
+    pub(crate) fn open_at<P: AsRef<Path>>(
+        dir: Option<&SharedFd>,
+        path: P,
+        options: &OpenOptions,
+    ) -> io::Result<Op<Open>> {
         // Here the path will be copied, so its safe.
         let path = cstr(path.as_ref())?;
         let flags = libc::O_CLOEXEC
             | options.access_mode()?
             | options.creation_mode()?
             | (options.custom_flags & !libc::O_ACCMODE);
+        #[cfg(target_os = "linux")]
+        let flags = if options.direct {
+            flags | libc::O_DIRECT
+        } else {
+            flags
+        };
         let mode = options.mode;
 
-        Op::submit_with(Open { path, flags, mode })
+        #[cfg(target_os = "linux")]
+        let how = match options.resolve_flags {
+            0 => None,
+            resolve => {
+                // Never fall back to openat, which would ignore the restriction.
+                #[cfg(feature = "iouring")]
+                if !super::is_legacy() && !super::is_uring_op_supported(opcode::OpenAt2::CODE) {
+                    return Err(io::Error::new(
+                        io::ErrorKind::Unsupported,
+                        "openat2 is not supported by the uring driver",
+                    ));
+                }
+                // Unlike openat, openat2 rejects the mode if no file is created.
+                let creating =
+                    flags & libc::O_CREAT != 0 || flags & libc::O_TMPFILE == libc::O_TMPFILE;
+                let mode = if creating { mode as u64 } else { 0 };
+                Some(Box::new(OpenHow {
+                    flags: flags as u64,
+                    mode,
+                    resolve,
+                }))
+            }
+        };
+
+        #[cfg(all(target_os = "linux", feature = "iouring"))]
+        let personality = {
+            let code = match how {
+                Some(_) => opcode::OpenAt2::CODE,
+                None => opcode::OpenAt::CODE,
+            };
+            super::personality(options.personality, code)?
+        };
+
+        Op::submit_with(Open {
+            path,
+            dir: dir.cloned(),
+            flags,
+            mode,
+            #[cfg(target_os = "linux")]
+            how,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            personality,
+        })
     }
 
     #[cfg(windows)]
@@ -53,13 +132,35 @@ impl Op<Open> {
     }
 }
 
+#[cfg(unix)]
+impl Open {
+    #[inline]
+    fn dir_fd(&self) -> i32 {
+        self.dir
+            .as_ref()
+            .map(|dir| dir.raw_fd())
+            .unwrap_or(libc::AT_FDCWD)
+    }
+}
+
 impl OpAble for Open {
     #[cfg(all(target_os = "linux", feature = "iouring"))]
     fn uring_op(&mut self) -> io_uring::squeue::Entry {
-        opcode::OpenAt::new(types::Fd(libc::AT_FDCWD), self.path.as_c_str().as_ptr())
-            .flags(self.flags)
-            .mode(self.mode)
-            .build()
+        let dir = types::Fd(self.dir_fd());
+        let entry = match &self.how {
+            // OpenHow has the same layout as io_uring's.
+            Some(how) => opcode::OpenAt2::new(
+                dir,
+                self.path.as_c_str().as_ptr(),
+                how.as_ref() as *const OpenHow as *const types::OpenHow,
+            )
+            .build(),
+            None => opcode::OpenAt::new(dir, self.path.as_c_str().as_ptr())
+                .flags(self.flags)
+                .mode(self.mode)
+                .build(),
+        };
+        entry.personality(self.personality)
     }
 
     #[cfg(any(feature = "legacy", feature = "poll-io"))]
@@ -70,7 +171,18 @@ impl OpAble for Open {
 
     #[cfg(all(any(feature = "legacy", feature = "poll-io"), not(windows)))]
     fn legacy_call(&mut self) -> io::Result<u32> {
-        syscall_u32!(open(
+        #[cfg(target_os = "linux")]
+        if let Some(how) = &self.how {
+            return syscall_u32!(syscall(
+                libc::SYS_openat2,
+                self.dir_fd(),
+                self.path.as_c_str().as_ptr(),
+                how.as_ref() as *const OpenHow,
+                std::mem::size_of::<OpenHow>()
+            ));
+        }
+        syscall_u32!(openat(
+            self.dir_fd(),
             self.path.as_c_str().as_ptr(),
             self.flags,
             self.mode as libc::c_int
@@ -1,7 +1,7 @@
 use std::io;
 
 #[cfg(all(target_os = "linux", feature = "iouring"))]
-use io_uring::{opcode, types};
+use io_uring::opcode;
 #[cfg(all(windows, any(feature = "legacy", feature = "poll-io")))]
 use {
     crate::syscall,
@@ -20,6 +20,11 @@ use crate::{
     BufResult,
 };
 
+/// The offset of -1 reads or writes at the current position of the file on
+/// io_uring, it is emulated with `read` and `write` on legacy driver.
+#[cfg(unix)]
+pub(crate) const CURRENT_POS: u64 = u64::MAX;
+
 pub(crate) struct Read<T> {
     /// Holds a strong ref to the FD, preventing the file from being closed
     /// while the operation is in-flight.
@@ -40,6 +45,13 @@ impl<T: IoBufMut> Op<Read<T>> {
         })
     }
 
+    /// Read at the current position of the file, which is the only
+    /// position of pipes and ttys. The position is moved forward.
+    #[cfg(unix)]
+    pub(crate) fn read_stream(fd: &SharedFd, buf: T) -> io::Result<Op<Read<T>>> {
+        Self::read_at(fd, buf, CURRENT_POS)
+    }
+
     pub(crate) async fn read(self) -> BufResult<usize, T> {
         let complete = self.await;
 
@@ -63,13 +75,11 @@ impl<T: IoBufMut> Op<Read<T>> {
 impl<T: IoBufMut> OpAble for Read<T> {
     #[cfg(all(target_os = "linux", feature = "iouring"))]
     fn uring_op(&mut self) -> io_uring::squeue::Entry {
-        opcode::Read::new(
-            types::Fd(self.fd.raw_fd()),
-            self.buf.write_ptr(),
-            self.buf.bytes_total() as _,
-        )
-        .offset(self.offset)
-        .build()
+        uring_fd!(self.fd, |fd| {
+            opcode::Read::new(fd, self.buf.write_ptr(), self.buf.bytes_total() as _)
+                .offset(self.offset)
+                .build()
+        })
     }
 
     #[cfg(any(feature = "legacy", feature = "poll-io"))]
@@ -81,6 +91,9 @@ impl<T: IoBufMut> OpAble for Read<T> {
     #[cfg(all(any(feature = "legacy", feature = "poll-io"), unix))]
     fn legacy_call(&mut self) -> io::Result<u32> {
         let fd = self.fd.as_raw_fd();
+        if self.offset == CURRENT_POS {
+            return syscall_u32!(read(fd, self.buf.write_ptr() as _, self.buf.bytes_total()));
+        }
         let seek_offset = libc::off_t::try_from(self.offset)
             .map_err(|_| io::Error::new(io::ErrorKind::Other, "offset too big"))?;
         #[cfg(not(target_os = "macos"))]
@@ -119,6 +132,74 @@ impl<T: IoBufMut> OpAble for Read<T> {
     }
 }
 
+/// Read into a buffer registered to the uring.
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+pub(crate) struct ReadFixed {
+    /// Holds a strong ref to the FD, preventing the file from being closed
+    /// while the operation is in-flight.
+    #[allow(unused)]
+    fd: SharedFd,
+    offset: u64,
+
+    /// Reference to the in-flight buffer.
+    pub(crate) buf: crate::buf::FixedBuf,
+}
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+impl Op<ReadFixed> {
+    pub(crate) fn read_fixed_at(
+        fd: &SharedFd,
+        buf: crate::buf::FixedBuf,
+        offset: u64,
+    ) -> io::Result<Self> {
+        Op::submit_with(ReadFixed {
+            fd: fd.clone(),
+            offset,
+            buf,
+        })
+    }
+
+    pub(crate) async fn read(self) -> BufResult<usize, crate::buf::FixedBuf> {
+        let complete = self.await;
+        let res = complete.meta.result.map(|v| v as usize);
+        let mut buf = complete.data.buf;
+        if let Ok(n) = res {
+            // Safety: the kernel wrote `n` bytes to the buffer.
+            unsafe {
+                buf.set_init(n);
+            }
+        }
+        (res, buf)
+    }
+}
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+impl OpAble for ReadFixed {
+    fn uring_op(&mut self) -> io_uring::squeue::Entry {
+        uring_fd!(self.fd, |fd| {
+            opcode::ReadFixed::new(
+                fd,
+                self.buf.write_ptr(),
+                self.buf.bytes_total() as _,
+                self.buf.buf_index(),
+            )
+            .offset(self.offset)
+            .build()
+        })
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    #[inline]
+    fn legacy_interest(&self) -> Option<(Direction, usize)> {
+        None
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    fn legacy_call(&mut self) -> io::Result<u32> {
+        Err(io::ErrorKind::Unsupported.into())
+    }
+}
+
 pub(crate) struct ReadVec<T> {
     /// Holds a strong ref to the FD, preventing the file from being closed
     /// while the operation is in-flight.
@@ -152,9 +233,11 @@ impl<T: IoVecBufMut> Op<ReadVec<T>> {
 impl<T: IoVecBufMut> OpAble for ReadVec<T> {
     #[cfg(all(target_os = "linux", feature = "iouring"))]
     fn uring_op(&mut self) -> io_uring::squeue::Entry {
-        let ptr = self.buf_vec.write_iovec_ptr() as _;
-        let len = self.buf_vec.write_iovec_len() as _;
-        opcode::Readv::new(types::Fd(self.fd.raw_fd()), ptr, len).build()
+        uring_fd!(self.fd, |fd| {
+            let ptr = self.buf_vec.write_iovec_ptr() as _;
+            let len = self.buf_vec.write_iovec_len() as _;
+            opcode::Readv::new(fd, ptr, len).build()
+        })
     }
 
     #[cfg(any(feature = "legacy", feature = "poll-io"))]
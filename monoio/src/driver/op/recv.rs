@@ -1,13 +1,17 @@
-use std::{io, net::SocketAddr};
+use std::{io, net::SocketAddr, time::Duration};
 
 #[cfg(all(target_os = "linux", feature = "iouring"))]
-use io_uring::{opcode, types};
+use io_uring::opcode;
 #[cfg(unix)]
 use {
-    crate::net::unix::SocketAddr as UnixSocketAddr,
+    crate::net::{
+        udp::RecvMeta,
+        unix::{SocketAddr as UnixSocketAddr, UCred},
+    },
     libc::{socklen_t, AF_INET, AF_INET6},
     std::mem::{transmute, MaybeUninit},
     std::net::{Ipv4Addr, Ipv6Addr, SocketAddrV4, SocketAddrV6},
+    std::os::unix::io::{FromRawFd, OwnedFd},
 };
 #[cfg(all(windows, any(feature = "legacy", feature = "poll-io")))]
 use {
@@ -17,7 +21,9 @@ use {
 #[cfg(all(unix, any(feature = "legacy", feature = "poll-io")))]
 use {crate::syscall_u32, std::os::unix::prelude::AsRawFd};
 
-use super::{super::shared_fd::SharedFd, Op, OpAble};
+use super::{super::shared_fd::SharedFd, Completion, Op, OpAble};
+#[cfg(unix)]
+use crate::buf::IoVecBufMut;
 #[cfg(any(feature = "legacy", feature = "poll-io"))]
 use crate::driver::ready::Direction;
 use crate::{buf::IoBufMut, BufResult};
@@ -30,11 +36,35 @@ pub(crate) struct Recv<T> {
 
     /// Reference to the in-flight buffer.
     pub(crate) buf: T,
+
+    /// Flags passed to recv, e.g. `MSG_PEEK`.
+    flags: i32,
 }
 
 impl<T: IoBufMut> Op<Recv<T>> {
     pub(crate) fn recv(fd: SharedFd, buf: T) -> io::Result<Self> {
-        Op::submit_with(Recv { fd, buf })
+        Op::submit_with(Recv { fd, buf, flags: 0 })
+    }
+
+    /// Recv with `MSG_PEEK`, the data is kept in the receive queue.
+    #[cfg(unix)]
+    pub(crate) fn recv_peek(fd: SharedFd, buf: T) -> io::Result<Self> {
+        Op::submit_with(Recv {
+            fd,
+            buf,
+            flags: libc::MSG_PEEK,
+        })
+    }
+
+    /// Recv with `MSG_TRUNC`, which makes the result the real length of the
+    /// record even if it is truncated.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub(crate) fn recv_trunc(fd: SharedFd, buf: T) -> io::Result<Self> {
+        Op::submit_with(Recv {
+            fd,
+            buf,
+            flags: libc::MSG_TRUNC,
+        })
     }
 
     #[allow(unused)]
@@ -42,11 +72,50 @@ impl<T: IoBufMut> Op<Recv<T>> {
         Recv {
             fd: fd.clone(),
             buf,
+            flags: 0,
         }
     }
 
     pub(crate) async fn read(self) -> BufResult<usize, T> {
+        Recv::read_result(self.await)
+    }
+
+    /// Wait for the op submitted with `recv_trunc`, returns the number of
+    /// bytes received into the buffer and the real length of the record.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub(crate) async fn read_trunc(self) -> BufResult<(usize, usize), T> {
         let complete = self.await;
+        let mut buf = complete.data.buf;
+        let res = complete.meta.result.map(|v| {
+            let record_len = v as usize;
+            let n = record_len.min(buf.bytes_total());
+            // Safety: the kernel wrote `n` bytes to the buffer.
+            unsafe {
+                buf.set_init(n);
+            }
+            (n, record_len)
+        });
+        (res, buf)
+    }
+
+    /// Recv which fails with `TimedOut` if it does not complete within
+    /// `timeout`.
+    pub(crate) async fn recv_timeout(
+        fd: &SharedFd,
+        buf: T,
+        timeout: Duration,
+    ) -> BufResult<usize, T> {
+        let complete = Op::with_timeout(Self::recv_raw(fd, buf), timeout)
+            .await
+            .unwrap();
+        Recv::read_result(complete)
+    }
+}
+
+impl<T: IoBufMut> Recv<T> {
+    /// Take the buffer of a completed recv and mark the received bytes as
+    /// initialized.
+    fn read_result(complete: Completion<Self>) -> BufResult<usize, T> {
         let res = complete.meta.result.map(|v| v as _);
         let mut buf = complete.data.buf;
 
@@ -63,12 +132,11 @@ impl<T: IoBufMut> Op<Recv<T>> {
 impl<T: IoBufMut> OpAble for Recv<T> {
     #[cfg(all(target_os = "linux", feature = "iouring"))]
     fn uring_op(&mut self) -> io_uring::squeue::Entry {
-        opcode::Recv::new(
-            types::Fd(self.fd.raw_fd()),
-            self.buf.write_ptr(),
-            self.buf.bytes_total() as _,
-        )
-        .build()
+        uring_fd!(self.fd, |fd| {
+            opcode::Recv::new(fd, self.buf.write_ptr(), self.buf.bytes_total() as _)
+                .flags(self.flags)
+                .build()
+        })
     }
 
     #[cfg(any(feature = "legacy", feature = "poll-io"))]
@@ -84,7 +152,7 @@ impl<T: IoBufMut> OpAble for Recv<T> {
             fd,
             self.buf.write_ptr() as _,
             self.buf.bytes_total().min(u32::MAX as usize),
-            0
+            self.flags
         ))
     }
 
@@ -96,7 +164,7 @@ impl<T: IoBufMut> OpAble for Recv<T> {
                 fd as _,
                 self.buf.write_ptr(),
                 self.buf.bytes_total() as _,
-                0
+                self.flags
             ),
             PartialOrd::ge,
             0
@@ -104,6 +172,124 @@ impl<T: IoBufMut> OpAble for Recv<T> {
     }
 }
 
+/// Multishot recv with provided buffers(requires kernel 6.0+).
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+pub(crate) struct RecvMulti {
+    /// Holds a strong ref to the FD, preventing the file from being closed
+    /// while the operation is in-flight.
+    #[allow(unused)]
+    fd: SharedFd,
+
+    /// Holds a strong ref to the buffer ring, the kernel may still write to
+    /// its buffers while the operation is in-flight.
+    pub(crate) ring: std::rc::Rc<crate::driver::BufRing>,
+}
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+impl Op<RecvMulti> {
+    pub(crate) fn recv_multi(
+        fd: SharedFd,
+        ring: std::rc::Rc<crate::driver::BufRing>,
+    ) -> io::Result<Self> {
+        Op::submit_with(RecvMulti { fd, ring })
+    }
+}
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+impl OpAble for RecvMulti {
+    fn uring_op(&mut self) -> io_uring::squeue::Entry {
+        uring_fd!(self.fd, |fd| {
+            opcode::RecvMulti::new(fd, self.ring.bgid()).build()
+        })
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    #[inline]
+    fn legacy_interest(&self) -> Option<(Direction, usize)> {
+        None
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    fn legacy_call(&mut self) -> io::Result<u32> {
+        Err(io::ErrorKind::Unsupported.into())
+    }
+}
+
+/// Recv with a buffer picked by the kernel from a provided buffer ring.
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+pub(crate) struct RecvProvided {
+    /// Holds a strong ref to the FD, preventing the file from being closed
+    /// while the operation is in-flight.
+    #[allow(unused)]
+    fd: SharedFd,
+
+    /// Holds a strong ref to the buffer ring, the kernel may still write to
+    /// its buffers while the operation is in-flight.
+    ring: std::rc::Rc<crate::driver::BufRing>,
+}
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+impl Op<RecvProvided> {
+    pub(crate) fn recv_provided(
+        fd: SharedFd,
+        ring: std::rc::Rc<crate::driver::BufRing>,
+    ) -> io::Result<Self> {
+        Op::submit_with(RecvProvided { fd, ring })
+    }
+
+    pub(crate) async fn wait(self) -> io::Result<crate::buf::ProvidedBuf> {
+        let complete = self.await;
+        let n = complete.meta.result?;
+        // Map the buffer id in the flags back to the slot of the ring.
+        let bid = io_uring::cqueue::buffer_select(complete.meta.flags);
+        Ok(crate::buf::ProvidedBuf::new(
+            complete.data.ring,
+            bid,
+            n as _,
+        ))
+    }
+}
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+impl OpAble for RecvProvided {
+    fn uring_op(&mut self) -> io_uring::squeue::Entry {
+        uring_fd!(self.fd, |fd| {
+            opcode::Recv::new(fd, std::ptr::null_mut(), self.ring.buf_len() as _)
+                .buf_group(self.ring.bgid())
+                .build()
+                .flags(io_uring::squeue::Flags::BUFFER_SELECT)
+        })
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    #[inline]
+    fn legacy_interest(&self) -> Option<(Direction, usize)> {
+        None
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    fn legacy_call(&mut self) -> io::Result<u32> {
+        Err(io::ErrorKind::Unsupported.into())
+    }
+}
+
+/// Gives the buffer picked by the kernel for the dropped op `op` back to its
+/// ring, or the ring runs out of buffers.
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+pub(super) fn recycle_orphan(op: &dyn std::any::Any, flags: u32) {
+    let ring = match (
+        op.downcast_ref::<RecvProvided>(),
+        op.downcast_ref::<RecvMulti>(),
+    ) {
+        (Some(op), _) => &op.ring,
+        (_, Some(op)) => &op.ring,
+        _ => return,
+    };
+    if let Some(bid) = io_uring::cqueue::buffer_select(flags) {
+        ring.recycle(bid);
+    }
+}
+
 pub(crate) struct RecvMsg<T> {
     /// Holds a strong ref to the FD, preventing the file from being closed
     /// while the operation is in-flight.
@@ -118,11 +304,51 @@ pub(crate) struct RecvMsg<T> {
         [libc::iovec; 1],
         libc::msghdr,
     )>,
+    #[cfg(unix)]
+    flags: libc::c_int,
+    /// Buffer of the ancillary data, u64 makes it aligned for cmsghdr.
+    #[cfg(unix)]
+    control: Vec<u64>,
 }
 
 #[cfg(unix)]
 impl<T: IoBufMut> Op<RecvMsg<T>> {
-    pub(crate) fn recv_msg(fd: SharedFd, mut buf: T) -> io::Result<Self> {
+    pub(crate) fn recv_msg(fd: SharedFd, buf: T) -> io::Result<Self> {
+        Self::recv_msg_with_flags(fd, buf, 0)
+    }
+
+    /// Receive with `MSG_TRUNC`, which makes the result the real length of
+    /// the datagram even if it is truncated.
+    pub(crate) fn recv_msg_trunc(fd: SharedFd, buf: T) -> io::Result<Self> {
+        Self::recv_msg_with_flags(fd, buf, libc::MSG_TRUNC)
+    }
+
+    pub(crate) fn recv_msg_with_flags(
+        fd: SharedFd,
+        buf: T,
+        flags: libc::c_int,
+    ) -> io::Result<Self> {
+        Self::recv_msg_with_control(fd, buf, flags, 0)
+    }
+
+    /// Receive with `MSG_TRUNC` like `recv_msg_trunc`, and a control buffer
+    /// for the `UDP_GRO` and `IP_PKTINFO`/`IPV6_PKTINFO` messages, which are
+    /// sent if the options are enabled on the socket.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub(crate) fn recv_msg_meta(fd: SharedFd, buf: T) -> io::Result<Self> {
+        let space = |len: usize| unsafe { libc::CMSG_SPACE(len as _) } as usize;
+        let control_len = space(std::mem::size_of::<libc::c_int>())
+            + space(std::mem::size_of::<libc::in_pktinfo>())
+            + space(std::mem::size_of::<libc::in6_pktinfo>());
+        Self::recv_msg_with_control(fd, buf, libc::MSG_TRUNC, control_len)
+    }
+
+    fn recv_msg_with_control(
+        fd: SharedFd,
+        mut buf: T,
+        flags: libc::c_int,
+        control_len: usize,
+    ) -> io::Result<Self> {
         let iovec = [libc::iovec {
             iov_base: buf.write_ptr() as *mut _,
             iov_len: buf.bytes_total(),
@@ -138,7 +364,19 @@ impl<T: IoBufMut> Op<RecvMsg<T>> {
         info.2.msg_name = &mut info.0 as *mut _ as *mut libc::c_void;
         info.2.msg_namelen = std::mem::size_of::<libc::sockaddr_storage>() as socklen_t;
 
-        Op::submit_with(RecvMsg { fd, buf, info })
+        let mut control = vec![0u64; control_len.div_ceil(std::mem::size_of::<u64>())];
+        if control_len != 0 {
+            info.2.msg_control = control.as_mut_ptr() as *mut libc::c_void;
+            info.2.msg_controllen = control_len as _;
+        }
+
+        Op::submit_with(RecvMsg {
+            fd,
+            buf,
+            info,
+            flags,
+            control,
+        })
     }
 
     pub(crate) async fn wait(self) -> BufResult<(usize, SocketAddr), T> {
@@ -147,36 +385,7 @@ impl<T: IoBufMut> Op<RecvMsg<T>> {
         let mut buf = complete.data.buf;
 
         let res = res.map(|n| {
-            let storage = unsafe { complete.data.info.0.assume_init() };
-
-            let addr = unsafe {
-                match storage.ss_family as libc::c_int {
-                    AF_INET => {
-                        // Safety: if the ss_family field is AF_INET then storage must be a
-                        // sockaddr_in.
-                        let addr: &libc::sockaddr_in = transmute(&storage);
-                        let ip = Ipv4Addr::from(addr.sin_addr.s_addr.to_ne_bytes());
-                        let port = u16::from_be(addr.sin_port);
-                        SocketAddr::V4(SocketAddrV4::new(ip, port))
-                    }
-                    AF_INET6 => {
-                        // Safety: if the ss_family field is AF_INET6 then storage must be a
-                        // sockaddr_in6.
-                        let addr: &libc::sockaddr_in6 = transmute(&storage);
-                        let ip = Ipv6Addr::from(addr.sin6_addr.s6_addr);
-                        let port = u16::from_be(addr.sin6_port);
-                        SocketAddr::V6(SocketAddrV6::new(
-                            ip,
-                            port,
-                            addr.sin6_flowinfo,
-                            addr.sin6_scope_id,
-                        ))
-                    }
-                    _ => {
-                        unreachable!()
-                    }
-                }
-            };
+            let addr = unsafe { sockaddr(complete.data.info.0.assume_init()) };
 
             // Safety: the kernel wrote `n` bytes to the buffer.
             unsafe {
@@ -187,6 +396,96 @@ impl<T: IoBufMut> Op<RecvMsg<T>> {
         });
         (res, buf)
     }
+
+    /// Wait for the op submitted with `recv_msg_trunc` or `recv_msg_meta`,
+    /// the control messages are parsed if there is a control buffer.
+    pub(crate) async fn wait_meta(self) -> BufResult<RecvMeta, T> {
+        let complete = self.await;
+        let res = complete.meta.result.map(|v| v as usize);
+        let mut buf = complete.data.buf;
+
+        let res = res.map(|datagram_len| {
+            let addr = unsafe { sockaddr(complete.data.info.0.assume_init()) };
+            let len = datagram_len.min(buf.bytes_total());
+
+            // Safety: the kernel wrote `len` bytes to the buffer.
+            unsafe {
+                buf.set_init(len);
+            }
+
+            let mut meta = RecvMeta {
+                len,
+                datagram_len,
+                segment_size: None,
+                dst_addr: None,
+                ifindex: None,
+                addr,
+            };
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            if !complete.data.control.is_empty() {
+                let msg = &complete.data.info.2;
+                let mut cmsg = unsafe { libc::CMSG_FIRSTHDR(msg) };
+                while !cmsg.is_null() {
+                    let header = unsafe { &*cmsg };
+                    let data = unsafe { libc::CMSG_DATA(cmsg) };
+                    match (header.cmsg_level, header.cmsg_type) {
+                        (libc::SOL_UDP, libc::UDP_GRO) => {
+                            let size = unsafe { (data as *const libc::c_int).read_unaligned() };
+                            meta.segment_size = Some(size as u16);
+                        }
+                        (libc::IPPROTO_IP, libc::IP_PKTINFO) => {
+                            let info =
+                                unsafe { (data as *const libc::in_pktinfo).read_unaligned() };
+                            let ip = Ipv4Addr::from(info.ipi_addr.s_addr.to_ne_bytes());
+                            meta.dst_addr = Some(ip.into());
+                            meta.ifindex = Some(info.ipi_ifindex as u32);
+                        }
+                        (libc::IPPROTO_IPV6, libc::IPV6_PKTINFO) => {
+                            let info =
+                                unsafe { (data as *const libc::in6_pktinfo).read_unaligned() };
+                            meta.dst_addr = Some(Ipv6Addr::from(info.ipi6_addr.s6_addr).into());
+                            meta.ifindex = Some(info.ipi6_ifindex);
+                        }
+                        _ => (),
+                    }
+                    cmsg = unsafe { libc::CMSG_NXTHDR(msg, cmsg) };
+                }
+            }
+            meta
+        });
+        (res, buf)
+    }
+}
+
+/// Convert the address written by the kernel.
+#[cfg(unix)]
+pub(super) unsafe fn sockaddr(storage: libc::sockaddr_storage) -> SocketAddr {
+    match storage.ss_family as libc::c_int {
+        AF_INET => {
+            // Safety: if the ss_family field is AF_INET then storage must be a
+            // sockaddr_in.
+            let addr: &libc::sockaddr_in = transmute(&storage);
+            let ip = Ipv4Addr::from(addr.sin_addr.s_addr.to_ne_bytes());
+            let port = u16::from_be(addr.sin_port);
+            SocketAddr::V4(SocketAddrV4::new(ip, port))
+        }
+        AF_INET6 => {
+            // Safety: if the ss_family field is AF_INET6 then storage must be a
+            // sockaddr_in6.
+            let addr: &libc::sockaddr_in6 = transmute(&storage);
+            let ip = Ipv6Addr::from(addr.sin6_addr.s6_addr);
+            let port = u16::from_be(addr.sin6_port);
+            SocketAddr::V6(SocketAddrV6::new(
+                ip,
+                port,
+                addr.sin6_flowinfo,
+                addr.sin6_scope_id,
+            ))
+        }
+        _ => {
+            unreachable!()
+        }
+    }
 }
 
 #[cfg(windows)]
@@ -204,7 +503,11 @@ impl<T: IoBufMut> Op<RecvMsg<T>> {
 impl<T: IoBufMut> OpAble for RecvMsg<T> {
     #[cfg(all(target_os = "linux", feature = "iouring"))]
     fn uring_op(&mut self) -> io_uring::squeue::Entry {
-        opcode::RecvMsg::new(types::Fd(self.fd.raw_fd()), &mut self.info.2 as *mut _).build()
+        uring_fd!(self.fd, |fd| {
+            opcode::RecvMsg::new(fd, &mut self.info.2 as *mut _)
+                .flags(self.flags as u32)
+                .build()
+        })
     }
 
     #[cfg(any(feature = "legacy", feature = "poll-io"))]
@@ -216,7 +519,7 @@ impl<T: IoBufMut> OpAble for RecvMsg<T> {
     #[cfg(all(any(feature = "legacy", feature = "poll-io"), unix))]
     fn legacy_call(&mut self) -> io::Result<u32> {
         let fd = self.fd.as_raw_fd();
-        syscall_u32!(recvmsg(fd, &mut self.info.2 as *mut _, 0))
+        syscall_u32!(recvmsg(fd, &mut self.info.2 as *mut _, self.flags))
     }
 
     #[cfg(all(any(feature = "legacy", feature = "poll-io"), windows))]
@@ -226,6 +529,87 @@ impl<T: IoBufMut> OpAble for RecvMsg<T> {
     }
 }
 
+#[cfg(unix)]
+pub(crate) struct RecvMsgVectored<T> {
+    /// Holds a strong ref to the FD, preventing the file from being closed
+    /// while the operation is in-flight.
+    #[allow(unused)]
+    fd: SharedFd,
+
+    /// Reference to the in-flight buffer, the msghdr points to its iovecs.
+    pub(crate) buf_vec: T,
+    pub(crate) info: Box<(MaybeUninit<libc::sockaddr_storage>, libc::msghdr)>,
+}
+
+#[cfg(unix)]
+impl<T: IoVecBufMut> Op<RecvMsgVectored<T>> {
+    pub(crate) fn recv_msg_vectored(fd: SharedFd, mut buf_vec: T) -> io::Result<Self> {
+        let mut info: Box<(MaybeUninit<libc::sockaddr_storage>, libc::msghdr)> =
+            Box::new((MaybeUninit::uninit(), unsafe { std::mem::zeroed() }));
+
+        info.1.msg_iov = buf_vec.write_iovec_ptr();
+        info.1.msg_iovlen = buf_vec.write_iovec_len() as _;
+        info.1.msg_name = &mut info.0 as *mut _ as *mut libc::c_void;
+        info.1.msg_namelen = std::mem::size_of::<libc::sockaddr_storage>() as socklen_t;
+
+        Op::submit_with(RecvMsgVectored { fd, buf_vec, info })
+    }
+
+    pub(crate) async fn wait(self) -> BufResult<(usize, SocketAddr), T> {
+        let complete = self.await;
+        let res = complete.meta.result.map(|v| v as _);
+        let mut buf_vec = complete.data.buf_vec;
+
+        let res = res.map(|n| {
+            let addr = unsafe { sockaddr(complete.data.info.0.assume_init()) };
+
+            // Safety: the kernel wrote `n` bytes to the buffers in order, so
+            // the sub-buffers are filled one after another.
+            unsafe {
+                buf_vec.set_init(n);
+            }
+
+            (n, addr)
+        });
+        (res, buf_vec)
+    }
+
+    /// Wait without converting the address, for the connected sockets and
+    /// the unix ones.
+    pub(crate) async fn read(self) -> BufResult<usize, T> {
+        let complete = self.await;
+        let res = complete.meta.result.map(|v| v as _);
+        let mut buf_vec = complete.data.buf_vec;
+        if let Ok(n) = res {
+            // Safety: the kernel wrote `n` bytes to the buffers in order.
+            unsafe { buf_vec.set_init(n) };
+        }
+        (res, buf_vec)
+    }
+}
+
+#[cfg(unix)]
+impl<T: IoVecBufMut> OpAble for RecvMsgVectored<T> {
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    fn uring_op(&mut self) -> io_uring::squeue::Entry {
+        uring_fd!(self.fd, |fd| {
+            opcode::RecvMsg::new(fd, &mut self.info.1 as *mut _).build()
+        })
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    #[inline]
+    fn legacy_interest(&self) -> Option<(Direction, usize)> {
+        self.fd.registered_index().map(|idx| (Direction::Read, idx))
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    fn legacy_call(&mut self) -> io::Result<u32> {
+        let fd = self.fd.as_raw_fd();
+        syscall_u32!(recvmsg(fd, &mut self.info.1 as *mut _, 0))
+    }
+}
+
 #[cfg(unix)]
 pub(crate) struct RecvMsgUnix<T> {
     /// Holds a strong ref to the FD, preventing the file from being closed
@@ -235,16 +619,100 @@ pub(crate) struct RecvMsgUnix<T> {
 
     /// Reference to the in-flight buffer.
     pub(crate) buf: T,
-    pub(crate) info: Box<(
+    msg: UnixMsg,
+    flags: libc::c_int,
+}
+
+/// The msghdr of `RecvMsgUnix` and its ancillary data. The fds received with
+/// `SCM_RIGHTS` are closed on drop unless they are taken, so they are not
+/// leaked if the op is dropped after the kernel completes it.
+#[cfg(unix)]
+struct UnixMsg {
+    info: Box<(
         MaybeUninit<libc::sockaddr_storage>,
         [libc::iovec; 1],
         libc::msghdr,
     )>,
+    /// Buffer of the ancillary data, u64 makes it aligned for cmsghdr.
+    control: Vec<u64>,
+}
+
+#[cfg(unix)]
+impl UnixMsg {
+    /// Takes the received fds and the credentials, at most once. The control
+    /// buffer is zeroed until the kernel writes it.
+    fn take_ancillary(&mut self) -> (Vec<OwnedFd>, Option<UCred>) {
+        let msg = &self.info.2;
+        let mut received = Vec::new();
+        #[allow(unused_mut)]
+        let mut creds = None;
+        let mut cmsg = unsafe { libc::CMSG_FIRSTHDR(msg) };
+        while !cmsg.is_null() {
+            let header = unsafe { &*cmsg };
+            let data = unsafe { libc::CMSG_DATA(cmsg) };
+            let Some(len) =
+                (header.cmsg_len as usize).checked_sub(unsafe { libc::CMSG_LEN(0) } as usize)
+            else {
+                break;
+            };
+            match (header.cmsg_level, header.cmsg_type) {
+                (libc::SOL_SOCKET, libc::SCM_RIGHTS) => {
+                    let data = data as *const libc::c_int;
+                    for i in 0..len / std::mem::size_of::<libc::c_int>() {
+                        let fd = unsafe { data.add(i).read_unaligned() };
+                        received.push(unsafe { OwnedFd::from_raw_fd(fd) });
+                    }
+                }
+                #[cfg(any(target_os = "linux", target_os = "android"))]
+                (libc::SOL_SOCKET, libc::SCM_CREDENTIALS)
+                    if len >= std::mem::size_of::<libc::ucred>() =>
+                {
+                    let ucred = unsafe { (data as *const libc::ucred).read_unaligned() };
+                    creds = Some(UCred::from_ucred(ucred));
+                }
+                _ => {}
+            }
+            cmsg = unsafe { libc::CMSG_NXTHDR(msg, cmsg) };
+        }
+        self.info.2.msg_controllen = 0;
+        (received, creds)
+    }
+}
+
+#[cfg(unix)]
+impl Drop for UnixMsg {
+    fn drop(&mut self) {
+        self.take_ancillary();
+    }
 }
 
 #[cfg(unix)]
 impl<T: IoBufMut> Op<RecvMsgUnix<T>> {
-    pub(crate) fn recv_msg_unix(fd: SharedFd, mut buf: T) -> io::Result<Self> {
+    pub(crate) fn recv_msg_unix(fd: SharedFd, buf: T) -> io::Result<Self> {
+        Self::recv_msg_unix_with_fds(fd, buf, 0)
+    }
+
+    /// Receive with a control buffer for at most `max_fds` fds passed with
+    /// `SCM_RIGHTS`. The received fds are close-on-exec.
+    pub(crate) fn recv_msg_unix_with_fds(fd: SharedFd, buf: T, max_fds: usize) -> io::Result<Self> {
+        Self::recv_msg_unix_with_ancillary(
+            fd,
+            buf,
+            max_fds,
+            cfg!(any(target_os = "linux", target_os = "android")),
+        )
+    }
+
+    /// Receive with a control buffer for at most `max_fds` fds, and the
+    /// `SCM_CREDENTIALS` message sent with every message if `SO_PASSCRED` is
+    /// enabled. It is only sized for the credentials with `creds`, so they
+    /// arriving with the fds do not truncate the fds.
+    pub(crate) fn recv_msg_unix_with_ancillary(
+        fd: SharedFd,
+        mut buf: T,
+        max_fds: usize,
+        creds: bool,
+    ) -> io::Result<Self> {
         let iovec = [libc::iovec {
             iov_base: buf.write_ptr() as *mut _,
             iov_len: buf.bytes_total(),
@@ -260,7 +728,91 @@ impl<T: IoBufMut> Op<RecvMsgUnix<T>> {
         info.2.msg_name = &mut info.0 as *mut _ as *mut libc::c_void;
         info.2.msg_namelen = std::mem::size_of::<libc::sockaddr_storage>() as socklen_t;
 
-        Op::submit_with(RecvMsgUnix { fd, buf, info })
+        // The credentials come before the fds.
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        let creds_space = if creds {
+            let space = unsafe { libc::CMSG_SPACE(std::mem::size_of::<libc::ucred>() as _) };
+            space as usize
+        } else {
+            0
+        };
+        #[cfg(not(any(target_os = "linux", target_os = "android")))]
+        let creds_space = {
+            let _ = creds;
+            0
+        };
+
+        let mut control = Vec::new();
+        let mut flags = 0;
+        if max_fds != 0 || creds_space != 0 {
+            let fds_len = (max_fds * std::mem::size_of::<libc::c_int>()) as _;
+            let space = creds_space + unsafe { libc::CMSG_SPACE(fds_len) } as usize;
+            control.resize(space.div_ceil(std::mem::size_of::<u64>()), 0);
+            info.2.msg_control = control.as_mut_ptr() as *mut libc::c_void;
+            // Not the padded space, or the kernel may put more fds than the
+            // slots into the padding.
+            let mut len = creds_space;
+            if max_fds != 0 {
+                len += unsafe { libc::CMSG_LEN(fds_len) } as usize;
+            }
+            info.2.msg_controllen = len as _;
+            flags = libc::MSG_CMSG_CLOEXEC;
+        }
+
+        Op::submit_with(RecvMsgUnix {
+            fd,
+            buf,
+            msg: UnixMsg { info, control },
+            flags,
+        })
+    }
+
+    /// Wait for the data and the fds, which are put into the slots of `fds`
+    /// in order. Returns the number of bytes and fds received.
+    pub(crate) async fn wait_with_fds(
+        self,
+        fds: &mut [Option<OwnedFd>],
+    ) -> BufResult<(usize, usize), T> {
+        let (res, buf) = self.wait_with_ancillary(fds).await;
+        (res.map(|(n, count, _)| (n, count)), buf)
+    }
+
+    /// Wait for the data, the fds and the credentials. Returns the number of
+    /// bytes and fds received, and the credentials if there are.
+    pub(crate) async fn wait_with_ancillary(
+        self,
+        fds: &mut [Option<OwnedFd>],
+    ) -> BufResult<(usize, usize, Option<UCred>), T> {
+        let complete = self.await;
+        let mut buf = complete.data.buf;
+        let n = match complete.meta.result {
+            Ok(n) => n as usize,
+            Err(e) => return (Err(e), buf),
+        };
+
+        // Take the ownership of all the received fds first, so they are
+        // closed if anything goes wrong.
+        let mut msg = complete.data.msg;
+        let (received, creds) = msg.take_ancillary();
+        // Without the credentials, the fds may take their space too.
+        if msg.info.2.msg_flags & libc::MSG_CTRUNC != 0 || received.len() > fds.len() {
+            return (
+                Err(io::Error::other(
+                    "ancillary data is truncated, there are more fds than the slots",
+                )),
+                buf,
+            );
+        }
+        let count = received.len();
+        for (slot, fd) in fds.iter_mut().zip(received) {
+            *slot = Some(fd);
+        }
+
+        // Safety: the kernel wrote `n` bytes to the buffer.
+        unsafe {
+            buf.set_init(n);
+        }
+        (Ok((n, count, creds)), buf)
     }
 
     pub(crate) async fn wait(self) -> BufResult<(usize, UnixSocketAddr), T> {
@@ -269,8 +821,8 @@ impl<T: IoBufMut> Op<RecvMsgUnix<T>> {
         let mut buf = complete.data.buf;
 
         let res = res.map(|n| {
-            let storage = unsafe { complete.data.info.0.assume_init() };
-            let name_len = complete.data.info.2.msg_namelen;
+            let storage = unsafe { complete.data.msg.info.0.assume_init() };
+            let name_len = complete.data.msg.info.2.msg_namelen;
 
             let addr = unsafe {
                 let addr: &libc::sockaddr_un = transmute(&storage);
@@ -292,7 +844,11 @@ impl<T: IoBufMut> Op<RecvMsgUnix<T>> {
 impl<T: IoBufMut> OpAble for RecvMsgUnix<T> {
     #[cfg(all(target_os = "linux", feature = "iouring"))]
     fn uring_op(&mut self) -> io_uring::squeue::Entry {
-        opcode::RecvMsg::new(types::Fd(self.fd.raw_fd()), &mut self.info.2 as *mut _).build()
+        uring_fd!(self.fd, |fd| {
+            opcode::RecvMsg::new(fd, &mut self.msg.info.2 as *mut _)
+                .flags(self.flags as u32)
+                .build()
+        })
     }
 
     #[cfg(any(feature = "legacy", feature = "poll-io"))]
@@ -304,6 +860,6 @@ impl<T: IoBufMut> OpAble for RecvMsgUnix<T> {
     #[cfg(any(feature = "legacy", feature = "poll-io"))]
     fn legacy_call(&mut self) -> io::Result<u32> {
         let fd = self.fd.as_raw_fd();
-        syscall_u32!(recvmsg(fd, &mut self.info.2 as *mut _, 0))
+        syscall_u32!(recvmsg(fd, &mut self.msg.info.2 as *mut _, self.flags))
     }
 }
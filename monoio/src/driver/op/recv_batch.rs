@@ -0,0 +1,202 @@
+use std::{
+    cell::UnsafeCell,
+    io,
+    mem::MaybeUninit,
+    net::{Ipv4Addr, SocketAddr},
+    rc::Rc,
+};
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+use io_uring::opcode;
+#[cfg(any(feature = "legacy", feature = "poll-io"))]
+use {crate::driver::ready::Direction, std::os::unix::prelude::AsRawFd};
+
+use super::{super::shared_fd::SharedFd, recv::sockaddr, send_batch::MmsgHdr, Op, OpAble};
+use crate::buf::IoBufMut;
+
+/// Buffers filled by [`recv_batch`], the headers of the whole batch are
+/// allocated once and point to the addresses and buffers. The length of each
+/// datagram is kept in `msg_len` like `recvmmsg` does.
+pub(crate) struct RecvBatch<T> {
+    fd: SharedFd,
+    bufs: Vec<T>,
+    slots: Box<[(MaybeUninit<libc::sockaddr_storage>, libc::iovec)]>,
+    hdrs: UnsafeCell<Box<[MmsgHdr]>>,
+}
+
+impl<T: IoBufMut> RecvBatch<T> {
+    pub(crate) fn new(fd: SharedFd, mut bufs: Vec<T>) -> Self {
+        let mut slots: Box<[(MaybeUninit<libc::sockaddr_storage>, libc::iovec)]> = bufs
+            .iter_mut()
+            .map(|buf| {
+                let iovec = libc::iovec {
+                    iov_base: buf.write_ptr() as *mut libc::c_void,
+                    iov_len: buf.bytes_total(),
+                };
+                (MaybeUninit::uninit(), iovec)
+            })
+            .collect();
+        let hdrs = slots
+            .iter_mut()
+            .map(|(addr, iovec)| {
+                let mut hdr: libc::msghdr = unsafe { std::mem::zeroed() };
+                hdr.msg_name = addr.as_mut_ptr() as *mut libc::c_void;
+                hdr.msg_namelen = std::mem::size_of::<libc::sockaddr_storage>() as _;
+                hdr.msg_iov = iovec;
+                hdr.msg_iovlen = 1;
+                MmsgHdr {
+                    msg_hdr: hdr,
+                    msg_len: 0,
+                }
+            })
+            .collect();
+        Self {
+            fd,
+            bufs,
+            slots,
+            hdrs: UnsafeCell::new(hdrs),
+        }
+    }
+
+    /// Take the buffers back, the first `filled` of them with the length and
+    /// origin of the datagram. The rest are untouched and returned with 0 and
+    /// the unspecified address.
+    pub(crate) fn into_bufs(self, filled: usize) -> Vec<(T, usize, SocketAddr)> {
+        let hdrs = self.hdrs.into_inner();
+        self.bufs
+            .into_iter()
+            .zip(self.slots.iter().zip(hdrs.iter()))
+            .enumerate()
+            .map(|(i, (mut buf, ((addr, _), hdr)))| {
+                if i >= filled {
+                    return (buf, 0, (Ipv4Addr::UNSPECIFIED, 0).into());
+                }
+                let n = hdr.msg_len as usize;
+                // Safety: the kernel wrote the address and `n` bytes.
+                unsafe {
+                    buf.set_init(n);
+                    (buf, n, sockaddr(addr.assume_init()))
+                }
+            })
+            .collect()
+    }
+
+    fn hdr(&self, index: usize) -> *mut MmsgHdr {
+        unsafe { (*self.hdrs.get()).as_mut_ptr().add(index) }
+    }
+}
+
+/// Receive into the buffers of the batch from `index`. It is one `recvmsg`
+/// on uring driver, which is linked with the ones of the next buffers, and
+/// one `recvmmsg` of the rest of the batch on legacy driver or if the kernel
+/// does not support `RecvMsg`.
+pub(crate) struct RecvMsgBatch<T> {
+    batch: Rc<RecvBatch<T>>,
+    index: usize,
+}
+
+/// Wait for a datagram, then receive the ones already queued without
+/// waiting. Returns how many buffers from the head of the batch are filled.
+pub(crate) async fn recv_batch<T: IoBufMut>(batch: &Rc<RecvBatch<T>>) -> io::Result<usize> {
+    let len = batch.bufs.len();
+    if len == 0 {
+        return Ok(0);
+    }
+
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    if super::is_uring_op_supported(opcode::RecvMsg::CODE) {
+        // The first one waits, the rest are `MSG_DONTWAIT` and the first
+        // `EAGAIN` cancels the ones after it.
+        let data = (0..len).map(|index| RecvMsgBatch {
+            batch: batch.clone(),
+            index,
+        });
+        let mut filled = 0;
+        let mut err = None;
+        for op in Op::submit_chain(data)? {
+            match op.await.meta.result {
+                Ok(n) if err.is_none() => {
+                    unsafe { (*batch.hdr(filled)).msg_len = n };
+                    filled += 1;
+                }
+                Ok(_) => (),
+                Err(e) => {
+                    err.get_or_insert(e);
+                }
+            }
+        }
+        return match err {
+            Some(e) if filled == 0 => Err(e),
+            _ => Ok(filled),
+        };
+    }
+
+    let op = Op::submit_with(RecvMsgBatch {
+        batch: batch.clone(),
+        index: 0,
+    })?;
+    op.await.meta.result.map(|n| n as usize)
+}
+
+impl<T: IoBufMut> OpAble for RecvMsgBatch<T> {
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    fn uring_op(&mut self) -> io_uring::squeue::Entry {
+        let hdr = self.batch.hdr(self.index);
+        let flags = if self.index == 0 {
+            0
+        } else {
+            libc::MSG_DONTWAIT
+        };
+        uring_fd!(self.batch.fd, |fd| {
+            opcode::RecvMsg::new(fd, unsafe { &mut (*hdr).msg_hdr })
+                .flags(flags as u32)
+                .build()
+        })
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    #[inline]
+    fn legacy_interest(&self) -> Option<(Direction, usize)> {
+        self.batch
+            .fd
+            .registered_index()
+            .map(|idx| (Direction::Read, idx))
+    }
+
+    #[cfg(all(
+        any(feature = "legacy", feature = "poll-io"),
+        any(target_os = "linux", target_os = "android")
+    ))]
+    fn legacy_call(&mut self) -> io::Result<u32> {
+        let len = self.batch.bufs.len() - self.index;
+        crate::syscall_u32!(recvmmsg(
+            self.batch.fd.as_raw_fd(),
+            self.batch.hdr(self.index) as *mut libc::mmsghdr,
+            len as _,
+            libc::MSG_WAITFORONE,
+            std::ptr::null_mut()
+        ))
+    }
+
+    // There is no recvmmsg, receive them one by one until none is queued.
+    #[cfg(all(
+        any(feature = "legacy", feature = "poll-io"),
+        not(any(target_os = "linux", target_os = "android"))
+    ))]
+    fn legacy_call(&mut self) -> io::Result<u32> {
+        let fd = self.batch.fd.as_raw_fd();
+        let mut filled = 0;
+        for index in self.index..self.batch.bufs.len() {
+            let hdr = self.batch.hdr(index);
+            match crate::syscall_u32!(recvmsg(fd, unsafe { &mut (*hdr).msg_hdr }, 0)) {
+                Ok(n) => {
+                    unsafe { (*hdr).msg_len = n };
+                    filled += 1;
+                }
+                Err(e) if filled == 0 => return Err(e),
+                Err(_) => break,
+            }
+        }
+        Ok(filled)
+    }
+}
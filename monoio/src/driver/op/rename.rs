@@ -0,0 +1,73 @@
+use std::{ffi::CString, future::Future, io, path::Path};
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+use io_uring::{opcode, types};
+
+use super::{Op, OpAble};
+use crate::driver::util::cstr;
+#[cfg(any(feature = "legacy", feature = "poll-io"))]
+use crate::{driver::ready::Direction, syscall_u32};
+
+/// Rename a file(uring requires kernel 5.11+).
+pub(crate) struct Rename {
+    from: CString,
+    to: CString,
+    flags: u32,
+}
+
+impl Op<Rename> {
+    /// Submit a request to rename `from` to `to`, `flags` are the ones of
+    /// renameat2(2) which are only supported on linux. See
+    /// [`Op::submit_or_asyncify`].
+    pub(crate) fn rename<P: AsRef<Path>, Q: AsRef<Path>>(
+        from: P,
+        to: Q,
+        flags: u32,
+    ) -> io::Result<impl Future<Output = io::Result<u32>>> {
+        // Here the paths will be copied, so its safe.
+        let from = cstr(from.as_ref())?;
+        let to = cstr(to.as_ref())?;
+        Op::submit_or_asyncify(Rename { from, to, flags })
+    }
+}
+
+impl OpAble for Rename {
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    fn uring_op(&mut self) -> io_uring::squeue::Entry {
+        opcode::RenameAt::new(
+            types::Fd(libc::AT_FDCWD),
+            self.from.as_ptr(),
+            types::Fd(libc::AT_FDCWD),
+            self.to.as_ptr(),
+        )
+        .flags(self.flags)
+        .build()
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    #[inline]
+    fn legacy_interest(&self) -> Option<(Direction, usize)> {
+        None
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    fn legacy_call(&mut self) -> io::Result<u32> {
+        #[cfg(target_os = "linux")]
+        if self.flags != 0 {
+            return syscall_u32!(syscall(
+                libc::SYS_renameat2,
+                libc::AT_FDCWD,
+                self.from.as_ptr(),
+                libc::AT_FDCWD,
+                self.to.as_ptr(),
+                self.flags
+            ));
+        }
+        syscall_u32!(renameat(
+            libc::AT_FDCWD,
+            self.from.as_ptr(),
+            libc::AT_FDCWD,
+            self.to.as_ptr()
+        ))
+    }
+}
@@ -1,9 +1,9 @@
-use std::{io, net::SocketAddr};
+use std::{io, net::SocketAddr, time::Duration};
 
 #[cfg(all(target_os = "linux", feature = "iouring"))]
-use io_uring::{opcode, types};
+use io_uring::opcode;
 #[cfg(unix)]
-use {crate::net::unix::SocketAddr as UnixSocketAddr, socket2::SockAddr};
+use {crate::net::unix::SocketAddr as UnixSocketAddr, socket2::SockAddr, std::os::unix::io::RawFd};
 #[cfg(all(windows, any(feature = "legacy", feature = "poll-io")))]
 use {
     crate::syscall, std::os::windows::io::AsRawSocket,
@@ -12,11 +12,19 @@ use {
 #[cfg(all(unix, any(feature = "legacy", feature = "poll-io")))]
 use {crate::syscall_u32, std::os::unix::prelude::AsRawFd};
 
-use super::{super::shared_fd::SharedFd, Op, OpAble};
+use super::{super::shared_fd::SharedFd, Completion, Op, OpAble};
+#[cfg(unix)]
+use crate::buf::IoVecBuf;
 #[cfg(any(feature = "legacy", feature = "poll-io"))]
 use crate::driver::ready::Direction;
 use crate::{buf::IoBuf, BufResult};
 
+/// According to Linux's documentation, zero copy introduces extra overhead and
+/// is only considered effective for at writes over around 10 KB.
+/// see also: https://www.kernel.org/doc/html/v4.16/networking/msg_zerocopy.html
+#[cfg(all(target_os = "linux", feature = "iouring", feature = "zero-copy"))]
+pub(crate) const MSG_ZEROCOPY_THRESHOLD: usize = 10 * 1024;
+
 pub(crate) struct Send<T> {
     /// Holds a strong ref to the FD, preventing the file from being closed
     /// while the operation is in-flight.
@@ -24,11 +32,39 @@ pub(crate) struct Send<T> {
     fd: SharedFd,
 
     pub(crate) buf: T,
+
+    // Writes over the threshold will be sent with MSG_ZEROCOPY
+    #[cfg(all(target_os = "linux", feature = "iouring", feature = "zero-copy"))]
+    zero_copy_threshold: usize,
+
+    // Flags passed besides MSG_NOSIGNAL
+    #[cfg(target_os = "linux")]
+    flags: libc::c_int,
 }
 
 impl<T: IoBuf> Op<Send<T>> {
     pub(crate) fn send(fd: SharedFd, buf: T) -> io::Result<Self> {
-        Op::submit_with(Send { fd, buf })
+        Op::submit_with(Send {
+            fd,
+            buf,
+            #[cfg(all(target_os = "linux", feature = "iouring", feature = "zero-copy"))]
+            zero_copy_threshold: super::zero_copy_threshold(),
+            #[cfg(target_os = "linux")]
+            flags: 0,
+        })
+    }
+
+    /// Send with `MSG_MORE`, which tells the kernel more data is coming and
+    /// the segment can be held until the next send without it.
+    #[cfg(target_os = "linux")]
+    pub(crate) fn send_more(fd: SharedFd, buf: T) -> io::Result<Self> {
+        Op::submit_with(Send {
+            fd,
+            buf,
+            #[cfg(all(target_os = "linux", feature = "iouring", feature = "zero-copy"))]
+            zero_copy_threshold: super::zero_copy_threshold(),
+            flags: libc::MSG_MORE,
+        })
     }
 
     #[allow(unused)]
@@ -36,47 +72,64 @@ impl<T: IoBuf> Op<Send<T>> {
         Send {
             fd: fd.clone(),
             buf,
+            #[cfg(all(target_os = "linux", feature = "iouring", feature = "zero-copy"))]
+            zero_copy_threshold: super::zero_copy_threshold(),
+            #[cfg(target_os = "linux")]
+            flags: 0,
         }
     }
 
     pub(crate) async fn write(self) -> BufResult<usize, T> {
-        let complete = self.await;
+        Send::write_result(self.await)
+    }
+
+    /// Send which fails with `TimedOut` if it does not complete within
+    /// `timeout`.
+    pub(crate) async fn send_timeout(
+        fd: &SharedFd,
+        buf: T,
+        timeout: Duration,
+    ) -> BufResult<usize, T> {
+        let complete = Op::with_timeout(Self::send_raw(fd, buf), timeout)
+            .await
+            .unwrap();
+        Send::write_result(complete)
+    }
+}
+
+impl<T: IoBuf> Send<T> {
+    /// Take the buffer of a completed send.
+    fn write_result(complete: Completion<Self>) -> BufResult<usize, T> {
         (complete.meta.result.map(|v| v as _), complete.data.buf)
     }
 }
 
+#[cfg(all(target_os = "linux", feature = "iouring", feature = "zero-copy"))]
+fn zero_copy_flag_guard(len: usize, threshold: usize) -> libc::c_int {
+    // TODO: use libc const after supported.
+    const MSG_ZEROCOPY: libc::c_int = 0x4000000;
+    #[allow(deprecated)]
+    if len >= threshold {
+        libc::MSG_NOSIGNAL as libc::c_int | MSG_ZEROCOPY
+    } else {
+        libc::MSG_NOSIGNAL as libc::c_int
+    }
+}
+
 impl<T: IoBuf> OpAble for Send<T> {
     #[cfg(all(target_os = "linux", feature = "iouring"))]
     fn uring_op(&mut self) -> io_uring::squeue::Entry {
-        #[allow(deprecated)]
-        #[cfg(feature = "zero-copy")]
-        fn zero_copy_flag_guard<T: IoBuf>(buf: &T) -> libc::c_int {
-            // TODO: use libc const after supported.
-            const MSG_ZEROCOPY: libc::c_int = 0x4000000;
-            // According to Linux's documentation, zero copy introduces extra overhead and
-            // is only considered effective for at writes over around 10 KB.
-            // see also: https://www.kernel.org/doc/html/v4.16/networking/msg_zerocopy.html
-            const MSG_ZEROCOPY_THRESHOLD: usize = 10 * 1024 * 1024;
-            if buf.bytes_init() >= MSG_ZEROCOPY_THRESHOLD {
-                libc::MSG_NOSIGNAL as libc::c_int | MSG_ZEROCOPY
-            } else {
-                libc::MSG_NOSIGNAL as libc::c_int
-            }
-        }
-
-        #[cfg(feature = "zero-copy")]
-        let flags = zero_copy_flag_guard(&self.buf);
-        #[cfg(not(feature = "zero-copy"))]
-        #[allow(deprecated)]
-        let flags = libc::MSG_NOSIGNAL as libc::c_int;
-
-        opcode::Send::new(
-            types::Fd(self.fd.raw_fd()),
-            self.buf.read_ptr(),
-            self.buf.bytes_init() as _,
-        )
-        .flags(flags)
-        .build()
+        uring_fd!(self.fd, |fd| {
+            #[cfg(feature = "zero-copy")]
+            let flags = zero_copy_flag_guard(self.buf.bytes_init(), self.zero_copy_threshold);
+            #[cfg(not(feature = "zero-copy"))]
+            #[allow(deprecated)]
+            let flags = libc::MSG_NOSIGNAL as libc::c_int;
+
+            opcode::Send::new(fd, self.buf.read_ptr(), self.buf.bytes_init() as _)
+                .flags(flags | self.flags)
+                .build()
+        })
     }
 
     #[cfg(any(feature = "legacy", feature = "poll-io"))]
@@ -92,7 +145,7 @@ impl<T: IoBuf> OpAble for Send<T> {
         let fd = self.fd.as_raw_fd();
         #[cfg(target_os = "linux")]
         #[allow(deprecated)]
-        let flags = libc::MSG_NOSIGNAL as _;
+        let flags = libc::MSG_NOSIGNAL as libc::c_int | self.flags;
         #[cfg(not(target_os = "linux"))]
         let flags = 0;
 
@@ -115,6 +168,68 @@ impl<T: IoBuf> OpAble for Send<T> {
     }
 }
 
+/// Zero copy send(requires kernel 6.0+).
+///
+/// It produces two completions: the result of the send and a notification
+/// telling the buffer is no longer used by the kernel.
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+pub(crate) struct SendZc<T> {
+    /// Holds a strong ref to the FD, preventing the file from being closed
+    /// while the operation is in-flight.
+    #[allow(unused)]
+    fd: SharedFd,
+
+    pub(crate) buf: T,
+}
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+impl<T: IoBuf> Op<SendZc<T>> {
+    pub(crate) fn send_zc(fd: SharedFd, buf: T) -> io::Result<Self> {
+        Op::submit_with(SendZc { fd, buf })
+    }
+
+    pub(crate) async fn wait(mut self) -> BufResult<usize, T> {
+        let meta = std::future::poll_fn(|cx| self.poll_multishot(cx)).await;
+        if meta.has_more() {
+            // The buffer is still used by the kernel until the notification arrives.
+            let _ = std::future::poll_fn(|cx| self.poll_multishot(cx)).await;
+        }
+        let data = self.data.take().expect("unexpected operation state");
+        (meta.result.map(|v| v as _), data.buf)
+    }
+}
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+impl<T: IoBuf> OpAble for SendZc<T> {
+    fn uring_op(&mut self) -> io_uring::squeue::Entry {
+        uring_fd!(self.fd, |fd| {
+            const FLAGS: libc::c_int = libc::MSG_NOSIGNAL;
+            opcode::SendZc::new(fd, self.buf.read_ptr(), self.buf.bytes_init() as _)
+                .flags(FLAGS)
+                .build()
+        })
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    #[inline]
+    fn legacy_interest(&self) -> Option<(Direction, usize)> {
+        self.fd
+            .registered_index()
+            .map(|idx| (Direction::Write, idx))
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    fn legacy_call(&mut self) -> io::Result<u32> {
+        const FLAGS: libc::c_int = libc::MSG_NOSIGNAL;
+        syscall_u32!(send(
+            self.fd.as_raw_fd(),
+            self.buf.read_ptr() as _,
+            self.buf.bytes_init(),
+            FLAGS
+        ))
+    }
+}
+
 pub(crate) struct SendMsg<T> {
     /// Holds a strong ref to the FD, preventing the file from being closed
     /// while the operation is in-flight.
@@ -125,6 +240,12 @@ pub(crate) struct SendMsg<T> {
     pub(crate) buf: T,
     #[cfg(unix)]
     pub(crate) info: Box<(Option<SockAddr>, [libc::iovec; 1], libc::msghdr)>,
+    // Flags passed besides MSG_NOSIGNAL
+    #[cfg(unix)]
+    flags: libc::c_int,
+    /// Buffer of the ancillary data, u64 makes it aligned for cmsghdr.
+    #[cfg(unix)]
+    control: Vec<u64>,
 }
 
 #[cfg(unix)]
@@ -133,6 +254,15 @@ impl<T: IoBuf> Op<SendMsg<T>> {
         fd: SharedFd,
         buf: T,
         socket_addr: Option<SocketAddr>,
+    ) -> io::Result<Self> {
+        Self::send_msg_with_flags(fd, buf, socket_addr, 0)
+    }
+
+    pub(crate) fn send_msg_with_flags(
+        fd: SharedFd,
+        buf: T,
+        socket_addr: Option<SocketAddr>,
+        flags: libc::c_int,
     ) -> io::Result<Self> {
         let iovec = [libc::iovec {
             iov_base: buf.read_ptr() as *const _ as *mut _,
@@ -157,7 +287,61 @@ impl<T: IoBuf> Op<SendMsg<T>> {
             }
         }
 
-        Op::submit_with(SendMsg { fd, buf, info })
+        Op::submit_with(SendMsg {
+            fd,
+            buf,
+            info,
+            flags,
+            control: Vec::new(),
+        })
+    }
+
+    /// Send with one control message of `level` and `ty`, e.g. `UDP_SEGMENT`
+    /// or `IP_TOS`, the data is copied into the control buffer.
+    pub(crate) fn send_msg_with_cmsg(
+        fd: SharedFd,
+        buf: T,
+        socket_addr: SocketAddr,
+        level: libc::c_int,
+        ty: libc::c_int,
+        data: &[u8],
+    ) -> io::Result<Self> {
+        let iovec = [libc::iovec {
+            iov_base: buf.read_ptr() as *const _ as *mut _,
+            iov_len: buf.bytes_init(),
+        }];
+        let mut info: Box<(Option<SockAddr>, [libc::iovec; 1], libc::msghdr)> =
+            Box::new((Some(socket_addr.into()), iovec, unsafe {
+                std::mem::zeroed()
+            }));
+
+        info.2.msg_iov = info.1.as_mut_ptr();
+        info.2.msg_iovlen = 1;
+        if let Some(socket_addr) = info.0.as_ref() {
+            info.2.msg_name = socket_addr.as_ptr() as *mut libc::c_void;
+            info.2.msg_namelen = socket_addr.len();
+        }
+
+        let data_len = data.len() as _;
+        let space = unsafe { libc::CMSG_SPACE(data_len) } as usize;
+        let mut control = vec![0u64; space.div_ceil(std::mem::size_of::<u64>())];
+        info.2.msg_control = control.as_mut_ptr() as *mut libc::c_void;
+        info.2.msg_controllen = space as _;
+        unsafe {
+            let cmsg = libc::CMSG_FIRSTHDR(&info.2);
+            (*cmsg).cmsg_level = level;
+            (*cmsg).cmsg_type = ty;
+            (*cmsg).cmsg_len = libc::CMSG_LEN(data_len) as _;
+            std::ptr::copy_nonoverlapping(data.as_ptr(), libc::CMSG_DATA(cmsg), data.len());
+        }
+
+        Op::submit_with(SendMsg {
+            fd,
+            buf,
+            info,
+            flags: 0,
+            control,
+        })
     }
 
     pub(crate) async fn wait(self) -> BufResult<usize, T> {
@@ -187,11 +371,13 @@ impl<T: IoBuf> Op<SendMsg<T>> {
 impl<T: IoBuf> OpAble for SendMsg<T> {
     #[cfg(all(target_os = "linux", feature = "iouring"))]
     fn uring_op(&mut self) -> io_uring::squeue::Entry {
-        #[allow(deprecated)]
-        const FLAGS: u32 = libc::MSG_NOSIGNAL as u32;
-        opcode::SendMsg::new(types::Fd(self.fd.raw_fd()), &mut self.info.2 as *mut _)
-            .flags(FLAGS)
-            .build()
+        uring_fd!(self.fd, |fd| {
+            #[allow(deprecated)]
+            const FLAGS: u32 = libc::MSG_NOSIGNAL as u32;
+            opcode::SendMsg::new(fd, &mut self.info.2 as *mut _)
+                .flags(FLAGS | self.flags as u32)
+                .build()
+        })
     }
 
     #[cfg(any(feature = "legacy", feature = "poll-io"))]
@@ -210,7 +396,7 @@ impl<T: IoBuf> OpAble for SendMsg<T> {
         #[cfg(not(target_os = "linux"))]
         const FLAGS: libc::c_int = 0;
         let fd = self.fd.as_raw_fd();
-        syscall_u32!(sendmsg(fd, &mut self.info.2 as *mut _, FLAGS))
+        syscall_u32!(sendmsg(fd, &mut self.info.2 as *mut _, FLAGS | self.flags))
     }
 
     #[cfg(all(any(feature = "legacy", feature = "poll-io"), windows))]
@@ -220,6 +406,86 @@ impl<T: IoBuf> OpAble for SendMsg<T> {
     }
 }
 
+#[cfg(unix)]
+pub(crate) struct SendMsgVectored<T> {
+    /// Holds a strong ref to the FD, preventing the file from being closed
+    /// while the operation is in-flight.
+    #[allow(unused)]
+    fd: SharedFd,
+
+    /// Reference to the in-flight buffer, the msghdr points to its iovecs.
+    pub(crate) buf_vec: T,
+    pub(crate) info: Box<(Option<SockAddr>, libc::msghdr)>,
+}
+
+#[cfg(unix)]
+impl<T: IoVecBuf> Op<SendMsgVectored<T>> {
+    pub(crate) fn send_msg_vectored(
+        fd: SharedFd,
+        buf_vec: T,
+        socket_addr: Option<SocketAddr>,
+    ) -> io::Result<Self> {
+        let mut info: Box<(Option<SockAddr>, libc::msghdr)> =
+            Box::new((socket_addr.map(Into::into), unsafe { std::mem::zeroed() }));
+
+        info.1.msg_iov = buf_vec.read_iovec_ptr() as *mut _;
+        info.1.msg_iovlen = buf_vec.read_iovec_len() as _;
+
+        match info.0.as_ref() {
+            Some(socket_addr) => {
+                info.1.msg_name = socket_addr.as_ptr() as *mut libc::c_void;
+                info.1.msg_namelen = socket_addr.len();
+            }
+            None => {
+                info.1.msg_name = std::ptr::null_mut();
+                info.1.msg_namelen = 0;
+            }
+        }
+
+        Op::submit_with(SendMsgVectored { fd, buf_vec, info })
+    }
+
+    pub(crate) async fn wait(self) -> BufResult<usize, T> {
+        let complete = self.await;
+        let res = complete.meta.result.map(|v| v as _);
+        let buf_vec = complete.data.buf_vec;
+        (res, buf_vec)
+    }
+}
+
+#[cfg(unix)]
+impl<T: IoVecBuf> OpAble for SendMsgVectored<T> {
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    fn uring_op(&mut self) -> io_uring::squeue::Entry {
+        uring_fd!(self.fd, |fd| {
+            #[allow(deprecated)]
+            const FLAGS: u32 = libc::MSG_NOSIGNAL as u32;
+            opcode::SendMsg::new(fd, &self.info.1 as *const _)
+                .flags(FLAGS)
+                .build()
+        })
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    #[inline]
+    fn legacy_interest(&self) -> Option<(Direction, usize)> {
+        self.fd
+            .registered_index()
+            .map(|idx| (Direction::Write, idx))
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    fn legacy_call(&mut self) -> io::Result<u32> {
+        #[cfg(target_os = "linux")]
+        #[allow(deprecated)]
+        const FLAGS: libc::c_int = libc::MSG_NOSIGNAL as libc::c_int;
+        #[cfg(not(target_os = "linux"))]
+        const FLAGS: libc::c_int = 0;
+        let fd = self.fd.as_raw_fd();
+        syscall_u32!(sendmsg(fd, &self.info.1 as *const _, FLAGS))
+    }
+}
+
 #[cfg(unix)]
 pub(crate) struct SendMsgUnix<T> {
     /// Holds a strong ref to the FD, preventing the file from being closed
@@ -230,6 +496,8 @@ pub(crate) struct SendMsgUnix<T> {
     /// Reference to the in-flight buffer.
     pub(crate) buf: T,
     pub(crate) info: Box<(Option<UnixSocketAddr>, [libc::iovec; 1], libc::msghdr)>,
+    /// Buffer of the ancillary data, u64 makes it aligned for cmsghdr.
+    control: Vec<u64>,
 }
 
 #[cfg(unix)]
@@ -238,6 +506,41 @@ impl<T: IoBuf> Op<SendMsgUnix<T>> {
         fd: SharedFd,
         buf: T,
         socket_addr: Option<UnixSocketAddr>,
+    ) -> io::Result<Self> {
+        Self::send_msg_unix_with_fds(fd, buf, socket_addr, &[])
+    }
+
+    /// Send with the fds attached in a `SCM_RIGHTS` message. The fds are
+    /// looked up when the kernel executes the op.
+    pub(crate) fn send_msg_unix_with_fds(
+        fd: SharedFd,
+        buf: T,
+        socket_addr: Option<UnixSocketAddr>,
+        fds: &[RawFd],
+    ) -> io::Result<Self> {
+        // Safety: the fds are plain integers.
+        let data = unsafe {
+            std::slice::from_raw_parts(fds.as_ptr() as *const u8, std::mem::size_of_val(fds))
+        };
+        Self::send_msg_unix_with_cmsg(
+            fd,
+            buf,
+            socket_addr,
+            libc::SOL_SOCKET,
+            libc::SCM_RIGHTS,
+            data,
+        )
+    }
+
+    /// Send with one control message of `level` and `ty`, e.g. `SCM_RIGHTS`
+    /// or `SCM_CREDENTIALS`. There is no control message if `data` is empty.
+    pub(crate) fn send_msg_unix_with_cmsg(
+        fd: SharedFd,
+        buf: T,
+        socket_addr: Option<UnixSocketAddr>,
+        level: libc::c_int,
+        ty: libc::c_int,
+        data: &[u8],
     ) -> io::Result<Self> {
         let iovec = [libc::iovec {
             iov_base: buf.read_ptr() as *const _ as *mut _,
@@ -262,7 +565,28 @@ impl<T: IoBuf> Op<SendMsgUnix<T>> {
             }
         }
 
-        Op::submit_with(SendMsgUnix { fd, buf, info })
+        let mut control = Vec::new();
+        if !data.is_empty() {
+            let data_len = data.len() as _;
+            let space = unsafe { libc::CMSG_SPACE(data_len) } as usize;
+            control.resize(space.div_ceil(std::mem::size_of::<u64>()), 0);
+            info.2.msg_control = control.as_mut_ptr() as *mut libc::c_void;
+            info.2.msg_controllen = space as _;
+            unsafe {
+                let cmsg = libc::CMSG_FIRSTHDR(&info.2);
+                (*cmsg).cmsg_level = level;
+                (*cmsg).cmsg_type = ty;
+                (*cmsg).cmsg_len = libc::CMSG_LEN(data_len) as _;
+                std::ptr::copy_nonoverlapping(data.as_ptr(), libc::CMSG_DATA(cmsg), data.len());
+            }
+        }
+
+        Op::submit_with(SendMsgUnix {
+            fd,
+            buf,
+            info,
+            control,
+        })
     }
 
     pub(crate) async fn wait(self) -> BufResult<usize, T> {
@@ -277,11 +601,13 @@ impl<T: IoBuf> Op<SendMsgUnix<T>> {
 impl<T: IoBuf> OpAble for SendMsgUnix<T> {
     #[cfg(all(target_os = "linux", feature = "iouring"))]
     fn uring_op(&mut self) -> io_uring::squeue::Entry {
-        #[allow(deprecated)]
-        const FLAGS: u32 = libc::MSG_NOSIGNAL as u32;
-        opcode::SendMsg::new(types::Fd(self.fd.raw_fd()), &mut self.info.2 as *mut _)
-            .flags(FLAGS)
-            .build()
+        uring_fd!(self.fd, |fd| {
+            #[allow(deprecated)]
+            const FLAGS: u32 = libc::MSG_NOSIGNAL as u32;
+            opcode::SendMsg::new(fd, &mut self.info.2 as *mut _)
+                .flags(FLAGS)
+                .build()
+        })
     }
 
     #[cfg(any(feature = "legacy", feature = "poll-io"))]
@@ -304,3 +630,32 @@ impl<T: IoBuf> OpAble for SendMsgUnix<T> {
         syscall_u32!(sendmsg(fd, &mut self.info.2 as *mut _, FLAGS))
     }
 }
+
+#[cfg(all(test, target_os = "linux", feature = "iouring", feature = "zero-copy"))]
+mod tests {
+    use super::*;
+
+    const MSG_ZEROCOPY: libc::c_int = 0x4000000;
+
+    #[test]
+    fn zero_copy_threshold() {
+        let flags = zero_copy_flag_guard(64 * 1024, MSG_ZEROCOPY_THRESHOLD);
+        assert_ne!(flags & MSG_ZEROCOPY, 0);
+        let flags = zero_copy_flag_guard(1024, MSG_ZEROCOPY_THRESHOLD);
+        assert_eq!(flags & MSG_ZEROCOPY, 0);
+    }
+
+    #[test]
+    fn configured_zero_copy_threshold() {
+        let mut rt = crate::RuntimeBuilder::<crate::IoUringDriver>::new()
+            .zero_copy_threshold(512)
+            .build()
+            .unwrap();
+        rt.block_on(async {
+            let threshold = super::super::zero_copy_threshold();
+            assert_eq!(threshold, 512);
+            let flags = zero_copy_flag_guard(1024, threshold);
+            assert_ne!(flags & MSG_ZEROCOPY, 0);
+        });
+    }
+}
@@ -0,0 +1,188 @@
+use std::{cell::UnsafeCell, io, net::SocketAddr, rc::Rc};
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+use io_uring::opcode;
+use socket2::SockAddr;
+#[cfg(any(feature = "legacy", feature = "poll-io"))]
+use {crate::driver::ready::Direction, std::os::unix::prelude::AsRawFd};
+
+use super::{super::shared_fd::SharedFd, Op, OpAble};
+use crate::buf::IoBuf;
+
+/// The layout of `struct mmsghdr` on Linux, which is missing elsewhere.
+#[repr(C)]
+pub(super) struct MmsgHdr {
+    pub(super) msg_hdr: libc::msghdr,
+    pub(super) msg_len: libc::c_uint,
+}
+
+/// Datagrams sent by [`send_batch`], the headers of the whole batch are
+/// allocated once and point to the addresses and buffers.
+pub(crate) struct SendBatch<T> {
+    fd: SharedFd,
+    bufs: Vec<T>,
+    #[allow(unused)]
+    slots: Box<[(SockAddr, libc::iovec)]>,
+    hdrs: UnsafeCell<Box<[MmsgHdr]>>,
+}
+
+impl<T: IoBuf> SendBatch<T> {
+    pub(crate) fn new(fd: SharedFd, msgs: Vec<(T, SocketAddr)>) -> Self {
+        let (bufs, addrs): (Vec<T>, Vec<SocketAddr>) = msgs.into_iter().unzip();
+        let mut slots: Box<[(SockAddr, libc::iovec)]> = bufs
+            .iter()
+            .zip(addrs)
+            .map(|(buf, addr)| {
+                let iovec = libc::iovec {
+                    iov_base: buf.read_ptr() as *mut libc::c_void,
+                    iov_len: buf.bytes_init(),
+                };
+                (addr.into(), iovec)
+            })
+            .collect();
+        let hdrs = slots
+            .iter_mut()
+            .map(|(addr, iovec)| {
+                let mut hdr: libc::msghdr = unsafe { std::mem::zeroed() };
+                hdr.msg_name = addr.as_ptr() as *mut libc::c_void;
+                hdr.msg_namelen = addr.len();
+                hdr.msg_iov = iovec;
+                hdr.msg_iovlen = 1;
+                MmsgHdr {
+                    msg_hdr: hdr,
+                    msg_len: 0,
+                }
+            })
+            .collect();
+        Self {
+            fd,
+            bufs,
+            slots,
+            hdrs: UnsafeCell::new(hdrs),
+        }
+    }
+
+    pub(crate) fn into_bufs(self) -> Vec<T> {
+        self.bufs
+    }
+
+    fn hdr(&self, index: usize) -> *mut MmsgHdr {
+        unsafe { (*self.hdrs.get()).as_mut_ptr().add(index) }
+    }
+}
+
+/// Send the datagrams of the batch from `index`. It is one `sendmsg` on
+/// uring driver, which is linked with the ones of the next datagrams, and one
+/// `sendmmsg` of the rest of the batch on legacy driver or if the kernel does
+/// not support `SendMsg`.
+pub(crate) struct SendMsgBatch<T> {
+    batch: Rc<SendBatch<T>>,
+    index: usize,
+}
+
+/// Send the datagrams in order and return how many of them are sent. An
+/// error is only returned if the first one fails, the datagrams after a
+/// failed one are not sent.
+pub(crate) async fn send_batch<T: IoBuf>(batch: &Rc<SendBatch<T>>) -> io::Result<usize> {
+    let len = batch.bufs.len();
+    let mut sent = 0;
+    let mut err = None;
+    while sent < len && err.is_none() {
+        #[cfg(all(target_os = "linux", feature = "iouring"))]
+        if super::is_uring_op_supported(opcode::SendMsg::CODE) {
+            // The chain is limited to the size of the SQ, the next one is
+            // submitted once it succeeds.
+            let data = (sent..len).map(|index| SendMsgBatch {
+                batch: batch.clone(),
+                index,
+            });
+            let ops = match Op::submit_chain(data) {
+                Ok(ops) => ops,
+                Err(e) => {
+                    err = Some(e);
+                    break;
+                }
+            };
+            // Wait for all of them, a failure cancels the rest of the chain
+            // with ECANCELED.
+            for op in ops {
+                match op.await.meta.result {
+                    Ok(_) if err.is_none() => sent += 1,
+                    Ok(_) => (),
+                    Err(e) => {
+                        err.get_or_insert(e);
+                    }
+                }
+            }
+            continue;
+        }
+
+        let op = Op::submit_with(SendMsgBatch {
+            batch: batch.clone(),
+            index: sent,
+        });
+        match op {
+            Ok(op) => match op.await.meta.result {
+                Ok(n) => sent += n as usize,
+                Err(e) => err = Some(e),
+            },
+            Err(e) => err = Some(e),
+        }
+    }
+    match err {
+        Some(e) if sent == 0 => Err(e),
+        _ => Ok(sent),
+    }
+}
+
+impl<T: IoBuf> OpAble for SendMsgBatch<T> {
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    fn uring_op(&mut self) -> io_uring::squeue::Entry {
+        let hdr = self.batch.hdr(self.index);
+        uring_fd!(self.batch.fd, |fd| {
+            opcode::SendMsg::new(fd, unsafe { &(*hdr).msg_hdr } as *const _).build()
+        })
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    #[inline]
+    fn legacy_interest(&self) -> Option<(Direction, usize)> {
+        self.batch
+            .fd
+            .registered_index()
+            .map(|idx| (Direction::Write, idx))
+    }
+
+    #[cfg(all(
+        any(feature = "legacy", feature = "poll-io"),
+        any(target_os = "linux", target_os = "android")
+    ))]
+    fn legacy_call(&mut self) -> io::Result<u32> {
+        let len = self.batch.bufs.len() - self.index;
+        crate::syscall_u32!(sendmmsg(
+            self.batch.fd.as_raw_fd(),
+            self.batch.hdr(self.index) as *mut libc::mmsghdr,
+            len as _,
+            0
+        ))
+    }
+
+    // There is no sendmmsg, send them one by one until one fails.
+    #[cfg(all(
+        any(feature = "legacy", feature = "poll-io"),
+        not(any(target_os = "linux", target_os = "android"))
+    ))]
+    fn legacy_call(&mut self) -> io::Result<u32> {
+        let fd = self.batch.fd.as_raw_fd();
+        let mut sent = 0;
+        for index in self.index..self.batch.bufs.len() {
+            let hdr = self.batch.hdr(index);
+            match crate::syscall_u32!(sendmsg(fd, unsafe { &(*hdr).msg_hdr }, 0)) {
+                Ok(_) => sent += 1,
+                Err(e) if sent == 0 => return Err(e),
+                Err(_) => break,
+            }
+        }
+        Ok(sent)
+    }
+}
@@ -0,0 +1,46 @@
+use std::io;
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+use io_uring::opcode;
+
+use super::{super::shared_fd::SharedFd, Op, OpAble};
+#[cfg(any(feature = "legacy", feature = "poll-io"))]
+use crate::{driver::ready::Direction, syscall_u32};
+
+/// Shut down part of a full-duplex connection with shutdown(2).
+pub(crate) struct Shutdown {
+    fd: SharedFd,
+    how: i32,
+}
+
+impl Op<Shutdown> {
+    pub(crate) fn shutdown(fd: &SharedFd, how: std::net::Shutdown) -> io::Result<Op<Shutdown>> {
+        let how = match how {
+            std::net::Shutdown::Read => libc::SHUT_RD,
+            std::net::Shutdown::Write => libc::SHUT_WR,
+            std::net::Shutdown::Both => libc::SHUT_RDWR,
+        };
+        Op::submit_with(Shutdown {
+            fd: fd.clone(),
+            how,
+        })
+    }
+}
+
+impl OpAble for Shutdown {
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    fn uring_op(&mut self) -> io_uring::squeue::Entry {
+        uring_fd!(self.fd, |fd| opcode::Shutdown::new(fd, self.how).build())
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    #[inline]
+    fn legacy_interest(&self) -> Option<(Direction, usize)> {
+        None
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    fn legacy_call(&mut self) -> io::Result<u32> {
+        syscall_u32!(shutdown(self.fd.raw_fd(), self.how))
+    }
+}
@@ -3,7 +3,7 @@
 use std::io;
 
 #[cfg(all(target_os = "linux", feature = "iouring"))]
-use io_uring::{opcode, types};
+use io_uring::opcode;
 #[cfg(all(unix, feature = "legacy"))]
 use {
     crate::{driver::ready::Direction, syscall_u32},
@@ -12,9 +12,11 @@ use {
 
 use super::{super::shared_fd::SharedFd, Op, OpAble};
 
-// Currently our Splice does not support setting offset.
+// Only the offset of the input side can be set, -1 means using the current
+// position or a pipe.
 pub(crate) struct Splice {
     fd_in: SharedFd,
+    off_in: i64,
     fd_out: SharedFd,
     len: u32,
     direction: SpliceDirection,
@@ -32,6 +34,24 @@ impl Op<Splice> {
     ) -> io::Result<Op<Splice>> {
         Op::submit_with(Splice {
             fd_in: fd_in.clone(),
+            off_in: -1,
+            fd_out: fd_out.clone(),
+            len,
+            direction: SpliceDirection::ToPipe,
+        })
+    }
+
+    /// Splice data at `offset` of a regular file to pipe, the file position is
+    /// not changed.
+    pub(crate) fn splice_file_to_pipe(
+        fd_in: &SharedFd,
+        offset: u64,
+        fd_out: &SharedFd,
+        len: u32,
+    ) -> io::Result<Op<Splice>> {
+        Op::submit_with(Splice {
+            fd_in: fd_in.clone(),
+            off_in: offset as i64,
             fd_out: fd_out.clone(),
             len,
             direction: SpliceDirection::ToPipe,
@@ -45,6 +65,7 @@ impl Op<Splice> {
     ) -> io::Result<Op<Splice>> {
         Op::submit_with(Splice {
             fd_in: fd_in.clone(),
+            off_in: -1,
             fd_out: fd_out.clone(),
             len,
             direction: SpliceDirection::FromPipe,
@@ -61,15 +82,11 @@ impl OpAble for Splice {
     #[cfg(all(target_os = "linux", feature = "iouring"))]
     fn uring_op(&mut self) -> io_uring::squeue::Entry {
         const FLAG: u32 = libc::SPLICE_F_MOVE;
-        opcode::Splice::new(
-            types::Fd(self.fd_in.raw_fd()),
-            -1,
-            types::Fd(self.fd_out.raw_fd()),
-            -1,
-            self.len,
-        )
-        .flags(FLAG)
-        .build()
+        uring_fd!(self.fd_in, |fd_in| uring_fd!(self.fd_out, |fd_out| {
+            opcode::Splice::new(fd_in, self.off_in, fd_out, -1, self.len)
+                .flags(FLAG)
+                .build()
+        }))
     }
 
     #[cfg(all(unix, feature = "legacy"))]
@@ -92,7 +109,11 @@ impl OpAble for Splice {
         const FLAG: u32 = libc::SPLICE_F_MOVE | libc::SPLICE_F_NONBLOCK;
         let fd_in = self.fd_in.as_raw_fd();
         let fd_out = self.fd_out.as_raw_fd();
-        let off_in = std::ptr::null_mut::<libc::loff_t>();
+        let off_in = if self.off_in < 0 {
+            std::ptr::null_mut::<libc::loff_t>()
+        } else {
+            &mut self.off_in as *mut libc::loff_t
+        };
         let off_out = std::ptr::null_mut::<libc::loff_t>();
         syscall_u32!(splice(
             fd_in,
@@ -104,3 +125,109 @@ impl OpAble for Splice {
         ))
     }
 }
+
+/// Send data at `offset` of a regular file to a socket with sendfile(2).
+///
+/// There is no sendfile opcode in io_uring, the uring driver splices through a
+/// pipe instead, so this op is only submitted to the legacy driver.
+pub(crate) struct SendFile {
+    fd_in: SharedFd,
+    offset: libc::off64_t,
+    fd_out: SharedFd,
+    len: usize,
+}
+
+impl Op<SendFile> {
+    pub(crate) fn sendfile(
+        fd_in: &SharedFd,
+        offset: u64,
+        fd_out: &SharedFd,
+        len: usize,
+    ) -> io::Result<Op<SendFile>> {
+        let offset = libc::off64_t::try_from(offset)
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "offset too big"))?;
+        Op::submit_with(SendFile {
+            fd_in: fd_in.clone(),
+            offset,
+            fd_out: fd_out.clone(),
+            len,
+        })
+    }
+}
+
+impl OpAble for SendFile {
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    fn uring_op(&mut self) -> io_uring::squeue::Entry {
+        unreachable!("sendfile is not supported by io_uring")
+    }
+
+    #[cfg(all(unix, feature = "legacy"))]
+    #[inline]
+    fn legacy_interest(&self) -> Option<(Direction, usize)> {
+        self.fd_out
+            .registered_index()
+            .map(|idx| (Direction::Write, idx))
+    }
+
+    #[cfg(all(unix, feature = "legacy"))]
+    fn legacy_call(&mut self) -> io::Result<u32> {
+        // Cap the length so the result fits in u32.
+        syscall_u32!(sendfile64(
+            self.fd_out.as_raw_fd(),
+            self.fd_in.as_raw_fd(),
+            &mut self.offset as *mut libc::off64_t,
+            self.len.min(u32::MAX as usize)
+        ))
+    }
+}
+
+/// Duplicate data from a pipe to another pipe without consuming it.
+pub(crate) struct Tee {
+    fd_in: SharedFd,
+    fd_out: SharedFd,
+    len: u32,
+}
+
+impl Op<Tee> {
+    pub(crate) fn tee_pipe(fd_in: &SharedFd, fd_out: &SharedFd, len: u32) -> io::Result<Op<Tee>> {
+        Op::submit_with(Tee {
+            fd_in: fd_in.clone(),
+            fd_out: fd_out.clone(),
+            len,
+        })
+    }
+
+    pub(crate) async fn tee(self) -> io::Result<u32> {
+        let complete = self.await;
+        complete.meta.result
+    }
+}
+
+impl OpAble for Tee {
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    fn uring_op(&mut self) -> io_uring::squeue::Entry {
+        uring_fd!(self.fd_in, |fd_in| uring_fd!(self.fd_out, |fd_out| {
+            opcode::Tee::new(fd_in, fd_out, self.len).build()
+        }))
+    }
+
+    // Wait for the source pipe to be readable, the syscall fails with EAGAIN
+    // if there is no data in it.
+    #[cfg(all(unix, feature = "legacy"))]
+    #[inline]
+    fn legacy_interest(&self) -> Option<(Direction, usize)> {
+        self.fd_in
+            .registered_index()
+            .map(|idx| (Direction::Read, idx))
+    }
+
+    #[cfg(all(unix, feature = "legacy"))]
+    fn legacy_call(&mut self) -> io::Result<u32> {
+        syscall_u32!(tee(
+            self.fd_in.as_raw_fd(),
+            self.fd_out.as_raw_fd(),
+            self.len as usize,
+            libc::SPLICE_F_NONBLOCK
+        ))
+    }
+}
@@ -0,0 +1,176 @@
+use std::{ffi::CString, io, path::Path};
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+use io_uring::{opcode, types};
+
+use super::{super::shared_fd::SharedFd, Op, OpAble};
+use crate::driver::util::cstr;
+#[cfg(any(feature = "legacy", feature = "poll-io"))]
+use crate::{driver::ready::Direction, syscall_u32};
+
+/// Get the status of a file with statx(2).
+pub(crate) struct Statx {
+    // The file to stat, or the directory the path is relative to.
+    fd: Option<SharedFd>,
+    path: CString,
+    flags: i32,
+    mask: u32,
+    // Credentials to run with, 0 for the ones of the thread.
+    personality: u16,
+    // Written by the kernel after the op is submitted, so it must be boxed.
+    statx: Box<libc::statx>,
+}
+
+impl Op<Statx> {
+    /// Get the status of an opened file, see [`Op::statx`].
+    pub(crate) async fn statx_fd(fd: &SharedFd) -> io::Result<libc::statx> {
+        Self::statx(
+            Some(fd),
+            CString::default(),
+            libc::AT_EMPTY_PATH,
+            Statx::MASK,
+        )
+        .await
+    }
+
+    /// Get the alignment of direct IO of an opened file, `stx_dio_mem_align`
+    /// and `stx_dio_offset_align`, see [`Op::statx`].
+    pub(crate) async fn statx_fd_dio_align(fd: &SharedFd) -> io::Result<libc::statx> {
+        Self::statx(
+            Some(fd),
+            CString::default(),
+            libc::AT_EMPTY_PATH,
+            libc::STATX_DIOALIGN,
+        )
+        .await
+    }
+
+    /// Get the status of a path, symbolic links are only followed if
+    /// `follow_symlinks` is true, see [`Op::statx`].
+    pub(crate) async fn statx_path<P: AsRef<Path>>(
+        path: P,
+        follow_symlinks: bool,
+    ) -> io::Result<libc::statx> {
+        // Here the path will be copied, so its safe.
+        let path = cstr(path.as_ref())?;
+        Self::statx(None, path, path_flags(follow_symlinks), Statx::MASK).await
+    }
+
+    /// Submit a request to get the status of a path with the credentials of
+    /// the personality.
+    #[cfg(feature = "iouring")]
+    pub(crate) fn statx_path_as<P: AsRef<Path>>(
+        path: P,
+        follow_symlinks: bool,
+        personality: super::PersonalityId,
+    ) -> io::Result<Op<Statx>> {
+        let personality = super::personality(Some(personality), opcode::Statx::CODE)?;
+        let path = cstr(path.as_ref())?;
+        Op::submit_with(Statx::new(
+            None,
+            path,
+            path_flags(follow_symlinks),
+            Statx::MASK,
+            personality,
+        ))
+    }
+
+    /// Get the status of a file, `fd` is the file or the directory the path is
+    /// relative to. It is submitted to uring, or runs on the blocking thread
+    /// pool where it would be a blocking call of the thread: on legacy driver
+    /// and on uring driver without the opcode. See
+    /// [`asyncify`](crate::fs::asyncify).
+    async fn statx(
+        fd: Option<&SharedFd>,
+        path: CString,
+        flags: i32,
+        mask: u32,
+    ) -> io::Result<libc::statx> {
+        #[cfg(feature = "iouring")]
+        if super::is_uring_op_supported(opcode::Statx::CODE) {
+            let op = Op::submit_with(Statx::new(fd.cloned(), path, flags, mask, 0))?;
+            return op.result().await;
+        }
+        let call = move |dir_fd| {
+            // Safety: statx is a plain C struct.
+            let mut statx: libc::statx = unsafe { std::mem::zeroed() };
+            crate::syscall!(statx(dir_fd, path.as_ptr(), flags, mask, &mut statx))?;
+            Ok(statx)
+        };
+        match fd {
+            Some(fd) => crate::fs::asyncify_fd(fd, call).await,
+            None => crate::fs::asyncify(move || call(libc::AT_FDCWD)).await,
+        }
+    }
+
+    pub(crate) async fn result(self) -> io::Result<libc::statx> {
+        let complete = self.await;
+        complete.meta.result?;
+        Ok(*complete.data.statx)
+    }
+}
+
+#[inline]
+fn path_flags(follow_symlinks: bool) -> i32 {
+    if follow_symlinks {
+        0
+    } else {
+        libc::AT_SYMLINK_NOFOLLOW
+    }
+}
+
+impl Statx {
+    const MASK: u32 = libc::STATX_BASIC_STATS | libc::STATX_BTIME;
+
+    fn new(fd: Option<SharedFd>, path: CString, flags: i32, mask: u32, personality: u16) -> Self {
+        Self {
+            fd,
+            path,
+            flags,
+            mask,
+            personality,
+            // Safety: statx is a plain C struct.
+            statx: Box::new(unsafe { std::mem::zeroed() }),
+        }
+    }
+
+    #[inline]
+    fn dir_fd(&self) -> i32 {
+        self.fd
+            .as_ref()
+            .map(|fd| fd.raw_fd())
+            .unwrap_or(libc::AT_FDCWD)
+    }
+}
+
+impl OpAble for Statx {
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    fn uring_op(&mut self) -> io_uring::squeue::Entry {
+        opcode::Statx::new(
+            types::Fd(self.dir_fd()),
+            self.path.as_ptr(),
+            self.statx.as_mut() as *mut libc::statx as *mut types::statx,
+        )
+        .flags(self.flags)
+        .mask(self.mask)
+        .build()
+        .personality(self.personality)
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    #[inline]
+    fn legacy_interest(&self) -> Option<(Direction, usize)> {
+        None
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    fn legacy_call(&mut self) -> io::Result<u32> {
+        syscall_u32!(statx(
+            self.dir_fd(),
+            self.path.as_ptr(),
+            self.flags,
+            self.mask,
+            self.statx.as_mut() as *mut libc::statx
+        ))
+    }
+}
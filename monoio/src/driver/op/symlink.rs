@@ -0,0 +1,56 @@
+use std::{ffi::CString, future::Future, io, path::Path};
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+use io_uring::{opcode, types};
+
+use super::{Op, OpAble};
+use crate::driver::util::cstr;
+#[cfg(any(feature = "legacy", feature = "poll-io"))]
+use crate::{driver::ready::Direction, syscall_u32};
+
+/// Create a symbolic link(uring requires kernel 5.15+).
+pub(crate) struct Symlink {
+    original: CString,
+    link: CString,
+}
+
+impl Op<Symlink> {
+    /// Submit a request to create a symbolic link at `link` pointing to
+    /// `original`, see [`Op::submit_or_asyncify`].
+    pub(crate) fn symlink<P: AsRef<Path>, Q: AsRef<Path>>(
+        original: P,
+        link: Q,
+    ) -> io::Result<impl Future<Output = io::Result<u32>>> {
+        // Here the paths will be copied, so its safe.
+        let original = cstr(original.as_ref())?;
+        let link = cstr(link.as_ref())?;
+        Op::submit_or_asyncify(Symlink { original, link })
+    }
+}
+
+impl OpAble for Symlink {
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    fn uring_op(&mut self) -> io_uring::squeue::Entry {
+        opcode::SymlinkAt::new(
+            types::Fd(libc::AT_FDCWD),
+            self.original.as_ptr(),
+            self.link.as_ptr(),
+        )
+        .build()
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    #[inline]
+    fn legacy_interest(&self) -> Option<(Direction, usize)> {
+        None
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    fn legacy_call(&mut self) -> io::Result<u32> {
+        syscall_u32!(symlinkat(
+            self.original.as_ptr(),
+            libc::AT_FDCWD,
+            self.link.as_ptr()
+        ))
+    }
+}
@@ -0,0 +1,105 @@
+use std::{io, time::Duration};
+
+use io_uring::{
+    opcode,
+    types::{TimeoutFlags, Timespec},
+};
+
+use super::{super::uring::UringInner, Op, OpAble};
+#[cfg(any(feature = "legacy", feature = "poll-io"))]
+use crate::driver::ready::Direction;
+use crate::{driver, time::Instant};
+
+/// A timer fired by the kernel at an absolute `CLOCK_MONOTONIC` deadline.
+pub(crate) struct Timeout {
+    // Read by the kernel when the sqe is submitted.
+    timespec: Box<Timespec>,
+}
+
+impl Op<Timeout> {
+    pub(crate) fn timeout(deadline: Instant) -> io::Result<Op<Timeout>> {
+        let mut now = libc::timespec {
+            tv_sec: 0,
+            tv_nsec: 0,
+        };
+        crate::syscall!(clock_gettime(libc::CLOCK_MONOTONIC, &mut now))?;
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        let deadline = Duration::new(now.tv_sec as u64, now.tv_nsec as u32)
+            .checked_add(remaining)
+            .unwrap_or(Duration::MAX);
+        Op::submit_with(Timeout {
+            timespec: Box::new(
+                Timespec::new()
+                    .sec(deadline.as_secs())
+                    .nsec(deadline.subsec_nanos()),
+            ),
+        })
+    }
+
+    /// Remove the timeout from the kernel with `TimeoutRemove`. The timespec
+    /// is kept until the removal completes.
+    pub(crate) fn remove(mut self) {
+        #[allow(irrefutable_let_patterns)]
+        if let driver::Inner::Uring(inner) = &self.driver {
+            UringInner::remove_timeout(inner, self.index, &mut self.data);
+            // The op is dropped by the driver now.
+            self.index = usize::MAX;
+        }
+    }
+}
+
+impl OpAble for Timeout {
+    fn uring_op(&mut self) -> io_uring::squeue::Entry {
+        opcode::Timeout::new(&*self.timespec as *const Timespec)
+            .flags(TimeoutFlags::ABS)
+            .build()
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    #[inline]
+    fn legacy_interest(&self) -> Option<(Direction, usize)> {
+        None
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    fn legacy_call(&mut self) -> io::Result<u32> {
+        unreachable!("timeout op is only submitted to uring driver")
+    }
+}
+
+/// A timeout linked to the previous op, which cancels the op when it fires.
+/// It completes with `ETIME` if fired, or `ECANCELED` if the op completes
+/// first.
+pub(crate) struct LinkTimeout {
+    // Read by the kernel when the sqe is submitted.
+    timespec: Box<Timespec>,
+}
+
+impl LinkTimeout {
+    pub(crate) fn new(timeout: Duration) -> Self {
+        Self {
+            timespec: Box::new(
+                Timespec::new()
+                    .sec(timeout.as_secs())
+                    .nsec(timeout.subsec_nanos()),
+            ),
+        }
+    }
+}
+
+impl OpAble for LinkTimeout {
+    fn uring_op(&mut self) -> io_uring::squeue::Entry {
+        opcode::LinkTimeout::new(&*self.timespec as *const Timespec).build()
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    #[inline]
+    fn legacy_interest(&self) -> Option<(Direction, usize)> {
+        None
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    fn legacy_call(&mut self) -> io::Result<u32> {
+        unreachable!("link timeout op is only submitted to uring driver")
+    }
+}
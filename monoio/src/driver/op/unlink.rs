@@ -0,0 +1,127 @@
+use std::{
+    ffi::CString,
+    future::Future,
+    io,
+    os::fd::{AsRawFd, OwnedFd, RawFd},
+    path::Path,
+    sync::Arc,
+};
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+use io_uring::{opcode, types};
+
+use super::{Op, OpAble};
+use crate::driver::util::cstr;
+#[cfg(any(feature = "legacy", feature = "poll-io"))]
+use crate::{driver::ready::Direction, syscall_u32};
+
+/// Remove a file or an empty directory(uring requires kernel 5.11+).
+pub(crate) struct Unlink {
+    // The directory `path` is relative to, the current one if `None`.
+    dir: Option<Arc<OwnedFd>>,
+    path: CString,
+    remove_dir: bool,
+    // Credentials to run with, 0 for the ones of the thread.
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    personality: u16,
+}
+
+impl Op<Unlink> {
+    /// Submit a request to remove a file, see [`Op::submit_or_asyncify`].
+    pub(crate) fn unlink_file<P: AsRef<Path>>(
+        path: P,
+    ) -> io::Result<impl Future<Output = io::Result<u32>>> {
+        Self::unlink(path, false)
+    }
+
+    /// Submit a request to remove an empty directory, see
+    /// [`Op::submit_or_asyncify`].
+    pub(crate) fn unlink_dir<P: AsRef<Path>>(
+        path: P,
+    ) -> io::Result<impl Future<Output = io::Result<u32>>> {
+        Self::unlink(path, true)
+    }
+
+    /// Submit a request to remove a file or an empty directory with the
+    /// credentials of the personality.
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    pub(crate) fn unlink_as<P: AsRef<Path>>(
+        path: P,
+        remove_dir: bool,
+        personality: super::PersonalityId,
+    ) -> io::Result<Op<Unlink>> {
+        let path = cstr(path.as_ref())?;
+        let personality = super::personality(Some(personality), opcode::UnlinkAt::CODE)?;
+        Op::submit_with(Unlink {
+            dir: None,
+            path,
+            remove_dir,
+            personality,
+        })
+    }
+
+    fn unlink<P: AsRef<Path>>(
+        path: P,
+        remove_dir: bool,
+    ) -> io::Result<impl Future<Output = io::Result<u32>>> {
+        // Here the path will be copied, so its safe.
+        let path = cstr(path.as_ref())?;
+        Self::unlink_at(None, path, remove_dir)
+    }
+
+    /// Submit a request to remove the entry `path` of the directory `dir`,
+    /// which is kept open until the request completes, see
+    /// [`Op::submit_or_asyncify`].
+    pub(crate) fn unlink_at(
+        dir: Option<Arc<OwnedFd>>,
+        path: CString,
+        remove_dir: bool,
+    ) -> io::Result<impl Future<Output = io::Result<u32>>> {
+        Op::submit_or_asyncify(Unlink {
+            dir,
+            path,
+            remove_dir,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            personality: 0,
+        })
+    }
+}
+
+impl Unlink {
+    #[inline]
+    fn dir_fd(&self) -> RawFd {
+        self.dir
+            .as_ref()
+            .map_or(libc::AT_FDCWD, |dir| dir.as_raw_fd())
+    }
+
+    #[inline]
+    fn flags(&self) -> i32 {
+        if self.remove_dir {
+            libc::AT_REMOVEDIR
+        } else {
+            0
+        }
+    }
+}
+
+impl OpAble for Unlink {
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    fn uring_op(&mut self) -> io_uring::squeue::Entry {
+        opcode::UnlinkAt::new(types::Fd(self.dir_fd()), self.path.as_ptr())
+            .flags(self.flags())
+            .build()
+            .personality(self.personality)
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    #[inline]
+    fn legacy_interest(&self) -> Option<(Direction, usize)> {
+        None
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    fn legacy_call(&mut self) -> io::Result<u32> {
+        syscall_u32!(unlinkat(self.dir_fd(), self.path.as_ptr(), self.flags()))
+    }
+}
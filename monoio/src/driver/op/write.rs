@@ -1,7 +1,7 @@
 use std::io;
 
 #[cfg(all(target_os = "linux", feature = "iouring"))]
-use io_uring::{opcode, types};
+use io_uring::opcode;
 #[cfg(all(windows, any(feature = "legacy", feature = "poll-io")))]
 use {
     crate::syscall,
@@ -12,7 +12,9 @@ use {
 #[cfg(all(unix, any(feature = "legacy", feature = "poll-io")))]
 use {crate::syscall_u32, std::os::unix::prelude::AsRawFd};
 
-use super::{super::shared_fd::SharedFd, Op, OpAble};
+#[cfg(unix)]
+use super::read::CURRENT_POS;
+use super::{super::shared_fd::SharedFd, fsync::Fsync, Link, Op, OpAble};
 #[cfg(any(feature = "legacy", feature = "poll-io"))]
 use crate::driver::ready::Direction;
 use crate::{
@@ -39,22 +41,42 @@ impl<T: IoBuf> Op<Write<T>> {
         })
     }
 
+    /// Write at the current position of the file, which is the only
+    /// position of pipes and ttys. The position is moved forward.
+    #[cfg(unix)]
+    pub(crate) fn write_stream(fd: &SharedFd, buf: T) -> io::Result<Op<Write<T>>> {
+        Self::write_at(fd, buf, CURRENT_POS)
+    }
+
     pub(crate) async fn write(self) -> BufResult<usize, T> {
         let complete = self.await;
         (complete.meta.result.map(|v| v as _), complete.data.buf)
     }
 }
 
+impl<T: IoBuf> Link<Write<T>, Fsync> {
+    /// Write at the offset, and fsync the file after the write completes.
+    /// The fsync is cancelled if the write fails or is short.
+    pub(crate) fn write_at_sync(fd: &SharedFd, buf: T, offset: u64) -> io::Result<Self> {
+        Link::submit_with(
+            Write {
+                fd: fd.clone(),
+                offset,
+                buf,
+            },
+            Fsync::new(fd, false),
+        )
+    }
+}
+
 impl<T: IoBuf> OpAble for Write<T> {
     #[cfg(all(target_os = "linux", feature = "iouring"))]
     fn uring_op(&mut self) -> io_uring::squeue::Entry {
-        opcode::Write::new(
-            types::Fd(self.fd.raw_fd()),
-            self.buf.read_ptr(),
-            self.buf.bytes_init() as _,
-        )
-        .offset(self.offset)
-        .build()
+        uring_fd!(self.fd, |fd| {
+            opcode::Write::new(fd, self.buf.read_ptr(), self.buf.bytes_init() as _)
+                .offset(self.offset)
+                .build()
+        })
     }
 
     #[cfg(any(feature = "legacy", feature = "poll-io"))]
@@ -68,6 +90,9 @@ impl<T: IoBuf> OpAble for Write<T> {
     #[cfg(all(any(feature = "legacy", feature = "poll-io"), unix))]
     fn legacy_call(&mut self) -> io::Result<u32> {
         let fd = self.fd.as_raw_fd();
+        if self.offset == CURRENT_POS {
+            return syscall_u32!(write(fd, self.buf.read_ptr() as _, self.buf.bytes_init()));
+        }
         let seek_offset = libc::off_t::try_from(self.offset)
             .map_err(|_| io::Error::new(io::ErrorKind::Other, "offset too big"))?;
         #[cfg(not(target_os = "macos"))]
@@ -106,6 +131,65 @@ impl<T: IoBuf> OpAble for Write<T> {
     }
 }
 
+/// Write from a buffer registered to the uring.
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+pub(crate) struct WriteFixed {
+    /// Holds a strong ref to the FD, preventing the file from being closed
+    /// while the operation is in-flight.
+    #[allow(unused)]
+    fd: SharedFd,
+    offset: u64,
+
+    pub(crate) buf: crate::buf::FixedBuf,
+}
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+impl Op<WriteFixed> {
+    pub(crate) fn write_fixed_at(
+        fd: &SharedFd,
+        buf: crate::buf::FixedBuf,
+        offset: u64,
+    ) -> io::Result<Self> {
+        Op::submit_with(WriteFixed {
+            fd: fd.clone(),
+            offset,
+            buf,
+        })
+    }
+
+    pub(crate) async fn write(self) -> BufResult<usize, crate::buf::FixedBuf> {
+        let complete = self.await;
+        (complete.meta.result.map(|v| v as _), complete.data.buf)
+    }
+}
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+impl OpAble for WriteFixed {
+    fn uring_op(&mut self) -> io_uring::squeue::Entry {
+        uring_fd!(self.fd, |fd| {
+            opcode::WriteFixed::new(
+                fd,
+                self.buf.read_ptr(),
+                self.buf.bytes_init() as _,
+                self.buf.buf_index(),
+            )
+            .offset(self.offset)
+            .build()
+        })
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    #[inline]
+    fn legacy_interest(&self) -> Option<(Direction, usize)> {
+        None
+    }
+
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    fn legacy_call(&mut self) -> io::Result<u32> {
+        Err(io::ErrorKind::Unsupported.into())
+    }
+}
+
 pub(crate) struct WriteVec<T> {
     /// Holds a strong ref to the FD, preventing the file from being closed
     /// while the operation is in-flight.
@@ -140,9 +224,11 @@ impl<T: IoVecBuf> Op<WriteVec<T>> {
 impl<T: IoVecBuf> OpAble for WriteVec<T> {
     #[cfg(all(target_os = "linux", feature = "iouring"))]
     fn uring_op(&mut self) -> io_uring::squeue::Entry {
-        let ptr = self.buf_vec.read_iovec_ptr() as *const _;
-        let len = self.buf_vec.read_iovec_len() as _;
-        opcode::Writev::new(types::Fd(self.fd.raw_fd()), ptr, len).build()
+        uring_fd!(self.fd, |fd| {
+            let ptr = self.buf_vec.read_iovec_ptr() as *const _;
+            let len = self.buf_vec.read_iovec_len() as _;
+            opcode::Writev::new(fd, ptr, len).build()
+        })
     }
 
     #[cfg(any(feature = "legacy", feature = "poll-io"))]
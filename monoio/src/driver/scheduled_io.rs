@@ -72,6 +72,14 @@ impl ScheduledIo {
         Poll::Pending
     }
 
+    #[inline]
+    pub(crate) fn clear_waker(&mut self, direction: Direction) {
+        match direction {
+            Direction::Read => self.reader = None,
+            Direction::Write => self.writer = None,
+        }
+    }
+
     #[inline]
     pub(crate) fn set_waker(&mut self, cx: &mut Context<'_>, direction: Direction) {
         let slot = match direction {
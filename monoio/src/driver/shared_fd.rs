@@ -22,6 +22,10 @@ struct Inner {
     #[cfg(any(unix, windows))]
     fd: RawFd,
 
+    // Slot in the registered file table
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    fixed: std::cell::Cell<Option<u32>>,
+
     // Waker to notify when the close operation completes.
     state: UnsafeCell<State>,
 }
@@ -236,6 +240,8 @@ impl SharedFd {
         Ok(SharedFd {
             inner: Rc::new(Inner {
                 fd,
+                #[cfg(all(target_os = "linux", feature = "iouring"))]
+                fixed: std::cell::Cell::new(None),
                 state: UnsafeCell::new(state),
             }),
         })
@@ -261,6 +267,8 @@ impl SharedFd {
         Ok(SharedFd {
             inner: Rc::new(Inner {
                 fd,
+                #[cfg(all(target_os = "linux", feature = "iouring"))]
+                fixed: std::cell::Cell::new(None),
                 state: UnsafeCell::new(state),
             }),
         })
@@ -286,11 +294,27 @@ impl SharedFd {
         SharedFd {
             inner: Rc::new(Inner {
                 fd,
+                #[cfg(all(target_os = "linux", feature = "iouring"))]
+                fixed: std::cell::Cell::new(None),
                 state: UnsafeCell::new(state),
             }),
         }
     }
 
+    /// Wrap a direct descriptor, which only lives in the slot of the
+    /// registered file table and has no fd. Ops on it are always submitted
+    /// with the slot, and the slot is given back when it is closed.
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    pub(crate) fn new_fixed(slot: u32) -> SharedFd {
+        SharedFd {
+            inner: Rc::new(Inner {
+                fd: -1,
+                fixed: std::cell::Cell::new(Some(slot)),
+                state: UnsafeCell::new(State::Uring(UringState::Init)),
+            }),
+        }
+    }
+
     #[cfg(windows)]
     #[allow(unreachable_code, unused)]
     pub(crate) fn new_without_register(fd: RawSocket) -> SharedFd {
@@ -312,6 +336,29 @@ impl SharedFd {
         self.inner.fd
     }
 
+    /// Duplicate the fd with `F_DUPFD_CLOEXEC`, the caller owns the new fd.
+    /// It refers to the same open file description, but it is closed
+    /// independently, so dropping either of them does not close the other.
+    #[cfg(unix)]
+    pub(crate) fn dup(&self) -> io::Result<RawFd> {
+        crate::syscall!(fcntl(self.raw_fd(), libc::F_DUPFD_CLOEXEC, 0))
+    }
+
+    /// Duplicate the fd into a new `SharedFd` registered to the current
+    /// driver, see [`dup`](SharedFd::dup). The ops in flight on either of
+    /// them are not affected by the other.
+    #[cfg(unix)]
+    pub(crate) fn try_clone(&self) -> io::Result<SharedFd> {
+        let fd = self.dup()?;
+        match SharedFd::new::<false>(fd) {
+            Ok(shared) => Ok(shared),
+            Err(e) => {
+                unsafe { libc::close(fd) };
+                Err(e)
+            }
+        }
+    }
+
     #[cfg(windows)]
     /// Returns the RawSocket
     pub(crate) fn raw_socket(&self) -> RawSocket {
@@ -332,6 +379,8 @@ impl SharedFd {
         let fd = self.inner.fd;
         match Rc::try_unwrap(self.inner) {
             Ok(inner) => {
+                #[cfg(all(target_os = "linux", feature = "iouring"))]
+                inner.unregister_fixed();
                 // Only drop Inner's state, skip its drop impl.
                 let mut inner_skip_drop = ManuallyDrop::new(inner);
                 #[allow(invalid_value)]
@@ -406,6 +455,45 @@ impl SharedFd {
         unimplemented!()
     }
 
+    /// Register the fd into the file table of uring driver, so ops on it are
+    /// submitted with the slot instead. Return false if it is not registered,
+    /// e.g. the table is full or not enabled.
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    pub(crate) fn register_fixed(&self) -> bool {
+        if self.inner.fixed.get().is_some() {
+            return true;
+        }
+        let state = unsafe { &*self.inner.state.get() };
+        #[allow(irrefutable_let_patterns)]
+        let State::Uring(UringState::Init) = state
+        else {
+            return false;
+        };
+        let slot = CURRENT.with(|inner| match inner {
+            super::Inner::Uring(inner) => {
+                super::IoUringDriver::register_fixed_file(inner, self.inner.fd)
+            }
+            #[cfg(feature = "legacy")]
+            super::Inner::Legacy(_) => None,
+        });
+        self.inner.fixed.set(slot);
+        slot.is_some()
+    }
+
+    /// Slot in the registered file table.
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    #[inline]
+    pub(crate) fn fixed_index(&self) -> Option<u32> {
+        self.inner.fixed.get()
+    }
+
+    /// Check if it is a direct descriptor without fd.
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    #[inline]
+    pub(crate) fn is_direct(&self) -> bool {
+        self.inner.fd < 0
+    }
+
     #[allow(unused)]
     pub(crate) fn registered_index(&self) -> Option<usize> {
         let state = unsafe { &*self.inner.state.get() };
@@ -426,12 +514,41 @@ impl SharedFd {
         }
     }
 
+    // The readiness of legacy driver is cached, it is cleared once the
+    // syscall would block so waiting for readiness does not return at once.
+    #[cfg(unix)]
+    #[allow(unused_variables)]
+    pub(crate) fn clear_readiness_if_blocked(&self, res: &io::Result<u32>, is_read: bool) {
+        #[cfg(feature = "legacy")]
+        if matches!(res, Err(e) if e.kind() == io::ErrorKind::WouldBlock) {
+            use crate::driver::ready::Direction;
+
+            let Some(idx) = self.registered_index() else {
+                return;
+            };
+            let direction = if is_read {
+                Direction::Read
+            } else {
+                Direction::Write
+            };
+            crate::driver::CURRENT.with(|inner| {
+                #[allow(irrefutable_let_patterns)]
+                if let crate::driver::Inner::Legacy(inner) = inner {
+                    if let Some(mut io) = unsafe { &mut *inner.get() }.io_dispatch.get(idx) {
+                        io.as_mut().clear_readiness(direction.mask());
+                    }
+                }
+            })
+        }
+    }
+
     /// An FD cannot be closed until all in-flight operation have completed.
     /// This prevents bugs where in-flight reads could operate on the incorrect
     /// file descriptor.
-    pub(crate) async fn close(self) {
-        // Here we only submit close op for uring mode.
-        // Fd will be closed when Inner drops for legacy mode.
+    ///
+    /// The error of closing is returned if this is the last reference.
+    pub(crate) async fn close(self) -> io::Result<()> {
+        // Here we submit close op for uring mode.
         #[cfg(all(target_os = "linux", feature = "iouring"))]
         {
             let fd = self.inner.fd;
@@ -439,17 +556,27 @@ impl SharedFd {
             #[allow(irrefutable_let_patterns)]
             if let State::Uring(uring_state) = unsafe { &mut *this.inner.state.get() } {
                 if Rc::get_mut(&mut this.inner).is_some() {
+                    this.inner.unregister_fixed();
+                    // The file of a direct descriptor is released with its slot.
+                    if fd < 0 {
+                        *uring_state = UringState::Closed;
+                        return Ok(());
+                    }
                     *uring_state = match super::op::Op::close(fd) {
                         Ok(op) => UringState::Closing(op),
                         Err(_) => {
-                            let _ = unsafe { std::fs::File::from_raw_fd(fd) };
-                            return;
+                            *uring_state = UringState::Closed;
+                            return crate::syscall!(close(fd)).map(|_| ());
                         }
                     };
                 }
-                this.inner.closed().await;
+                return this.inner.closed().await;
             }
+            #[allow(unreachable_code)]
+            close_legacy(this).await
         }
+        #[cfg(not(all(target_os = "linux", feature = "iouring")))]
+        close_legacy(self).await
     }
 
     #[cfg(feature = "poll-io")]
@@ -469,9 +596,25 @@ impl SharedFd {
 
 #[cfg(all(target_os = "linux", feature = "iouring"))]
 impl Inner {
+    /// Give the slot in the registered file table back. In-flight ops on the
+    /// slot have completed since they hold the Inner.
+    fn unregister_fixed(&self) {
+        let Some(slot) = self.fixed.take() else {
+            return;
+        };
+        if CURRENT.is_set() {
+            CURRENT.with(|inner| {
+                #[allow(irrefutable_let_patterns)]
+                if let super::Inner::Uring(inner) = inner {
+                    super::IoUringDriver::unregister_fixed_file(inner, slot);
+                }
+            });
+        }
+    }
+
     /// Completes when the FD has been closed.
     /// Should only be called for uring mode.
-    async fn closed(&self) {
+    async fn closed(&self) -> io::Result<()> {
         use std::task::Poll;
 
         crate::macros::support::poll_fn(|cx| {
@@ -498,19 +641,18 @@ impl Inner {
                         Poll::Pending
                     }
                     UringState::Closing(op) => {
-                        // Nothing to do if the close operation failed.
-                        let _ = ready!(Pin::new(op).poll(cx));
+                        let completion = ready!(Pin::new(op).poll(cx));
                         *uring_state = UringState::Closed;
-                        Poll::Ready(())
+                        Poll::Ready(completion.meta.result.map(|_| ()))
                     }
-                    UringState::Closed => Poll::Ready(()),
+                    UringState::Closed => Poll::Ready(Ok(())),
                     #[cfg(feature = "poll-io")]
-                    UringState::Legacy(_) => Poll::Ready(()),
+                    UringState::Legacy(_) => Poll::Ready(Ok(())),
                 };
             }
-            Poll::Ready(())
+            Poll::Ready(Ok(()))
         })
-        .await;
+        .await
     }
 }
 
@@ -523,7 +665,8 @@ impl Drop for Inner {
         match state {
             #[cfg(all(target_os = "linux", feature = "iouring"))]
             State::Uring(UringState::Init) | State::Uring(UringState::Waiting(..)) => {
-                if super::op::Op::close(fd).is_err() {
+                self.unregister_fixed();
+                if fd >= 0 && super::op::Op::close(fd).is_err() {
                     let _ = unsafe { std::fs::File::from_raw_fd(fd) };
                 };
             }
@@ -536,6 +679,17 @@ impl Drop for Inner {
     }
 }
 
+/// Deregister and close the fd with the syscall if it is the last reference,
+/// or it is closed when the last reference drops.
+#[allow(unused)]
+async fn close_legacy(fd: SharedFd) -> io::Result<()> {
+    #[cfg(unix)]
+    if let Ok(fd) = fd.try_unwrap() {
+        return crate::syscall!(close(fd)).map(|_| ());
+    }
+    Ok(())
+}
+
 #[allow(unused_mut)]
 #[cfg(feature = "legacy")]
 fn drop_legacy(mut fd: RawFd, idx: Option<usize>) {
@@ -0,0 +1,160 @@
+//! Provided buffer ring(requires kernel 5.19+).
+
+use std::{
+    alloc::{alloc_zeroed, dealloc, Layout},
+    cell::{Cell, UnsafeCell},
+    io,
+    ptr::NonNull,
+    rc::Rc,
+    sync::atomic::{AtomicU16, Ordering},
+};
+
+use io_uring::types::BufRingEntry;
+
+use super::UringInner;
+use crate::driver::{Inner, CURRENT};
+
+const PAGE_SIZE: usize = 4096;
+
+/// A ring of fixed-size buffers registered to the uring. The kernel picks a
+/// buffer from the ring when the data arrives, and the buffer id is reported
+/// in the completion flags.
+pub(crate) struct BufRing {
+    driver: Rc<UnsafeCell<UringInner>>,
+    bgid: u16,
+
+    // Shared with the kernel, must be page aligned.
+    ring: NonNull<BufRingEntry>,
+    entries: u16,
+    // Local copy of the ring tail.
+    tail: Cell<u16>,
+
+    buf_len: usize,
+    // Written by the kernel, so we keep it as a raw pointer.
+    bufs: NonNull<[u8]>,
+}
+
+impl BufRing {
+    /// Create and register a buffer ring to the current uring driver.
+    /// `entries` must be a power of 2.
+    pub(crate) fn new(entries: u16, buf_len: usize) -> io::Result<Self> {
+        if !entries.is_power_of_two() || buf_len == 0 || buf_len > u32::MAX as usize {
+            return Err(io::ErrorKind::InvalidInput.into());
+        }
+        let driver = CURRENT.with(|inner| -> io::Result<_> {
+            match inner {
+                Inner::Uring(this) => Ok(this.clone()),
+                #[cfg(feature = "legacy")]
+                Inner::Legacy(_) => Err(io::ErrorKind::Unsupported.into()),
+            }
+        })?;
+        let inner = unsafe { &mut *driver.get() };
+
+        let layout = Self::layout(entries);
+        let ring = NonNull::new(unsafe { alloc_zeroed(layout) } as *mut BufRingEntry)
+            .ok_or_else(|| io::Error::from(io::ErrorKind::OutOfMemory))?;
+
+        let bgid = match inner.alloc_buf_group() {
+            Ok(bgid) => bgid,
+            Err(e) => {
+                unsafe { dealloc(ring.as_ptr() as *mut u8, layout) };
+                return Err(e);
+            }
+        };
+        if let Err(e) = unsafe {
+            inner
+                .uring
+                .submitter()
+                .register_buf_ring(ring.as_ptr() as u64, entries, bgid)
+        } {
+            inner.release_buf_group(bgid);
+            unsafe { dealloc(ring.as_ptr() as *mut u8, layout) };
+            return Err(e);
+        }
+
+        let this = Self {
+            driver,
+            bgid,
+            ring,
+            entries,
+            tail: Cell::new(0),
+            buf_len,
+            bufs: NonNull::from(Box::leak(
+                vec![0; entries as usize * buf_len].into_boxed_slice(),
+            )),
+        };
+        for bid in 0..entries {
+            this.push(bid);
+        }
+        this.publish();
+        Ok(this)
+    }
+
+    #[inline]
+    fn layout(entries: u16) -> Layout {
+        let size = entries as usize * std::mem::size_of::<BufRingEntry>();
+        Layout::from_size_align(size, PAGE_SIZE).expect("invalid buf ring layout")
+    }
+
+    /// Buffer group id of the ring.
+    #[inline]
+    pub(crate) fn bgid(&self) -> u16 {
+        self.bgid
+    }
+
+    /// Length of each buffer.
+    #[inline]
+    pub(crate) fn buf_len(&self) -> usize {
+        self.buf_len
+    }
+
+    /// Get the first `len` bytes of the buffer picked by the kernel.
+    ///
+    /// # Safety
+    /// The buffer must be reported by the kernel and not recycled yet.
+    #[inline]
+    pub(crate) unsafe fn buf(&self, bid: u16, len: usize) -> &[u8] {
+        std::slice::from_raw_parts(self.buf_ptr(bid), len.min(self.buf_len))
+    }
+
+    /// Give the buffer back to the kernel.
+    #[inline]
+    pub(crate) fn recycle(&self, bid: u16) {
+        self.push(bid);
+        self.publish();
+    }
+
+    fn push(&self, bid: u16) {
+        let tail = self.tail.get();
+        let idx = tail & (self.entries - 1);
+        let entry = unsafe { &mut *self.ring.as_ptr().add(idx as usize) };
+        entry.set_addr(self.buf_ptr(bid) as u64);
+        entry.set_len(self.buf_len as u32);
+        entry.set_bid(bid);
+        self.tail.set(tail.wrapping_add(1));
+    }
+
+    #[inline]
+    fn buf_ptr(&self, bid: u16) -> *mut u8 {
+        debug_assert!(bid < self.entries);
+        unsafe { (self.bufs.as_ptr() as *mut u8).add(bid as usize * self.buf_len) }
+    }
+
+    fn publish(&self) {
+        // Safety: the tail is a u16 field in the ring shared with the kernel.
+        let tail = unsafe { &*(BufRingEntry::tail(self.ring.as_ptr()) as *const AtomicU16) };
+        tail.store(self.tail.get(), Ordering::Release);
+    }
+}
+
+impl Drop for BufRing {
+    fn drop(&mut self) {
+        let inner = unsafe { &mut *self.driver.get() };
+        let _ = inner.uring.submitter().unregister_buf_ring(self.bgid);
+        inner.release_buf_group(self.bgid);
+        unsafe {
+            dealloc(self.ring.as_ptr() as *mut u8, Self::layout(self.entries));
+            drop(Box::from_raw(self.bufs.as_ptr()));
+        }
+    }
+}
@@ -0,0 +1,74 @@
+//! Registered buffers for fixed read and write.
+
+use std::{cell::UnsafeCell, io, ptr::NonNull, rc::Rc};
+
+use super::UringInner;
+use crate::driver::{Inner, CURRENT};
+
+/// A slab of fixed-size buffers registered to the uring. Fixed ops refer to
+/// a buffer with its index, so the kernel does not need to pin and unpin the
+/// pages for every op.
+pub(crate) struct FixedBufs {
+    driver: Rc<UnsafeCell<UringInner>>,
+
+    count: u16,
+    buf_len: usize,
+    // Registered to the kernel, it must never move or be freed until
+    // unregistered.
+    bufs: NonNull<[u8]>,
+}
+
+impl FixedBufs {
+    /// Allocate and register `count` buffers to the current uring driver.
+    /// Only one set of buffers can be registered to a uring at a time.
+    pub(crate) fn new(count: u16, buf_len: usize) -> io::Result<Self> {
+        if count == 0 || buf_len == 0 {
+            return Err(io::ErrorKind::InvalidInput.into());
+        }
+        let driver = CURRENT.with(|inner| -> io::Result<_> {
+            match inner {
+                Inner::Uring(this) => Ok(this.clone()),
+                #[cfg(feature = "legacy")]
+                Inner::Legacy(_) => Err(io::ErrorKind::Unsupported.into()),
+            }
+        })?;
+        let inner = unsafe { &mut *driver.get() };
+
+        let bufs = NonNull::from(Box::leak(
+            vec![0; count as usize * buf_len].into_boxed_slice(),
+        ));
+        let base = bufs.as_ptr() as *mut u8;
+        let iovecs: Vec<libc::iovec> = (0..count as usize)
+            .map(|idx| libc::iovec {
+                iov_base: unsafe { base.add(idx * buf_len) } as _,
+                iov_len: buf_len,
+            })
+            .collect();
+        if let Err(e) = unsafe { inner.uring.submitter().register_buffers(&iovecs) } {
+            unsafe { drop(Box::from_raw(bufs.as_ptr())) };
+            return Err(e);
+        }
+
+        Ok(Self {
+            driver,
+            count,
+            buf_len,
+            bufs,
+        })
+    }
+
+    /// Pointer to the start of the buffer at `index`.
+    #[inline]
+    pub(crate) fn buf_ptr(&self, index: u16) -> *mut u8 {
+        debug_assert!(index < self.count);
+        unsafe { (self.bufs.as_ptr() as *mut u8).add(index as usize * self.buf_len) }
+    }
+}
+
+impl Drop for FixedBufs {
+    fn drop(&mut self) {
+        let inner = unsafe { &mut *self.driver.get() };
+        let _ = inner.uring.submitter().unregister_buffers();
+        unsafe { drop(Box::from_raw(self.bufs.as_ptr())) };
+    }
+}
@@ -2,11 +2,17 @@
 //! Partly borrow from tokio-uring.
 
 use std::{
+    collections::VecDeque,
     io,
     task::{Context, Poll, Waker},
 };
 
-use crate::{driver::op::CompletionMeta, utils::slab::Ref};
+use io_uring::{cqueue, squeue};
+
+use crate::{
+    driver::op::{close_orphan, CompletionMeta},
+    utils::slab::Ref,
+};
 
 pub(crate) enum Lifecycle {
     /// The operation has been submitted to uring and is currently in-flight
@@ -20,13 +26,62 @@ pub(crate) enum Lifecycle {
     #[allow(dead_code)]
     Ignored(Box<dyn std::any::Any>),
 
+    /// The submitter dropped the operation and a cancel request has been
+    /// submitted. Like `Ignored`, the state is held until the operation
+    /// completes, either cancelled or finished before the cancel arrives.
+    #[allow(dead_code)]
+    Cancelling(Box<dyn std::any::Any>),
+
     /// The operation has completed.
     Completed(io::Result<u32>, u32),
+
+    /// The entries of the operation wait for space in the submission queue.
+    /// The state to take once they are pushed is kept aside, which is
+    /// `Submitted`, `Waiting` or `Ignored`.
+    Unsubmitted(Box<[squeue::Entry]>, Box<Lifecycle>),
+
+    /// The operation is multishot and some completions are not consumed yet.
+    /// The last completion without `IORING_CQE_F_MORE` terminates it.
+    Progressing(VecDeque<(io::Result<u32>, u32)>, Option<Waker>),
 }
 
 impl<'a> Ref<'a, Lifecycle> {
     pub(crate) fn complete(mut self, result: io::Result<u32>, flags: u32) {
         let ref_mut = &mut *self;
+        if cqueue::more(flags) {
+            // Multishot operation, the kernel will post more completions.
+            match ref_mut {
+                Lifecycle::Submitted => {
+                    *ref_mut = Lifecycle::Progressing(VecDeque::from([(result, flags)]), None);
+                }
+                Lifecycle::Waiting(_) => {
+                    let old = std::mem::replace(
+                        ref_mut,
+                        Lifecycle::Progressing(VecDeque::from([(result, flags)]), None),
+                    );
+                    match old {
+                        Lifecycle::Waiting(waker) => {
+                            waker.wake();
+                        }
+                        _ => unsafe { std::hint::unreachable_unchecked() },
+                    }
+                }
+                Lifecycle::Progressing(queue, waker) => {
+                    queue.push_back((result, flags));
+                    if let Some(waker) = waker.take() {
+                        waker.wake();
+                    }
+                }
+                Lifecycle::Ignored(data) | Lifecycle::Cancelling(data) => {
+                    close_orphan(&**data, &result, flags);
+                }
+                Lifecycle::Completed(..) | Lifecycle::Unsubmitted(..) => unsafe {
+                    std::hint::unreachable_unchecked()
+                },
+            }
+            return;
+        }
+
         match ref_mut {
             Lifecycle::Submitted => {
                 *ref_mut = Lifecycle::Completed(result, flags);
@@ -40,10 +95,19 @@ impl<'a> Ref<'a, Lifecycle> {
                     _ => unsafe { std::hint::unreachable_unchecked() },
                 }
             }
-            Lifecycle::Ignored(..) => {
+            Lifecycle::Progressing(queue, waker) => {
+                queue.push_back((result, flags));
+                if let Some(waker) = waker.take() {
+                    waker.wake();
+                }
+            }
+            Lifecycle::Ignored(data) | Lifecycle::Cancelling(data) => {
+                close_orphan(&**data, &result, flags);
                 self.remove();
             }
-            Lifecycle::Completed(..) => unsafe { std::hint::unreachable_unchecked() },
+            Lifecycle::Completed(..) | Lifecycle::Unsubmitted(..) => unsafe {
+                std::hint::unreachable_unchecked()
+            },
         }
     }
 
@@ -61,6 +125,25 @@ impl<'a> Ref<'a, Lifecycle> {
                 }
                 return Poll::Pending;
             }
+            Lifecycle::Unsubmitted(_, next) => {
+                **next = Lifecycle::Waiting(cx.waker().clone());
+                return Poll::Pending;
+            }
+            Lifecycle::Progressing(queue, waker) => {
+                match queue.pop_front() {
+                    Some((result, flags)) => {
+                        if !cqueue::more(flags) {
+                            // The last completion, the operation is finished.
+                            self.remove();
+                        }
+                        return Poll::Ready(CompletionMeta { result, flags });
+                    }
+                    None => {
+                        *waker = Some(cx.waker().clone());
+                        return Poll::Pending;
+                    }
+                }
+            }
             _ => {}
         }
 
@@ -71,22 +154,45 @@ impl<'a> Ref<'a, Lifecycle> {
     }
 
     // return if the op must has been finished
-    pub(crate) fn drop_op<T: 'static>(mut self, data: &mut Option<T>) -> bool {
+    // If `cancel` is set, the caller submits a cancel request for the op.
+    pub(crate) fn drop_op<T: 'static>(mut self, data: &mut Option<T>, cancel: bool) -> bool {
         let ref_mut = &mut *self;
         match ref_mut {
-            Lifecycle::Submitted | Lifecycle::Waiting(_) => {
-                if let Some(data) = data.take() {
-                    *ref_mut = Lifecycle::Ignored(Box::new(data));
-                } else {
-                    *ref_mut = Lifecycle::Ignored(Box::new(())); // () is a ZST, so it does not
-                                                                 // allocate
+            Lifecycle::Submitted | Lifecycle::Waiting(_) | Lifecycle::Progressing(..) => {
+                if let (Lifecycle::Progressing(queue, _), Some(data)) = (&mut *ref_mut, &*data) {
+                    for (result, flags) in queue.drain(..) {
+                        close_orphan(data, &result, flags);
+                    }
+                }
+                let data: Box<dyn std::any::Any> = match data.take() {
+                    Some(data) => Box::new(data),
+                    // () is a ZST, so it does not allocate
+                    None => Box::new(()),
+                };
+                *ref_mut = match cancel {
+                    true => Lifecycle::Cancelling(data),
+                    false => Lifecycle::Ignored(data),
                 };
                 return false;
             }
-            Lifecycle::Completed(..) => {
+            Lifecycle::Completed(result, flags) => {
+                if let Some(data) = &*data {
+                    close_orphan(data, result, *flags);
+                }
                 self.remove();
             }
-            Lifecycle::Ignored(..) => unsafe { std::hint::unreachable_unchecked() },
+            // The entries are still pushed later, e.g. the close of a dropped
+            // fd. There is nothing in the kernel to cancel yet.
+            Lifecycle::Unsubmitted(_, next) => {
+                let data: Box<dyn std::any::Any> = match data.take() {
+                    Some(data) => Box::new(data),
+                    None => Box::new(()),
+                };
+                **next = Lifecycle::Ignored(data);
+            }
+            Lifecycle::Ignored(..) | Lifecycle::Cancelling(..) => unsafe {
+                std::hint::unreachable_unchecked()
+            },
         }
         true
     }
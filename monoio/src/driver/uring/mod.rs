@@ -2,6 +2,7 @@
 
 use std::{
     cell::UnsafeCell,
+    collections::VecDeque,
     io,
     mem::ManuallyDrop,
     os::unix::prelude::{AsRawFd, RawFd},
@@ -10,7 +11,7 @@ use std::{
     time::Duration,
 };
 
-use io_uring::{cqueue, opcode, types::Timespec, IoUring};
+use io_uring::{cqueue, opcode, squeue, types::Timespec, IoUring, Probe};
 use lifecycle::Lifecycle;
 
 use super::{
@@ -24,21 +25,44 @@ use super::{
 };
 use crate::utils::slab::Slab;
 
+pub(crate) mod buf_ring;
+pub(crate) mod fixed_buf;
 mod lifecycle;
+mod restriction;
 #[cfg(feature = "sync")]
 mod waker;
 #[cfg(feature = "sync")]
 pub(crate) use waker::UnparkHandle;
 
-#[allow(unused)]
+pub use self::restriction::AllowedOp;
+
 pub(crate) const CANCEL_USERDATA: u64 = u64::MAX;
 pub(crate) const TIMEOUT_USERDATA: u64 = u64::MAX - 1;
 #[allow(unused)]
 pub(crate) const EVENTFD_USERDATA: u64 = u64::MAX - 2;
 #[cfg(feature = "poll-io")]
 pub(crate) const POLLER_USERDATA: u64 = u64::MAX - 3;
+#[allow(unused)]
+pub(crate) const MSG_RING_USERDATA: u64 = u64::MAX - 4;
 
-pub(crate) const MIN_REVERSED_USERDATA: u64 = u64::MAX - 3;
+pub(crate) const MIN_REVERSED_USERDATA: u64 = u64::MAX - 4;
+
+// Tag of the user data of the sent msg_ring sqes, the other bits are the key
+// of the target in `msg_rings`. It is far above the op indexes.
+#[cfg(feature = "sync")]
+const MSG_RING_SENT_TAG: u64 = 1 << 62;
+
+// Index of the ops which are not supported by the kernel and polled with the
+// poller. It is never allocated in the slab.
+#[cfg(feature = "poll-io")]
+const LEGACY_INDEX: usize = usize::MAX - 1;
+
+// Not exported by io_uring crate.
+const IORING_ENTER_GETEVENTS: u32 = 1;
+const IORING_ENTER_SQ_WAKEUP: u32 = 2;
+const IORING_ENTER_SQ_WAIT: u32 = 4;
+const IORING_ENTER_EXT_ARG: u32 = 8;
+const IORING_ENTER_REGISTERED_RING: u32 = 16;
 
 /// Driver with uring.
 pub struct IoUringDriver {
@@ -68,6 +92,20 @@ pub(crate) struct UringInner {
     /// IoUring bindings
     uring: ManuallyDrop<IoUring>,
 
+    // Entries of the driver itself waiting for space in the submission
+    // queue, e.g. cancels, in submission order
+    pending: VecDeque<squeue::Entry>,
+
+    // Ops waiting for space in the submission queue, in submission order.
+    // Their entries are kept in their own `Lifecycle::Unsubmitted`.
+    sq_waiters: VecDeque<usize>,
+
+    // Times that entries had to wait for space in the submission queue
+    sq_full: u64,
+
+    // Times that the completion queue overflowed
+    cq_overflow: u64,
+
     /// Shared waker
     #[cfg(feature = "sync")]
     shared_waker: std::sync::Arc<waker::EventWaker>,
@@ -76,12 +114,62 @@ pub(crate) struct UringInner {
     #[cfg(feature = "sync")]
     eventfd_installed: bool,
 
+    // Targets of the msg_ring sqes in flight, which are woken with their
+    // eventfd if the message fails.
+    #[cfg(feature = "sync")]
+    msg_rings: fxhash::FxHashMap<u64, std::sync::Weak<waker::EventWaker>>,
+    #[cfg(feature = "sync")]
+    msg_ring_key: u64,
+
     // Waker receiver
     #[cfg(feature = "sync")]
     waker_receiver: flume::Receiver<std::task::Waker>,
 
     // Uring support ext_arg
     ext_arg: bool,
+
+    // Completions are only posted when entering with GETEVENTS
+    defer_taskrun: bool,
+
+    // Supported opcodes
+    opcodes: Opcodes,
+
+    // Opcodes rejected by the restrictions of the ring
+    denied: Opcodes,
+
+    // Busy poll timeout of the registered NAPI
+    napi_busy_poll: Option<Duration>,
+
+    // Index of the registered ring fd, used instead of the fd to enter
+    ring_index: Option<u32>,
+
+    // Ids of the registered personalities
+    personalities: Vec<u16>,
+
+    // Buffer group id allocator for provided buffer rings
+    buf_groups: BufGroups,
+
+    // Slot allocator for the registered file table
+    fixed_files: FixedFiles,
+
+    // Writes over the threshold will be sent with MSG_ZEROCOPY
+    #[cfg(feature = "zero-copy")]
+    zero_copy_threshold: usize,
+}
+
+// Allocates buffer group ids for provided buffer rings.
+#[derive(Default)]
+struct BufGroups {
+    next: u16,
+    free: Vec<u16>,
+}
+
+// Allocates slots of the registered file table.
+#[derive(Default)]
+struct FixedFiles {
+    capacity: u32,
+    next: u32,
+    free: Vec<u32>,
 }
 
 // When dropping the driver, all in-flight operations must have completed. This
@@ -91,18 +179,56 @@ struct Ops {
 }
 
 impl IoUringDriver {
-    const DEFAULT_ENTRIES: u32 = 1024;
+    pub(crate) const DEFAULT_ENTRIES: u32 = 1024;
+
+    pub(crate) fn new(b: &io_uring::Builder, taskrun: bool) -> io::Result<IoUringDriver> {
+        Self::new_with_entries(b, Self::DEFAULT_ENTRIES, taskrun)
+    }
+
+    /// Build the ring with COOP_TASKRUN, SINGLE_ISSUER and DEFER_TASKRUN if
+    /// `taskrun` is set, and retry without them if the kernel does not know
+    /// them(before 6.1). Returns if DEFER_TASKRUN is enabled.
+    fn build_uring(
+        urb: &io_uring::Builder,
+        entries: u32,
+        taskrun: bool,
+    ) -> io::Result<(IoUring, bool)> {
+        if taskrun {
+            let mut flagged = urb.clone();
+            flagged
+                .setup_coop_taskrun()
+                .setup_single_issuer()
+                .setup_defer_taskrun();
+            match flagged.build(entries) {
+                Ok(uring) => return Ok((uring, true)),
+                Err(e) if e.raw_os_error() == Some(libc::EINVAL) => (),
+                Err(e) => return Err(e),
+            }
+        }
+        Ok((urb.build(entries)?, false))
+    }
 
-    pub(crate) fn new(b: &io_uring::Builder) -> io::Result<IoUringDriver> {
-        Self::new_with_entries(b, Self::DEFAULT_ENTRIES)
+    /// Without NODROP(before kernel 5.5), the completions are dropped when
+    /// the completion queue overflows and the ops never complete.
+    fn check_nodrop(uring: &IoUring) -> io::Result<()> {
+        if uring.params().is_feature_nodrop() {
+            return Ok(());
+        }
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "io_uring without IORING_FEAT_NODROP may lose completions, kernel 5.6+ is required",
+        ))
     }
 
     #[cfg(not(feature = "sync"))]
     pub(crate) fn new_with_entries(
         urb: &io_uring::Builder,
         entries: u32,
+        taskrun: bool,
     ) -> io::Result<IoUringDriver> {
-        let uring = ManuallyDrop::new(urb.build(entries)?);
+        let (uring, defer_taskrun) = Self::build_uring(urb, entries, taskrun)?;
+        Self::check_nodrop(&uring)?;
+        let uring = ManuallyDrop::new(uring);
 
         let inner = Rc::new(UnsafeCell::new(UringInner {
             #[cfg(feature = "poll-io")]
@@ -111,7 +237,21 @@ impl IoUringDriver {
             poller_installed: false,
             ops: Ops::new(),
             ext_arg: uring.params().is_feature_ext_arg(),
+            defer_taskrun,
+            opcodes: Opcodes::probe(&uring),
+            denied: Opcodes([0; 4]),
+            napi_busy_poll: None,
+            ring_index: None,
+            personalities: Vec::new(),
             uring,
+            pending: VecDeque::new(),
+            sq_waiters: VecDeque::new(),
+            sq_full: 0,
+            cq_overflow: 0,
+            buf_groups: BufGroups::default(),
+            fixed_files: FixedFiles::default(),
+            #[cfg(feature = "zero-copy")]
+            zero_copy_threshold: super::op::MSG_ZEROCOPY_THRESHOLD,
         }));
 
         Ok(IoUringDriver {
@@ -124,8 +264,11 @@ impl IoUringDriver {
     pub(crate) fn new_with_entries(
         urb: &io_uring::Builder,
         entries: u32,
+        taskrun: bool,
     ) -> io::Result<IoUringDriver> {
-        let uring = ManuallyDrop::new(urb.build(entries)?);
+        let (uring, defer_taskrun) = Self::build_uring(urb, entries, taskrun)?;
+        Self::check_nodrop(&uring)?;
+        let uring = ManuallyDrop::new(uring);
 
         // Create eventfd and register it to the ring.
         let waker = {
@@ -145,9 +288,25 @@ impl IoUringDriver {
             poll: super::poll::Poll::with_capacity(entries as usize)?,
             ops: Ops::new(),
             ext_arg: uring.params().is_feature_ext_arg(),
+            defer_taskrun,
+            opcodes: Opcodes::probe(&uring),
+            denied: Opcodes([0; 4]),
+            napi_busy_poll: None,
+            ring_index: None,
+            personalities: Vec::new(),
             uring,
+            pending: VecDeque::new(),
+            sq_waiters: VecDeque::new(),
+            sq_full: 0,
+            cq_overflow: 0,
+            buf_groups: BufGroups::default(),
+            fixed_files: FixedFiles::default(),
+            #[cfg(feature = "zero-copy")]
+            zero_copy_threshold: super::op::MSG_ZEROCOPY_THRESHOLD,
             shared_waker: std::sync::Arc::new(waker::EventWaker::new(waker)),
             eventfd_installed: false,
+            msg_rings: fxhash::FxHashMap::default(),
+            msg_ring_key: 0,
             waker_receiver,
         }));
 
@@ -165,6 +324,106 @@ impl IoUringDriver {
         Ok(driver)
     }
 
+    #[cfg(feature = "zero-copy")]
+    pub(crate) fn set_zero_copy_threshold(&self, threshold: usize) {
+        let inner = self.inner.get();
+        unsafe { (*inner).zero_copy_threshold = threshold };
+    }
+
+    /// Register a sparse file table with `capacity` slots(requires kernel
+    /// 5.19+). Fds can then be registered into it and addressed by slot.
+    /// If it fails, fds are not registered and used as is.
+    pub(crate) fn register_files(&self, capacity: u32) {
+        let inner = unsafe { &mut *self.inner.get() };
+        if inner
+            .uring
+            .submitter()
+            .register_files_sparse(capacity)
+            .is_ok()
+        {
+            inner.fixed_files.capacity = capacity;
+        }
+    }
+
+    /// Register the ring fd, so entering the ring does not look up the fd
+    /// (requires kernel 5.18+). If it fails, the fd is used as is.
+    pub(crate) fn register_ring_fd(&self) {
+        let inner = unsafe { &mut *self.inner.get() };
+        let mut update = IoUringRsrcUpdate {
+            // Let the kernel pick a free slot.
+            offset: u32::MAX,
+            resv: 0,
+            data: inner.uring.as_raw_fd() as u64,
+        };
+        if rsrc_update(&inner.uring, IORING_REGISTER_RING_FDS, &mut update).is_ok() {
+            inner.ring_index = Some(update.offset);
+        }
+    }
+
+    /// Register NAPI busy polling of the sockets used by the ring(requires
+    /// kernel 6.9+). If the kernel does not support it, the ring works as
+    /// usual.
+    pub(crate) fn register_napi(
+        &self,
+        busy_poll_us: u32,
+        prefer_busy_poll: bool,
+    ) -> io::Result<()> {
+        static UNSUPPORTED: std::sync::Once = std::sync::Once::new();
+
+        let inner = unsafe { &mut *self.inner.get() };
+        let mut napi = IoUringNapi {
+            busy_poll_to: busy_poll_us,
+            prefer_busy_poll: prefer_busy_poll as u8,
+            ..Default::default()
+        };
+        match napi_register(&inner.uring, IORING_REGISTER_NAPI, &mut napi) {
+            Ok(()) => {
+                inner.napi_busy_poll = Some(Duration::from_micros(busy_poll_us as u64));
+                Ok(())
+            }
+            Err(e) if matches!(e.raw_os_error(), Some(libc::EINVAL | libc::EOPNOTSUPP)) => {
+                UNSUPPORTED.call_once(|| {
+                    info!("io_uring NAPI is not supported, busy polling is disabled");
+                });
+                Ok(())
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Run the ops with the opcodes in the legacy way.
+    pub(crate) fn disable_opcodes(&self, codes: &[u8]) {
+        let inner = unsafe { &mut *self.inner.get() };
+        codes.iter().for_each(|code| inner.opcodes.remove(*code));
+    }
+
+    /// Register the allowlist of opcodes to the ring created with
+    /// `R_DISABLED`, then enable it(requires kernel 5.10+). The opcodes not
+    /// allowed are rejected on submission, except the ones in `fallback`
+    /// which are run in the legacy way.
+    pub(crate) fn restrict(&self, allowed: &[AllowedOp], fallback: &[u8]) -> io::Result<()> {
+        let inner = unsafe { &mut *self.inner.get() };
+        let mut permitted = Opcodes([0; 4]);
+        allowed
+            .iter()
+            .flat_map(|op| op.opcodes())
+            .for_each(|code| permitted.insert(*code));
+        let registered = (0..=u8::MAX)
+            .filter(|code| permitted.contains(*code) || restriction::DRIVER_OPCODES.contains(code));
+        let mut res = restriction::restrictions(registered);
+        let submitter = inner.uring.submitter();
+        submitter.register_restrictions(&mut res)?;
+        submitter.register_enable_rings()?;
+
+        for code in (0..=u8::MAX).filter(|code| !permitted.contains(*code)) {
+            inner.opcodes.remove(code);
+            if !fallback.contains(&code) {
+                inner.denied.insert(code);
+            }
+        }
+        Ok(())
+    }
+
     #[allow(unused)]
     fn num_operations(&self) -> usize {
         let inner = self.inner.get();
@@ -173,10 +432,11 @@ impl IoUringDriver {
 
     // Flush to make enough space
     fn flush_space(inner: &mut UringInner, need: usize) -> io::Result<()> {
-        let sq = inner.uring.submission();
-        debug_assert!(sq.capacity() >= need);
-        if sq.len() + need > sq.capacity() {
-            drop(sq);
+        let capacity = inner.uring.submission().capacity();
+        debug_assert!(capacity >= need);
+        // With SQPOLL the entries are consumed by the polling thread, so the
+        // space may not be enough after one submit.
+        while inner.uring.submission().len() + need > capacity {
             inner.submit()?;
         }
         Ok(())
@@ -220,8 +480,28 @@ impl IoUringDriver {
     fn inner_park(&self, timeout: Option<Duration>) -> io::Result<()> {
         let inner = unsafe { &mut *self.inner.get() };
 
+        // The waiting entries must reach the kernel before waiting, the ops
+        // in flight may never complete without them. Every chain fits in the
+        // submission queue, so each submit moves some of them.
+        while inner.has_unsubmitted() {
+            inner.submit()?;
+        }
+
+        // With NAPI the SQPOLL thread busy polls the sockets, so spin for the
+        // busy poll timeout rather than sleep. Without SQPOLL the kernel busy
+        // polls while waiting in io_uring_enter.
+        if let Some(busy_poll) = inner
+            .napi_busy_poll
+            .filter(|_| inner.uring.params().is_setup_sqpoll())
+        {
+            inner.submit()?;
+            inner.spin(busy_poll.min(timeout.unwrap_or(Duration::MAX)));
+        }
+
+        // With SQPOLL the ops may complete without entering the kernel, so
+        // do not wait if there are completions already.
         #[allow(unused_mut)]
-        let mut need_wait = true;
+        let mut need_wait = inner.uring.completion().is_empty();
 
         #[cfg(feature = "sync")]
         {
@@ -285,14 +565,13 @@ impl IoUringDriver {
                     // Better compatibility(5.4+).
                     false => {
                         self.install_timeout(inner, duration);
-                        inner.uring.submit_and_wait(1)?;
+                        inner.submit_and_wait(1, None)?;
                     }
                     // Submit and Wait with enter args.
                     // Better performance(5.11+).
                     true => {
                         let timespec = timespec(duration);
-                        let args = io_uring::types::SubmitArgs::new().timespec(&timespec);
-                        if let Err(e) = inner.uring.submitter().submit_with_args(1, &args) {
+                        if let Err(e) = inner.submit_and_wait(1, Some(&timespec)) {
                             if e.raw_os_error() != Some(libc::ETIME) {
                                 return Err(e);
                             }
@@ -301,11 +580,11 @@ impl IoUringDriver {
                 }
             } else {
                 // Submit and Wait without timeout
-                inner.uring.submit_and_wait(1)?;
+                inner.submit_and_wait(1, None)?;
             }
         } else {
             // Submit only
-            inner.uring.submit()?;
+            inner.submit()?;
         }
 
         // Set status as awake
@@ -342,6 +621,44 @@ impl IoUringDriver {
         let inner = unsafe { &mut *this.get() };
         inner.poll.deregister(source, token)
     }
+
+    /// Take a free slot of the file table, return None if the table is full
+    /// or not registered. The slot should be given back with
+    /// `unregister_fixed_file`.
+    pub(crate) fn alloc_fixed_file(this: &Rc<UnsafeCell<UringInner>>) -> Option<u32> {
+        let inner = unsafe { &mut *this.get() };
+        let files = &mut inner.fixed_files;
+        match files.free.pop() {
+            Some(slot) => Some(slot),
+            None if files.next < files.capacity => {
+                files.next += 1;
+                Some(files.next - 1)
+            }
+            None => None,
+        }
+    }
+
+    /// Register the fd into a free slot of the file table, return None if
+    /// the table is full or not registered.
+    pub(crate) fn register_fixed_file(this: &Rc<UnsafeCell<UringInner>>, fd: RawFd) -> Option<u32> {
+        let slot = Self::alloc_fixed_file(this)?;
+        let inner = unsafe { &mut *this.get() };
+        match inner.uring.submitter().register_files_update(slot, &[fd]) {
+            Ok(_) => Some(slot),
+            Err(_) => {
+                inner.fixed_files.free.push(slot);
+                None
+            }
+        }
+    }
+
+    /// Clear the slot of the file table and recycle it. In-flight ops on the
+    /// slot still hold the file until they complete.
+    pub(crate) fn unregister_fixed_file(this: &Rc<UnsafeCell<UringInner>>, slot: u32) {
+        let inner = unsafe { &mut *this.get() };
+        let _ = inner.uring.submitter().register_files_update(slot, &[-1]);
+        inner.fixed_files.free.push(slot);
+    }
 }
 
 impl Driver for IoUringDriver {
@@ -378,28 +695,118 @@ impl Driver for IoUringDriver {
 
 impl UringInner {
     fn tick(&mut self) -> io::Result<()> {
-        let cq = self.uring.completion();
-
-        for cqe in cq {
-            let index = cqe.user_data();
-            match index {
-                #[cfg(feature = "sync")]
-                EVENTFD_USERDATA => self.eventfd_installed = false,
-                #[cfg(feature = "poll-io")]
-                POLLER_USERDATA => {
-                    self.poller_installed = false;
-                    self.poll.tick(Some(Duration::ZERO))?;
+        loop {
+            let cq = self.uring.completion();
+
+            for cqe in cq {
+                let index = cqe.user_data();
+                match index {
+                    #[cfg(feature = "sync")]
+                    EVENTFD_USERDATA => self.eventfd_installed = false,
+                    #[cfg(feature = "poll-io")]
+                    POLLER_USERDATA => {
+                        self.poller_installed = false;
+                        self.poll.tick(Some(Duration::ZERO))?;
+                    }
+                    // The cancel fails with ENOENT or EALREADY if the operation
+                    // has completed or can not be cancelled, its own completion
+                    // releases the slot anyway.
+                    CANCEL_USERDATA => (),
+                    _ if index >= MIN_REVERSED_USERDATA => (),
+                    #[cfg(feature = "sync")]
+                    _ if index & MSG_RING_SENT_TAG != 0 => {
+                        msg_ring_sent(&mut self.msg_rings, index, cqe.result())
+                    }
+                    _ => self.ops.complete(index as _, resultify(&cqe), cqe.flags()),
                 }
-                _ if index >= MIN_REVERSED_USERDATA => (),
-                _ => self.ops.complete(index as _, resultify(&cqe), cqe.flags()),
             }
+
+            // The completions which do not fit in the ring are held by the
+            // kernel(NODROP), enter with GETEVENTS to flush them into it.
+            if !self.uring.submission().cq_overflow() {
+                return Ok(());
+            }
+            self.cq_overflow += 1;
+            self.enter(0, 0, IORING_ENTER_GETEVENTS, None)?;
         }
-        Ok(())
+    }
+
+    // Enter the ring with the registered index if there is one.
+    fn enter(
+        &self,
+        to_submit: u32,
+        min_complete: u32,
+        flags: u32,
+        arg: Option<&GeteventsArg>,
+    ) -> io::Result<usize> {
+        let (fd, flags) = match self.ring_index {
+            Some(index) => (index as libc::c_long, flags | IORING_ENTER_REGISTERED_RING),
+            None => (self.uring.as_raw_fd() as libc::c_long, flags),
+        };
+        let (arg, size) = match arg {
+            Some(arg) => (
+                arg as *const GeteventsArg,
+                std::mem::size_of::<GeteventsArg>(),
+            ),
+            None => (std::ptr::null(), 0),
+        };
+        crate::syscall!(syscall(
+            libc::SYS_io_uring_enter,
+            fd,
+            to_submit,
+            min_complete,
+            flags,
+            arg,
+            size
+        ))
+        .map(|n| n as usize)
+    }
+
+    // Submit the queued entries and wait for `want` completions at most for
+    // the timeout, the same as `Submitter::submit_with_args` but entering
+    // with the registered ring.
+    fn submit_and_wait(&mut self, want: u32, timeout: Option<&Timespec>) -> io::Result<usize> {
+        let (iopoll, sqpoll) = {
+            let params = self.uring.params();
+            (params.is_setup_iopoll(), params.is_setup_sqpoll())
+        };
+        let (len, cq_overflow, need_wakeup) = {
+            let sq = self.uring.submission();
+            (sq.len(), sq.cq_overflow(), sq.need_wakeup())
+        };
+        let mut flags = 0;
+        if want > 0 || iopoll || cq_overflow {
+            flags |= IORING_ENTER_GETEVENTS;
+        }
+        if sqpoll {
+            if need_wakeup {
+                flags |= IORING_ENTER_SQ_WAKEUP;
+            } else if want == 0 {
+                // The polling thread is awake and consumes the entries.
+                return Ok(len);
+            }
+        }
+        let arg = timeout.map(|ts| {
+            flags |= IORING_ENTER_EXT_ARG;
+            GeteventsArg {
+                ts: ts as *const Timespec as u64,
+                ..Default::default()
+            }
+        });
+        self.enter(len as u32, want, flags, arg.as_ref())
     }
 
     fn submit(&mut self) -> io::Result<()> {
         loop {
-            match self.uring.submit() {
+            let res = if self.defer_taskrun {
+                // Run the deferred task work, or the completions are not
+                // posted until the driver parks.
+                let len = self.uring.submission().len() as u32;
+                self.enter(len, 0, IORING_ENTER_GETEVENTS, None)
+            } else {
+                self.submit_and_wait(0, None)
+            };
+            match res {
                 #[cfg(feature = "unstable")]
                 Err(ref e)
                     if matches!(e.kind(), io::ErrorKind::Other | io::ErrorKind::ResourceBusy) =>
@@ -416,15 +823,25 @@ impl UringInner {
                     // to get the raw error code.
                     self.tick()?;
                 }
-                e => return e.map(|_| ()),
+                Ok(_) => break,
+                Err(e) => return Err(e),
             }
         }
+        self.push_pending();
+        // The polling thread may not have consumed the entries yet, wait for
+        // space so the next push does not fail.
+        if self.uring.params().is_setup_sqpoll() && self.uring.submission().is_full() {
+            self.enter(0, 0, IORING_ENTER_SQ_WAIT, None)?;
+        }
+        Ok(())
     }
 
     fn new_op<T>(data: T, inner: &mut UringInner, driver: Inner) -> Op<T> {
         Op {
             driver,
             index: inner.ops.insert(),
+            #[cfg(feature = "legacy")]
+            legacy_interest: None,
             data: Some(data),
         }
     }
@@ -437,10 +854,6 @@ impl UringInner {
         T: OpAble,
     {
         let inner = unsafe { &mut *this.get() };
-        // If the submission queue is full, flush it to the kernel
-        if inner.uring.submission().is_full() {
-            inner.submit()?;
-        }
 
         // Create the operation
         let mut op = Self::new_op(data, inner, Inner::Uring(this.clone()));
@@ -448,16 +861,39 @@ impl UringInner {
         // Configure the SQE
         let data_mut = unsafe { op.data.as_mut().unwrap_unchecked() };
         let sqe = OpAble::uring_op(data_mut).user_data(op.index as _);
+        if let Err(e) = inner.check_restricted(&sqe) {
+            inner.ops.slab.remove(op.index);
+            op.index = usize::MAX;
+            return Err(e);
+        }
 
-        {
-            let mut sq = inner.uring.submission();
-
-            // Push the new operation
-            if unsafe { sq.push(&sqe).is_err() } {
-                unimplemented!("when is this hit?");
+        // Run the op in the legacy way if the kernel does not support it.
+        #[cfg(any(feature = "legacy", feature = "poll-io"))]
+        if !inner.opcodes.contains(sqe_opcode(&sqe)) {
+            match data_mut.legacy_interest() {
+                // The op does not wait for readiness, do syscall right now.
+                None => {
+                    let result = OpAble::legacy_call(data_mut);
+                    inner.ops.complete(op.index, result, 0);
+                    return Ok(op);
+                }
+                // The op is polled with the poller when the future is polled.
+                #[cfg(feature = "poll-io")]
+                Some(_) => {
+                    inner.ops.slab.remove(op.index);
+                    op.index = LEGACY_INDEX;
+                    return Ok(op);
+                }
+                // There is no way to wait for readiness, submit it anyway.
+                #[cfg(not(feature = "poll-io"))]
+                Some(_) => (),
             }
         }
 
+        // Push the new operation. If the queue is full, it waits for space
+        // and the future completes later as usual.
+        inner.push_op_sqes(op.index, std::slice::from_ref(&sqe));
+
         // Submit the new operation. At this point, the operation has been
         // pushed onto the queue and the tail pointer has been updated, so
         // the submission entry is visible to the kernel. If there is an
@@ -470,11 +906,110 @@ impl UringInner {
         Ok(op)
     }
 
-    pub(crate) fn poll_op(
+    /// Submit two operations linked with `IOSQE_IO_LINK`. Both SQEs are pushed
+    /// at once, so the chain is never split by a submit.
+    pub(crate) fn submit_link_with_data<A, B>(
+        this: &Rc<UnsafeCell<UringInner>>,
+        first: A,
+        second: B,
+    ) -> io::Result<(Op<A>, Op<B>)>
+    where
+        A: OpAble,
+        B: OpAble,
+    {
+        let inner = unsafe { &mut *this.get() };
+        // A chain is never split, so it must fit in the submission queue.
+        if inner.uring.params().sq_entries() < 2 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "linked operations do not fit in the submission queue",
+            ));
+        }
+
+        let mut first = Self::new_op(first, inner, Inner::Uring(this.clone()));
+        let mut second = Self::new_op(second, inner, Inner::Uring(this.clone()));
+
+        let sqes = [
+            OpAble::uring_op(unsafe { first.data.as_mut().unwrap_unchecked() })
+                .user_data(first.index as _)
+                .flags(squeue::Flags::IO_LINK),
+            OpAble::uring_op(unsafe { second.data.as_mut().unwrap_unchecked() })
+                .user_data(second.index as _),
+        ];
+        if let Err(e) = sqes.iter().try_for_each(|sqe| inner.check_restricted(sqe)) {
+            for op in [&mut first.index, &mut second.index] {
+                inner.ops.slab.remove(*op);
+                *op = usize::MAX;
+            }
+            return Err(e);
+        }
+        inner.push_op_sqes(first.index, &sqes);
+        Ok((first, second))
+    }
+
+    /// Submit the operations linked in order like
+    /// [`submit_link_with_data`](Self::submit_link_with_data). A chain is
+    /// never split by the SQ, so at most `sq_entries` of them are taken from
+    /// `data` and the rest are left to the caller. The ops are not run in the
+    /// legacy way, so it fails with `Unsupported` if the kernel does not
+    /// support the opcode and the caller submits them one by one instead.
+    pub(crate) fn submit_chain_with_data<T: OpAble>(
+        this: &Rc<UnsafeCell<UringInner>>,
+        data: impl Iterator<Item = T>,
+    ) -> io::Result<Vec<Op<T>>> {
+        let inner = unsafe { &mut *this.get() };
+        let max = inner.uring.params().sq_entries() as usize;
+
+        let mut data = data.take(max).peekable();
+        if let Some(first) = data.peek_mut() {
+            let code = sqe_opcode(&first.uring_op());
+            if !inner.opcodes.contains(code) {
+                return Err(io::Error::new(
+                    io::ErrorKind::Unsupported,
+                    format!("io_uring opcode {code} is not supported"),
+                ));
+            }
+        }
+        let mut ops: Vec<Op<T>> = data
+            .map(|data| Self::new_op(data, inner, Inner::Uring(this.clone())))
+            .collect();
+        let last = ops.len().saturating_sub(1);
+        let sqes: Vec<squeue::Entry> = ops
+            .iter_mut()
+            .enumerate()
+            .map(|(i, op)| {
+                let sqe = OpAble::uring_op(unsafe { op.data.as_mut().unwrap_unchecked() })
+                    .user_data(op.index as _);
+                if i < last {
+                    sqe.flags(squeue::Flags::IO_LINK)
+                } else {
+                    sqe
+                }
+            })
+            .collect();
+        if let Err(e) = sqes.iter().try_for_each(|sqe| inner.check_restricted(sqe)) {
+            for op in ops.iter_mut() {
+                inner.ops.slab.remove(op.index);
+                op.index = usize::MAX;
+            }
+            return Err(e);
+        }
+        if let Some(first) = ops.first() {
+            inner.push_op_sqes(first.index, &sqes);
+        }
+        Ok(ops)
+    }
+
+    pub(crate) fn poll_op<T: OpAble>(
         this: &Rc<UnsafeCell<UringInner>>,
+        #[allow(unused)] data: &mut T,
         index: usize,
         cx: &mut Context<'_>,
     ) -> Poll<CompletionMeta> {
+        #[cfg(feature = "poll-io")]
+        if index == LEGACY_INDEX {
+            return Self::poll_legacy_op(this, data, cx);
+        }
         let inner = unsafe { &mut *this.get() };
         let lifecycle = unsafe { inner.ops.slab.get(index).unwrap_unchecked() };
         lifecycle.poll_op(cx)
@@ -515,41 +1050,295 @@ impl UringInner {
             // already finished
             return;
         }
+        #[cfg(feature = "poll-io")]
+        if index == LEGACY_INDEX {
+            return;
+        }
         if let Some(lifecycle) = inner.ops.slab.get(index) {
-            let _must_finished = lifecycle.drop_op(data);
+            let _must_finished = lifecycle.drop_op(data, cfg!(feature = "async-cancel"));
             #[cfg(feature = "async-cancel")]
             if !_must_finished {
-                unsafe {
-                    let cancel = opcode::AsyncCancel::new(index as u64)
-                        .build()
-                        .user_data(u64::MAX);
-
-                    // Try push cancel, if failed, will submit and re-push.
-                    if inner.uring.submission().push(&cancel).is_err() {
-                        let _ = inner.submit();
-                        let _ = inner.uring.submission().push(&cancel);
-                    }
-                }
+                inner.push_cancel(index);
             }
         }
     }
 
     pub(crate) unsafe fn cancel_op(this: &Rc<UnsafeCell<UringInner>>, index: usize) {
         let inner = &mut *this.get();
-        let cancel = opcode::AsyncCancel::new(index as u64)
-            .build()
-            .user_data(u64::MAX);
-        if inner.uring.submission().push(&cancel).is_err() {
-            let _ = inner.submit();
-            let _ = inner.uring.submission().push(&cancel);
+        inner.push_cancel(index);
+    }
+
+    /// Drop a timeout operation and remove it from the kernel.
+    pub(crate) fn remove_timeout<T: 'static>(
+        this: &Rc<UnsafeCell<UringInner>>,
+        index: usize,
+        data: &mut Option<T>,
+    ) {
+        let inner = unsafe { &mut *this.get() };
+        if index == usize::MAX {
+            // already finished
+            return;
+        }
+        if let Some(lifecycle) = inner.ops.slab.get(index) {
+            if !lifecycle.drop_op(data, true) {
+                inner.push_cancel_entry(opcode::TimeoutRemove::new(index as u64).build());
+            }
+        }
+    }
+
+    /// Push a cancel request for the operation keyed by its user_data.
+    fn push_cancel(&mut self, index: usize) {
+        self.push_cancel_entry(opcode::AsyncCancel::new(index as u64).build());
+    }
+
+    fn push_cancel_entry(&mut self, cancel: io_uring::squeue::Entry) {
+        // Queued behind the op, so the cancel never reaches the kernel before
+        // the op it targets.
+        self.push_sqes(&[cancel.user_data(CANCEL_USERDATA)]);
+    }
+
+    // Spin until there are completions or foreign wakers, or the duration
+    // has elapsed.
+    fn spin(&mut self, duration: Duration) {
+        let deadline = std::time::Instant::now() + duration;
+        loop {
+            if !self.uring.completion().is_empty() {
+                return;
+            }
+            #[cfg(feature = "sync")]
+            if !self.waker_receiver.is_empty() {
+                return;
+            }
+            if std::time::Instant::now() >= deadline {
+                return;
+            }
+            std::hint::spin_loop();
+        }
+    }
+
+    // Reject the opcodes not allowed by the restrictions before the kernel
+    // fails them with EACCES.
+    fn check_restricted(&self, sqe: &squeue::Entry) -> io::Result<()> {
+        let code = sqe_opcode(sqe);
+        if !self.denied.contains(code) {
+            return Ok(());
+        }
+        Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            format!("io_uring opcode {code} is not allowed by the restrictions of the runtime"),
+        ))
+    }
+
+    /// Push the entries to the submission queue. If it is full or there are
+    /// entries waiting already, they are appended to the pending queue
+    /// instead, which is moved to the submission queue in order after the
+    /// next submit makes space. The entries of a link chain are kept
+    /// together.
+    fn push_sqes(&mut self, sqes: &[squeue::Entry]) {
+        if self.pending.is_empty() {
+            let mut sq = self.uring.submission();
+            if unsafe { sq.push_multiple(sqes).is_ok() } {
+                return;
+            }
+        }
+        self.sq_full += 1;
+        self.pending.extend(sqes.iter().cloned());
+    }
+
+    /// Push the entries of the op `index`, which are all its chain. If the
+    /// submission queue is full or other ops are waiting already, the op
+    /// waits for space with the entries kept in its lifecycle, and its
+    /// future registers the waker there as usual.
+    fn push_op_sqes(&mut self, index: usize, sqes: &[squeue::Entry]) {
+        if self.pending.is_empty() && self.sq_waiters.is_empty() {
+            let mut sq = self.uring.submission();
+            if unsafe { sq.push_multiple(sqes).is_ok() } {
+                return;
+            }
+        }
+        self.sq_full += 1;
+        let mut lifecycle = unsafe { self.ops.slab.get(index).unwrap_unchecked() };
+        *lifecycle = Lifecycle::Unsubmitted(sqes.into(), Box::new(Lifecycle::Submitted));
+        self.sq_waiters.push_back(index);
+    }
+
+    fn has_unsubmitted(&self) -> bool {
+        !self.pending.is_empty() || !self.sq_waiters.is_empty()
+    }
+
+    /// Move the waiting entries to the submission queue in order, the ones of
+    /// the driver first, as long as there is space.
+    fn push_pending(&mut self) {
+        let mut sq = self.uring.submission();
+        while !self.pending.is_empty() {
+            // A chain ends with the first entry which is not linked.
+            let chain = self
+                .pending
+                .iter()
+                .position(|sqe| sqe_flags(sqe) & SQE_LINK_FLAGS == 0)
+                .map_or(self.pending.len(), |idx| idx + 1);
+            if sq.capacity() - sq.len() < chain {
+                return;
+            }
+            for sqe in self.pending.drain(..chain) {
+                let _ = unsafe { sq.push(&sqe) };
+            }
         }
+        while let Some(&index) = self.sq_waiters.front() {
+            let mut lifecycle = unsafe { self.ops.slab.get(index).unwrap_unchecked() };
+            let Lifecycle::Unsubmitted(sqes, _) = &*lifecycle else {
+                unreachable!("unexpected operation state");
+            };
+            if unsafe { sq.push_multiple(sqes).is_err() } {
+                return;
+            }
+            self.sq_waiters.pop_front();
+            if let Lifecycle::Unsubmitted(_, next) =
+                std::mem::replace(&mut *lifecycle, Lifecycle::Submitted)
+            {
+                *lifecycle = *next;
+            }
+        }
+    }
+
+    pub(crate) fn sq_full_events(this: &Rc<UnsafeCell<UringInner>>) -> u64 {
+        let inner = unsafe { &*this.get() };
+        inner.sq_full
+    }
+
+    pub(crate) fn cq_overflow_events(this: &Rc<UnsafeCell<UringInner>>) -> u64 {
+        let inner = unsafe { &*this.get() };
+        inner.cq_overflow
+    }
+
+    #[cfg(feature = "zero-copy")]
+    pub(crate) fn zero_copy_threshold(this: &Rc<UnsafeCell<UringInner>>) -> usize {
+        let inner = unsafe { &*this.get() };
+        inner.zero_copy_threshold
+    }
+
+    /// Register the credentials of the current thread as a personality.
+    pub(crate) fn register_personality(this: &Rc<UnsafeCell<UringInner>>) -> io::Result<u16> {
+        let inner = unsafe { &mut *this.get() };
+        let id = inner.uring.submitter().register_personality()?;
+        inner.personalities.push(id);
+        Ok(id)
+    }
+
+    pub(crate) fn unregister_personality(
+        this: &Rc<UnsafeCell<UringInner>>,
+        id: u16,
+    ) -> io::Result<()> {
+        let inner = unsafe { &mut *this.get() };
+        let pos = inner
+            .personalities
+            .iter()
+            .position(|p| *p == id)
+            .ok_or_else(|| unregistered_personality(id))?;
+        inner.uring.submitter().unregister_personality(id)?;
+        inner.personalities.swap_remove(pos);
+        Ok(())
+    }
+
+    /// Check the personality is registered to the ring, and the op with
+    /// `code` is issued by the ring, otherwise it would run with the
+    /// credentials of the thread.
+    pub(crate) fn check_personality(
+        this: &Rc<UnsafeCell<UringInner>>,
+        id: u16,
+        code: u8,
+    ) -> io::Result<()> {
+        let inner = unsafe { &*this.get() };
+        if !inner.personalities.contains(&id) {
+            return Err(unregistered_personality(id));
+        }
+        if !inner.opcodes.contains(code) {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                format!("io_uring opcode {code} is not supported, the personality can not be used"),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Check if the op of the sqe would run as a blocking call, which is when
+    /// the kernel does not support its opcode. The ops not allowed by the
+    /// restrictions are rejected.
+    #[cfg(any(feature = "legacy", feature = "poll-io"))]
+    pub(crate) fn is_blocking_op(
+        this: &Rc<UnsafeCell<UringInner>>,
+        sqe: &squeue::Entry,
+    ) -> io::Result<bool> {
+        let inner = unsafe { &*this.get() };
+        inner.check_restricted(sqe)?;
+        Ok(!inner.opcodes.contains(sqe_opcode(sqe)))
+    }
+
+    pub(crate) fn is_op_supported(this: &Rc<UnsafeCell<UringInner>>, code: u8) -> bool {
+        let inner = unsafe { &*this.get() };
+        inner.opcodes.contains(code)
+    }
+
+    /// Entries of the submission and completion queues.
+    pub(crate) fn entries(this: &Rc<UnsafeCell<UringInner>>) -> (u32, u32) {
+        let params = unsafe { (*this.get()).uring.params() };
+        (params.sq_entries(), params.cq_entries())
+    }
+
+    fn alloc_buf_group(&mut self) -> io::Result<u16> {
+        if let Some(bgid) = self.buf_groups.free.pop() {
+            return Ok(bgid);
+        }
+        let bgid = self.buf_groups.next;
+        self.buf_groups.next = bgid.checked_add(1).ok_or_else(|| {
+            io::Error::new(io::ErrorKind::OutOfMemory, "buffer group ids are exhausted")
+        })?;
+        Ok(bgid)
+    }
+
+    fn release_buf_group(&mut self, bgid: u16) {
+        self.buf_groups.free.push(bgid);
     }
 
     #[cfg(feature = "sync")]
     pub(crate) fn unpark(this: &Rc<UnsafeCell<UringInner>>) -> waker::UnparkHandle {
         let inner = unsafe { &*this.get() };
-        let weak = std::sync::Arc::downgrade(&inner.shared_waker);
-        waker::UnparkHandle(weak)
+        waker::UnparkHandle {
+            waker: std::sync::Arc::downgrade(&inner.shared_waker),
+            ring_fd: inner.uring.as_raw_fd(),
+        }
+    }
+
+    /// Wake the driver of `ring_fd` by posting a cqe into its ring(requires
+    /// kernel 5.18+). Return false if it is not supported. If the message
+    /// fails later, e.g. the target ring is gone or out of memory, the target
+    /// is woken with the eventfd of `waker` instead.
+    #[cfg(feature = "sync")]
+    pub(crate) fn msg_ring(
+        this: &Rc<UnsafeCell<UringInner>>,
+        ring_fd: RawFd,
+        waker: &std::sync::Weak<waker::EventWaker>,
+    ) -> bool {
+        let inner = unsafe { &mut *this.get() };
+        if !inner.opcodes.contains(opcode::MsgRingData::CODE) {
+            return false;
+        }
+        let key = inner.msg_ring_key;
+        inner.msg_ring_key = (key + 1) & (MSG_RING_SENT_TAG - 1);
+        let entry =
+            opcode::MsgRingData::new(io_uring::types::Fd(ring_fd), 0, MSG_RING_USERDATA, None)
+                .build()
+                .user_data(MSG_RING_SENT_TAG | key);
+        unsafe {
+            if inner.uring.submission().push(&entry).is_err()
+                && (inner.submit().is_err() || inner.uring.submission().push(&entry).is_err())
+            {
+                return false;
+            }
+        }
+        inner.msg_rings.insert(key, waker.clone());
+        // Submit now, the target may sleep until the sqe is submitted.
+        inner.submit().is_ok()
     }
 }
 
@@ -583,6 +1372,31 @@ impl Drop for IoUringDriver {
 
 impl Drop for UringInner {
     fn drop(&mut self) {
+        // Submit the queued ops, e.g. the close ops of the dropped fds which
+        // no one waits for, and release the completed ones. All the ops left
+        // are detached since their owners hold the driver.
+        while self.submit().is_ok() && self.has_unsubmitted() {}
+        let _ = self.tick();
+        // The messages still in flight may fail unnoticed.
+        #[cfg(feature = "sync")]
+        for waker in self.msg_rings.values().filter_map(|waker| waker.upgrade()) {
+            let _ = waker.wake();
+        }
+        if let Some(index) = self.ring_index {
+            let mut update = IoUringRsrcUpdate {
+                offset: index,
+                resv: 0,
+                data: 0,
+            };
+            let _ = rsrc_update(&self.uring, IORING_UNREGISTER_RING_FDS, &mut update);
+        }
+        if self.napi_busy_poll.is_some() {
+            let _ = napi_register(
+                &self.uring,
+                IORING_UNREGISTER_NAPI,
+                &mut IoUringNapi::default(),
+            );
+        }
         unsafe {
             ManuallyDrop::drop(&mut self.uring);
         }
@@ -605,6 +1419,138 @@ impl Ops {
     }
 }
 
+// Bitset of the opcodes supported by the kernel.
+struct Opcodes([u64; 4]);
+
+impl Opcodes {
+    // Probe supported opcodes(requires kernel 5.6+). If probing is not
+    // supported, only the opcodes of kernel 5.4 are reported as supported.
+    fn probe(uring: &IoUring) -> Self {
+        let mut opcodes = Opcodes([0; 4]);
+        let mut probe = Probe::new();
+        match uring.submitter().register_probe(&mut probe) {
+            Ok(_) => (0..=u8::MAX)
+                .filter(|code| probe.is_supported(*code))
+                .for_each(|code| opcodes.insert(code)),
+            Err(_) => (0..=opcode::Timeout::CODE).for_each(|code| opcodes.insert(code)),
+        }
+        opcodes
+    }
+
+    #[inline]
+    fn insert(&mut self, code: u8) {
+        self.0[code as usize / 64] |= 1 << (code % 64);
+    }
+
+    #[inline]
+    fn remove(&mut self, code: u8) {
+        self.0[code as usize / 64] &= !(1 << (code % 64));
+    }
+
+    #[inline]
+    fn contains(&self, code: u8) -> bool {
+        self.0[code as usize / 64] & (1 << (code % 64)) != 0
+    }
+}
+
+// The cqe of a sent msg_ring sqe, the target is woken with its eventfd if the
+// message is not posted.
+#[cfg(feature = "sync")]
+fn msg_ring_sent(
+    targets: &mut fxhash::FxHashMap<u64, std::sync::Weak<waker::EventWaker>>,
+    user_data: u64,
+    result: i32,
+) {
+    let waker = targets.remove(&(user_data & !MSG_RING_SENT_TAG));
+    if result >= 0 {
+        return;
+    }
+    if let Some(waker) = waker.and_then(|waker| waker.upgrade()) {
+        let _ = waker.wake();
+    }
+}
+
+fn unregistered_personality(id: u16) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!("personality {id} is not registered to the current runtime"),
+    )
+}
+
+const IORING_REGISTER_RING_FDS: libc::c_uint = 20;
+const IORING_UNREGISTER_RING_FDS: libc::c_uint = 21;
+const IORING_REGISTER_NAPI: libc::c_uint = 27;
+const IORING_UNREGISTER_NAPI: libc::c_uint = 28;
+
+// struct io_uring_napi
+#[repr(C)]
+#[derive(Default)]
+struct IoUringNapi {
+    busy_poll_to: u32,
+    prefer_busy_poll: u8,
+    pad: [u8; 3],
+    resv: u64,
+}
+
+// struct io_uring_getevents_arg
+#[repr(C)]
+#[derive(Default)]
+struct GeteventsArg {
+    sigmask: u64,
+    sigmask_sz: u32,
+    min_wait_usec: u32,
+    ts: u64,
+}
+
+// struct io_uring_rsrc_update
+#[repr(C)]
+struct IoUringRsrcUpdate {
+    offset: u32,
+    resv: u32,
+    data: u64,
+}
+
+fn rsrc_update(
+    uring: &IoUring,
+    opcode: libc::c_uint,
+    update: &mut IoUringRsrcUpdate,
+) -> io::Result<()> {
+    crate::syscall!(syscall(
+        libc::SYS_io_uring_register,
+        uring.as_raw_fd(),
+        opcode,
+        update as *mut IoUringRsrcUpdate,
+        1
+    ))
+    .map(drop)
+}
+
+// The io-uring crate does not support NAPI, register it with the syscall.
+fn napi_register(uring: &IoUring, opcode: libc::c_uint, napi: &mut IoUringNapi) -> io::Result<()> {
+    crate::syscall!(syscall(
+        libc::SYS_io_uring_register,
+        uring.as_raw_fd(),
+        opcode,
+        napi as *mut IoUringNapi,
+        1
+    ))
+    .map(drop)
+}
+
+// The opcode is the first byte of the sqe.
+#[inline]
+fn sqe_opcode(sqe: &squeue::Entry) -> u8 {
+    unsafe { *(sqe as *const squeue::Entry as *const u8) }
+}
+
+const SQE_LINK_FLAGS: u8 = squeue::Flags::IO_LINK.bits() | squeue::Flags::IO_HARDLINK.bits();
+
+// The flags are the second byte of the sqe.
+#[inline]
+fn sqe_flags(sqe: &squeue::Entry) -> u8 {
+    unsafe { *(sqe as *const squeue::Entry as *const u8).add(1) }
+}
+
 #[inline]
 fn resultify(cqe: &cqueue::Entry) -> io::Result<u32> {
     let res = cqe.result();
@@ -0,0 +1,136 @@
+//! Opcode allowlist registered with `IORING_REGISTER_RESTRICTIONS`.
+
+use io_uring::{opcode, register::Restriction, squeue::Flags};
+
+use crate::driver::op::fd_install::FixedFdInstall;
+
+/// Kinds of io_uring ops issued by monoio, used to build the allowlist of
+/// [`RuntimeBuilder::uring_restrictions`](crate::RuntimeBuilder::uring_restrictions).
+///
+/// Each kind covers all the opcodes of its variants, e.g. `Read` covers the
+/// vectored and fixed reads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum AllowedOp {
+    /// `Read`, `Readv` and `ReadFixed`.
+    Read,
+    /// `Write`, `Writev` and `WriteFixed`.
+    Write,
+    /// `Recv` and `RecvMsg`, including the multishot ones.
+    Recv,
+    /// `Send` and `SendMsg`, including the zero copy ones.
+    Send,
+    /// `Accept`, including the multishot one.
+    Accept,
+    /// `Connect`.
+    Connect,
+    /// `Shutdown`.
+    Shutdown,
+    /// `Close`.
+    Close,
+    /// `OpenAt` and `OpenAt2`.
+    Open,
+    /// `Statx`.
+    Statx,
+    /// `Fsync` and `SyncFileRange`.
+    Fsync,
+    /// `Fallocate`.
+    Fallocate,
+    /// `Fadvise` and `Madvise`.
+    Advise,
+    /// `MkDirAt`, `UnlinkAt`, `RenameAt`, `SymlinkAt` and `LinkAt`.
+    FsMeta,
+    /// `Splice` and `Tee`.
+    Splice,
+    /// `PollAdd`, used to wait for readiness.
+    Poll,
+    /// `Timeout` and `LinkTimeout`, used by precise timers and the ops with
+    /// timeouts.
+    Timeout,
+    /// `FutexWait`, used by the semaphore.
+    Futex,
+    /// `Nop`.
+    Nop,
+    /// `MsgRingData`, used to wake the runtimes of other threads.
+    MsgRing,
+    /// `ProvideBuffers`.
+    ProvideBuffers,
+    /// `FixedFdInstall`, used to convert direct descriptors.
+    FixedFdInstall,
+}
+
+impl AllowedOp {
+    pub(crate) fn opcodes(self) -> &'static [u8] {
+        match self {
+            AllowedOp::Read => &[
+                opcode::Read::CODE,
+                opcode::Readv::CODE,
+                opcode::ReadFixed::CODE,
+            ],
+            AllowedOp::Write => &[
+                opcode::Write::CODE,
+                opcode::Writev::CODE,
+                opcode::WriteFixed::CODE,
+            ],
+            AllowedOp::Recv => &[opcode::Recv::CODE, opcode::RecvMsg::CODE],
+            AllowedOp::Send => &[
+                opcode::Send::CODE,
+                opcode::SendMsg::CODE,
+                opcode::SendZc::CODE,
+                opcode::SendMsgZc::CODE,
+            ],
+            AllowedOp::Accept => &[opcode::Accept::CODE],
+            AllowedOp::Connect => &[opcode::Connect::CODE],
+            AllowedOp::Shutdown => &[opcode::Shutdown::CODE],
+            AllowedOp::Close => &[opcode::Close::CODE],
+            AllowedOp::Open => &[opcode::OpenAt::CODE, opcode::OpenAt2::CODE],
+            AllowedOp::Statx => &[opcode::Statx::CODE],
+            AllowedOp::Fsync => &[opcode::Fsync::CODE, opcode::SyncFileRange::CODE],
+            AllowedOp::Fallocate => &[opcode::Fallocate::CODE],
+            AllowedOp::Advise => &[opcode::Fadvise::CODE, opcode::Madvise::CODE],
+            AllowedOp::FsMeta => &[
+                opcode::MkDirAt::CODE,
+                opcode::UnlinkAt::CODE,
+                opcode::RenameAt::CODE,
+                opcode::SymlinkAt::CODE,
+                opcode::LinkAt::CODE,
+            ],
+            AllowedOp::Splice => &[opcode::Splice::CODE, opcode::Tee::CODE],
+            AllowedOp::Poll => &[opcode::PollAdd::CODE],
+            AllowedOp::Timeout => &[opcode::Timeout::CODE, opcode::LinkTimeout::CODE],
+            AllowedOp::Futex => &[opcode::FutexWait::CODE],
+            AllowedOp::Nop => &[opcode::Nop::CODE],
+            AllowedOp::MsgRing => &[opcode::MsgRingData::CODE],
+            AllowedOp::ProvideBuffers => &[opcode::ProvideBuffers::CODE],
+            AllowedOp::FixedFdInstall => &[FixedFdInstall::CODE],
+        }
+    }
+}
+
+// Pushed by the driver itself, they are always allowed in the ring. The
+// eventfd of the waker is read with `Read` and the poller is waited with
+// `PollAdd`, but the ops of users still respect the allowlist.
+pub(super) const DRIVER_OPCODES: &[u8] = &[
+    opcode::AsyncCancel::CODE,
+    opcode::Timeout::CODE,
+    opcode::TimeoutRemove::CODE,
+    #[cfg(feature = "sync")]
+    opcode::Read::CODE,
+    #[cfg(feature = "poll-io")]
+    opcode::PollAdd::CODE,
+];
+
+// IORING_REGISTER_* used after the ring is enabled: buffers, files update,
+// probe, personalities, sparse files, unregistering the ring fd, provided
+// buffer rings and NAPI.
+const REGISTER_OPS: &[u8] = &[0, 1, 6, 8, 9, 10, 13, 21, 22, 23, 27, 28];
+
+/// Build the restrictions allowing the opcodes in `allowed`.
+pub(super) fn restrictions(allowed: impl Iterator<Item = u8>) -> Vec<Restriction> {
+    let flags = Flags::all().bits();
+    allowed
+        .map(Restriction::sqe_op)
+        .chain(REGISTER_OPS.iter().map(|op| Restriction::register_op(*op)))
+        .chain(std::iter::once(Restriction::sqe_flags_allowed(flags)))
+        .collect()
+}
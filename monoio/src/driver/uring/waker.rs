@@ -1,4 +1,5 @@
-//! Custom thread waker based on eventfd.
+//! Custom thread waker based on eventfd, or msg_ring if the unpark is issued
+//! from another uring driver.
 
 use std::os::unix::prelude::{AsRawFd, RawFd};
 
@@ -44,14 +45,34 @@ impl AsRawFd for EventWaker {
 }
 
 #[derive(Clone)]
-pub struct UnparkHandle(pub(crate) std::sync::Weak<EventWaker>);
+pub struct UnparkHandle {
+    pub(crate) waker: std::sync::Weak<EventWaker>,
+    // Fd of the ring to wake
+    pub(crate) ring_fd: RawFd,
+}
 
 impl Unpark for UnparkHandle {
     fn unpark(&self) -> std::io::Result<()> {
-        if let Some(w) = self.0.upgrade() {
-            w.wake()
-        } else {
-            Ok(())
+        let Some(w) = self.waker.upgrade() else {
+            return Ok(());
+        };
+        // Skip wake if already awake
+        if w.awake.load(std::sync::atomic::Ordering::Acquire) {
+            return Ok(());
+        }
+        // Post a cqe into the target ring directly if we are on a uring
+        // driver, which saves the eventfd write and read.
+        if crate::driver::CURRENT.is_set()
+            && crate::driver::CURRENT.with(|inner| match inner {
+                crate::driver::Inner::Uring(this) => {
+                    super::UringInner::msg_ring(this, self.ring_fd, &self.waker)
+                }
+                #[cfg(feature = "legacy")]
+                crate::driver::Inner::Legacy(_) => false,
+            })
+        {
+            return Ok(());
         }
+        w.wake()
     }
 }
@@ -1,7 +1,7 @@
 use std::{ffi::CString, io, path::Path};
 
 #[allow(unused_variables)]
-pub(super) fn cstr(p: &Path) -> io::Result<CString> {
+pub(crate) fn cstr(p: &Path) -> io::Result<CString> {
     #[cfg(unix)]
     {
         use std::os::unix::ffi::OsStrExt;
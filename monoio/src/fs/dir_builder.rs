@@ -0,0 +1,146 @@
+#[cfg(unix)]
+use std::os::unix::fs::DirBuilderExt;
+use std::{io, path::Path};
+
+/// A builder used to create directories in various manners.
+///
+/// # Examples
+///
+/// ```no_run
+/// use monoio::fs::DirBuilder;
+///
+/// #[monoio::main]
+/// async fn main() -> std::io::Result<()> {
+///     DirBuilder::new().create("/some/dir").await?;
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct DirBuilder {
+    recursive: bool,
+    #[cfg(unix)]
+    mode: libc::mode_t,
+}
+
+impl DirBuilder {
+    /// Creates a new set of options with default mode/security settings for
+    /// all platforms.
+    pub fn new() -> DirBuilder {
+        DirBuilder {
+            recursive: false,
+            #[cfg(unix)]
+            mode: 0o777,
+        }
+    }
+
+    /// Indicates that directories should be created recursively, creating all
+    /// parent directories. Parents that do not exist are created with the
+    /// same security and permissions settings.
+    ///
+    /// This option defaults to `false`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use monoio::fs::DirBuilder;
+    ///
+    /// #[monoio::main]
+    /// async fn main() -> std::io::Result<()> {
+    ///     DirBuilder::new().recursive(true).create("/some/dir").await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn recursive(&mut self, recursive: bool) -> &mut Self {
+        self.recursive = recursive;
+        self
+    }
+
+    /// Creates the specified directory with the options configured in this
+    /// builder.
+    ///
+    /// # Errors
+    ///
+    /// An error of the kind `AlreadyExists` is returned if the path already
+    /// exists, other errors may be returned if e.g. the parent directory does
+    /// not exist or the user lacks permissions.
+    ///
+    /// If [`recursive`] is set, it is not an error if the path or any of its
+    /// parents already exists as a directory, even if it is created
+    /// concurrently, but it is if any of them exists and is not a directory.
+    ///
+    /// [`recursive`]: DirBuilder::recursive
+    pub async fn create<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        if self.recursive {
+            self.create_dir_all(path.as_ref()).await
+        } else {
+            self.mkdir(path.as_ref()).await
+        }
+    }
+
+    async fn mkdir(&self, path: &Path) -> io::Result<()> {
+        #[cfg(unix)]
+        return crate::driver::op::Op::mkdir(path, self.mode)?
+            .await
+            .map(|_| ());
+        // Other platforms use the blocking syscall of std.
+        #[cfg(not(unix))]
+        {
+            let path = path.to_owned();
+            super::asyncify(move || std::fs::create_dir(path)).await
+        }
+    }
+
+    // Try the deepest directory first and walk up only while the parent is
+    // missing, then create the missing ones downward. A directory already
+    // existing at any level, e.g. created concurrently, is not an error.
+    async fn create_dir_all(&self, path: &Path) -> io::Result<()> {
+        if path.as_os_str().is_empty() {
+            return Ok(());
+        }
+        let mut missing = Vec::new();
+        let mut current = path;
+        loop {
+            match self.mkdir(current).await {
+                Ok(()) => break,
+                Err(e) if e.kind() == io::ErrorKind::NotFound => match current.parent() {
+                    Some(parent) if !parent.as_os_str().is_empty() => {
+                        missing.push(current);
+                        current = parent;
+                    }
+                    _ => return Err(e),
+                },
+                Err(_) if is_dir(current).await => break,
+                Err(e) => return Err(e),
+            }
+        }
+        for dir in missing.into_iter().rev() {
+            match self.mkdir(dir).await {
+                Ok(()) => (),
+                Err(_) if is_dir(dir).await => (),
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(())
+    }
+}
+
+async fn is_dir(path: &Path) -> bool {
+    super::metadata(path)
+        .await
+        .map(|metadata| metadata.is_dir())
+        .unwrap_or(false)
+}
+
+impl Default for DirBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(unix)]
+impl DirBuilderExt for DirBuilder {
+    fn mode(&mut self, mode: u32) -> &mut Self {
+        self.mode = mode as libc::mode_t;
+        self
+    }
+}
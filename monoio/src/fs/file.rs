@@ -8,12 +8,15 @@ use std::{
         unix::io::{AsRawFd, RawFd},
     },
 };
-use std::{io, path::Path};
+use std::{fs::Permissions, io, path::Path, time::SystemTime};
 
 use crate::{
     buf::{IoBuf, IoBufMut},
-    driver::{op::Op, shared_fd::SharedFd},
-    fs::OpenOptions,
+    driver::{
+        op::{Link, Op},
+        shared_fd::SharedFd,
+    },
+    fs::{Metadata, OpenOptions},
 };
 
 /// A reference to an open file on the filesystem.
@@ -63,6 +66,14 @@ use crate::{
 pub struct File {
     /// Open file descriptor
     fd: SharedFd,
+    /// Alignment of the memory and the offset of direct IO, if the file is
+    /// opened with `O_DIRECT` and the file system reports it.
+    #[cfg(target_os = "linux")]
+    dio_align: Option<(u32, u32)>,
+    /// The path of a named temporary file, which is removed on drop unless
+    /// persisted.
+    #[cfg(target_os = "linux")]
+    temp_path: Option<super::temp::TempPath>,
 }
 
 impl File {
@@ -124,7 +135,61 @@ impl File {
     }
 
     pub(crate) fn from_shared_fd(fd: SharedFd) -> File {
-        File { fd }
+        File {
+            fd,
+            #[cfg(target_os = "linux")]
+            dio_align: None,
+            #[cfg(target_os = "linux")]
+            temp_path: None,
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    pub(crate) fn with_temp_path(mut self, path: super::temp::TempPath) -> File {
+        self.temp_path = Some(path);
+        self
+    }
+
+    /// Query the alignment of direct IO with statx, it is unknown if the
+    /// kernel or the file system does not report it.
+    #[cfg(target_os = "linux")]
+    pub(crate) async fn with_dio_align(mut self) -> io::Result<File> {
+        let stat = Op::statx_fd_dio_align(&self.fd).await?;
+        if stat.stx_mask & libc::STATX_DIOALIGN != 0 && stat.stx_dio_mem_align != 0 {
+            self.dio_align = Some((stat.stx_dio_mem_align, stat.stx_dio_offset_align));
+        }
+        Ok(self)
+    }
+
+    /// Check the buffer, the length and the offset of direct IO, which fails
+    /// with EINVAL if any of them is misaligned.
+    #[cfg(target_os = "linux")]
+    fn check_dio_align(&self, ptr: *const u8, len: usize, pos: u64) -> io::Result<()> {
+        let Some((mem_align, offset_align)) = self.dio_align else {
+            return Ok(());
+        };
+        let (mem_align, offset_align) = (mem_align as u64, offset_align as u64);
+        if !(ptr as u64).is_multiple_of(mem_align) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("the buffer of direct IO must be aligned to {mem_align} bytes"),
+            ));
+        }
+        if !(len as u64).is_multiple_of(offset_align) || !pos.is_multiple_of(offset_align) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "the length and offset of direct IO must be aligned to {offset_align} bytes"
+                ),
+            ));
+        }
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[inline]
+    pub(crate) fn shared_fd(&self) -> &SharedFd {
+        &self.fd
     }
 
     /// Converts a [`std::fs::File`] to a [`monoio::fs::File`](File).
@@ -139,9 +204,40 @@ impl File {
     /// ```
     #[cfg(unix)]
     pub fn from_std(std: StdFile) -> io::Result<File> {
-        Ok(File {
-            fd: SharedFd::new::<false>(std.into_raw_fd())?,
-        })
+        Ok(File::from_shared_fd(SharedFd::new::<false>(
+            std.into_raw_fd(),
+        )?))
+    }
+
+    /// Creates a new `File` instance that shares the same underlying file
+    /// handle as the existing `File` instance, with `F_DUPFD_CLOEXEC`.
+    ///
+    /// Both of them refer to the same open file description, so they share
+    /// e.g. the status flags and the advisory locks, but each of them is
+    /// closed independently, and the operations in flight on one are not
+    /// affected by the other. As the reads and writes are positional, the
+    /// two can be used by different tasks at different offsets.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use monoio::fs::File;
+    ///
+    /// #[monoio::main]
+    /// async fn main() -> std::io::Result<()> {
+    ///     let file = File::open("foo.txt").await?;
+    ///     let file_copy = file.try_clone()?;
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(unix)]
+    pub fn try_clone(&self) -> io::Result<File> {
+        let mut file = File::from_shared_fd(SharedFd::new_without_register(self.fd.dup()?));
+        #[cfg(target_os = "linux")]
+        {
+            file.dio_align = self.dio_align;
+        }
+        Ok(file)
     }
 
     /// Read some bytes at the specified offset from the file into the specified
@@ -188,12 +284,32 @@ impl File {
     ///     Ok(())
     /// }
     /// ```
-    pub async fn read_at<T: IoBufMut>(&self, buf: T, pos: u64) -> crate::BufResult<usize, T> {
+    #[allow(unused_mut)]
+    pub async fn read_at<T: IoBufMut>(&self, mut buf: T, pos: u64) -> crate::BufResult<usize, T> {
+        #[cfg(target_os = "linux")]
+        if let Err(e) = self.check_dio_align(buf.write_ptr(), buf.bytes_total(), pos) {
+            return (Err(e), buf);
+        }
         // Submit the read operation
         let op = Op::read_at(&self.fd, buf, pos).unwrap();
         op.read().await
     }
 
+    /// Read some bytes at the specified offset from the file into a buffer
+    /// taken from [`FixedBufPool`](crate::buf::FixedBufPool).
+    ///
+    /// It works like [`read_at`](Self::read_at), but the kernel does not need
+    /// to map the buffer for every read since it is registered in advance.
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    pub async fn read_fixed_at(
+        &self,
+        buf: crate::buf::FixedBuf,
+        pos: u64,
+    ) -> crate::BufResult<usize, crate::buf::FixedBuf> {
+        let op = Op::read_fixed_at(&self.fd, buf, pos).unwrap();
+        op.read().await
+    }
+
     /// Read the exact number of bytes required to fill `buf` at the specified
     /// offset from the file.
     ///
@@ -322,6 +438,10 @@ impl File {
     ///
     /// [`Ok(n)`]: Ok
     pub async fn write_at<T: IoBuf>(&self, buf: T, pos: u64) -> crate::BufResult<usize, T> {
+        #[cfg(target_os = "linux")]
+        if let Err(e) = self.check_dio_align(buf.read_ptr(), buf.bytes_init(), pos) {
+            return (Err(e), buf);
+        }
         let op = Op::write_at(&self.fd, buf, pos).unwrap();
         op.write().await
     }
@@ -395,6 +515,67 @@ impl File {
         (Ok(()), buf)
     }
 
+    /// Write an entire buffer into this file at the specified offset, then sync
+    /// all OS-internal metadata to disk.
+    ///
+    /// It works like [`write_all_at`] followed by [`sync_all`], but on io_uring
+    /// driver the fsync is linked to the write and submitted together, so it
+    /// is executed by the kernel right after the write completes without a
+    /// round trip to userspace.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use monoio::fs::File;
+    ///
+    /// #[monoio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let file = File::create("foo.txt").await?;
+    ///
+    ///     let (res, _) = file.write_all_at_sync(&b"some bytes"[..], 0).await;
+    ///     res?;
+    ///
+    ///     // Close the file
+    ///     file.close().await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// [`write_all_at`]: File::write_all_at
+    /// [`sync_all`]: File::sync_all
+    pub async fn write_all_at_sync<T: IoBuf>(
+        &self,
+        mut buf: T,
+        pos: u64,
+    ) -> crate::BufResult<(), T> {
+        let len = buf.bytes_init();
+        let mut written = 0;
+        loop {
+            let slice = unsafe { buf.slice_unchecked(written..len) };
+            let (write, sync) = Link::write_at_sync(&self.fd, slice, pos + written as u64)
+                .unwrap()
+                .wait()
+                .await;
+            buf = write.data.buf.into_inner();
+            match write.meta.result {
+                // The fsync is only executed when the whole rest is written.
+                Ok(n) if written + n as usize == len => return (sync.meta.result.map(|_| ()), buf),
+                Ok(0) => {
+                    return (
+                        Err(io::Error::new(
+                            io::ErrorKind::WriteZero,
+                            "failed to write whole buffer",
+                        )),
+                        buf,
+                    )
+                }
+                Ok(n) => written += n as usize,
+                Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return (Err(e), buf),
+            };
+        }
+    }
+
     /// Attempts to sync all OS-internal metadata to disk.
     ///
     /// This function will attempt to ensure that all in-memory data reaches the
@@ -470,6 +651,664 @@ impl File {
         Ok(())
     }
 
+    /// Send up to `len` bytes starting at `pos` of the file to `dst`, the data
+    /// is not copied to userspace.
+    ///
+    /// This is useful for serving static files. On the legacy driver it is
+    /// implemented with `sendfile(2)`, and on the io_uring driver the data is
+    /// spliced through an internal pipe. Short transfers are retried, so it
+    /// returns less than `len` only when the end of the file is reached.
+    ///
+    /// # Errors
+    ///
+    /// The errors of the underlying syscalls are returned as is, e.g. a
+    /// `BrokenPipe` error is returned if the peer has closed the connection.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use monoio::{fs::File, net::TcpStream};
+    ///
+    /// #[monoio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let file = File::open("foo.txt").await?;
+    ///     let mut stream = TcpStream::connect("127.0.0.1:8080").await?;
+    ///
+    ///     let n = file.send_to(&mut stream, 0, 4096).await?;
+    ///     println!("sent {} bytes", n);
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(all(target_os = "linux", feature = "splice"))]
+    pub async fn send_to<T: crate::io::as_fd::AsWriteFd>(
+        &self,
+        dst: &mut T,
+        pos: u64,
+        len: usize,
+    ) -> io::Result<usize> {
+        let dst = dst.as_writer_fd().as_ref();
+        let mut sent = 0;
+        if crate::driver::op::is_legacy() {
+            while sent < len {
+                match Op::sendfile(&self.fd, pos + sent as u64, dst, len - sent)?
+                    .await
+                    .meta
+                    .result
+                {
+                    Ok(0) => break,
+                    Ok(n) => sent += n as usize,
+                    Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {}
+                    Err(e) => return Err(e),
+                }
+            }
+            return Ok(sent);
+        }
+
+        let (pr, pw) = crate::net::unix::new_pipe()?;
+        while sent < len {
+            let chunk = (len - sent).min(u32::MAX as usize) as u32;
+            let n = Op::splice_file_to_pipe(&self.fd, pos + sent as u64, &pw.fd, chunk)?
+                .splice()
+                .await?;
+            if n == 0 {
+                break;
+            }
+            let mut to_write = n;
+            while to_write > 0 {
+                let written = Op::splice_from_pipe(&pr.fd, dst, to_write)?
+                    .splice()
+                    .await?;
+                if written == 0 {
+                    return Err(io::ErrorKind::WriteZero.into());
+                }
+                to_write -= written;
+            }
+            sent += n as usize;
+        }
+        Ok(sent)
+    }
+
+    /// Truncates or extends the underlying file, updating the size of this
+    /// file to become `size`.
+    ///
+    /// If the `size` is less than the current file's size, then the file will
+    /// be shrunk. If it is greater than the current file's size, then the file
+    /// will be extended to `size` and have all of the intermediate data filled
+    /// in with 0s. The file must be opened for writing.
+    ///
+    /// # Errors
+    ///
+    /// An `InvalidInput` error is returned if `size` can not be represented by
+    /// the offset type of the platform.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use monoio::fs::File;
+    ///
+    /// #[monoio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let f = File::create("foo.txt").await?;
+    ///     f.set_len(10).await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(unix)]
+    pub async fn set_len(&self, size: u64) -> io::Result<()> {
+        #[cfg(target_os = "linux")]
+        let size = libc::off64_t::try_from(size);
+        #[cfg(not(target_os = "linux"))]
+        let size = libc::off_t::try_from(size);
+        let size = size
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "file size is too large"))?;
+        #[cfg(all(target_os = "linux", feature = "iouring"))]
+        if crate::driver::op::is_uring_op_supported(crate::driver::op::Ftruncate::CODE) {
+            let op = Op::ftruncate(&self.fd, size)?;
+            return op.await.meta.result.map(|_| ());
+        }
+        // Without the opcode(requires kernel 6.9+) it is a blocking call.
+        super::asyncify_fd(&self.fd, move |fd| {
+            #[cfg(target_os = "linux")]
+            let res = crate::syscall!(ftruncate64(fd, size));
+            #[cfg(not(target_os = "linux"))]
+            let res = crate::syscall!(ftruncate(fd, size));
+            res.map(|_| ())
+        })
+        .await
+    }
+
+    /// Initiates or waits for the write-back of the dirty pages in the byte
+    /// range starting at `offset` and continuing for `nbytes` bytes. A
+    /// `nbytes` of 0 means until the end of the file.
+    ///
+    /// It can push the data to disk incrementally, so that a final
+    /// [`sync_all`](Self::sync_all) does not stall for long. Note that it does
+    /// not flush the file metadata nor the disk write cache, so it is not a
+    /// durability guarantee on its own.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use monoio::fs::{File, SyncRangeFlags};
+    ///
+    /// #[monoio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let f = File::create("foo.txt").await?;
+    ///     let (res, _) = f.write_all_at(&b"some bytes"[..], 0).await;
+    ///     res?;
+    ///
+    ///     f.sync_range(0, 0, SyncRangeFlags::WRITE | SyncRangeFlags::WAIT_AFTER)
+    ///         .await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(target_os = "linux")]
+    pub async fn sync_range(
+        &self,
+        offset: u64,
+        nbytes: u64,
+        flags: SyncRangeFlags,
+    ) -> io::Result<()> {
+        let (offset, nbytes) = match (i64::try_from(offset), i64::try_from(nbytes)) {
+            (Ok(offset), Ok(nbytes)) => (offset, nbytes),
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "offset or length too big",
+                ))
+            }
+        };
+        // The length of the opcode is 32-bit, a larger range and the legacy
+        // driver, where it is a blocking call, go to the blocking pool.
+        #[cfg(feature = "iouring")]
+        if let Ok(len) = u32::try_from(nbytes) {
+            if crate::driver::op::is_uring_op_supported(io_uring::opcode::SyncFileRange::CODE) {
+                let op = Op::sync_file_range(&self.fd, offset as u64, len, flags.0)?;
+                return op.await.meta.result.map(|_| ());
+            }
+        }
+        let flags = flags.0;
+        super::asyncify_fd(&self.fd, move |fd| {
+            crate::syscall!(sync_file_range(fd, offset, nbytes, flags)).map(|_| ())
+        })
+        .await
+    }
+
+    /// Allocates disk space for the byte range starting at `offset` and
+    /// continuing for `len` bytes. The file size is extended if the range is
+    /// beyond the end of the file.
+    ///
+    /// Subsequent writes into the range are guaranteed not to fail because of
+    /// lack of disk space.
+    ///
+    /// On legacy driver, or if the uring driver does not support fallocate,
+    /// it runs on the blocking thread pool, see
+    /// [`spawn_blocking`](crate::spawn_blocking). So do
+    /// [`punch_hole`](Self::punch_hole) and [`zero_range`](Self::zero_range).
+    ///
+    /// # Errors
+    ///
+    /// If the file system does not support it, an error of the kind
+    /// [`io::ErrorKind::Unsupported`] is returned, so callers can fall back to
+    /// writing zeros.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use monoio::fs::File;
+    ///
+    /// #[monoio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let f = File::create("foo.txt").await?;
+    ///     f.allocate(0, 4096).await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(target_os = "linux")]
+    pub async fn allocate(&self, offset: u64, len: u64) -> io::Result<()> {
+        self.fallocate(offset, len, 0).await
+    }
+
+    /// Deallocates the byte range starting at `offset` and continuing for
+    /// `len` bytes. The range reads as zeros afterwards, and the file size is
+    /// not changed.
+    ///
+    /// # Errors
+    ///
+    /// If the file system does not support it, an error of the kind
+    /// [`io::ErrorKind::Unsupported`] is returned, so callers can fall back to
+    /// writing zeros.
+    #[cfg(target_os = "linux")]
+    pub async fn punch_hole(&self, offset: u64, len: u64) -> io::Result<()> {
+        self.fallocate(
+            offset,
+            len,
+            libc::FALLOC_FL_PUNCH_HOLE | libc::FALLOC_FL_KEEP_SIZE,
+        )
+        .await
+    }
+
+    /// Zeroes the byte range starting at `offset` and continuing for `len`
+    /// bytes. Unlike [`punch_hole`](Self::punch_hole), the disk space of the
+    /// range is kept allocated, and the file size is extended if the range is
+    /// beyond the end of the file.
+    ///
+    /// # Errors
+    ///
+    /// If the file system does not support it, an error of the kind
+    /// [`io::ErrorKind::Unsupported`] is returned, so callers can fall back to
+    /// writing zeros.
+    #[cfg(target_os = "linux")]
+    pub async fn zero_range(&self, offset: u64, len: u64) -> io::Result<()> {
+        self.fallocate(offset, len, libc::FALLOC_FL_ZERO_RANGE)
+            .await
+    }
+
+    #[cfg(target_os = "linux")]
+    async fn fallocate(&self, offset: u64, len: u64, mode: i32) -> io::Result<()> {
+        // Both are signed in the syscall, they must not wrap to negative.
+        let too_big = |_| io::Error::new(io::ErrorKind::InvalidInput, "offset or length too big");
+        let offset = libc::off64_t::try_from(offset).map_err(too_big)?;
+        let len = libc::off64_t::try_from(len).map_err(too_big)?;
+        #[cfg(feature = "iouring")]
+        if crate::driver::op::is_uring_op_supported(io_uring::opcode::Fallocate::CODE) {
+            let op = Op::fallocate(&self.fd, offset, len, mode)?;
+            return op.await.meta.result.map(|_| ());
+        }
+        // On legacy driver or without the opcode it is a blocking call.
+        super::asyncify_fd(&self.fd, move |fd| {
+            crate::syscall!(fallocate64(fd, mode, offset, len)).map(|_| ())
+        })
+        .await
+    }
+
+    /// Announces the intention to access the byte range starting at `offset`
+    /// and continuing for `len` bytes in a specific pattern, so the kernel
+    /// can optimize the page cache for it. A `len` of 0 means until the end
+    /// of the file.
+    ///
+    /// The returned future does not borrow the file, so the advice can be
+    /// fire-and-forget by spawning it. On platforms without fadvise it does
+    /// nothing.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use monoio::fs::{Advice, File};
+    ///
+    /// #[monoio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let f = File::open("foo.txt").await?;
+    ///     f.advise(0, 0, Advice::Sequential).await?;
+    ///
+    ///     // Drop the cached pages in the background.
+    ///     monoio::spawn(f.advise(0, 0, Advice::DontNeed));
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn advise(
+        &self,
+        offset: u64,
+        len: u64,
+        advice: Advice,
+    ) -> impl std::future::Future<Output = io::Result<()>> + 'static {
+        #[cfg(target_os = "linux")]
+        let fd = self.fd.clone();
+        #[cfg(not(target_os = "linux"))]
+        let _ = (offset, len, advice);
+        async move {
+            #[cfg(target_os = "linux")]
+            {
+                use crate::driver::op::FADVISE_CHUNK;
+
+                let advice = advice.as_raw();
+                // A range beyond the max size of files is the rest of the file.
+                let len = match offset.checked_add(len) {
+                    Some(end) if end <= i64::MAX as u64 => len,
+                    _ => 0,
+                };
+                if len <= FADVISE_CHUNK || crate::driver::op::is_legacy() {
+                    Op::fadvise(&fd, offset, len, advice)?.await.meta.result?;
+                    return Ok(());
+                }
+                // Io_uring takes a 32-bit length, so a longer range is split
+                // into chunks, or advised until the end if it covers the end.
+                let end = offset + len;
+                if end >= Op::statx_fd(&fd).await?.stx_size {
+                    Op::fadvise(&fd, offset, 0, advice)?.await.meta.result?;
+                    return Ok(());
+                }
+                let mut pos = offset;
+                while pos < end {
+                    let chunk = (end - pos).min(FADVISE_CHUNK);
+                    Op::fadvise(&fd, pos, chunk, advice)?.await.meta.result?;
+                    pos += chunk;
+                }
+            }
+            Ok(())
+        }
+    }
+
+    /// Queries metadata about the underlying file.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use monoio::fs::File;
+    ///
+    /// #[monoio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let f = File::open("foo.txt").await?;
+    ///     let metadata = f.metadata().await?;
+    ///     println!("{} bytes", metadata.len());
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn metadata(&self) -> io::Result<Metadata> {
+        #[cfg(target_os = "linux")]
+        return Op::statx_fd(&self.fd).await.map(Metadata::new);
+        // Other unix platforms use std on the blocking thread pool.
+        #[cfg(all(unix, not(target_os = "linux")))]
+        {
+            use std::os::fd::FromRawFd;
+
+            super::asyncify_fd(&self.fd, |fd| {
+                let std = std::mem::ManuallyDrop::new(unsafe { StdFile::from_raw_fd(fd) });
+                std.metadata()
+            })
+            .await
+        }
+        // Windows uses the blocking syscall of std.
+        #[cfg(windows)]
+        {
+            use std::os::windows::io::FromRawHandle;
+
+            let std = std::mem::ManuallyDrop::new(unsafe {
+                std::fs::File::from_raw_handle(self.fd.raw_handle())
+            });
+            std.metadata()
+        }
+    }
+
+    /// Changes the permissions of the underlying file.
+    ///
+    /// There is no chmod opcode of io_uring, so `fchmod` runs on the blocking
+    /// thread pool if one is attached, see
+    /// [`spawn_blocking`](crate::spawn_blocking).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use monoio::fs::File;
+    ///
+    /// #[monoio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let f = File::create("foo.txt").await?;
+    ///     let mut perms = f.metadata().await?.permissions();
+    ///     perms.set_readonly(true);
+    ///     f.set_permissions(perms).await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn set_permissions(&self, perm: Permissions) -> io::Result<()> {
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+
+            let mode = perm.mode() as libc::mode_t;
+            super::asyncify_fd(&self.fd, move |fd| {
+                crate::syscall!(fchmod(fd, mode)).map(|_| ())
+            })
+            .await
+        }
+        // Other platforms use the blocking syscall of std.
+        #[cfg(windows)]
+        {
+            use std::os::windows::io::FromRawHandle;
+
+            let std = std::mem::ManuallyDrop::new(unsafe {
+                std::fs::File::from_raw_handle(self.fd.raw_handle())
+            });
+            std.set_permissions(perm)
+        }
+    }
+
+    /// Acquires an exclusive advisory lock on the file with flock(2), waiting
+    /// until no other lock is held on it.
+    ///
+    /// There is no flock opcode of io_uring, so the waiting `flock` runs on
+    /// the blocking thread pool, see [`spawn_blocking`]. A thread pool must be
+    /// attached to the runtime or the `ExecuteLocal` blocking strategy set,
+    /// otherwise it panics. If the future is dropped while waiting, the lock
+    /// may still be acquired afterwards, and it is held until [`unlock`] or
+    /// the close of the file.
+    ///
+    /// The lock belongs to the open file description, so it is shared by the
+    /// duplicates of the fd, e.g. the ones of dup(2) or of the child processes,
+    /// and is released once all of them are closed. Opening the file again
+    /// creates another description, whose locks conflict with this one even
+    /// in the same process.
+    ///
+    /// The lock is released when the returned guard is dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use monoio::fs::File;
+    ///
+    /// fn main() -> std::io::Result<()> {
+    ///     monoio::RuntimeBuilder::<monoio::FusionDriver>::new()
+    ///         .attach_thread_pool(Box::new(monoio::blocking::DefaultThreadPool::new(1)))
+    ///         .build()?
+    ///         .block_on(async {
+    ///             let file = File::create("foo.lock").await?;
+    ///             let lock = file.lock_exclusive().await?;
+    ///             // Only one process runs this at a time.
+    ///             lock.unlock()
+    ///         })
+    /// }
+    /// ```
+    ///
+    /// [`spawn_blocking`]: crate::spawn_blocking
+    /// [`unlock`]: File::unlock
+    #[cfg(all(unix, feature = "sync"))]
+    pub async fn lock_exclusive(&self) -> io::Result<FileLock<'_>> {
+        self.lock_blocking(libc::LOCK_EX).await
+    }
+
+    /// Acquires a shared advisory lock on the file with flock(2), waiting
+    /// until no exclusive lock is held on it, see [`lock_exclusive`].
+    ///
+    /// [`lock_exclusive`]: File::lock_exclusive
+    #[cfg(all(unix, feature = "sync"))]
+    pub async fn lock_shared(&self) -> io::Result<FileLock<'_>> {
+        self.lock_blocking(libc::LOCK_SH).await
+    }
+
+    /// Tries to acquire an exclusive advisory lock on the file without
+    /// waiting, an error of the kind `WouldBlock` is returned if another lock
+    /// is held on it. See [`lock_exclusive`].
+    ///
+    /// [`lock_exclusive`]: File::lock_exclusive
+    #[cfg(unix)]
+    pub fn try_lock_exclusive(&self) -> io::Result<FileLock<'_>> {
+        self.flock(libc::LOCK_EX | libc::LOCK_NB)
+    }
+
+    /// Tries to acquire a shared advisory lock on the file without waiting,
+    /// an error of the kind `WouldBlock` is returned if an exclusive lock is
+    /// held on it. See [`lock_exclusive`].
+    ///
+    /// [`lock_exclusive`]: File::lock_exclusive
+    #[cfg(unix)]
+    pub fn try_lock_shared(&self) -> io::Result<FileLock<'_>> {
+        self.flock(libc::LOCK_SH | libc::LOCK_NB)
+    }
+
+    /// Releases the advisory lock held on the file, e.g. the one of a
+    /// forgotten guard or of another duplicate of the fd.
+    #[cfg(unix)]
+    pub fn unlock(&self) -> io::Result<()> {
+        crate::syscall!(flock(self.fd.raw_fd(), libc::LOCK_UN)).map(|_| ())
+    }
+
+    #[cfg(unix)]
+    fn flock(&self, operation: libc::c_int) -> io::Result<FileLock<'_>> {
+        crate::syscall!(flock(self.fd.raw_fd(), operation))?;
+        Ok(FileLock { file: self })
+    }
+
+    #[cfg(all(unix, feature = "sync"))]
+    async fn lock_blocking(&self, operation: libc::c_int) -> io::Result<FileLock<'_>> {
+        use std::os::fd::{FromRawFd, OwnedFd};
+
+        // The duplicate shares the lock, and it stays open even if the file is
+        // closed while the pool is waiting, so the fd is never reused.
+        let fd = unsafe { OwnedFd::from_raw_fd(self.fd.dup()?) };
+        crate::spawn_blocking(move || loop {
+            match crate::syscall!(flock(fd.as_raw_fd(), operation)) {
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                res => return res,
+            }
+        })
+        .await
+        .map_err(|_| io::Error::other("the blocking flock is canceled"))??;
+        Ok(FileLock { file: self })
+    }
+
+    /// Links the file into the directory tree at `path`, replacing the file
+    /// at `path` if it exists, e.g. a temporary file created by
+    /// [`tempfile_in`].
+    ///
+    /// An unnamed file is linked with `linkat`, which fails if `path` exists,
+    /// so it is then linked to a random name next to `path` first and renamed
+    /// over `path`, which replaces it atomically. A named temporary file of
+    /// the fallback of [`tempfile_in`] is renamed, and it is not removed on
+    /// drop afterwards. The file stays open and usable.
+    ///
+    /// Linking an unnamed file by fd requires `CAP_DAC_READ_SEARCH`, without
+    /// it the magic link of the file in `/proc/self/fd` is linked instead, so
+    /// procfs must be mounted. The file must be on the same file system as
+    /// `path`, and an unnamed one must not be opened with `O_EXCL`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// #[monoio::main]
+    /// async fn main() -> std::io::Result<()> {
+    ///     let mut file = monoio::fs::tempfile_in(".").await?;
+    ///     let (res, _) = file.write_all_at(&b"hello"[..], 0).await;
+    ///     res?;
+    ///     file.sync_all().await?;
+    ///     file.persist("hello.txt").await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// [`tempfile_in`]: crate::fs::tempfile_in
+    #[cfg(target_os = "linux")]
+    pub async fn persist<P: AsRef<Path>>(&mut self, path: P) -> io::Result<()> {
+        use super::temp::{temp_name, too_many_files, NUM_RETRIES};
+
+        let path = path.as_ref();
+        if let Some(temp_path) = self.temp_path.take() {
+            let res = Op::rename(temp_path.path(), path, 0)?.await;
+            match res {
+                Ok(_) => temp_path.keep(),
+                Err(_) => self.temp_path = Some(temp_path),
+            }
+            return res.map(|_| ());
+        }
+
+        match self.link_to(path).await {
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => (),
+            res => return res,
+        }
+        let dir = match path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir,
+            _ => Path::new("."),
+        };
+        for _ in 0..NUM_RETRIES {
+            let temp = dir.join(temp_name(".tmp"));
+            match self.link_to(&temp).await {
+                Ok(()) => (),
+                Err(e) if e.kind() == io::ErrorKind::AlreadyExists => continue,
+                Err(e) => return Err(e),
+            }
+            let res = Op::rename(&temp, path, 0)?.await;
+            if res.is_err() {
+                let _ = super::remove_file(&temp).await;
+            }
+            return res.map(|_| ());
+        }
+        Err(too_many_files())
+    }
+
+    #[cfg(target_os = "linux")]
+    async fn link_to(&self, path: &Path) -> io::Result<()> {
+        match Op::link_fd(&self.fd, path)?.await {
+            Err(e) if e.raw_os_error() == Some(libc::ENOENT) => {
+                let fd_path = format!("/proc/self/fd/{}", self.fd.raw_fd());
+                Op::hard_link_follow(fd_path, path)?.await.map(|_| ())
+            }
+            res => res.map(|_| ()),
+        }
+    }
+
+    /// Changes the last access and modification times of the file. The time
+    /// is not changed for `None`.
+    ///
+    /// There is no opcode of io_uring for it, so `futimens` runs on the
+    /// blocking thread pool on both drivers if one is attached, see
+    /// [`spawn_blocking`](crate::spawn_blocking).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::time::{Duration, SystemTime};
+    ///
+    /// use monoio::fs::File;
+    ///
+    /// #[monoio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let f = File::create("foo.txt").await?;
+    ///     let yesterday = SystemTime::now() - Duration::from_secs(86400);
+    ///     f.set_times(None, Some(yesterday)).await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn set_times(
+        &self,
+        accessed: Option<SystemTime>,
+        modified: Option<SystemTime>,
+    ) -> io::Result<()> {
+        #[cfg(unix)]
+        {
+            let times = [super::timespec(accessed)?, super::timespec(modified)?];
+            super::asyncify_fd(&self.fd, move |fd| {
+                crate::syscall!(futimens(fd, times.as_ptr())).map(|_| ())
+            })
+            .await
+        }
+        // Other platforms use the blocking syscall of std.
+        #[cfg(windows)]
+        {
+            use std::os::windows::io::FromRawHandle;
+
+            let std = std::mem::ManuallyDrop::new(unsafe {
+                std::fs::File::from_raw_handle(self.fd.raw_handle())
+            });
+            let mut times = std::fs::FileTimes::new();
+            if let Some(accessed) = accessed {
+                times = times.set_accessed(accessed);
+            }
+            if let Some(modified) = modified {
+                times = times.set_modified(modified);
+            }
+            std.set_times(times)
+        }
+    }
+
     /// Closes the file.
     ///
     /// The method completes once the close operation has completed,
@@ -495,8 +1334,127 @@ impl File {
     /// }
     /// ```
     pub async fn close(self) -> io::Result<()> {
-        self.fd.close().await;
-        Ok(())
+        self.fd.close().await
+    }
+
+    /// Converts the file into a [`FileStream`] with a cursor at offset 0, which
+    /// implements [`AsyncReadRent`] and [`AsyncWriteRent`].
+    ///
+    /// [`FileStream`]: crate::fs::FileStream
+    /// [`AsyncReadRent`]: crate::io::AsyncReadRent
+    /// [`AsyncWriteRent`]: crate::io::AsyncWriteRent
+    pub fn into_stream(self) -> super::FileStream {
+        super::FileStream::new(self)
+    }
+}
+
+/// An advisory lock held on a [`File`], which is released when the guard is
+/// dropped.
+///
+/// Releasing never waits, so the drop releases it in place and ignores the
+/// error, call [`unlock`] to get it.
+///
+/// [`unlock`]: FileLock::unlock
+#[cfg(unix)]
+#[derive(Debug)]
+#[must_use = "the lock is released immediately if the guard is unused"]
+pub struct FileLock<'a> {
+    file: &'a File,
+}
+
+#[cfg(unix)]
+impl FileLock<'_> {
+    /// Releases the lock.
+    pub fn unlock(self) -> io::Result<()> {
+        let file = self.file;
+        std::mem::forget(self);
+        file.unlock()
+    }
+}
+
+#[cfg(unix)]
+impl Drop for FileLock<'_> {
+    fn drop(&mut self) {
+        let _ = self.file.unlock();
+    }
+}
+
+/// Flags for [`File::sync_range`], they can be combined with `|`.
+#[cfg(target_os = "linux")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SyncRangeFlags(u32);
+
+#[cfg(target_os = "linux")]
+impl SyncRangeFlags {
+    /// Wait for the write-back of the pages already being written.
+    pub const WAIT_BEFORE: SyncRangeFlags = SyncRangeFlags(libc::SYNC_FILE_RANGE_WAIT_BEFORE);
+    /// Start the write-back of the dirty pages.
+    pub const WRITE: SyncRangeFlags = SyncRangeFlags(libc::SYNC_FILE_RANGE_WRITE);
+    /// Wait for the write-back to complete.
+    pub const WAIT_AFTER: SyncRangeFlags = SyncRangeFlags(libc::SYNC_FILE_RANGE_WAIT_AFTER);
+}
+
+#[cfg(target_os = "linux")]
+impl std::ops::BitOr for SyncRangeFlags {
+    type Output = SyncRangeFlags;
+
+    #[inline]
+    fn bitor(self, other: SyncRangeFlags) -> SyncRangeFlags {
+        SyncRangeFlags(self.0 | other.0)
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl std::ops::BitOrAssign for SyncRangeFlags {
+    #[inline]
+    fn bitor_assign(&mut self, other: SyncRangeFlags) {
+        self.0 |= other.0;
+    }
+}
+
+/// Access patterns for [`File::advise`] and [`advise_memory`], see
+/// posix_fadvise(2) and madvise(2).
+///
+/// [`advise_memory`]: crate::fs::advise_memory
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Advice {
+    /// No special treatment, the default.
+    Normal,
+    /// The data will be accessed sequentially.
+    Sequential,
+    /// The data will be accessed in random order.
+    Random,
+    /// The data will be accessed only once.
+    NoReuse,
+    /// The data will be accessed in the near future.
+    WillNeed,
+    /// The data will not be accessed in the near future.
+    DontNeed,
+}
+
+#[cfg(target_os = "linux")]
+impl Advice {
+    pub(crate) fn as_raw(self) -> i32 {
+        match self {
+            Advice::Normal => libc::POSIX_FADV_NORMAL,
+            Advice::Sequential => libc::POSIX_FADV_SEQUENTIAL,
+            Advice::Random => libc::POSIX_FADV_RANDOM,
+            Advice::NoReuse => libc::POSIX_FADV_NOREUSE,
+            Advice::WillNeed => libc::POSIX_FADV_WILLNEED,
+            Advice::DontNeed => libc::POSIX_FADV_DONTNEED,
+        }
+    }
+
+    // There is no madvise counterpart of NoReuse.
+    pub(crate) fn as_raw_memory(self) -> Option<i32> {
+        match self {
+            Advice::Normal => Some(libc::MADV_NORMAL),
+            Advice::Sequential => Some(libc::MADV_SEQUENTIAL),
+            Advice::Random => Some(libc::MADV_RANDOM),
+            Advice::NoReuse => None,
+            Advice::WillNeed => Some(libc::MADV_WILLNEED),
+            Advice::DontNeed => Some(libc::MADV_DONTNEED),
+        }
     }
 }
 
@@ -513,3 +1471,17 @@ impl AsRawHandle for File {
         self.fd.raw_handle()
     }
 }
+
+impl crate::io::as_fd::AsReadFd for File {
+    #[inline]
+    fn as_reader_fd(&mut self) -> &crate::io::as_fd::SharedFdWrapper {
+        crate::io::as_fd::SharedFdWrapper::new(&self.fd)
+    }
+}
+
+impl crate::io::as_fd::AsWriteFd for File {
+    #[inline]
+    fn as_writer_fd(&mut self) -> &crate::io::as_fd::SharedFdWrapper {
+        crate::io::as_fd::SharedFdWrapper::new(&self.fd)
+    }
+}
@@ -0,0 +1,139 @@
+use std::io::{self, SeekFrom};
+
+use super::File;
+use crate::{
+    buf::{IoBuf, IoBufMut, IoVecBuf, IoVecBufMut, IoVecWrapper, IoVecWrapperMut},
+    io::{AsyncReadRent, AsyncSeekRent, AsyncWriteRent},
+    BufResult,
+};
+
+/// A [`File`] with a cursor, created by [`File::into_stream`].
+///
+/// It reads and writes at the cursor and moves it forward by the bytes
+/// transferred, so it works with [`BufReader`], [`copy`] and other stream
+/// based utils. Reaching the end of the file returns `Ok(0)`.
+///
+/// The ops take `&mut self`, so there is at most one read or write in flight
+/// on the stream. For files opened in append mode, writes always go to the end
+/// of the file regardless of the cursor.
+///
+/// The cursor is moved by [`AsyncSeekRent`] too, which only needs the
+/// length of the file for `SeekFrom::End`.
+///
+/// [`BufReader`]: crate::io::BufReader
+/// [`copy`]: crate::io::copy
+#[derive(Debug)]
+pub struct FileStream {
+    file: File,
+    pos: u64,
+}
+
+impl FileStream {
+    pub(crate) fn new(file: File) -> Self {
+        Self { file, pos: 0 }
+    }
+
+    /// Returns the current position of the cursor.
+    #[inline]
+    pub fn position(&self) -> u64 {
+        self.pos
+    }
+
+    /// Sets the position of the cursor.
+    #[inline]
+    pub fn set_position(&mut self, pos: u64) {
+        self.pos = pos;
+    }
+
+    /// Gets a reference to the file, whose positional ops do not move the
+    /// cursor.
+    #[inline]
+    pub fn get_ref(&self) -> &File {
+        &self.file
+    }
+
+    /// Returns the file and drops the cursor.
+    #[inline]
+    pub fn into_inner(self) -> File {
+        self.file
+    }
+}
+
+impl AsyncReadRent for FileStream {
+    async fn read<T: IoBufMut>(&mut self, buf: T) -> BufResult<usize, T> {
+        let (res, buf) = self.file.read_at(buf, self.pos).await;
+        if let Ok(n) = res {
+            self.pos += n as u64;
+        }
+        (res, buf)
+    }
+
+    // Only the first buffer is filled, like a short read.
+    async fn readv<T: IoVecBufMut>(&mut self, mut buf: T) -> BufResult<usize, T> {
+        let slice = match IoVecWrapperMut::new(buf) {
+            Ok(slice) => slice,
+            Err(buf) => return (Ok(0), buf),
+        };
+
+        let (res, slice) = self.read(slice).await;
+        buf = slice.into_inner();
+        if let Ok(n) = res {
+            unsafe { buf.set_init(n) };
+        }
+        (res, buf)
+    }
+}
+
+impl AsyncWriteRent for FileStream {
+    async fn write<T: IoBuf>(&mut self, buf: T) -> BufResult<usize, T> {
+        let (res, buf) = self.file.write_at(buf, self.pos).await;
+        if let Ok(n) = res {
+            self.pos += n as u64;
+        }
+        (res, buf)
+    }
+
+    // Only the first buffer is written, like a short write.
+    async fn writev<T: IoVecBuf>(&mut self, buf_vec: T) -> BufResult<usize, T> {
+        let slice = match IoVecWrapper::new(buf_vec) {
+            Ok(slice) => slice,
+            Err(buf_vec) => return (Ok(0), buf_vec),
+        };
+
+        let (res, slice) = self.write(slice).await;
+        (res, slice.into_inner())
+    }
+
+    async fn flush(&mut self) -> std::io::Result<()> {
+        // The data is not buffered in user space.
+        Ok(())
+    }
+
+    async fn shutdown(&mut self) -> std::io::Result<()> {
+        // Use `File::sync_all` to make sure the data reaches the disk.
+        Ok(())
+    }
+}
+
+impl AsyncSeekRent for FileStream {
+    async fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let (base, offset) = match pos {
+            SeekFrom::Start(pos) => {
+                self.pos = pos;
+                return Ok(pos);
+            }
+            SeekFrom::Current(offset) => (self.pos, offset),
+            SeekFrom::End(offset) => (self.file.metadata().await?.len(), offset),
+        };
+        match base.checked_add_signed(offset) {
+            Some(pos) => {
+                self.pos = pos;
+                Ok(pos)
+            }
+            None => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            )),
+        }
+    }
+}
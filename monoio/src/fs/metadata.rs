@@ -0,0 +1,278 @@
+//! Metadata of files, backed by statx(2) on linux.
+//!
+//! On other platforms it is the metadata of std, which is queried on the
+//! blocking thread pool.
+
+use std::{io, path::Path};
+
+#[cfg(not(target_os = "linux"))]
+pub use std::fs::{FileType, Metadata};
+#[cfg(target_os = "linux")]
+use std::{
+    fs::Permissions,
+    os::unix::fs::PermissionsExt,
+    time::{Duration, SystemTime},
+};
+
+#[cfg(target_os = "linux")]
+use crate::driver::op::Op;
+
+/// Given a path, query the file system to get information about a file,
+/// directory, etc.
+///
+/// This function will traverse symbolic links to query information about the
+/// destination file.
+///
+/// # Examples
+///
+/// ```no_run
+/// #[monoio::main]
+/// async fn main() -> std::io::Result<()> {
+///     let attr = monoio::fs::metadata("/some/file/path.txt").await?;
+///     // inspect attr ...
+///     Ok(())
+/// }
+/// ```
+pub async fn metadata<P: AsRef<Path>>(path: P) -> io::Result<Metadata> {
+    #[cfg(target_os = "linux")]
+    return Op::statx_path(path, true).await.map(Metadata::new);
+    #[cfg(not(target_os = "linux"))]
+    {
+        let path = path.as_ref().to_owned();
+        super::asyncify(move || std::fs::metadata(path)).await
+    }
+}
+
+/// Query the metadata about a file like [`metadata`], with the credentials of
+/// the personality instead of the ones of the thread, see
+/// [`OpenOptions::personality`](crate::fs::OpenOptions::personality).
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+pub async fn metadata_as<P: AsRef<Path>>(
+    path: P,
+    personality: crate::utils::PersonalityId,
+) -> io::Result<Metadata> {
+    Op::statx_path_as(path, true, personality)?
+        .result()
+        .await
+        .map(Metadata::new)
+}
+
+/// Query the metadata about a file without following symlinks.
+///
+/// # Examples
+///
+/// ```no_run
+/// #[monoio::main]
+/// async fn main() -> std::io::Result<()> {
+///     let attr = monoio::fs::symlink_metadata("/some/file/path.txt").await?;
+///     // inspect attr ...
+///     Ok(())
+/// }
+/// ```
+pub async fn symlink_metadata<P: AsRef<Path>>(path: P) -> io::Result<Metadata> {
+    #[cfg(target_os = "linux")]
+    return Op::statx_path(path, false).await.map(Metadata::new);
+    #[cfg(not(target_os = "linux"))]
+    {
+        let path = path.as_ref().to_owned();
+        super::asyncify(move || std::fs::symlink_metadata(path)).await
+    }
+}
+
+/// Metadata information about a file.
+///
+/// It is returned by [`metadata`], [`symlink_metadata`] and
+/// [`File::metadata`](crate::fs::File::metadata).
+#[cfg(target_os = "linux")]
+#[derive(Clone)]
+pub struct Metadata {
+    stat: libc::statx,
+}
+
+#[cfg(target_os = "linux")]
+impl Metadata {
+    pub(crate) fn new(stat: libc::statx) -> Self {
+        Metadata { stat }
+    }
+
+    /// Returns the file type for this metadata.
+    pub fn file_type(&self) -> FileType {
+        FileType {
+            mode: self.stat.stx_mode as u32,
+        }
+    }
+
+    /// Returns `true` if this metadata is for a directory.
+    pub fn is_dir(&self) -> bool {
+        self.file_type().is_dir()
+    }
+
+    /// Returns `true` if this metadata is for a regular file.
+    pub fn is_file(&self) -> bool {
+        self.file_type().is_file()
+    }
+
+    /// Returns `true` if this metadata is for a symbolic link.
+    pub fn is_symlink(&self) -> bool {
+        self.file_type().is_symlink()
+    }
+
+    /// Returns the size of the file, in bytes, this metadata is for.
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> u64 {
+        self.stat.stx_size
+    }
+
+    /// Returns the permissions of the file this metadata is for.
+    pub fn permissions(&self) -> Permissions {
+        Permissions::from_mode(self.stat.stx_mode as u32)
+    }
+
+    /// Returns the last modification time listed in this metadata.
+    pub fn modified(&self) -> io::Result<SystemTime> {
+        self.time(libc::STATX_MTIME, &self.stat.stx_mtime, "modified")
+    }
+
+    /// Returns the last access time of this metadata.
+    pub fn accessed(&self) -> io::Result<SystemTime> {
+        self.time(libc::STATX_ATIME, &self.stat.stx_atime, "accessed")
+    }
+
+    /// Returns the creation time listed in this metadata.
+    ///
+    /// Not all file systems record the creation time, an error is returned
+    /// in that case.
+    pub fn created(&self) -> io::Result<SystemTime> {
+        self.time(libc::STATX_BTIME, &self.stat.stx_btime, "creation")
+    }
+
+    fn time(&self, mask: u32, ts: &libc::statx_timestamp, name: &str) -> io::Result<SystemTime> {
+        if self.stat.stx_mask & mask == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                format!("{name} time is not available"),
+            ));
+        }
+        // The nanoseconds count forward from the seconds, even before the
+        // epoch.
+        let secs = Duration::from_secs(ts.tv_sec.unsigned_abs());
+        let nanos = Duration::from_nanos(ts.tv_nsec as u64);
+        Ok(if ts.tv_sec >= 0 {
+            SystemTime::UNIX_EPOCH + secs + nanos
+        } else {
+            SystemTime::UNIX_EPOCH - secs + nanos
+        })
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl std::os::unix::fs::MetadataExt for Metadata {
+    fn dev(&self) -> u64 {
+        libc::makedev(self.stat.stx_dev_major, self.stat.stx_dev_minor)
+    }
+
+    fn ino(&self) -> u64 {
+        self.stat.stx_ino
+    }
+
+    fn mode(&self) -> u32 {
+        self.stat.stx_mode as u32
+    }
+
+    fn nlink(&self) -> u64 {
+        self.stat.stx_nlink as u64
+    }
+
+    fn uid(&self) -> u32 {
+        self.stat.stx_uid
+    }
+
+    fn gid(&self) -> u32 {
+        self.stat.stx_gid
+    }
+
+    fn rdev(&self) -> u64 {
+        libc::makedev(self.stat.stx_rdev_major, self.stat.stx_rdev_minor)
+    }
+
+    fn size(&self) -> u64 {
+        self.stat.stx_size
+    }
+
+    fn atime(&self) -> i64 {
+        self.stat.stx_atime.tv_sec
+    }
+
+    fn atime_nsec(&self) -> i64 {
+        self.stat.stx_atime.tv_nsec as i64
+    }
+
+    fn mtime(&self) -> i64 {
+        self.stat.stx_mtime.tv_sec
+    }
+
+    fn mtime_nsec(&self) -> i64 {
+        self.stat.stx_mtime.tv_nsec as i64
+    }
+
+    fn ctime(&self) -> i64 {
+        self.stat.stx_ctime.tv_sec
+    }
+
+    fn ctime_nsec(&self) -> i64 {
+        self.stat.stx_ctime.tv_nsec as i64
+    }
+
+    fn blksize(&self) -> u64 {
+        self.stat.stx_blksize as u64
+    }
+
+    fn blocks(&self) -> u64 {
+        self.stat.stx_blocks
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl std::fmt::Debug for Metadata {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Metadata")
+            .field("file_type", &self.file_type())
+            .field("is_dir", &self.is_dir())
+            .field("is_file", &self.is_file())
+            .field("permissions", &self.permissions())
+            .field("modified", &self.modified())
+            .field("accessed", &self.accessed())
+            .field("created", &self.created())
+            .finish_non_exhaustive()
+    }
+}
+
+/// A structure representing a type of file with accessors for each file type.
+#[cfg(target_os = "linux")]
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct FileType {
+    mode: u32,
+}
+
+#[cfg(target_os = "linux")]
+impl FileType {
+    /// Tests whether this file type represents a directory.
+    pub fn is_dir(&self) -> bool {
+        self.is(libc::S_IFDIR)
+    }
+
+    /// Tests whether this file type represents a regular file.
+    pub fn is_file(&self) -> bool {
+        self.is(libc::S_IFREG)
+    }
+
+    /// Tests whether this file type represents a symbolic link.
+    pub fn is_symlink(&self) -> bool {
+        self.is(libc::S_IFLNK)
+    }
+
+    #[inline]
+    fn is(&self, mode: libc::mode_t) -> bool {
+        self.mode & libc::S_IFMT == mode
+    }
+}
@@ -1,35 +1,189 @@
 //! Filesystem manipulation operations.
 
 mod file;
-use std::{io, path::Path};
+#[cfg(unix)]
+use std::{
+    ffi::{CStr, CString},
+    os::fd::OwnedFd,
+    time::SystemTime,
+};
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
+
+#[cfg(unix)]
+pub use file::FileLock;
+#[cfg(target_os = "linux")]
+pub use file::SyncRangeFlags;
+pub use file::{Advice, File};
 
-pub use file::File;
+mod file_stream;
+pub use file_stream::FileStream;
 
 mod open_options;
 pub use open_options::OpenOptions;
 
+mod metadata;
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+pub use metadata::metadata_as;
+pub use metadata::{metadata, symlink_metadata, FileType, Metadata};
+
+mod dir_builder;
+pub use dir_builder::DirBuilder;
+
+#[cfg(target_os = "linux")]
+mod temp;
+#[cfg(target_os = "linux")]
+pub use temp::tempfile_in;
+
 use crate::buf::IoBuf;
 
+/// Runs the blocking `f` on the thread pool attached to the runtime, see
+/// [`spawn_blocking`](crate::spawn_blocking). Without a thread pool it runs on
+/// the current thread, like the ops which have no async version.
+///
+/// It starts right away rather than when polled, like the submitted ops, so
+/// several of them run at once.
+#[cfg(feature = "sync")]
+pub(crate) fn asyncify<F, T>(f: F) -> impl std::future::Future<Output = io::Result<T>>
+where
+    F: FnOnce() -> io::Result<T> + Send + 'static,
+    T: Send + 'static,
+{
+    let (handle, res) = match crate::blocking::has_thread_pool() {
+        true => (Some(crate::spawn_blocking(f)), None),
+        false => (None, Some(f())),
+    };
+    async move {
+        match (handle, res) {
+            (Some(handle), _) => handle
+                .await
+                .map_err(|_| io::Error::other("the blocking task is canceled"))?,
+            (None, res) => res.unwrap(),
+        }
+    }
+}
+
+/// Runs the blocking `f` on the current thread, see the version with the
+/// `sync` feature.
+#[cfg(not(feature = "sync"))]
+pub(crate) fn asyncify<F, T>(f: F) -> impl std::future::Future<Output = io::Result<T>>
+where
+    F: FnOnce() -> io::Result<T> + Send + 'static,
+    T: Send + 'static,
+{
+    std::future::ready(f())
+}
+
+/// Runs the blocking `f` with the fd, see [`asyncify`]. The pool gets a
+/// duplicate, which stays open even if the file is closed meanwhile, so the
+/// fd is never reused under it.
+#[cfg(unix)]
+pub(crate) fn asyncify_fd<F, T>(
+    fd: &crate::driver::shared_fd::SharedFd,
+    f: F,
+) -> impl std::future::Future<Output = io::Result<T>>
+where
+    F: FnOnce(std::os::fd::RawFd) -> io::Result<T> + Send + 'static,
+    T: Send + 'static,
+{
+    let raw_fd = fd.raw_fd();
+    #[cfg(feature = "sync")]
+    let dup = crate::blocking::has_thread_pool().then(|| fd.dup());
+    asyncify(move || {
+        #[cfg(feature = "sync")]
+        if let Some(fd) = dup {
+            use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+
+            let fd = unsafe { OwnedFd::from_raw_fd(fd?) };
+            return f(fd.as_raw_fd());
+        }
+        f(raw_fd)
+    })
+}
+
 /// Read the entire contents of a file into a bytes vector.
+///
+/// The size of the file is only a hint of the capacity to allocate, the file
+/// is read until the end even if it grows or shrinks meanwhile, and short
+/// reads are continued.
+///
+/// # Examples
+///
+/// ```no_run
+/// #[monoio::main]
+/// async fn main() -> std::io::Result<()> {
+///     let contents = monoio::fs::read("foo.txt").await?;
+///     println!("{} bytes", contents.len());
+///     Ok(())
+/// }
+/// ```
 #[cfg(unix)]
 pub async fn read<P: AsRef<Path>>(path: P) -> io::Result<Vec<u8>> {
-    use std::os::fd::{AsRawFd, FromRawFd, IntoRawFd};
-
     use crate::buf::IoBufMut;
 
+    // Read in chunks of at least this size once the hint is exhausted.
+    const PROBE: usize = 8 * 1024;
+
     let file = File::open(path).await?;
-    let sys_file = unsafe { std::fs::File::from_raw_fd(file.as_raw_fd()) };
-    let size = sys_file.metadata()?.len() as usize;
-    let _ = sys_file.into_raw_fd();
+    let size = file.metadata().await?.len() as usize;
 
-    let (res, buf) = file
-        .read_exact_at(Vec::with_capacity(size).slice_mut(0..size), 0)
-        .await;
-    res?;
-    Ok(buf.into_inner())
+    let mut buf = Vec::with_capacity(size.max(PROBE));
+    loop {
+        if buf.len() == buf.capacity() {
+            buf.reserve(PROBE);
+        }
+        let pos = buf.len();
+        let end = buf.capacity();
+        let (res, slice) = file.read_at(buf.slice_mut(pos..end), pos as u64).await;
+        buf = slice.into_inner();
+        match res {
+            Ok(0) => return Ok(buf),
+            Ok(_) => (),
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => (),
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Read the entire contents of a file into a string, see [`read`].
+///
+/// An error of the kind `InvalidData` is returned if the contents are not
+/// valid UTF-8.
+///
+/// # Examples
+///
+/// ```no_run
+/// #[monoio::main]
+/// async fn main() -> std::io::Result<()> {
+///     let contents = monoio::fs::read_to_string("foo.txt").await?;
+///     println!("{contents}");
+///     Ok(())
+/// }
+/// ```
+#[cfg(unix)]
+pub async fn read_to_string<P: AsRef<Path>>(path: P) -> io::Result<String> {
+    String::from_utf8(read(path).await?)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.utf8_error()))
 }
 
 /// Write a buffer as the entire contents of a file.
+///
+/// The file is created if it does not exist and truncated if it does, and
+/// short writes are continued until the whole buffer is written. The buffer
+/// is returned with the result.
+///
+/// # Examples
+///
+/// ```no_run
+/// #[monoio::main]
+/// async fn main() -> std::io::Result<()> {
+///     let (res, _) = monoio::fs::write("foo.txt", b"hello").await;
+///     res?;
+///     Ok(())
+/// }
+/// ```
 pub async fn write<P: AsRef<Path>, C: IoBuf>(path: P, contents: C) -> (io::Result<()>, C) {
     let file = match File::create(path).await {
         Ok(f) => f,
@@ -37,3 +191,911 @@ pub async fn write<P: AsRef<Path>, C: IoBuf>(path: P, contents: C) -> (io::Resul
     };
     file.write_all_at(contents, 0).await
 }
+
+/// Copies the contents of one file to another, the permissions of the
+/// original file are copied to the destination file too. It creates the
+/// destination if it does not exist and truncates it if it does. Returns
+/// the total number of bytes copied.
+///
+/// On linux the copy is done by `copy_file_range` in the kernel, which clones
+/// the data on the file systems supporting reflinks. There is no opcode of it
+/// in io_uring, so it runs on the blocking thread pool on both drivers if one
+/// is attached, see [`spawn_blocking`](crate::spawn_blocking). It falls back
+/// to reads and writes with a buffer, which are async ops, if the files do
+/// not support it, e.g. they are on different file systems.
+///
+/// # Errors
+///
+/// An error of the kind `InvalidInput` is returned if `from` is not a regular
+/// file, other errors may be returned if e.g. `from` does not exist or the
+/// user lacks permissions.
+///
+/// # Examples
+///
+/// ```no_run
+/// #[monoio::main]
+/// async fn main() -> std::io::Result<()> {
+///     let n = monoio::fs::copy("foo.txt", "bar.txt").await?;
+///     println!("copied {n} bytes");
+///     Ok(())
+/// }
+/// ```
+pub async fn copy<P: AsRef<Path>, Q: AsRef<Path>>(from: P, to: Q) -> io::Result<u64> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+
+        let from = File::open(from).await?;
+        let metadata = from.metadata().await?;
+        if !metadata.is_file() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "the source path is not a regular file",
+            ));
+        }
+        let perm = metadata.permissions();
+        let to = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .mode(perm.mode())
+            .open(to)
+            .await?;
+        // The mode of a new file is masked by the umask, and the one of an
+        // existing file is kept by open.
+        to.set_permissions(perm).await?;
+
+        #[cfg(target_os = "linux")]
+        let copied = {
+            use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+
+            // The pool holds a duplicate of `from` too, see `asyncify_fd`.
+            let from = unsafe { OwnedFd::from_raw_fd(from.shared_fd().dup()?) };
+            let res = asyncify_fd(to.shared_fd(), move |to| {
+                copy_file_range(from.as_raw_fd(), to)
+            });
+            match res.await? {
+                (copied, true) => return Ok(copied),
+                (copied, false) => copied,
+            }
+        };
+        #[cfg(not(target_os = "linux"))]
+        let copied = 0;
+        copy_buffered(&from, &to, copied).await
+    }
+    // Other platforms use the blocking syscalls of std.
+    #[cfg(not(unix))]
+    {
+        let (from, to) = (from.as_ref().to_owned(), to.as_ref().to_owned());
+        asyncify(move || std::fs::copy(from, to)).await
+    }
+}
+
+/// Copy with `copy_file_range` until the end of `from`. Returns the bytes
+/// copied and whether the copy is complete, it is not if `copy_file_range` is
+/// not supported for the files.
+#[cfg(target_os = "linux")]
+fn copy_file_range(from: std::os::fd::RawFd, to: std::os::fd::RawFd) -> io::Result<(u64, bool)> {
+    // The kernel copies at most about 2 GiB at once.
+    const CHUNK: usize = 1 << 30;
+
+    let mut copied = 0;
+    loop {
+        let mut off_in = copied as libc::loff_t;
+        let mut off_out = copied as libc::loff_t;
+        match crate::syscall!(copy_file_range(
+            from,
+            &mut off_in,
+            to,
+            &mut off_out,
+            CHUNK,
+            0
+        )) {
+            // The files of e.g. procfs have no size, copy_file_range copies
+            // nothing from them.
+            Ok(0) => return Ok((copied, copied != 0)),
+            Ok(n) => copied += n as u64,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => (),
+            Err(e) => match e.raw_os_error() {
+                Some(
+                    libc::EXDEV | libc::EOPNOTSUPP | libc::ENOSYS | libc::EINVAL | libc::EPERM,
+                ) => return Ok((copied, false)),
+                _ => return Err(e),
+            },
+        }
+    }
+}
+
+/// Copy with a buffer from `pos` until the end of `from`. Returns the bytes
+/// copied in total.
+#[cfg(unix)]
+async fn copy_buffered(from: &File, to: &File, mut pos: u64) -> io::Result<u64> {
+    const BUF_SIZE: usize = 64 * 1024;
+
+    let mut buf = Vec::with_capacity(BUF_SIZE);
+    loop {
+        let (res, read) = from.read_at(buf, pos).await;
+        match res {
+            Ok(0) => return Ok(pos),
+            Ok(_) => (),
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {
+                buf = read;
+                continue;
+            }
+            Err(e) => return Err(e),
+        }
+        let (res, written) = to.write_all_at(read, pos).await;
+        res?;
+        pos += written.len() as u64;
+        buf = written;
+    }
+}
+
+/// Creates a new, empty directory at the provided path.
+///
+/// # Errors
+///
+/// This function will return an error in the following situations, but is not
+/// limited to just these cases:
+///
+/// * User lacks permissions to create directory at `path`.
+/// * A parent of the given path doesn't exist.
+/// * `path` already exists, the error is of the kind `AlreadyExists`.
+///
+/// # Examples
+///
+/// ```no_run
+/// #[monoio::main]
+/// async fn main() -> std::io::Result<()> {
+///     monoio::fs::create_dir("/some/dir").await?;
+///     Ok(())
+/// }
+/// ```
+pub async fn create_dir<P: AsRef<Path>>(path: P) -> io::Result<()> {
+    DirBuilder::new().create(path).await
+}
+
+/// Recursively creates a directory and all of its parent components if they
+/// are missing.
+///
+/// The missing parents are created from the deepest one that exists, and a
+/// directory created concurrently at any level is not an error.
+///
+/// # Errors
+///
+/// This function will return an error in the following situations, but is not
+/// limited to just these cases:
+///
+/// * User lacks permissions to create any of the directories.
+/// * Any component of `path` exists and is not a directory.
+///
+/// # Examples
+///
+/// ```no_run
+/// #[monoio::main]
+/// async fn main() -> std::io::Result<()> {
+///     monoio::fs::create_dir_all("/some/dir").await?;
+///     Ok(())
+/// }
+/// ```
+pub async fn create_dir_all<P: AsRef<Path>>(path: P) -> io::Result<()> {
+    DirBuilder::new().recursive(true).create(path).await
+}
+
+/// Removes a file from the filesystem.
+///
+/// Note that there is no guarantee that the file is immediately deleted (e.g.,
+/// depending on platform, other open file descriptors may prevent immediate
+/// removal).
+///
+/// # Errors
+///
+/// This function will return an error in the following situations, but is not
+/// limited to just these cases:
+///
+/// * `path` points to a directory.
+/// * The file doesn't exist.
+/// * The user lacks permissions to remove the file.
+///
+/// # Examples
+///
+/// ```no_run
+/// #[monoio::main]
+/// async fn main() -> std::io::Result<()> {
+///     monoio::fs::remove_file("a.txt").await?;
+///     Ok(())
+/// }
+/// ```
+pub async fn remove_file<P: AsRef<Path>>(path: P) -> io::Result<()> {
+    #[cfg(unix)]
+    return crate::driver::op::Op::unlink_file(path)?.await.map(|_| ());
+    // Other platforms use the blocking syscall of std.
+    #[cfg(not(unix))]
+    {
+        let path = path.as_ref().to_owned();
+        asyncify(move || std::fs::remove_file(path)).await
+    }
+}
+
+/// Removes a file like [`remove_file`], with the credentials of the
+/// personality instead of the ones of the thread, see
+/// [`OpenOptions::personality`].
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+pub async fn remove_file_as<P: AsRef<Path>>(
+    path: P,
+    personality: crate::utils::PersonalityId,
+) -> io::Result<()> {
+    crate::driver::op::Op::unlink_as(path, false, personality)?
+        .await
+        .meta
+        .result
+        .map(|_| ())
+}
+
+/// Removes an empty directory.
+///
+/// # Errors
+///
+/// This function will return an error in the following situations, but is not
+/// limited to just these cases:
+///
+/// * `path` doesn't exist.
+/// * `path` isn't a directory.
+/// * The user lacks permissions to remove the directory at the provided `path`.
+/// * The directory isn't empty.
+///
+/// # Examples
+///
+/// ```no_run
+/// #[monoio::main]
+/// async fn main() -> std::io::Result<()> {
+///     monoio::fs::remove_dir("/some/dir").await?;
+///     Ok(())
+/// }
+/// ```
+pub async fn remove_dir<P: AsRef<Path>>(path: P) -> io::Result<()> {
+    #[cfg(unix)]
+    return crate::driver::op::Op::unlink_dir(path)?.await.map(|_| ());
+    #[cfg(not(unix))]
+    {
+        let path = path.as_ref().to_owned();
+        asyncify(move || std::fs::remove_dir(path)).await
+    }
+}
+
+/// Removes an empty directory like [`remove_dir`], with the credentials of the
+/// personality instead of the ones of the thread, see
+/// [`OpenOptions::personality`].
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+pub async fn remove_dir_as<P: AsRef<Path>>(
+    path: P,
+    personality: crate::utils::PersonalityId,
+) -> io::Result<()> {
+    crate::driver::op::Op::unlink_as(path, true, personality)?
+        .await
+        .meta
+        .result
+        .map(|_| ())
+}
+
+/// Removes a directory at this path, after removing all its contents. Use
+/// carefully!
+///
+/// Symbolic links are not followed, they are removed like the other files,
+/// and so is `path` itself if it is a symbolic link. The tree is walked
+/// iteratively, and the entries removed concurrently are skipped instead of
+/// failing the walk. The files of each directory are unlinked with several
+/// operations in flight at once.
+///
+/// There is no getdents opcode of io_uring, so the entries are listed with
+/// the blocking `read_dir` of std, on the blocking thread pool if one is
+/// attached, see [`spawn_blocking`](crate::spawn_blocking).
+///
+/// # Examples
+///
+/// ```no_run
+/// #[monoio::main]
+/// async fn main() -> std::io::Result<()> {
+///     monoio::fs::remove_dir_all("/some/dir").await?;
+///     Ok(())
+/// }
+/// ```
+pub async fn remove_dir_all<P: AsRef<Path>>(path: P) -> io::Result<()> {
+    #[cfg(unix)]
+    return remove_dir_all_unix(path.as_ref()).await;
+    // Other platforms use the blocking syscalls of std.
+    #[cfg(not(unix))]
+    {
+        let path = path.as_ref().to_owned();
+        asyncify(move || std::fs::remove_dir_all(path)).await
+    }
+}
+
+#[cfg(unix)]
+async fn remove_dir_all_unix(path: &Path) -> io::Result<()> {
+    use std::sync::Arc;
+
+    use crate::driver::op::Op;
+
+    // The unlinks submitted at once.
+    const BATCH: usize = 64;
+
+    fn skip_not_found<T>(res: io::Result<T>) -> io::Result<()> {
+        match res {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        }
+    }
+
+    if symlink_metadata(path).await?.file_type().is_symlink() {
+        return remove_file(path).await;
+    }
+
+    // The entries are listed and unlinked relative to the fd of their parent
+    // directory, which is opened without following a symbolic link, so a
+    // directory replaced by a link meanwhile never leads out of the tree. A
+    // directory is pushed back below its subdirectories with the flag set
+    // once its other entries are removed, so it is empty when popped again.
+    let mut stack: Vec<(Option<Arc<OwnedFd>>, CString, bool)> =
+        vec![(None, crate::driver::util::cstr(path)?, false)];
+    while let Some((parent, name, emptied)) = stack.pop() {
+        if emptied {
+            skip_not_found(Op::unlink_at(parent, name, true)?.await)?;
+            continue;
+        }
+        let listed = asyncify({
+            let (parent, name) = (parent.clone(), name.clone());
+            move || list_dir(parent.as_deref(), &name)
+        })
+        .await;
+        let (dir, dirs, files) = match listed {
+            Ok(listed) => listed,
+            Err(e) if parent.is_none() => return Err(e),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+            // It is not a directory anymore, e.g. a link, which is unlinked
+            // instead of followed.
+            Err(e) if matches!(e.raw_os_error(), Some(libc::ELOOP | libc::ENOTDIR)) => {
+                skip_not_found(Op::unlink_at(parent, name, false)?.await)?;
+                continue;
+            }
+            Err(e) => return Err(e),
+        };
+        let dir = Arc::new(dir);
+        stack.push((parent, name, true));
+        stack.extend(
+            dirs.into_iter()
+                .map(|name| (Some(dir.clone()), name, false)),
+        );
+        for batch in files.chunks(BATCH) {
+            let ops = batch
+                .iter()
+                .map(|name| Op::unlink_at(Some(dir.clone()), name.clone(), false))
+                .collect::<io::Result<Vec<_>>>()?;
+            for op in ops {
+                skip_not_found(op.await)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+// Open the directory `name` of `parent` without following a symbolic link,
+// and list its subdirectories and other entries. The entries gone meanwhile
+// are skipped.
+#[cfg(unix)]
+fn list_dir(
+    parent: Option<&OwnedFd>,
+    name: &CStr,
+) -> io::Result<(OwnedFd, Vec<CString>, Vec<CString>)> {
+    use std::os::fd::{AsRawFd, FromRawFd};
+
+    let parent = parent.map_or(libc::AT_FDCWD, |fd| fd.as_raw_fd());
+    let fd = crate::syscall!(openat(
+        parent,
+        name.as_ptr(),
+        libc::O_RDONLY | libc::O_DIRECTORY | libc::O_NOFOLLOW | libc::O_CLOEXEC
+    ))?;
+    let dir = unsafe { OwnedFd::from_raw_fd(fd) };
+    // The stream owns and closes a duplicate, the fd is kept for the unlinks.
+    let dup = crate::syscall!(fcntl(fd, libc::F_DUPFD_CLOEXEC, 0))?;
+    let stream = unsafe { libc::fdopendir(dup) };
+    if stream.is_null() {
+        let e = io::Error::last_os_error();
+        unsafe { libc::close(dup) };
+        return Err(e);
+    }
+
+    let (mut dirs, mut files) = (Vec::new(), Vec::new());
+    let res = loop {
+        // The end of the stream and the errors are told apart by errno.
+        unsafe { *errno() = 0 };
+        let entry = unsafe { libc::readdir(stream) };
+        if entry.is_null() {
+            match io::Error::last_os_error() {
+                e if e.raw_os_error() == Some(0) => break Ok(()),
+                e => break Err(e),
+            }
+        }
+        let entry = unsafe { &*entry };
+        let name = unsafe { CStr::from_ptr(entry.d_name.as_ptr()) };
+        if name.to_bytes() == b"." || name.to_bytes() == b".." {
+            continue;
+        }
+        // The type of the entry itself like lstat, a symbolic link to a
+        // directory is unlinked instead of walked.
+        let is_dir = match entry.d_type {
+            libc::DT_UNKNOWN => {
+                let mut stat: libc::stat = unsafe { std::mem::zeroed() };
+                match crate::syscall!(fstatat(
+                    fd,
+                    name.as_ptr(),
+                    &mut stat,
+                    libc::AT_SYMLINK_NOFOLLOW
+                )) {
+                    Ok(_) => stat.st_mode & libc::S_IFMT == libc::S_IFDIR,
+                    Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+                    Err(e) => break Err(e),
+                }
+            }
+            ty => ty == libc::DT_DIR,
+        };
+        match is_dir {
+            true => dirs.push(name.to_owned()),
+            false => files.push(name.to_owned()),
+        }
+    };
+    unsafe { libc::closedir(stream) };
+    res.map(|_| (dir, dirs, files))
+}
+
+/// The errno of the current thread.
+#[cfg(unix)]
+fn errno() -> *mut libc::c_int {
+    #[cfg(any(target_os = "linux", target_os = "redox"))]
+    return unsafe { libc::__errno_location() };
+    #[cfg(any(target_os = "android", target_os = "netbsd", target_os = "openbsd"))]
+    return unsafe { libc::__errno() };
+    #[cfg(any(
+        target_vendor = "apple",
+        target_os = "freebsd",
+        target_os = "dragonfly"
+    ))]
+    return unsafe { libc::__error() };
+}
+
+/// Renames a file or directory to a new name, replacing the original file if
+/// `to` already exists.
+///
+/// This will not work if the new name is on a different mount point.
+///
+/// # Errors
+///
+/// This function will return an error in the following situations, but is not
+/// limited to just these cases:
+///
+/// * `from` does not exist.
+/// * The user lacks permissions to view contents.
+/// * `from` and `to` are on separate filesystems.
+///
+/// # Examples
+///
+/// ```no_run
+/// #[monoio::main]
+/// async fn main() -> std::io::Result<()> {
+///     monoio::fs::rename("a.txt", "b.txt").await?;
+///     Ok(())
+/// }
+/// ```
+pub async fn rename<P: AsRef<Path>, Q: AsRef<Path>>(from: P, to: Q) -> io::Result<()> {
+    #[cfg(unix)]
+    return crate::driver::op::Op::rename(from, to, 0)?
+        .await
+        .map(|_| ());
+    #[cfg(not(unix))]
+    {
+        let (from, to) = (from.as_ref().to_owned(), to.as_ref().to_owned());
+        asyncify(move || std::fs::rename(from, to)).await
+    }
+}
+
+/// Renames a file or directory to a new name like [`rename`], but fails with
+/// an `AlreadyExists` error instead of replacing `to` if it exists.
+///
+/// The check and the rename are done atomically.
+///
+/// # Examples
+///
+/// ```no_run
+/// #[monoio::main]
+/// async fn main() -> std::io::Result<()> {
+///     monoio::fs::rename_noreplace("a.txt", "b.txt").await?;
+///     Ok(())
+/// }
+/// ```
+#[cfg(target_os = "linux")]
+pub async fn rename_noreplace<P: AsRef<Path>, Q: AsRef<Path>>(from: P, to: Q) -> io::Result<()> {
+    crate::driver::op::Op::rename(from, to, libc::RENAME_NOREPLACE)?
+        .await
+        .map(|_| ())
+}
+
+/// Creates a new symbolic link on the filesystem.
+///
+/// The `link` path will be a symbolic link pointing to the `original` path.
+///
+/// # Errors
+///
+/// An error of the kind `AlreadyExists` is returned if `link` already exists,
+/// and `PermissionDenied` if the user lacks permissions to create it.
+///
+/// # Examples
+///
+/// ```no_run
+/// #[monoio::main]
+/// async fn main() -> std::io::Result<()> {
+///     monoio::fs::symlink("a.txt", "b.txt").await?;
+///     Ok(())
+/// }
+/// ```
+#[cfg(unix)]
+pub async fn symlink<P: AsRef<Path>, Q: AsRef<Path>>(original: P, link: Q) -> io::Result<()> {
+    crate::driver::op::Op::symlink(original, link)?
+        .await
+        .map(|_| ())
+}
+
+/// Creates a new symbolic link to a file on the filesystem.
+///
+/// It is the same as [`symlink`] on unix, which does not distinguish the links
+/// to files and directories.
+pub async fn symlink_file<P: AsRef<Path>, Q: AsRef<Path>>(original: P, link: Q) -> io::Result<()> {
+    #[cfg(unix)]
+    return symlink(original, link).await;
+    // Other platforms use the blocking syscall of std.
+    #[cfg(windows)]
+    {
+        let (original, link) = (original.as_ref().to_owned(), link.as_ref().to_owned());
+        asyncify(move || std::os::windows::fs::symlink_file(original, link)).await
+    }
+}
+
+/// Creates a new symbolic link to a directory on the filesystem.
+///
+/// It is the same as [`symlink`] on unix, which does not distinguish the links
+/// to files and directories.
+pub async fn symlink_dir<P: AsRef<Path>, Q: AsRef<Path>>(original: P, link: Q) -> io::Result<()> {
+    #[cfg(unix)]
+    return symlink(original, link).await;
+    #[cfg(windows)]
+    {
+        let (original, link) = (original.as_ref().to_owned(), link.as_ref().to_owned());
+        asyncify(move || std::os::windows::fs::symlink_dir(original, link)).await
+    }
+}
+
+/// Creates a new hard link on the filesystem.
+///
+/// The `link` path will be a link pointing to the `original` path. Note that
+/// systems often require these two paths to both be located on the same
+/// filesystem, an error with the raw os error `EXDEV` is returned if not, so
+/// callers can fall back to a copy.
+///
+/// # Examples
+///
+/// ```no_run
+/// #[monoio::main]
+/// async fn main() -> std::io::Result<()> {
+///     monoio::fs::hard_link("a.txt", "b.txt").await?;
+///     Ok(())
+/// }
+/// ```
+pub async fn hard_link<P: AsRef<Path>, Q: AsRef<Path>>(original: P, link: Q) -> io::Result<()> {
+    #[cfg(unix)]
+    return crate::driver::op::Op::hard_link(original, link)?
+        .await
+        .map(|_| ());
+    // Other platforms use the blocking syscall of std.
+    #[cfg(not(unix))]
+    {
+        let (original, link) = (original.as_ref().to_owned(), link.as_ref().to_owned());
+        asyncify(move || std::fs::hard_link(original, link)).await
+    }
+}
+
+/// Reads a symbolic link, returning the path that the link points to.
+///
+/// There is no readlink opcode of io_uring, so `readlink` runs on the
+/// blocking thread pool on both drivers if one is attached, see
+/// [`spawn_blocking`](crate::spawn_blocking). The target is read again with a
+/// larger buffer until it fits, as `readlink` truncates it silently.
+///
+/// # Errors
+///
+/// An error of the kind `NotFound` is returned if `path` does not exist, and
+/// `InvalidInput` if it is not a symbolic link.
+///
+/// # Examples
+///
+/// ```no_run
+/// #[monoio::main]
+/// async fn main() -> std::io::Result<()> {
+///     let path = monoio::fs::read_link("a.txt").await?;
+///     Ok(())
+/// }
+/// ```
+pub async fn read_link<P: AsRef<Path>>(path: P) -> io::Result<PathBuf> {
+    #[cfg(unix)]
+    {
+        use std::{ffi::OsString, os::unix::ffi::OsStringExt};
+
+        let path = crate::driver::util::cstr(path.as_ref())?;
+        asyncify(move || {
+            let mut buf = Vec::<u8>::with_capacity(256);
+            loop {
+                let n = crate::syscall!(readlink(
+                    path.as_ptr(),
+                    buf.as_mut_ptr() as *mut libc::c_char,
+                    buf.capacity()
+                ))? as usize;
+                // A full buffer may be truncated.
+                if n < buf.capacity() {
+                    // Safety: the kernel wrote `n` bytes.
+                    unsafe { buf.set_len(n) };
+                    buf.shrink_to_fit();
+                    return Ok(PathBuf::from(OsString::from_vec(buf)));
+                }
+                buf.reserve(buf.capacity() * 2);
+            }
+        })
+        .await
+    }
+    // Other platforms use the blocking syscall of std.
+    #[cfg(not(unix))]
+    {
+        let path = path.as_ref().to_owned();
+        asyncify(move || std::fs::read_link(path)).await
+    }
+}
+
+/// Returns the canonical, absolute form of a path with all intermediate
+/// components normalized and symbolic links resolved.
+///
+/// There is no opcode of io_uring to resolve a path, so `realpath` runs on the
+/// blocking thread pool on both drivers if one is attached, see
+/// [`spawn_blocking`](crate::spawn_blocking).
+///
+/// # Errors
+///
+/// An error of the kind `NotFound` is returned if any component of `path`
+/// does not exist, and the raw os error `ELOOP` if there are too many
+/// symbolic links to resolve, e.g. a loop of them.
+///
+/// # Examples
+///
+/// ```no_run
+/// #[monoio::main]
+/// async fn main() -> std::io::Result<()> {
+///     let path = monoio::fs::canonicalize("../a/../foo.txt").await?;
+///     Ok(())
+/// }
+/// ```
+pub async fn canonicalize<P: AsRef<Path>>(path: P) -> io::Result<PathBuf> {
+    #[cfg(unix)]
+    {
+        use std::{
+            ffi::{CStr, OsStr},
+            os::unix::ffi::OsStrExt,
+        };
+
+        let path = crate::driver::util::cstr(path.as_ref())?;
+        asyncify(move || {
+            // The resolved path is allocated by realpath with malloc.
+            let resolved = unsafe { libc::realpath(path.as_ptr(), std::ptr::null_mut()) };
+            if resolved.is_null() {
+                return Err(io::Error::last_os_error());
+            }
+            let path = PathBuf::from(OsStr::from_bytes(
+                unsafe { CStr::from_ptr(resolved) }.to_bytes(),
+            ));
+            unsafe { libc::free(resolved as *mut libc::c_void) };
+            Ok(path)
+        })
+        .await
+    }
+    #[cfg(not(unix))]
+    {
+        let path = path.as_ref().to_owned();
+        asyncify(move || std::fs::canonicalize(path)).await
+    }
+}
+
+/// Changes the permissions found on a file or a directory, symbolic links
+/// are followed.
+///
+/// There is no chmod opcode of io_uring, so `fchmodat` runs on the blocking
+/// thread pool on both drivers if one is attached, see
+/// [`spawn_blocking`](crate::spawn_blocking). Use [`File::set_permissions`]
+/// if the file is open.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::{fs::Permissions, os::unix::fs::PermissionsExt};
+///
+/// #[monoio::main]
+/// async fn main() -> std::io::Result<()> {
+///     monoio::fs::set_permissions("a.txt", Permissions::from_mode(0o600)).await?;
+///     Ok(())
+/// }
+/// ```
+pub async fn set_permissions<P: AsRef<Path>>(
+    path: P,
+    perm: std::fs::Permissions,
+) -> io::Result<()> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path = crate::driver::util::cstr(path.as_ref())?;
+        let mode = perm.mode() as libc::mode_t;
+        asyncify(move || {
+            crate::syscall!(fchmodat(libc::AT_FDCWD, path.as_ptr(), mode, 0)).map(|_| ())
+        })
+        .await
+    }
+    // Other platforms use the blocking syscall of std.
+    #[cfg(not(unix))]
+    {
+        let path = path.as_ref().to_owned();
+        asyncify(move || std::fs::set_permissions(path, perm)).await
+    }
+}
+
+/// Changes the last access and modification times of a file or a directory,
+/// symbolic links are followed. The time is not changed for `None`.
+///
+/// There is no opcode of io_uring for it, so `utimensat` runs on the blocking
+/// thread pool on both drivers if one is attached, see
+/// [`spawn_blocking`](crate::spawn_blocking). Use [`File::set_times`] if the
+/// file is open.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::time::SystemTime;
+///
+/// #[monoio::main]
+/// async fn main() -> std::io::Result<()> {
+///     monoio::fs::set_times("a.txt", None, Some(SystemTime::now())).await?;
+///     Ok(())
+/// }
+/// ```
+#[cfg(unix)]
+pub async fn set_times<P: AsRef<Path>>(
+    path: P,
+    accessed: Option<SystemTime>,
+    modified: Option<SystemTime>,
+) -> io::Result<()> {
+    utimensat(path.as_ref(), accessed, modified, 0).await
+}
+
+/// Changes the times like [`set_times`], but if `path` is a symbolic link,
+/// the times of the link itself are changed instead of the ones of its
+/// target.
+#[cfg(unix)]
+pub async fn set_symlink_times<P: AsRef<Path>>(
+    path: P,
+    accessed: Option<SystemTime>,
+    modified: Option<SystemTime>,
+) -> io::Result<()> {
+    utimensat(path.as_ref(), accessed, modified, libc::AT_SYMLINK_NOFOLLOW).await
+}
+
+#[cfg(unix)]
+async fn utimensat(
+    path: &Path,
+    accessed: Option<SystemTime>,
+    modified: Option<SystemTime>,
+    flags: libc::c_int,
+) -> io::Result<()> {
+    let path = crate::driver::util::cstr(path)?;
+    let times = [timespec(accessed)?, timespec(modified)?];
+    asyncify(move || {
+        crate::syscall!(utimensat(
+            libc::AT_FDCWD,
+            path.as_ptr(),
+            times.as_ptr(),
+            flags
+        ))
+        .map(|_| ())
+    })
+    .await
+}
+
+/// The timespec of `futimens` and `utimensat`, `UTIME_OMIT` for `None`.
+#[cfg(unix)]
+fn timespec(time: Option<SystemTime>) -> io::Result<libc::timespec> {
+    // The fields differ in types and padding across the platforms.
+    let mut ts: libc::timespec = unsafe { std::mem::zeroed() };
+    let Some(time) = time else {
+        ts.tv_nsec = libc::UTIME_OMIT as _;
+        return Ok(ts);
+    };
+    let (secs, nanos) = match time.duration_since(SystemTime::UNIX_EPOCH) {
+        Ok(offset) => (i64::try_from(offset.as_secs()).ok(), offset.subsec_nanos()),
+        // The nanoseconds count forward from the seconds, even before the
+        // epoch.
+        Err(e) => {
+            let offset = e.duration();
+            let secs = i64::try_from(offset.as_secs()).ok().map(|secs| -secs);
+            match offset.subsec_nanos() {
+                0 => (secs, 0),
+                nanos => (
+                    secs.and_then(|secs| secs.checked_sub(1)),
+                    1_000_000_000 - nanos,
+                ),
+            }
+        }
+    };
+    ts.tv_sec = secs
+        .and_then(|secs| libc::time_t::try_from(secs).ok())
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "timestamp is out of range"))?;
+    ts.tv_nsec = nanos as _;
+    Ok(ts)
+}
+
+/// Give advice about the use of the memory of `buf`, e.g. to read ahead the
+/// pages of a memory-mapped file with [`Advice::WillNeed`].
+///
+/// The advised range is the initialized bytes of `buf`, which must start at a
+/// page boundary. The buffer is held until the operation completes, so the
+/// memory stays mapped, and it is returned with the result.
+///
+/// [`Advice::NoReuse`] has no effect on memory, and [`Advice::DontNeed`] may
+/// discard the data of the whole pages of the range, so an `InvalidInput`
+/// error is returned for them, see [`advise_memory_unchecked`]. On platforms
+/// other than linux an `Unsupported` error is returned.
+pub async fn advise_memory<T: IoBuf>(buf: T, advice: Advice) -> crate::BufResult<(), T> {
+    if matches!(advice, Advice::DontNeed) {
+        return (Err(io::ErrorKind::InvalidInput.into()), buf);
+    }
+    madvise(buf, advice).await
+}
+
+/// Give advice about the use of the memory of `buf` like [`advise_memory`],
+/// but [`Advice::DontNeed`] is allowed.
+///
+/// # Safety
+///
+/// With [`Advice::DontNeed`], the kernel discards the whole pages of the
+/// range, including the bytes after the end of `buf` in its last page.
+/// Private pages read back as zeros then, and pages of a shared file mapping
+/// are read again from the file. The caller must own the memory of these
+/// pages, like a mapping created by `mmap`, and no references may be held
+/// to the discarded data.
+pub async unsafe fn advise_memory_unchecked<T: IoBuf>(
+    buf: T,
+    advice: Advice,
+) -> crate::BufResult<(), T> {
+    madvise(buf, advice).await
+}
+
+async fn madvise<T: IoBuf>(buf: T, advice: Advice) -> crate::BufResult<(), T> {
+    #[cfg(target_os = "linux")]
+    {
+        let Some(advice) = advice.as_raw_memory() else {
+            return (Err(io::ErrorKind::InvalidInput.into()), buf);
+        };
+        // The length is a u32 in the sqe.
+        if buf.bytes_init() > u32::MAX as usize {
+            return (Err(io::ErrorKind::InvalidInput.into()), buf);
+        }
+        let op = crate::driver::op::Op::madvise(buf, advice).unwrap();
+        op.result().await
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = advice;
+        (Err(io::ErrorKind::Unsupported.into()), buf)
+    }
+}
@@ -1,5 +1,7 @@
 #[cfg(unix)]
 use std::os::unix::prelude::OpenOptionsExt;
+#[cfg(windows)]
+use std::os::windows::fs::OpenOptionsExt;
 use std::{io, path::Path};
 
 #[cfg(windows)]
@@ -9,10 +11,12 @@ use windows_sys::Win32::{
     Storage::FileSystem::{
         CREATE_ALWAYS, CREATE_NEW, FILE_FLAG_OPEN_REPARSE_POINT, FILE_GENERIC_WRITE,
         FILE_SHARE_DELETE, FILE_SHARE_READ, FILE_SHARE_WRITE, FILE_WRITE_DATA, OPEN_ALWAYS,
-        OPEN_EXISTING, TRUNCATE_EXISTING,
+        OPEN_EXISTING, SECURITY_SQOS_PRESENT, TRUNCATE_EXISTING,
     },
 };
 
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+use crate::utils::PersonalityId;
 use crate::{
     driver::{op::Op, shared_fd::SharedFd},
     fs::File,
@@ -74,6 +78,12 @@ pub struct OpenOptions {
     pub(crate) mode: libc::mode_t,
     #[cfg(unix)]
     pub(crate) custom_flags: libc::c_int,
+    #[cfg(target_os = "linux")]
+    pub(crate) resolve_flags: u64,
+    #[cfg(target_os = "linux")]
+    pub(crate) direct: bool,
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    pub(crate) personality: Option<PersonalityId>,
     #[cfg(windows)]
     pub(crate) custom_flags: u32,
     #[cfg(windows)]
@@ -118,6 +128,12 @@ impl OpenOptions {
             mode: 0o666,
             #[cfg(unix)]
             custom_flags: 0,
+            #[cfg(target_os = "linux")]
+            resolve_flags: 0,
+            #[cfg(target_os = "linux")]
+            direct: false,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            personality: None,
             #[cfg(windows)]
             custom_flags: 0,
             #[cfg(windows)]
@@ -351,9 +367,148 @@ impl OpenOptions {
         let completion = op.await;
 
         // The file is open
-        Ok(File::from_shared_fd(SharedFd::new_without_register(
-            completion.meta.result? as _,
-        )))
+        let file =
+            File::from_shared_fd(SharedFd::new_without_register(completion.meta.result? as _));
+        self.opened(file).await
+    }
+
+    /// Opens a file at `path` relative to the directory `dir` with the options
+    /// specified by `self`.
+    ///
+    /// `dir` must be a [`File`] opened on a directory. If `path` is absolute,
+    /// `dir` is ignored unless it's restricted by [`resolve_flags`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use monoio::fs::{File, OpenOptions};
+    ///
+    /// #[monoio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let dir = File::open("/srv/www").await?;
+    ///     let file = OpenOptions::new()
+    ///         .read(true)
+    ///         .open_at(&dir, "index.html")
+    ///         .await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// [`resolve_flags`]: OpenOptions::resolve_flags
+    #[cfg(unix)]
+    pub async fn open_at(&self, dir: &File, path: impl AsRef<Path>) -> io::Result<File> {
+        let op = Op::open_at(Some(dir.shared_fd()), path.as_ref(), self)?;
+
+        // Await the completion of the event
+        let completion = op.await;
+
+        // The file is open
+        let file =
+            File::from_shared_fd(SharedFd::new_without_register(completion.meta.result? as _));
+        self.opened(file).await
+    }
+
+    /// Query the alignment of the direct IO of the opened file.
+    async fn opened(&self, file: File) -> io::Result<File> {
+        #[cfg(target_os = "linux")]
+        if self.direct {
+            return file.with_dio_align().await;
+        }
+        Ok(file)
+    }
+
+    /// Sets the `RESOLVE_*` flags of openat2(2), which restrict how the path
+    /// is resolved, e.g. `libc::RESOLVE_BENEATH` rejects paths escaping the
+    /// directory passed to [`open_at`].
+    ///
+    /// When the flags are not zero the file is opened with openat2. If the
+    /// kernel or the driver does not support it, the open fails with
+    /// [`io::ErrorKind::Unsupported`] instead of ignoring the restriction.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use monoio::fs::{File, OpenOptions};
+    ///
+    /// #[monoio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let dir = File::open("/srv/www").await?;
+    ///     let file = OpenOptions::new()
+    ///         .read(true)
+    ///         .resolve_flags(libc::RESOLVE_BENEATH | libc::RESOLVE_NO_SYMLINKS)
+    ///         .open_at(&dir, "index.html")
+    ///         .await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// [`open_at`]: OpenOptions::open_at
+    #[cfg(target_os = "linux")]
+    pub fn resolve_flags(&mut self, flags: u64) -> &mut OpenOptions {
+        self.resolve_flags = flags;
+        self
+    }
+
+    /// Sets the option to open the file with `O_DIRECT`, so the reads and
+    /// writes bypass the page cache.
+    ///
+    /// The buffers, the offsets and the lengths of the direct IO must be
+    /// aligned, see [`AlignedBuf`]. The alignment is queried from the file
+    /// system on open, [`File::read_at`] and [`File::write_at`] return an
+    /// [`io::ErrorKind::InvalidInput`] error instead of submitting a
+    /// misaligned IO if it is known.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use monoio::{buf::AlignedBuf, fs::OpenOptions};
+    ///
+    /// #[monoio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let file = OpenOptions::new().read(true).direct(true).open("foo.db").await?;
+    ///     let (res, _buf) = file.read_at(AlignedBuf::new(4096, 4096), 0).await;
+    ///     res?;
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// [`AlignedBuf`]: crate::buf::AlignedBuf
+    #[cfg(target_os = "linux")]
+    pub fn direct(&mut self, direct: bool) -> &mut OpenOptions {
+        self.direct = direct;
+        self
+    }
+
+    /// Opens the file with the credentials of the personality instead of the
+    /// ones of the thread, see [`register_personality`].
+    ///
+    /// The open fails with [`io::ErrorKind::InvalidInput`] if the personality
+    /// is not registered to the current runtime, and with
+    /// [`io::ErrorKind::Unsupported`] if the open is not issued by io_uring.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use monoio::fs::OpenOptions;
+    ///
+    /// #[monoio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     // Registered before dropping privileges.
+    ///     let root = monoio::utils::register_personality()?;
+    ///     let file = OpenOptions::new()
+    ///         .read(true)
+    ///         .personality(root)
+    ///         .open("/etc/ssl/private/key.pem")
+    ///         .await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// [`register_personality`]: crate::utils::register_personality
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    pub fn personality(&mut self, personality: PersonalityId) -> &mut OpenOptions {
+        self.personality = Some(personality);
+        self
     }
 
     #[cfg(unix)]
@@ -458,3 +613,32 @@ impl OpenOptionsExt for OpenOptions {
         self
     }
 }
+
+#[cfg(windows)]
+impl OpenOptionsExt for OpenOptions {
+    fn access_mode(&mut self, access: u32) -> &mut Self {
+        self.access_mode = Some(access);
+        self
+    }
+
+    fn share_mode(&mut self, share: u32) -> &mut Self {
+        self.share_mode = share;
+        self
+    }
+
+    fn custom_flags(&mut self, flags: u32) -> &mut Self {
+        self.custom_flags = flags;
+        self
+    }
+
+    fn attributes(&mut self, attributes: u32) -> &mut Self {
+        self.attributes = attributes;
+        self
+    }
+
+    fn security_qos_flags(&mut self, flags: u32) -> &mut Self {
+        // The flags are only taken with SECURITY_SQOS_PRESENT, like std.
+        self.security_qos_flags = flags | SECURITY_SQOS_PRESENT;
+        self
+    }
+}
@@ -0,0 +1,162 @@
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hasher},
+    io,
+    os::unix::fs::OpenOptionsExt,
+    path::{Path, PathBuf},
+};
+
+use super::{File, OpenOptions};
+
+// The random names tried before giving up, like the tempfile crate.
+pub(crate) const NUM_RETRIES: u32 = 1 << 31;
+
+/// Creates an unnamed temporary file in the directory `dir`, which is opened
+/// for reading and writing.
+///
+/// The file is created with `O_TMPFILE`, so it has no name and it is removed
+/// when it is closed, unless it is linked into the directory with
+/// [`File::persist`]. Writing it, syncing it with [`File::sync_all`] and then
+/// persisting it makes sure that either the whole contents or nothing is
+/// found at the final path, even if the process crashes.
+///
+/// If the kernel or the file system does not support `O_TMPFILE`, the file is
+/// created with a random name beginning with `.tmp` and `O_EXCL` instead, and
+/// it is removed when the `File` is dropped or closed unless persisted.
+///
+/// # Examples
+///
+/// ```no_run
+/// #[monoio::main]
+/// async fn main() -> std::io::Result<()> {
+///     let mut file = monoio::fs::tempfile_in("/some/dir").await?;
+///     let (res, _) = file.write_all_at(&b"hello"[..], 0).await;
+///     res?;
+///     file.sync_all().await?;
+///     file.persist("/some/dir/hello.txt").await?;
+///     Ok(())
+/// }
+/// ```
+pub async fn tempfile_in<P: AsRef<Path>>(dir: P) -> io::Result<File> {
+    let dir = dir.as_ref();
+    let res = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .mode(0o600)
+        .custom_flags(libc::O_TMPFILE)
+        .open(dir)
+        .await;
+    match res {
+        // The kernels before O_TMPFILE take it as O_DIRECTORY and fail with
+        // EISDIR.
+        Err(e)
+            if matches!(
+                e.raw_os_error(),
+                Some(libc::EOPNOTSUPP | libc::EISDIR | libc::EINVAL)
+            ) =>
+        {
+            tempfile_named(dir).await
+        }
+        res => res,
+    }
+}
+
+/// Creates a temporary file with a random name in `dir`.
+pub(crate) async fn tempfile_named(dir: &Path) -> io::Result<File> {
+    for _ in 0..NUM_RETRIES {
+        let path = dir.join(temp_name(".tmp"));
+        let res = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create_new(true)
+            .mode(0o600)
+            .open(&path)
+            .await;
+        match res {
+            Ok(file) => return Ok(file.with_temp_path(TempPath(Some(path)))),
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(e),
+        }
+    }
+    Err(too_many_files())
+}
+
+/// A random file name, the prefix followed by 16 hex digits.
+pub(crate) fn temp_name(prefix: &str) -> String {
+    // Each RandomState is seeded with new random keys.
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u32(std::process::id());
+    format!("{prefix}{:016x}", hasher.finish())
+}
+
+pub(crate) fn too_many_files() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::AlreadyExists,
+        "too many temporary files exist",
+    )
+}
+
+/// The path of a named temporary file, which is removed on drop unless it is
+/// kept.
+#[derive(Debug)]
+pub(crate) struct TempPath(Option<PathBuf>);
+
+impl TempPath {
+    #[inline]
+    pub(crate) fn path(&self) -> &Path {
+        self.0.as_deref().expect("temporary path is kept")
+    }
+
+    /// Forget the path, so it is not removed.
+    #[inline]
+    pub(crate) fn keep(mut self) {
+        self.0 = None;
+    }
+}
+
+impl Drop for TempPath {
+    fn drop(&mut self) {
+        // It is a blocking unlink, as a drop can not wait for an op.
+        if let Some(path) = self.0.take() {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fs::File;
+
+    async fn read_to_end(file: &File) -> Vec<u8> {
+        let (res, buf) = file.read_at(Vec::with_capacity(64), 0).await;
+        res.unwrap();
+        buf
+    }
+
+    #[test]
+    fn named_fallback() {
+        let dir = tempfile::tempdir().unwrap();
+        let entries = || std::fs::read_dir(dir.path()).unwrap().count();
+        let mut rt = crate::RuntimeBuilder::<crate::FusionDriver>::new()
+            .build()
+            .unwrap();
+        rt.block_on(async {
+            let mut file = super::tempfile_named(dir.path()).await.unwrap();
+            assert_eq!(entries(), 1);
+            file.write_all_at(&b"hello"[..], 0).await.0.unwrap();
+            let path = dir.path().join("hello");
+            std::fs::write(&path, b"old").unwrap();
+            file.persist(&path).await.unwrap();
+            assert_eq!(std::fs::read(&path).unwrap(), b"hello");
+            assert_eq!(read_to_end(&file).await, b"hello");
+            drop(file);
+            assert_eq!(entries(), 1);
+
+            // Removed if not persisted.
+            let file = super::tempfile_named(dir.path()).await.unwrap();
+            assert_eq!(entries(), 2);
+            drop(file);
+            assert_eq!(entries(), 1);
+        });
+    }
+}
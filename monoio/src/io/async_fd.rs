@@ -0,0 +1,102 @@
+//! Readiness of arbitrary file descriptors.
+
+use std::{
+    io,
+    os::unix::io::{AsRawFd, RawFd},
+};
+
+use crate::driver::{op::Op, shared_fd::SharedFd};
+
+/// Readiness to wait for in [`AsyncFd::try_io`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Interest {
+    /// The fd is readable.
+    Readable,
+    /// The fd is writable.
+    Writable,
+}
+
+/// Wrap a file descriptor which monoio does not know about(e.g. netlink
+/// sockets, fuse devices or fence fds) and wait for its readiness.
+///
+/// On uring driver the readiness is waited with a `PollAdd` op; on legacy
+/// driver the fd is registered to the poller. The io itself is done by the
+/// user, so the fd should be in non-blocking mode.
+///
+/// The fd is duplicated when it is registered, so the inner value keeps the
+/// ownership of the original fd. Dropping the `AsyncFd` deregisters it and
+/// cancels the pending polls.
+#[derive(Debug)]
+pub struct AsyncFd<T: AsRawFd> {
+    fd: SharedFd,
+    inner: T,
+}
+
+impl<T: AsRawFd> AsyncFd<T> {
+    /// Register the fd of `inner` to the current driver.
+    pub fn new(inner: T) -> io::Result<Self> {
+        let fd = crate::syscall!(fcntl(inner.as_raw_fd(), libc::F_DUPFD_CLOEXEC, 0))?;
+        let fd = match SharedFd::new::<false>(fd) {
+            Ok(fd) => fd,
+            Err(e) => {
+                unsafe { libc::close(fd) };
+                return Err(e);
+            }
+        };
+        Ok(Self { fd, inner })
+    }
+
+    /// Get a reference to the inner value.
+    #[inline]
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the inner value.
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Deregister the fd and return the inner value.
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+
+    /// Wait until the fd is readable.
+    pub async fn readable(&self) -> io::Result<()> {
+        Op::poll_read(&self.fd, false)?.wait().await
+    }
+
+    /// Wait until the fd is writable.
+    pub async fn writable(&self) -> io::Result<()> {
+        Op::poll_write(&self.fd, false)?.wait().await
+    }
+
+    /// Do io on the inner value with `f` until it does not return
+    /// `WouldBlock`, waiting for the given readiness between the tries.
+    pub async fn try_io<R>(
+        &self,
+        interest: Interest,
+        mut f: impl FnMut(&T) -> io::Result<R>,
+    ) -> io::Result<R> {
+        loop {
+            match f(&self.inner) {
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => {}
+                res => return res,
+            }
+            match interest {
+                Interest::Readable => self.readable().await?,
+                Interest::Writable => self.writable().await?,
+            }
+        }
+    }
+}
+
+impl<T: AsRawFd> AsRawFd for AsyncFd<T> {
+    #[inline]
+    fn as_raw_fd(&self) -> RawFd {
+        self.inner.as_raw_fd()
+    }
+}
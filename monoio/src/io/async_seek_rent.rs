@@ -0,0 +1,18 @@
+use std::{future::Future, io::SeekFrom};
+
+/// AsyncSeekRent: async seek of the cursor of a stream, like
+/// `std::io::Seek`
+pub trait AsyncSeekRent {
+    /// Seek to an offset in bytes, returns the new position from the start.
+    ///
+    /// Seeking before the start is an error of the kind `InvalidInput`,
+    /// seeking beyond the end is allowed.
+    fn seek(&mut self, pos: SeekFrom) -> impl Future<Output = std::io::Result<u64>>;
+}
+
+impl<A: ?Sized + AsyncSeekRent> AsyncSeekRent for &mut A {
+    #[inline]
+    fn seek(&mut self, pos: SeekFrom) -> impl Future<Output = std::io::Result<u64>> {
+        (**self).seek(pos)
+    }
+}
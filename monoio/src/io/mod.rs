@@ -6,6 +6,7 @@ mod async_read_rent;
 mod async_read_rent_ext;
 mod async_rent_cancelable;
 mod async_rent_cancelable_ext;
+mod async_seek_rent;
 mod async_write_rent;
 mod async_write_rent_ext;
 
@@ -13,25 +14,41 @@ pub mod sink;
 pub mod stream;
 
 pub mod as_fd;
+#[cfg(unix)]
+mod async_fd;
 #[cfg(all(target_os = "linux", feature = "splice"))]
 pub mod splice;
+#[cfg(unix)]
+mod stdio;
 
 pub use async_buf_read::AsyncBufRead;
 pub use async_buf_read_ext::AsyncBufReadExt;
+#[cfg(unix)]
+pub use async_fd::{AsyncFd, Interest};
 pub use async_read_rent::{AsyncReadRent, AsyncReadRentAt};
 pub use async_read_rent_ext::AsyncReadRentExt;
 pub use async_rent_cancelable::{CancelableAsyncReadRent, CancelableAsyncWriteRent};
 pub use async_rent_cancelable_ext::{CancelableAsyncReadRentExt, CancelableAsyncWriteRentExt};
+pub use async_seek_rent::AsyncSeekRent;
 pub use async_write_rent::{AsyncWriteRent, AsyncWriteRentAt};
 pub use async_write_rent_ext::AsyncWriteRentExt;
+#[cfg(unix)]
+pub use stdio::{stderr, stdin, stdout, Stderr, Stdin, Stdout};
+#[cfg(all(unix, feature = "sync"))]
+pub use stdio::{StderrLock, StdoutLock};
 
 mod util;
 
+#[cfg(all(target_os = "linux", feature = "splice"))]
+pub use splice::splice;
 #[cfg(feature = "poll-io")]
 pub use tokio::io as poll_io;
 pub(crate) use util::operation_canceled;
 #[cfg(all(target_os = "linux", feature = "splice"))]
 pub use util::zero_copy;
+
+#[cfg(all(target_os = "linux", feature = "splice"))]
+pub use crate::net::{unix::new_pipe, Pipe};
 pub use util::{
     copy, BufReader, BufWriter, CancelHandle, Canceller, OwnedReadHalf, OwnedWriteHalf,
     PrefixedReadIo, Split, Splitable,
@@ -3,7 +3,10 @@
 use std::future::Future;
 
 use super::as_fd::{AsReadFd, AsWriteFd};
-use crate::{driver::op::Op, net::Pipe};
+use crate::{
+    driver::op::Op,
+    net::{unix::new_pipe, Pipe},
+};
 
 /// Splice data from self to pipe.
 pub trait SpliceSource {
@@ -50,3 +53,27 @@ impl<T: AsWriteFd> SpliceDestination for T {
             .await
     }
 }
+
+/// Move up to `len` bytes from `src` to `dst` through a pipe, the data is not
+/// copied to userspace.
+///
+/// Return the number of bytes moved, 0 means `src` reaches EOF. It may be less
+/// than `len`, but all bytes taken from `src` are written to `dst` before it
+/// returns, so callers can loop until the expected length or EOF.
+pub async fn splice<SRC: AsReadFd, DST: AsWriteFd>(
+    src: &mut SRC,
+    dst: &mut DST,
+    len: u32,
+) -> std::io::Result<u32> {
+    let (mut pr, mut pw) = new_pipe()?;
+    let moved = src.splice_to_pipe(&mut pw, len).await?;
+    let mut to_write = moved;
+    while to_write > 0 {
+        let written = dst.splice_from_pipe(&mut pr, to_write).await?;
+        if written == 0 {
+            return Err(std::io::ErrorKind::WriteZero.into());
+        }
+        to_write -= written;
+    }
+    Ok(moved)
+}
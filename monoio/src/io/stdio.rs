@@ -0,0 +1,383 @@
+//! Async handles of the standard input, output and error of the process.
+
+use std::{
+    ffi::CString,
+    io,
+    os::unix::prelude::{AsRawFd, RawFd},
+};
+
+#[cfg(feature = "sync")]
+use crate::sync::{Mutex, MutexGuard};
+use crate::{
+    buf::{IoBuf, IoBufMut, IoVecBuf, IoVecBufMut, IoVecWrapper, IoVecWrapperMut},
+    driver::{op::Op, shared_fd::SharedFd},
+    io::{AsyncReadRent, AsyncWriteRent},
+    BufResult,
+};
+
+#[cfg(feature = "sync")]
+static STDOUT_LOCK: Mutex<()> = Mutex::new(());
+#[cfg(feature = "sync")]
+static STDERR_LOCK: Mutex<()> = Mutex::new(());
+
+/// The fd of a stdio handle.
+#[derive(Debug)]
+struct Stdio {
+    fd: SharedFd,
+    // The fd can not be waited for readiness, so it is read and written on
+    // the blocking thread pool.
+    blocking: bool,
+}
+
+/// Opens a handle of the stdio fd, which must be done in the runtime.
+///
+/// The fd is shared with the process and maybe other processes, so the
+/// handle owns its own fd and never changes the flags of the original one.
+fn open(fd: RawFd, flags: libc::c_int) -> io::Result<Stdio> {
+    // Uring reads and writes the blocking fd asynchronously.
+    let legacy = crate::driver::op::is_legacy();
+    if legacy {
+        if let Some(fd) = reopen(fd, flags) {
+            return Ok(Stdio {
+                fd,
+                blocking: false,
+            });
+        }
+    }
+    let fd = crate::syscall!(fcntl(fd, libc::F_DUPFD_CLOEXEC, 0))?;
+    Ok(Stdio {
+        fd: SharedFd::new_without_register(fd),
+        blocking: legacy,
+    })
+}
+
+/// Legacy driver needs a non-blocking fd to wait for its readiness, but
+/// setting `O_NONBLOCK` on a duplicated fd changes the open file description
+/// shared with others, like the shell owning the terminal. The fd is reopened
+/// to get a private description instead, and registered to the driver.
+fn reopen(fd: RawFd, flags: libc::c_int) -> Option<SharedFd> {
+    let path = reopen_path(fd)?;
+    let fd = crate::syscall!(open(
+        path.as_ptr(),
+        flags | libc::O_NONBLOCK | libc::O_CLOEXEC | libc::O_NOCTTY
+    ))
+    .ok()?;
+    match SharedFd::new::<false>(fd) {
+        Ok(fd) => Some(fd),
+        // Some char devices like `/dev/null` can not be polled.
+        Err(_) => {
+            let _ = crate::syscall!(close(fd));
+            None
+        }
+    }
+}
+
+/// Pipes, fifos and ttys are reopened from procfs.
+#[cfg(target_os = "linux")]
+fn reopen_path(fd: RawFd) -> Option<CString> {
+    let mut stat: libc::stat = unsafe { std::mem::zeroed() };
+    crate::syscall!(fstat(fd, &mut stat)).ok()?;
+    if !matches!(stat.st_mode & libc::S_IFMT, libc::S_IFIFO | libc::S_IFCHR) {
+        return None;
+    }
+    Some(CString::new(format!("/proc/self/fd/{fd}")).unwrap())
+}
+
+/// Ttys are reopened from their device. Opening `/dev/fd` duplicates the fd
+/// on these systems, so pipes can not get a private description.
+#[cfg(not(target_os = "linux"))]
+fn reopen_path(fd: RawFd) -> Option<CString> {
+    if unsafe { libc::isatty(fd) } != 1 {
+        return None;
+    }
+    let mut buf = [0 as libc::c_char; 256];
+    if unsafe { libc::ttyname_r(fd, buf.as_mut_ptr(), buf.len()) } != 0 {
+        return None;
+    }
+    Some(unsafe { std::ffi::CStr::from_ptr(buf.as_ptr()) }.to_owned())
+}
+
+async fn read<T: IoBufMut>(stdio: &Stdio, mut buf: T) -> BufResult<usize, T> {
+    if !stdio.blocking {
+        let op = Op::read_stream(&stdio.fd, buf).unwrap();
+        return op.read().await;
+    }
+
+    // The buffer may not be sent to the pool, the data is copied instead.
+    let len = buf.bytes_total();
+    let res = crate::fs::asyncify_fd(&stdio.fd, move |fd| {
+        let mut data = Vec::with_capacity(len);
+        let n = crate::syscall!(read(fd, data.as_mut_ptr() as _, len))?;
+        unsafe { data.set_len(n as usize) };
+        Ok(data)
+    })
+    .await;
+    match res {
+        Ok(data) => {
+            unsafe {
+                std::ptr::copy_nonoverlapping(data.as_ptr(), buf.write_ptr(), data.len());
+                buf.set_init(data.len());
+            }
+            (Ok(data.len()), buf)
+        }
+        Err(e) => (Err(e), buf),
+    }
+}
+
+// Only the first buffer is filled, like a short read.
+async fn readv<T: IoVecBufMut>(stdio: &Stdio, mut buf: T) -> BufResult<usize, T> {
+    let slice = match IoVecWrapperMut::new(buf) {
+        Ok(slice) => slice,
+        Err(buf) => return (Ok(0), buf),
+    };
+
+    let (res, slice) = read(stdio, slice).await;
+    buf = slice.into_inner();
+    if let Ok(n) = res {
+        unsafe { buf.set_init(n) };
+    }
+    (res, buf)
+}
+
+async fn write<T: IoBuf>(stdio: &Stdio, buf: T) -> BufResult<usize, T> {
+    if !stdio.blocking {
+        let op = Op::write_stream(&stdio.fd, buf).unwrap();
+        return op.write().await;
+    }
+
+    // The buffer may not be sent to the pool, the data is copied instead.
+    let data = unsafe { std::slice::from_raw_parts(buf.read_ptr(), buf.bytes_init()) }.to_vec();
+    let res = crate::fs::asyncify_fd(&stdio.fd, move |fd| {
+        crate::syscall!(write(fd, data.as_ptr() as _, data.len())).map(|n| n as usize)
+    })
+    .await;
+    (res, buf)
+}
+
+// Only the first buffer is written, like a short write.
+async fn writev<T: IoVecBuf>(stdio: &Stdio, buf_vec: T) -> BufResult<usize, T> {
+    let slice = match IoVecWrapper::new(buf_vec) {
+        Ok(slice) => slice,
+        Err(buf_vec) => return (Ok(0), buf_vec),
+    };
+
+    let (res, slice) = write(stdio, slice).await;
+    (res, slice.into_inner())
+}
+
+/// A handle to the standard input of the process, created by [`stdin`].
+///
+/// On legacy driver, pipes and ttys get a private non-blocking fd which is
+/// waited for readiness, while other fds like regular files are read on the
+/// blocking thread pool, see [`spawn_blocking`](crate::spawn_blocking).
+/// Reading from a file moves the position of the file shared with the
+/// process.
+#[derive(Debug)]
+pub struct Stdin {
+    inner: Stdio,
+}
+
+/// Constructs a new handle to the standard input of the process.
+///
+/// It must be called in the runtime, each handle owns its own fd, see
+/// [`Stdin`].
+///
+/// # Examples
+///
+/// ```no_run
+/// use monoio::io::AsyncReadRent;
+///
+/// #[monoio::main]
+/// async fn main() -> std::io::Result<()> {
+///     let mut stdin = monoio::io::stdin()?;
+///     let (res, buf) = stdin.read(Vec::with_capacity(1024)).await;
+///     println!("read {} bytes: {:?}", res?, buf);
+///     Ok(())
+/// }
+/// ```
+pub fn stdin() -> io::Result<Stdin> {
+    Ok(Stdin {
+        inner: open(libc::STDIN_FILENO, libc::O_RDONLY)?,
+    })
+}
+
+impl AsyncReadRent for Stdin {
+    #[inline]
+    fn read<T: IoBufMut>(
+        &mut self,
+        buf: T,
+    ) -> impl std::future::Future<Output = BufResult<usize, T>> {
+        read(&self.inner, buf)
+    }
+
+    #[inline]
+    fn readv<T: IoVecBufMut>(
+        &mut self,
+        buf: T,
+    ) -> impl std::future::Future<Output = BufResult<usize, T>> {
+        readv(&self.inner, buf)
+    }
+}
+
+impl AsRawFd for Stdin {
+    #[inline]
+    fn as_raw_fd(&self) -> RawFd {
+        self.inner.fd.raw_fd()
+    }
+}
+
+macro_rules! output {
+    ($(#[$doc:meta])* $name:ident, $lock_name:ident, $lock:ident, $fn_name:ident, $fd:ident, $what:literal) => {
+        $(#[$doc])*
+        ///
+        /// On legacy driver, pipes and ttys get a private non-blocking fd
+        /// which is waited for readiness, while other fds like regular files
+        /// are written on the blocking thread pool, see
+        /// [`spawn_blocking`](crate::spawn_blocking).
+        ///
+        /// The writes are not buffered, so `flush` does nothing. Wrap it with
+        /// [`BufWriter`] to buffer the writes, and flush before exit.
+        ///
+        /// [`BufWriter`]: crate::io::BufWriter
+        #[derive(Debug)]
+        pub struct $name {
+            inner: Stdio,
+        }
+
+        impl $name {
+            /// Locks the handle with a lock of the process, so the writes
+            /// of the guard are not interleaved with the ones of other
+            /// guards, even on other threads.
+            ///
+            /// The lock is not the one of std, so it does not serialize
+            /// with `println!` and friends.
+            #[cfg(feature = "sync")]
+            pub async fn lock(&mut self) -> $lock_name<'_> {
+                $lock_name {
+                    inner: self,
+                    _guard: $lock.lock().await,
+                }
+            }
+        }
+
+        impl AsyncWriteRent for $name {
+            #[inline]
+            fn write<T: IoBuf>(
+                &mut self,
+                buf: T,
+            ) -> impl std::future::Future<Output = BufResult<usize, T>> {
+                write(&self.inner, buf)
+            }
+
+            #[inline]
+            fn writev<T: IoVecBuf>(
+                &mut self,
+                buf_vec: T,
+            ) -> impl std::future::Future<Output = BufResult<usize, T>> {
+                writev(&self.inner, buf_vec)
+            }
+
+            #[inline]
+            async fn flush(&mut self) -> io::Result<()> {
+                // The data is not buffered in user space.
+                Ok(())
+            }
+
+            #[inline]
+            async fn shutdown(&mut self) -> io::Result<()> {
+                // The fd is shared with the process, it is not shut down.
+                Ok(())
+            }
+        }
+
+        impl AsRawFd for $name {
+            #[inline]
+            fn as_raw_fd(&self) -> RawFd {
+                self.inner.fd.raw_fd()
+            }
+        }
+
+        #[doc = concat!("A locked [`", stringify!($name), "`], created by [`", stringify!($name), "::lock`].")]
+        ///
+        /// The lock is released when it is dropped.
+        #[cfg(feature = "sync")]
+        #[derive(Debug)]
+        pub struct $lock_name<'a> {
+            inner: &'a mut $name,
+            _guard: MutexGuard<'static, ()>,
+        }
+
+        #[cfg(feature = "sync")]
+        impl AsyncWriteRent for $lock_name<'_> {
+            #[inline]
+            fn write<T: IoBuf>(
+                &mut self,
+                buf: T,
+            ) -> impl std::future::Future<Output = BufResult<usize, T>> {
+                self.inner.write(buf)
+            }
+
+            #[inline]
+            fn writev<T: IoVecBuf>(
+                &mut self,
+                buf_vec: T,
+            ) -> impl std::future::Future<Output = BufResult<usize, T>> {
+                self.inner.writev(buf_vec)
+            }
+
+            #[inline]
+            fn flush(&mut self) -> impl std::future::Future<Output = io::Result<()>> {
+                self.inner.flush()
+            }
+
+            #[inline]
+            fn shutdown(&mut self) -> impl std::future::Future<Output = io::Result<()>> {
+                self.inner.shutdown()
+            }
+        }
+
+        #[doc = concat!("Constructs a new handle to the standard ", $what, " of the process.")]
+        ///
+        /// It must be called in the runtime, each handle owns its own fd, see
+        #[doc = concat!("[`", stringify!($name), "`].")]
+        pub fn $fn_name() -> io::Result<$name> {
+            Ok($name {
+                inner: open(libc::$fd, libc::O_WRONLY)?,
+            })
+        }
+    };
+}
+
+output!(
+    /// A handle to the standard output of the process, created by [`stdout`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use monoio::io::AsyncWriteRentExt;
+    ///
+    /// #[monoio::main]
+    /// async fn main() -> std::io::Result<()> {
+    ///     let mut stdout = monoio::io::stdout()?;
+    ///     let (res, _) = stdout.write_all(b"hello world\n").await;
+    ///     res?;
+    ///     Ok(())
+    /// }
+    /// ```
+    Stdout,
+    StdoutLock,
+    STDOUT_LOCK,
+    stdout,
+    STDOUT_FILENO,
+    "output"
+);
+
+output!(
+    /// A handle to the standard error of the process, created by [`stderr`].
+    Stderr,
+    StderrLock,
+    STDERR_LOCK,
+    stderr,
+    STDERR_FILENO,
+    "error"
+);
@@ -1,8 +1,11 @@
-use std::future::Future;
+use std::{
+    future::Future,
+    io::{self, SeekFrom},
+};
 
 use crate::{
     buf::{IoBuf, IoBufMut, IoVecBuf, IoVecBufMut, IoVecWrapperMut},
-    io::{AsyncBufRead, AsyncReadRent, AsyncWriteRent},
+    io::{AsyncBufRead, AsyncReadRent, AsyncSeekRent, AsyncWriteRent},
     BufResult,
 };
 
@@ -153,6 +156,51 @@ impl<R: AsyncReadRent> AsyncBufRead for BufReader<R> {
     }
 }
 
+impl<R: AsyncSeekRent> BufReader<R> {
+    /// Seeks relative to the current position. If the new position lies
+    /// within the buffer, the buffer is not discarded and the inner reader is
+    /// not seeked, like `std::io::BufReader::seek_relative`.
+    pub async fn seek_relative(&mut self, offset: i64) -> io::Result<()> {
+        let pos = self.pos as u64;
+        if offset < 0 {
+            if let Some(new_pos) = pos.checked_sub(offset.unsigned_abs()) {
+                self.pos = new_pos as usize;
+                return Ok(());
+            }
+        } else if let Some(new_pos) = pos.checked_add(offset as u64) {
+            if new_pos <= self.cap as u64 {
+                self.pos = new_pos as usize;
+                return Ok(());
+            }
+        }
+        self.seek(SeekFrom::Current(offset)).await.map(drop)
+    }
+}
+
+/// Seeking discards the buffer, even if the new position lies within it, see
+/// [`BufReader::seek_relative`] to keep it. `SeekFrom::Current` is relative to
+/// the position of the data not read yet from the buffer, not the one of the
+/// inner reader.
+impl<R: AsyncSeekRent> AsyncSeekRent for BufReader<R> {
+    async fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let result = if let SeekFrom::Current(offset) = pos {
+            let remainder = (self.cap - self.pos) as i64;
+            if let Some(offset) = offset.checked_sub(remainder) {
+                self.inner.seek(SeekFrom::Current(offset)).await?
+            } else {
+                // Seek back to the buffered position first to not overflow.
+                self.inner.seek(SeekFrom::Current(-remainder)).await?;
+                self.discard_buffer();
+                self.inner.seek(SeekFrom::Current(offset)).await?
+            }
+        } else {
+            self.inner.seek(pos).await?
+        };
+        self.discard_buffer();
+        Ok(result)
+    }
+}
+
 impl<R: AsyncReadRent + AsyncWriteRent> AsyncWriteRent for BufReader<R> {
     #[inline]
     fn write<T: IoBuf>(&mut self, buf: T) -> impl Future<Output = BufResult<usize, T>> {
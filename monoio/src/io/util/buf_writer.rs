@@ -1,8 +1,11 @@
-use std::{future::Future, io};
+use std::{
+    future::Future,
+    io::{self, SeekFrom},
+};
 
 use crate::{
     buf::{IoBuf, IoBufMut, IoVecBuf, IoVecBufMut, IoVecWrapper, Slice},
-    io::{AsyncBufRead, AsyncReadRent, AsyncWriteRent, AsyncWriteRentExt},
+    io::{AsyncBufRead, AsyncReadRent, AsyncSeekRent, AsyncWriteRent, AsyncWriteRentExt},
     BufResult,
 };
 
@@ -151,6 +154,14 @@ impl<W: AsyncWriteRent> AsyncWriteRent for BufWriter<W> {
     }
 }
 
+/// Seeking writes the buffered data before seeking the inner writer.
+impl<W: AsyncWriteRent + AsyncSeekRent> AsyncSeekRent for BufWriter<W> {
+    async fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        self.flush_buf().await?;
+        self.inner.seek(pos).await
+    }
+}
+
 impl<W: AsyncWriteRent + AsyncReadRent> AsyncReadRent for BufWriter<W> {
     #[inline]
     fn read<T: IoBufMut>(&mut self, buf: T) -> impl Future<Output = BufResult<usize, T>> {
@@ -28,6 +28,8 @@ pub mod buf;
 pub mod fs;
 pub mod io;
 pub mod net;
+#[cfg(feature = "sync")]
+pub mod sync;
 pub mod task;
 pub mod utils;
 
@@ -35,12 +37,14 @@ use std::future::Future;
 
 #[cfg(feature = "sync")]
 pub use blocking::spawn_blocking;
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+pub use builder::RuntimeGroup;
 pub use builder::{Buildable, RuntimeBuilder};
 pub use driver::Driver;
-#[cfg(all(target_os = "linux", feature = "iouring"))]
-pub use driver::IoUringDriver;
 #[cfg(all(unix, feature = "legacy"))]
 pub use driver::LegacyDriver;
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+pub use driver::{AllowedOp, IoUringDriver};
 #[cfg(feature = "macros")]
 pub use monoio_macros::{main, test, test_all};
 pub use runtime::{spawn, Runtime};
@@ -0,0 +1,107 @@
+//! Binding sockets to a network interface, `SO_BINDTODEVICE` on Linux and
+//! `IP_BOUND_IF`/`IPV6_BOUND_IF` on macOS.
+//!
+//! Errors are returned as is, e.g. `EPERM` when the binding requires
+//! `CAP_NET_RAW` and the process does not have it.
+
+use std::io;
+
+/// Bind to the interface by name, or remove the binding with None.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub(crate) fn set_bind_device(socket: &socket2::Socket, device: Option<&str>) -> io::Result<()> {
+    socket.bind_device(device.map(str::as_bytes))
+}
+
+/// Bind to the interface by index, or remove the binding with 0.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub(crate) fn bind_device_by_index(socket: &socket2::Socket, index: u32) -> io::Result<()> {
+    use std::os::unix::io::AsRawFd;
+
+    // Missing in libc for linux, the value is from asm-generic/socket.h.
+    const SO_BINDTOIFINDEX: libc::c_int = 62;
+    let index = index as libc::c_int;
+    crate::syscall!(setsockopt(
+        socket.as_raw_fd(),
+        libc::SOL_SOCKET,
+        SO_BINDTOIFINDEX,
+        &index as *const libc::c_int as *const libc::c_void,
+        std::mem::size_of::<libc::c_int>() as libc::socklen_t
+    ))
+    .map(|_| ())
+}
+
+/// Get the name of the bound interface.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub(crate) fn bind_device(socket: &socket2::Socket) -> io::Result<Option<String>> {
+    Ok(socket
+        .device()?
+        .map(|device| String::from_utf8_lossy(&device).into_owned()))
+}
+
+#[cfg(target_vendor = "apple")]
+pub(crate) fn set_bind_device(socket: &socket2::Socket, device: Option<&str>) -> io::Result<()> {
+    let index = match device {
+        Some(device) => {
+            let name = std::ffi::CString::new(device)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+            match unsafe { libc::if_nametoindex(name.as_ptr()) } {
+                0 => return Err(io::Error::last_os_error()),
+                index => index,
+            }
+        }
+        None => 0,
+    };
+    bind_device_by_index(socket, index)
+}
+
+#[cfg(target_vendor = "apple")]
+pub(crate) fn bind_device_by_index(socket: &socket2::Socket, index: u32) -> io::Result<()> {
+    let index = std::num::NonZeroU32::new(index);
+    if socket.local_addr()?.is_ipv6() {
+        socket.bind_device_by_index_v6(index)
+    } else {
+        socket.bind_device_by_index_v4(index)
+    }
+}
+
+#[cfg(target_vendor = "apple")]
+pub(crate) fn bind_device(socket: &socket2::Socket) -> io::Result<Option<String>> {
+    let index = if socket.local_addr()?.is_ipv6() {
+        socket.device_index_v6()?
+    } else {
+        socket.device_index_v4()?
+    };
+    let Some(index) = index else {
+        return Ok(None);
+    };
+    let mut name = [0 as libc::c_char; libc::IF_NAMESIZE];
+    let ptr = unsafe { libc::if_indextoname(index.get(), name.as_mut_ptr()) };
+    if ptr.is_null() {
+        return Err(io::Error::last_os_error());
+    }
+    let name = unsafe { std::ffi::CStr::from_ptr(ptr) };
+    Ok(Some(name.to_string_lossy().into_owned()))
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android", target_vendor = "apple")))]
+pub(crate) fn set_bind_device(_: &socket2::Socket, _: Option<&str>) -> io::Result<()> {
+    Err(unsupported())
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android", target_vendor = "apple")))]
+pub(crate) fn bind_device_by_index(_: &socket2::Socket, _: u32) -> io::Result<()> {
+    Err(unsupported())
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android", target_vendor = "apple")))]
+pub(crate) fn bind_device(_: &socket2::Socket) -> io::Result<Option<String>> {
+    Err(unsupported())
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android", target_vendor = "apple")))]
+fn unsupported() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::Unsupported,
+        "binding to a device is not supported on this platform",
+    )
+}
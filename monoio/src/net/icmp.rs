@@ -0,0 +1,165 @@
+//! ICMP socket impl.
+
+use std::{
+    io,
+    net::{IpAddr, SocketAddr},
+    os::unix::prelude::{AsFd, AsRawFd, BorrowedFd, IntoRawFd, RawFd},
+};
+
+use socket2::SockRef;
+
+use crate::{
+    buf::{IoBuf, IoBufMut},
+    driver::{op::Op, shared_fd::SharedFd},
+};
+
+/// An ICMP socket, which sends and receives ICMP messages, e.g. the echo
+/// requests of ping, or the errors of the probes with a small TTL of
+/// traceroute.
+///
+/// [`new_v4`] creates an ICMP datagram socket, which does not need privilege
+/// where it is supported, and [`new_raw_v4`] creates a raw socket. The
+/// addresses have no port, the peer is identified by the IP address only.
+///
+/// [`new_v4`]: IcmpSocket::new_v4
+/// [`new_raw_v4`]: IcmpSocket::new_raw_v4
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::net::Ipv4Addr;
+///
+/// use monoio::net::IcmpSocket;
+///
+/// #[monoio::main]
+/// async fn main() -> std::io::Result<()> {
+///     let socket = IcmpSocket::new_v4()?;
+///     // An echo request, the kernel fills the identifier and the checksum.
+///     let request = vec![8, 0, 0, 0, 0, 0, 0, 1];
+///     socket.send_to(request, Ipv4Addr::LOCALHOST.into()).await.0?;
+///     let (res, reply) = socket.recv_from(vec![0; 64]).await;
+///     let (n, from) = res?;
+///     println!("{n} bytes from {from}: type {}", reply[0]);
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug)]
+pub struct IcmpSocket {
+    fd: SharedFd,
+    v6: bool,
+}
+
+impl IcmpSocket {
+    /// Create an ICMP datagram socket, `SOCK_DGRAM` with `IPPROTO_ICMP`.
+    ///
+    /// It needs no privilege on macOS, and on Linux if the group of the
+    /// process is in `net.ipv4.ping_group_range`, otherwise it fails with
+    /// `EACCES`. Only echo requests can be sent, the kernel sets the
+    /// identifier to the local port and fills the checksum, and the echo
+    /// replies and the errors of the requests are received without the IP
+    /// header.
+    pub fn new_v4() -> io::Result<Self> {
+        Self::new(libc::AF_INET, libc::SOCK_DGRAM, libc::IPPROTO_ICMP)
+    }
+
+    /// Create an ICMPv6 datagram socket, `SOCK_DGRAM` with `IPPROTO_ICMPV6`,
+    /// see [`new_v4`](IcmpSocket::new_v4).
+    pub fn new_v6() -> io::Result<Self> {
+        Self::new(libc::AF_INET6, libc::SOCK_DGRAM, libc::IPPROTO_ICMPV6)
+    }
+
+    /// Create a raw ICMP socket, `SOCK_RAW` with `IPPROTO_ICMP`. It receives
+    /// all the ICMP messages with the IP header, and the messages sent must
+    /// have the checksum.
+    ///
+    /// It needs `CAP_NET_RAW` or root, otherwise it fails with `EPERM`, i.e.
+    /// `ErrorKind::PermissionDenied`.
+    pub fn new_raw_v4() -> io::Result<Self> {
+        Self::new(libc::AF_INET, libc::SOCK_RAW, libc::IPPROTO_ICMP)
+    }
+
+    /// Create a raw ICMPv6 socket, `SOCK_RAW` with `IPPROTO_ICMPV6`, see
+    /// [`new_raw_v4`](IcmpSocket::new_raw_v4). The messages are received
+    /// without the IP header and the kernel fills the checksum.
+    pub fn new_raw_v6() -> io::Result<Self> {
+        Self::new(libc::AF_INET6, libc::SOCK_RAW, libc::IPPROTO_ICMPV6)
+    }
+
+    fn new(domain: libc::c_int, ty: libc::c_int, protocol: libc::c_int) -> io::Result<Self> {
+        let socket = crate::net::new_socket_with_protocol(domain, ty, protocol)?;
+        Ok(Self {
+            fd: SharedFd::new::<false>(socket)?,
+            v6: domain == libc::AF_INET6,
+        })
+    }
+
+    /// Bind the socket to the local address, which is the source address of
+    /// the messages sent.
+    pub fn bind(&self, addr: IpAddr) -> io::Result<()> {
+        SockRef::from(self).bind(&SocketAddr::new(addr, 0).into())
+    }
+
+    /// Get the local address of the socket.
+    pub fn local_addr(&self) -> io::Result<IpAddr> {
+        SockRef::from(self)
+            .local_addr()?
+            .as_socket()
+            .map(|addr| addr.ip())
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "invalid socket address"))
+    }
+
+    /// Sends the ICMP message to the given address. On success, returns the
+    /// number of bytes written.
+    pub async fn send_to<T: IoBuf>(&self, buf: T, addr: IpAddr) -> crate::BufResult<usize, T> {
+        let op = Op::send_msg(self.fd.clone(), buf, Some(SocketAddr::new(addr, 0))).unwrap();
+        op.wait().await
+    }
+
+    /// Receives an ICMP message. On success, returns the number of bytes
+    /// read and the origin.
+    pub async fn recv_from<T: IoBufMut>(&self, buf: T) -> crate::BufResult<(usize, IpAddr), T> {
+        let op = Op::recv_msg(self.fd.clone(), buf).unwrap();
+        let (res, buf) = op.wait().await;
+        (res.map(|(n, addr)| (n, addr.ip())), buf)
+    }
+
+    /// Set the TTL of the messages sent, `IP_TTL` on IPv4 sockets and
+    /// `IPV6_UNICAST_HOPS` on IPv6 sockets.
+    pub fn set_ttl(&self, ttl: u32) -> io::Result<()> {
+        if self.v6 {
+            SockRef::from(self).set_unicast_hops_v6(ttl)
+        } else {
+            SockRef::from(self).set_ttl(ttl)
+        }
+    }
+
+    /// Get the TTL of the messages sent.
+    pub fn ttl(&self) -> io::Result<u32> {
+        if self.v6 {
+            SockRef::from(self).unicast_hops_v6()
+        } else {
+            SockRef::from(self).ttl()
+        }
+    }
+}
+
+impl AsRawFd for IcmpSocket {
+    fn as_raw_fd(&self) -> RawFd {
+        self.fd.raw_fd()
+    }
+}
+
+impl AsFd for IcmpSocket {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        unsafe { BorrowedFd::borrow_raw(self.fd.raw_fd()) }
+    }
+}
+
+impl IntoRawFd for IcmpSocket {
+    #[inline]
+    fn into_raw_fd(self) -> RawFd {
+        self.fd
+            .try_unwrap()
+            .expect("unexpected multiple reference to rawfd")
+    }
+}
@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 /// Custom listener options
 #[derive(Debug, Clone, Copy)]
 #[non_exhaustive]
@@ -14,6 +16,14 @@ pub struct ListenerOpts {
     pub recv_buf_size: Option<usize>,
     /// TCP fast open.
     pub tcp_fast_open: bool,
+    /// Max length of the queue of pending TCP fast open requests, or None to
+    /// use the backlog.
+    pub tcp_fast_open_queue: Option<u32>,
+    /// Whether an IPv6 listener only accepts IPv6 connections, `IPV6_V6ONLY`.
+    /// It is ignored for IPv4 addresses.
+    pub only_v6: bool,
+    /// Timeout of `TCP_DEFER_ACCEPT` or None to disable it.
+    pub defer_accept: Option<Duration>,
 }
 
 impl Default for ListenerOpts {
@@ -34,6 +44,9 @@ impl ListenerOpts {
             send_buf_size: None,
             recv_buf_size: None,
             tcp_fast_open: false,
+            tcp_fast_open_queue: None,
+            only_v6: false,
+            defer_accept: None,
         }
     }
 
@@ -88,4 +101,37 @@ impl ListenerOpts {
         self.tcp_fast_open = fast_open;
         self
     }
+
+    /// Enable FastOpen with the max length of the queue of pending requests,
+    /// which is ignored on macOS.
+    #[must_use]
+    #[inline]
+    pub fn fastopen(mut self, queue_len: u32) -> Self {
+        self.tcp_fast_open = true;
+        self.tcp_fast_open_queue = Some(queue_len);
+        self
+    }
+
+    /// Set IPV6_V6ONLY before binding an IPv6 address. It is disabled by
+    /// default, so a listener on `[::]` accepts IPv4 connections too instead
+    /// of depending on the system default.
+    #[must_use]
+    #[inline]
+    pub fn only_v6(mut self, only_v6: bool) -> Self {
+        self.only_v6 = only_v6;
+        self
+    }
+
+    /// Set TCP_DEFER_ACCEPT before listen, so accept does not complete until
+    /// the client sends data or the timeout expires, which is rounded up to
+    /// seconds. It saves a wakeup per connection for protocols where the
+    /// client speaks first like HTTP.
+    ///
+    /// It is Linux only, binding fails with `Unsupported` on other platforms.
+    #[must_use]
+    #[inline]
+    pub fn defer_accept(mut self, timeout: Duration) -> Self {
+        self.defer_accept = Some(timeout);
+        self
+    }
 }
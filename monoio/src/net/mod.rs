@@ -1,8 +1,14 @@
 //! Network related
 //! Currently, TCP/UnixStream/UnixDatagram are implemented.
 
+#[cfg(unix)]
+mod bind_device;
+#[cfg(unix)]
+mod icmp;
 mod listener_config;
 pub mod tcp;
+#[cfg(unix)]
+mod tos;
 pub mod udp;
 #[cfg(unix)]
 pub mod unix;
@@ -10,18 +16,31 @@ pub mod unix;
 #[cfg(windows)]
 use std::os::windows::prelude::{AsRawSocket, RawSocket};
 
+#[cfg(unix)]
+pub use icmp::IcmpSocket;
 pub use listener_config::ListenerOpts;
 #[deprecated(since = "0.2.0", note = "use ListenerOpts")]
 pub use listener_config::ListenerOpts as ListenerConfig;
-pub use tcp::{TcpConnectOpts, TcpListener, TcpStream};
+#[cfg(unix)]
+pub use tcp::TcpSocket;
+pub use tcp::{KeepAlive, TcpConnectOpts, TcpInfo, TcpListener, TcpStream};
 #[cfg(unix)]
 pub use unix::{Pipe, UnixDatagram, UnixListener, UnixStream};
 
-// Copied from mio.
 #[cfg(unix)]
 pub(crate) fn new_socket(
     domain: libc::c_int,
     socket_type: libc::c_int,
+) -> std::io::Result<libc::c_int> {
+    new_socket_with_protocol(domain, socket_type, 0)
+}
+
+// Copied from mio.
+#[cfg(unix)]
+pub(crate) fn new_socket_with_protocol(
+    domain: libc::c_int,
+    socket_type: libc::c_int,
+    protocol: libc::c_int,
 ) -> std::io::Result<libc::c_int> {
     #[cfg(any(
         target_os = "android",
@@ -45,7 +64,7 @@ pub(crate) fn new_socket(
     // Gives a warning for platforms without SOCK_NONBLOCK.
     #[allow(clippy::let_and_return)]
     #[cfg(unix)]
-    let socket = crate::syscall!(socket(domain, socket_type, 0));
+    let socket = crate::syscall!(socket(domain, socket_type, protocol));
 
     // Mimic `libstd` and set `SO_NOSIGPIPE` on apple systems.
     #[cfg(target_vendor = "apple")]
@@ -0,0 +1,171 @@
+use std::{io, time::Duration};
+
+/// TCP statistics of a connection from the kernel, see
+/// [`TcpStream::tcp_info`].
+///
+/// It is `TCP_INFO` on Linux and `TCP_CONNECTION_INFO` on macOS, the fields
+/// the platform or an older kernel does not report are None.
+///
+/// [`TcpStream::tcp_info`]: super::TcpStream::tcp_info
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct TcpInfo {
+    /// Smoothed round trip time.
+    pub rtt: Duration,
+    /// Round trip time variance.
+    pub rttvar: Duration,
+    /// Minimum round trip time seen, Linux 4.6+.
+    pub min_rtt: Option<Duration>,
+    /// Retransmission timeout.
+    pub rto: Duration,
+    /// Congestion window in segments.
+    pub snd_cwnd: u32,
+    /// Slow start threshold in segments.
+    pub snd_ssthresh: u32,
+    /// Maximum segment size for sending.
+    pub snd_mss: u32,
+    /// Total number of retransmitted segments, estimated from the
+    /// retransmitted bytes on macOS.
+    pub retrans: u32,
+    /// Number of bytes acknowledged by the peer, which counts the SYN as one
+    /// byte, Linux 4.1+.
+    pub bytes_acked: Option<u64>,
+    /// Number of bytes received, Linux 4.1+ and macOS.
+    pub bytes_received: Option<u64>,
+    /// Most recent delivery rate in bytes per second, Linux 4.9+.
+    pub delivery_rate: Option<u64>,
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub(crate) fn tcp_info(socket: &socket2::Socket) -> io::Result<TcpInfo> {
+    use std::{mem::offset_of, os::unix::io::AsRawFd};
+
+    // The head of struct tcp_info in linux/tcp.h. The kernel copies as much
+    // as it has and returns the length, older kernels have fewer fields and
+    // the libc definition stops at tcpi_total_retrans.
+    #[repr(C)]
+    #[derive(Default)]
+    #[allow(dead_code)]
+    struct RawTcpInfo {
+        state: u8,
+        ca_state: u8,
+        retransmits: u8,
+        probes: u8,
+        backoff: u8,
+        options: u8,
+        wscale: u8,
+        flags: u8,
+        rto: u32,
+        ato: u32,
+        snd_mss: u32,
+        rcv_mss: u32,
+        unacked: u32,
+        sacked: u32,
+        lost: u32,
+        retrans: u32,
+        fackets: u32,
+        last_data_sent: u32,
+        last_ack_sent: u32,
+        last_data_recv: u32,
+        last_ack_recv: u32,
+        pmtu: u32,
+        rcv_ssthresh: u32,
+        rtt: u32,
+        rttvar: u32,
+        snd_ssthresh: u32,
+        snd_cwnd: u32,
+        advmss: u32,
+        reordering: u32,
+        rcv_rtt: u32,
+        rcv_space: u32,
+        total_retrans: u32,
+        pacing_rate: u64,
+        max_pacing_rate: u64,
+        bytes_acked: u64,
+        bytes_received: u64,
+        segs_out: u32,
+        segs_in: u32,
+        notsent_bytes: u32,
+        min_rtt: u32,
+        data_segs_in: u32,
+        data_segs_out: u32,
+        delivery_rate: u64,
+    }
+
+    let mut raw = RawTcpInfo::default();
+    let mut len = std::mem::size_of::<RawTcpInfo>() as libc::socklen_t;
+    crate::syscall!(getsockopt(
+        socket.as_raw_fd(),
+        libc::IPPROTO_TCP,
+        libc::TCP_INFO,
+        &mut raw as *mut RawTcpInfo as *mut libc::c_void,
+        &mut len
+    ))?;
+    let len = len as usize;
+    macro_rules! field {
+        ($field:ident) => {
+            (len >= offset_of!(RawTcpInfo, $field) + std::mem::size_of_val(&raw.$field))
+                .then_some(raw.$field)
+        };
+    }
+    if field!(total_retrans).is_none() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "TCP_INFO returned a truncated struct",
+        ));
+    }
+    Ok(TcpInfo {
+        rtt: Duration::from_micros(raw.rtt.into()),
+        rttvar: Duration::from_micros(raw.rttvar.into()),
+        // ~0U until the first sample.
+        min_rtt: field!(min_rtt)
+            .filter(|&rtt| rtt != u32::MAX)
+            .map(|rtt| Duration::from_micros(rtt.into())),
+        rto: Duration::from_micros(raw.rto.into()),
+        snd_cwnd: raw.snd_cwnd,
+        snd_ssthresh: raw.snd_ssthresh,
+        snd_mss: raw.snd_mss,
+        retrans: raw.total_retrans,
+        bytes_acked: field!(bytes_acked),
+        bytes_received: field!(bytes_received),
+        delivery_rate: field!(delivery_rate),
+    })
+}
+
+#[cfg(target_vendor = "apple")]
+pub(crate) fn tcp_info(socket: &socket2::Socket) -> io::Result<TcpInfo> {
+    use std::os::unix::io::AsRawFd;
+
+    let mut raw: libc::tcp_connection_info = unsafe { std::mem::zeroed() };
+    let mut len = std::mem::size_of::<libc::tcp_connection_info>() as libc::socklen_t;
+    crate::syscall!(getsockopt(
+        socket.as_raw_fd(),
+        libc::IPPROTO_TCP,
+        libc::TCP_CONNECTION_INFO,
+        &mut raw as *mut libc::tcp_connection_info as *mut libc::c_void,
+        &mut len
+    ))?;
+    // The windows and thresholds are in bytes and the times in milliseconds.
+    let mss = raw.tcpi_maxseg.max(1);
+    Ok(TcpInfo {
+        rtt: Duration::from_millis(raw.tcpi_srtt.into()),
+        rttvar: Duration::from_millis(raw.tcpi_rttvar.into()),
+        min_rtt: None,
+        rto: Duration::from_millis(raw.tcpi_rto.into()),
+        snd_cwnd: raw.tcpi_snd_cwnd / mss,
+        snd_ssthresh: raw.tcpi_snd_ssthresh / mss,
+        snd_mss: raw.tcpi_maxseg,
+        retrans: (raw.tcpi_txretransmitbytes / u64::from(mss)).min(u32::MAX.into()) as u32,
+        bytes_acked: None,
+        bytes_received: Some(raw.tcpi_rxbytes),
+        delivery_rate: None,
+    })
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android", target_vendor = "apple")))]
+pub(crate) fn tcp_info(_: &socket2::Socket) -> io::Result<TcpInfo> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "TCP_INFO is not supported on this platform",
+    ))
+}
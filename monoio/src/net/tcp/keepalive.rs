@@ -0,0 +1,55 @@
+use std::{io, time::Duration};
+
+/// TCP keepalive parameters, see [`TcpStream::set_keepalive`].
+///
+/// [`TcpStream::set_keepalive`]: super::TcpStream::set_keepalive
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeepAlive {
+    /// Idle time before the first probe is sent, `TCP_KEEPIDLE`
+    /// (`TCP_KEEPALIVE` on macOS).
+    pub time: Duration,
+    /// Interval between the probes, `TCP_KEEPINTVL`.
+    pub interval: Duration,
+    /// Number of unacknowledged probes before the connection is dropped,
+    /// `TCP_KEEPCNT`. It is fixed by the system on Windows and ignored.
+    pub retries: u32,
+}
+
+/// Enable `SO_KEEPALIVE` with the parameters, or disable it with None.
+pub(crate) fn set_keepalive(
+    socket: &socket2::Socket,
+    keepalive: Option<KeepAlive>,
+) -> io::Result<()> {
+    let Some(keepalive) = keepalive else {
+        return socket.set_keepalive(false);
+    };
+    let params = socket2::TcpKeepalive::new()
+        .with_time(keepalive.time)
+        .with_interval(keepalive.interval);
+    #[cfg(unix)]
+    let params = params.with_retries(keepalive.retries);
+    // SIO_KEEPALIVE_VALS on Windows, it also enables SO_KEEPALIVE.
+    socket.set_tcp_keepalive(&params)
+}
+
+/// Read back the parameters, None if `SO_KEEPALIVE` is disabled.
+#[cfg(unix)]
+pub(crate) fn keepalive(socket: &socket2::Socket) -> io::Result<Option<KeepAlive>> {
+    if !socket.keepalive()? {
+        return Ok(None);
+    }
+    Ok(Some(KeepAlive {
+        time: socket.keepalive_time()?,
+        interval: socket.keepalive_interval()?,
+        retries: socket.keepalive_retries()?,
+    }))
+}
+
+// SIO_KEEPALIVE_VALS is write-only.
+#[cfg(windows)]
+pub(crate) fn keepalive(_: &socket2::Socket) -> io::Result<Option<KeepAlive>> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "keepalive parameters can not be read on windows",
+    ))
+}
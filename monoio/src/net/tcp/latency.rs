@@ -0,0 +1,57 @@
+//! `TCP_DEFER_ACCEPT` and `TCP_QUICKACK`, which only exist on Linux. Other
+//! platforms return `Unsupported` so the callers can tell it from a no-op.
+
+use std::{io, time::Duration};
+
+/// Call before listen, accept does not complete until data arrives or the
+/// timeout expires. The timeout is rounded up to seconds, zero disables it.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub(crate) fn set_defer_accept(socket: &socket2::Socket, timeout: Duration) -> io::Result<()> {
+    use std::os::unix::io::AsRawFd;
+
+    let secs = timeout
+        .as_secs()
+        .saturating_add((timeout.subsec_nanos() > 0) as u64)
+        .min(libc::c_int::MAX as u64) as libc::c_int;
+    crate::syscall!(setsockopt(
+        socket.as_raw_fd(),
+        libc::IPPROTO_TCP,
+        libc::TCP_DEFER_ACCEPT,
+        &secs as *const libc::c_int as *const libc::c_void,
+        std::mem::size_of::<libc::c_int>() as libc::socklen_t
+    ))
+    .map(|_| ())
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub(crate) fn set_quickack(socket: &socket2::Socket, quickack: bool) -> io::Result<()> {
+    socket.set_quickack(quickack)
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub(crate) fn quickack(socket: &socket2::Socket) -> io::Result<bool> {
+    socket.quickack()
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+pub(crate) fn set_defer_accept(_: &socket2::Socket, _: Duration) -> io::Result<()> {
+    Err(unsupported("TCP_DEFER_ACCEPT"))
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+pub(crate) fn set_quickack(_: &socket2::Socket, _: bool) -> io::Result<()> {
+    Err(unsupported("TCP_QUICKACK"))
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+pub(crate) fn quickack(_: &socket2::Socket) -> io::Result<bool> {
+    Err(unsupported("TCP_QUICKACK"))
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+fn unsupported(option: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::Unsupported,
+        format!("{option} is not supported on this platform"),
+    )
+}
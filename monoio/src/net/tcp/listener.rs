@@ -9,7 +9,7 @@ use std::{
 #[cfg(unix)]
 use {
     std::net::{Ipv4Addr, Ipv6Addr, SocketAddrV4, SocketAddrV6},
-    std::os::unix::prelude::{AsRawFd, FromRawFd, IntoRawFd, RawFd},
+    std::os::unix::prelude::{AsRawFd, FromRawFd, IntoRawFd, OwnedFd, RawFd},
 };
 
 use super::stream::TcpStream;
@@ -60,6 +60,9 @@ impl TcpListener {
         #[cfg(all(unix, feature = "legacy"))]
         Self::set_non_blocking(&sys_listener)?;
 
+        if addr.is_ipv6() {
+            sys_listener.set_only_v6(opts.only_v6)?;
+        }
         let addr = socket2::SockAddr::from(addr);
         #[cfg(unix)]
         if opts.reuse_port {
@@ -76,10 +79,17 @@ impl TcpListener {
         }
         if opts.tcp_fast_open {
             #[cfg(any(target_os = "linux", target_os = "android"))]
-            super::tfo::set_tcp_fastopen(&sys_listener, opts.backlog)?;
+            super::tfo::set_tcp_fastopen(
+                &sys_listener,
+                opts.tcp_fast_open_queue
+                    .map_or(opts.backlog, |len| len.min(i32::MAX as u32) as i32),
+            )?;
             #[cfg(any(target_os = "ios", target_os = "macos"))]
             let _ = super::tfo::set_tcp_fastopen_force_enable(&sys_listener);
         }
+        if let Some(timeout) = opts.defer_accept {
+            super::latency::set_defer_accept(&sys_listener, timeout)?;
+        }
         sys_listener.bind(&addr)?;
         sys_listener.listen(opts.backlog)?;
 
@@ -97,7 +107,9 @@ impl TcpListener {
         Ok(Self::from_shared_fd(fd))
     }
 
-    /// Bind to address
+    /// Bind to address with the default [`ListenerOpts`]. An IPv6 wildcard
+    /// address like `[::]:8080` is dual-stack and accepts IPv4 connections
+    /// too, use [`ListenerOpts::only_v6`] to disable it.
     pub fn bind<A: ToSocketAddrs>(addr: A) -> io::Result<Self> {
         const DEFAULT_CFG: ListenerOpts = ListenerOpts::new();
         Self::bind_with_config(addr, &DEFAULT_CFG)
@@ -118,34 +130,7 @@ impl TcpListener {
         let stream = TcpStream::from_shared_fd(SharedFd::new::<false>(fd as _)?);
 
         // Construct SocketAddr
-        let storage = completion.data.addr.0.as_ptr();
-        let addr = unsafe {
-            match (*storage).ss_family as libc::c_int {
-                libc::AF_INET => {
-                    // Safety: if the ss_family field is AF_INET then storage must be a sockaddr_in.
-                    let addr: &libc::sockaddr_in = &*(storage as *const libc::sockaddr_in);
-                    let ip = Ipv4Addr::from(addr.sin_addr.s_addr.to_ne_bytes());
-                    let port = u16::from_be(addr.sin_port);
-                    SocketAddr::V4(SocketAddrV4::new(ip, port))
-                }
-                libc::AF_INET6 => {
-                    // Safety: if the ss_family field is AF_INET6 then storage must be a
-                    // sockaddr_in6.
-                    let addr: &libc::sockaddr_in6 = &*(storage as *const libc::sockaddr_in6);
-                    let ip = Ipv6Addr::from(addr.sin6_addr.s6_addr);
-                    let port = u16::from_be(addr.sin6_port);
-                    SocketAddr::V6(SocketAddrV6::new(
-                        ip,
-                        port,
-                        addr.sin6_flowinfo,
-                        addr.sin6_scope_id,
-                    ))
-                }
-                _ => {
-                    return Err(io::ErrorKind::InvalidInput.into());
-                }
-            }
-        };
+        let addr = unsafe { sockaddr(completion.data.addr.0.as_ptr()) }?;
 
         Ok((stream, addr))
     }
@@ -171,34 +156,7 @@ impl TcpListener {
         let stream = TcpStream::from_shared_fd(SharedFd::new::<false>(fd as _)?);
 
         // Construct SocketAddr
-        let storage = completion.data.addr.0.as_ptr();
-        let addr = unsafe {
-            match (*storage).ss_family as libc::c_int {
-                libc::AF_INET => {
-                    // Safety: if the ss_family field is AF_INET then storage must be a sockaddr_in.
-                    let addr: &libc::sockaddr_in = &*(storage as *const libc::sockaddr_in);
-                    let ip = Ipv4Addr::from(addr.sin_addr.s_addr.to_ne_bytes());
-                    let port = u16::from_be(addr.sin_port);
-                    SocketAddr::V4(SocketAddrV4::new(ip, port))
-                }
-                libc::AF_INET6 => {
-                    // Safety: if the ss_family field is AF_INET6 then storage must be a
-                    // sockaddr_in6.
-                    let addr: &libc::sockaddr_in6 = &*(storage as *const libc::sockaddr_in6);
-                    let ip = Ipv6Addr::from(addr.sin6_addr.s6_addr);
-                    let port = u16::from_be(addr.sin6_port);
-                    SocketAddr::V6(SocketAddrV6::new(
-                        ip,
-                        port,
-                        addr.sin6_flowinfo,
-                        addr.sin6_scope_id,
-                    ))
-                }
-                _ => {
-                    return Err(io::ErrorKind::InvalidInput.into());
-                }
-            }
-        };
+        let addr = unsafe { sockaddr(completion.data.addr.0.as_ptr()) }?;
 
         Ok((stream, addr))
     }
@@ -209,6 +167,92 @@ impl TcpListener {
         unimplemented!()
     }
 
+    /// Accept a connection as a direct descriptor, which is installed into
+    /// the file table of io_uring driver instead of the fd table of the
+    /// process(requires kernel 5.19+). It saves the fd allocation for a large
+    /// number of connections.
+    ///
+    /// The table must be enabled with [`RuntimeBuilder::register_files`]. If
+    /// the table is full or not enabled, or on legacy driver, it falls back
+    /// to [`TcpListener::accept`] and registers the accepted fd if possible.
+    ///
+    /// The returned stream has no fd, so the socket options are not
+    /// available and `as_raw_fd` returns -1. Use [`TcpStream::into_std`] to
+    /// get a fd.
+    ///
+    /// [`RuntimeBuilder::register_files`]: crate::RuntimeBuilder::register_files
+    #[cfg(unix)]
+    pub async fn accept_direct(&self) -> io::Result<(TcpStream, SocketAddr)> {
+        #[cfg(all(target_os = "linux", feature = "iouring"))]
+        if let Some(slot) = crate::driver::op::is_uring_op_supported(io_uring::opcode::Accept::CODE)
+            .then(crate::driver::op::alloc_fixed_slot)
+            .flatten()
+        {
+            // The slot is given back if the op fails or is dropped.
+            let mut completion = Op::accept_direct(&self.fd, slot)?.await;
+            completion.meta.result?;
+            let addr = unsafe { sockaddr(completion.data.addr.0.as_ptr()) }?;
+            let slot = completion.data.file_index.take().expect("slot is taken");
+            return Ok((
+                TcpStream::from_direct(SharedFd::new_fixed(slot), addr),
+                addr,
+            ));
+        }
+
+        let (mut stream, addr) = self.accept().await?;
+        stream.register();
+        Ok((stream, addr))
+    }
+
+    /// Accept connections with a single multishot accept operation.
+    ///
+    /// On uring driver with kernel 5.19+, one multishot accept is submitted and
+    /// accepted connections are yielded from the queued completions. It will
+    /// only be re-armed when the kernel terminates the multishot. On legacy
+    /// driver or older kernels, it falls back to the one-shot accept.
+    ///
+    /// The pending multishot accept is canceled when the returned
+    /// [`AcceptMulti`] is dropped.
+    #[cfg(unix)]
+    pub fn accept_multi(&self) -> AcceptMulti<'_> {
+        AcceptMulti {
+            listener: self,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            op: None,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            fallback: false,
+        }
+    }
+
+    /// Returns a [`futures_core::Stream`] of the accepted connections, so it
+    /// can be used with the combinators of `futures_util::StreamExt`.
+    ///
+    /// The pending accept is kept across polls and canceled when the
+    /// [`Incoming`] is dropped, so the listener can be used again.
+    #[cfg(all(unix, feature = "futures-stream"))]
+    pub fn incoming(&self) -> Incoming<'_> {
+        Incoming {
+            listener: self,
+            op: None,
+        }
+    }
+
+    /// Set the value of the `IP_TTL` option on this socket, accepted streams
+    /// inherit it.
+    pub fn set_ttl(&self, ttl: u32) -> io::Result<()> {
+        self.sys_listener.as_ref().unwrap().set_ttl(ttl)
+    }
+
+    /// Get the value of the `IP_TTL` option on this socket.
+    pub fn ttl(&self) -> io::Result<u32> {
+        self.sys_listener.as_ref().unwrap().ttl()
+    }
+
+    /// Get and clear the pending error of the socket, `SO_ERROR`.
+    pub fn take_error(&self) -> io::Result<Option<io::Error>> {
+        self.sys_listener.as_ref().unwrap().take_error()
+    }
+
     /// Returns the local address that this listener is bound to.
     pub fn local_addr(&self) -> io::Result<SocketAddr> {
         let meta = self.meta.get();
@@ -253,6 +297,10 @@ impl TcpListener {
     /// Creates new `TcpListener` from a `std::net::TcpListener`.
     #[cfg(unix)]
     pub fn from_std(stdl: std::net::TcpListener) -> io::Result<Self> {
+        // The io of legacy driver relies on readiness.
+        if crate::driver::op::is_legacy() {
+            stdl.set_nonblocking(true)?;
+        }
         match SharedFd::new::<false>(stdl.as_raw_fd()) {
             Ok(shared) => {
                 stdl.into_raw_fd();
@@ -273,6 +321,23 @@ impl TcpListener {
             Err(e) => Err(e),
         }
     }
+
+    /// Converts the listener into a `std::net::TcpListener`. It fails if
+    /// there are ops in flight, e.g. an accept which is dropped but not
+    /// completed yet.
+    ///
+    /// On legacy driver the fd is deregistered and stays non-blocking.
+    #[cfg(unix)]
+    pub fn into_std(self) -> io::Result<std::net::TcpListener> {
+        let this = std::mem::ManuallyDrop::new(self);
+        // Skip the drop of self, the fd is owned by the shared fd.
+        let fd = unsafe { std::ptr::read(&this.fd) };
+        let _ = unsafe { std::ptr::read(&this.sys_listener) }.map(IntoRawFd::into_raw_fd);
+        let fd = fd
+            .try_unwrap()
+            .map_err(|_| io::Error::other("unexpected multiple reference to rawfd"))?;
+        Ok(unsafe { std::net::TcpListener::from_raw_fd(fd) })
+    }
 }
 
 impl Stream for TcpListener {
@@ -284,6 +349,155 @@ impl Stream for TcpListener {
     }
 }
 
+/// Stream of accepted connections created by [`TcpListener::accept_multi`].
+#[cfg(unix)]
+pub struct AcceptMulti<'a> {
+    listener: &'a TcpListener,
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    op: Option<Op<crate::driver::op::AcceptMulti>>,
+    // Set if multishot accept is not supported by the kernel
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    fallback: bool,
+}
+
+#[cfg(unix)]
+impl AcceptMulti<'_> {
+    /// Accept a connection.
+    /// The accepted stream and its peer address are returned like
+    /// [`TcpListener::accept`].
+    pub async fn accept(&mut self) -> io::Result<(TcpStream, SocketAddr)> {
+        #[cfg(all(target_os = "linux", feature = "iouring"))]
+        if !self.fallback && !crate::driver::op::is_legacy() {
+            let op = match self.op.as_mut() {
+                Some(op) => op,
+                None => self.op.insert(Op::accept_multi(&self.listener.fd)?),
+            };
+            let meta = std::future::poll_fn(|cx| op.poll_multishot(cx)).await;
+            if !meta.has_more() {
+                // The multishot is terminated by the kernel, re-arm on next call.
+                self.op = None;
+            }
+            match meta.result {
+                Ok(fd) => {
+                    let stream = TcpStream::from_shared_fd(SharedFd::new::<false>(fd as _)?);
+                    let addr = stream.peer_addr()?;
+                    return Ok((stream, addr));
+                }
+                // Multishot accept is not supported, use one-shot accept instead.
+                Err(e) if e.raw_os_error() == Some(libc::EINVAL) => self.fallback = true,
+                Err(e) => return Err(e),
+            }
+        }
+        self.listener.accept().await
+    }
+}
+
+#[cfg(unix)]
+impl Stream for AcceptMulti<'_> {
+    type Item = io::Result<(TcpStream, SocketAddr)>;
+
+    #[inline]
+    async fn next(&mut self) -> Option<Self::Item> {
+        Some(self.accept().await)
+    }
+}
+
+#[cfg(unix)]
+impl Drop for AcceptMulti<'_> {
+    fn drop(&mut self) {
+        // Multishot accept never finishes by itself, so we must cancel it even
+        // if async-cancel is not enabled.
+        #[cfg(all(
+            target_os = "linux",
+            feature = "iouring",
+            not(feature = "async-cancel")
+        ))]
+        if let Some(op) = self.op.take() {
+            unsafe { op.op_canceller().cancel() };
+        }
+    }
+}
+
+/// Stream of accepted connections created by [`TcpListener::incoming`].
+#[cfg(all(unix, feature = "futures-stream"))]
+pub struct Incoming<'a> {
+    listener: &'a TcpListener,
+    op: Option<Op<crate::driver::op::Accept>>,
+}
+
+#[cfg(all(unix, feature = "futures-stream"))]
+impl futures_core::Stream for Incoming<'_> {
+    type Item = io::Result<TcpStream>;
+
+    fn poll_next(
+        self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        let op = match this.op.as_mut() {
+            Some(op) => op,
+            None => match Op::accept(&this.listener.fd) {
+                Ok(op) => this.op.insert(op),
+                Err(e) => return std::task::Poll::Ready(Some(Err(e))),
+            },
+        };
+        let completion = std::task::ready!(std::future::Future::poll(std::pin::Pin::new(op), cx));
+        this.op = None;
+        let res = completion
+            .meta
+            .result
+            .and_then(|fd| Ok(TcpStream::from_shared_fd(SharedFd::new::<false>(fd as _)?)));
+        std::task::Poll::Ready(Some(res))
+    }
+}
+
+#[cfg(all(unix, feature = "futures-stream"))]
+impl Drop for Incoming<'_> {
+    fn drop(&mut self) {
+        // A pending accept would take the next connection of the listener, so
+        // we must cancel it even if async-cancel is not enabled. There is
+        // nothing in flight on legacy driver.
+        #[cfg(all(
+            target_os = "linux",
+            feature = "iouring",
+            not(feature = "async-cancel")
+        ))]
+        if let Some(op) = self.op.take() {
+            if !crate::driver::op::is_legacy() {
+                unsafe { op.op_canceller().cancel() };
+            }
+        }
+    }
+}
+
+/// Convert the peer address written by the kernel.
+#[cfg(unix)]
+unsafe fn sockaddr(storage: *const libc::sockaddr_storage) -> io::Result<SocketAddr> {
+    match (*storage).ss_family as libc::c_int {
+        libc::AF_INET => {
+            // Safety: if the ss_family field is AF_INET then storage must be a sockaddr_in.
+            let addr: &libc::sockaddr_in = &*(storage as *const libc::sockaddr_in);
+            let ip = Ipv4Addr::from(addr.sin_addr.s_addr.to_ne_bytes());
+            let port = u16::from_be(addr.sin_port);
+            Ok(SocketAddr::V4(SocketAddrV4::new(ip, port)))
+        }
+        libc::AF_INET6 => {
+            // Safety: if the ss_family field is AF_INET6 then storage must be a
+            // sockaddr_in6.
+            let addr: &libc::sockaddr_in6 = &*(storage as *const libc::sockaddr_in6);
+            let ip = Ipv6Addr::from(addr.sin6_addr.s6_addr);
+            let port = u16::from_be(addr.sin6_port);
+            Ok(SocketAddr::V6(SocketAddrV6::new(
+                ip,
+                port,
+                addr.sin6_flowinfo,
+                addr.sin6_scope_id,
+            )))
+        }
+        _ => Err(io::ErrorKind::InvalidInput.into()),
+    }
+}
+
 impl std::fmt::Debug for TcpListener {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("TcpListener").field("fd", &self.fd).finish()
@@ -298,6 +512,16 @@ impl AsRawFd for TcpListener {
     }
 }
 
+#[cfg(unix)]
+impl TryFrom<OwnedFd> for TcpListener {
+    type Error = io::Error;
+
+    /// The fd must be a listening TCP socket, see [`TcpListener::from_std`].
+    fn try_from(fd: OwnedFd) -> io::Result<Self> {
+        Self::from_std(std::net::TcpListener::from(fd))
+    }
+}
+
 #[cfg(windows)]
 impl AsRawSocket for TcpListener {
     #[inline]
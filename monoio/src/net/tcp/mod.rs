@@ -1,13 +1,28 @@
 #![allow(unreachable_pub)]
 //! TCP related.
 
+mod info;
+mod keepalive;
+mod latency;
 mod listener;
+#[cfg(unix)]
+mod socket;
 mod split;
 mod stream;
 mod tfo;
 
+pub use info::TcpInfo;
+pub use keepalive::KeepAlive;
+#[cfg(unix)]
+pub use listener::AcceptMulti;
+#[cfg(all(unix, feature = "futures-stream"))]
+pub use listener::Incoming;
 pub use listener::TcpListener;
+#[cfg(unix)]
+pub use socket::TcpSocket;
 pub use split::{TcpOwnedReadHalf, TcpOwnedWriteHalf};
+#[cfg(feature = "bytes")]
+pub use stream::RecvMulti;
 pub use stream::{TcpConnectOpts, TcpStream};
 
 #[cfg(feature = "poll-io")]
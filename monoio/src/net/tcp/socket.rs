@@ -0,0 +1,215 @@
+use std::{
+    io,
+    net::SocketAddr,
+    os::unix::prelude::{AsRawFd, FromRawFd, IntoRawFd, RawFd},
+    time::Duration,
+};
+
+use super::{keepalive, KeepAlive, TcpListener, TcpStream};
+use crate::{driver::shared_fd::SharedFd, net::bind_device};
+
+/// A TCP socket which is not connected or listening yet, used to set the
+/// socket options before [`connect`](TcpSocket::connect) or
+/// [`listen`](TcpSocket::listen).
+///
+/// # Examples
+///
+/// ```no_run
+/// use monoio::net::TcpSocket;
+///
+/// #[monoio::main]
+/// async fn main() -> std::io::Result<()> {
+///     let socket = TcpSocket::new_v4()?;
+///     socket.set_reuseaddr(true)?;
+///     socket.bind("127.0.0.1:0".parse().unwrap())?;
+///     let stream = socket.connect("127.0.0.1:8080".parse().unwrap()).await?;
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug)]
+pub struct TcpSocket {
+    inner: socket2::Socket,
+}
+
+impl TcpSocket {
+    /// Create a new IPv4 TCP socket.
+    pub fn new_v4() -> io::Result<Self> {
+        Self::new(libc::AF_INET)
+    }
+
+    /// Create a new IPv6 TCP socket.
+    pub fn new_v6() -> io::Result<Self> {
+        Self::new(libc::AF_INET6)
+    }
+
+    fn new(domain: libc::c_int) -> io::Result<Self> {
+        let fd = crate::net::new_socket(domain, libc::SOCK_STREAM)?;
+        Ok(Self {
+            inner: unsafe { socket2::Socket::from_raw_fd(fd) },
+        })
+    }
+
+    /// Set `SO_REUSEADDR`, which allows binding to an address in the
+    /// `TIME_WAIT` state.
+    pub fn set_reuseaddr(&self, reuseaddr: bool) -> io::Result<()> {
+        self.inner.set_reuse_address(reuseaddr)
+    }
+
+    /// Get the value of `SO_REUSEADDR`.
+    pub fn reuseaddr(&self) -> io::Result<bool> {
+        self.inner.reuse_address()
+    }
+
+    /// Set `SO_REUSEPORT`, which allows the sockets to bind to the same
+    /// address and port.
+    pub fn set_reuseport(&self, reuseport: bool) -> io::Result<()> {
+        self.inner.set_reuse_port(reuseport)
+    }
+
+    /// Get the value of `SO_REUSEPORT`.
+    pub fn reuseport(&self) -> io::Result<bool> {
+        self.inner.reuse_port()
+    }
+
+    /// Set the size of the send buffer, `SO_SNDBUF`.
+    pub fn set_send_buffer_size(&self, size: u32) -> io::Result<()> {
+        self.inner.set_send_buffer_size(size as usize)
+    }
+
+    /// Get the size of the send buffer, which may be larger than the one set
+    /// since the kernel doubles it.
+    pub fn send_buffer_size(&self) -> io::Result<u32> {
+        self.inner.send_buffer_size().map(|size| size as u32)
+    }
+
+    /// Set the size of the receive buffer, `SO_RCVBUF`.
+    pub fn set_recv_buffer_size(&self, size: u32) -> io::Result<()> {
+        self.inner.set_recv_buffer_size(size as usize)
+    }
+
+    /// Get the size of the receive buffer, which may be larger than the one
+    /// set since the kernel doubles it.
+    pub fn recv_buffer_size(&self) -> io::Result<u32> {
+        self.inner.recv_buffer_size().map(|size| size as u32)
+    }
+
+    /// Set `IPV6_V6ONLY` on an IPv6 socket before binding it, a listener on
+    /// the wildcard address then only accepts IPv6 connections.
+    pub fn set_only_v6(&self, only_v6: bool) -> io::Result<()> {
+        self.inner.set_only_v6(only_v6)
+    }
+
+    /// Get the value of `IPV6_V6ONLY`.
+    pub fn only_v6(&self) -> io::Result<bool> {
+        self.inner.only_v6()
+    }
+
+    /// Set `TCP_NODELAY`, which disables the Nagle algorithm.
+    pub fn set_nodelay(&self, nodelay: bool) -> io::Result<()> {
+        self.inner.set_nodelay(nodelay)
+    }
+
+    /// Get the value of `TCP_NODELAY`.
+    pub fn nodelay(&self) -> io::Result<bool> {
+        self.inner.nodelay()
+    }
+
+    /// Enable `SO_KEEPALIVE` with the idle time, interval and retries of the
+    /// probes, or disable it with None. Accepted streams inherit it.
+    pub fn set_keepalive(&self, keepalive: Option<KeepAlive>) -> io::Result<()> {
+        keepalive::set_keepalive(&self.inner, keepalive)
+    }
+
+    /// Get the keepalive parameters, None if `SO_KEEPALIVE` is disabled.
+    pub fn keepalive(&self) -> io::Result<Option<KeepAlive>> {
+        keepalive::keepalive(&self.inner)
+    }
+
+    /// Set `SO_LINGER`, closing the socket waits for the pending data to be
+    /// sent for the duration.
+    pub fn set_linger(&self, linger: Option<Duration>) -> io::Result<()> {
+        self.inner.set_linger(linger)
+    }
+
+    /// Get the value of `SO_LINGER`.
+    pub fn linger(&self) -> io::Result<Option<Duration>> {
+        self.inner.linger()
+    }
+
+    /// Bind the socket to the network interface by name, or remove the
+    /// binding with None. It is `SO_BINDTODEVICE` on Linux, which may fail
+    /// with `EPERM` without `CAP_NET_RAW`, and `IP_BOUND_IF` or
+    /// `IPV6_BOUND_IF` on macOS.
+    pub fn set_bind_device(&self, device: Option<&str>) -> io::Result<()> {
+        bind_device::set_bind_device(&self.inner, device)
+    }
+
+    /// Bind the socket to the network interface by index, or remove the
+    /// binding with 0.
+    pub fn bind_device_by_index(&self, index: u32) -> io::Result<()> {
+        bind_device::bind_device_by_index(&self.inner, index)
+    }
+
+    /// Get the name of the network interface the socket is bound to.
+    pub fn bind_device(&self) -> io::Result<Option<String>> {
+        bind_device::bind_device(&self.inner)
+    }
+
+    /// Bind the socket to the local address.
+    pub fn bind(&self, addr: SocketAddr) -> io::Result<()> {
+        self.inner.bind(&addr.into())
+    }
+
+    /// Get the local address of the socket.
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.inner
+            .local_addr()?
+            .as_socket()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "invalid socket address"))
+    }
+
+    /// Connect to `addr`, the socket is turned into a [`TcpStream`].
+    pub async fn connect(self, addr: SocketAddr) -> io::Result<TcpStream> {
+        TcpStream::connect_fd(self.into_shared_fd()?, addr, false, None).await
+    }
+
+    /// Listen for connections with the backlog, the socket is turned into a
+    /// [`TcpListener`].
+    pub fn listen(self, backlog: u32) -> io::Result<TcpListener> {
+        let backlog = backlog.min(i32::MAX as u32) as i32;
+        self.inner.listen(backlog)?;
+        Ok(TcpListener::from_shared_fd(self.into_shared_fd()?))
+    }
+
+    fn into_shared_fd(self) -> io::Result<SharedFd> {
+        let fd = self.inner.into_raw_fd();
+        match SharedFd::new::<false>(fd) {
+            Ok(shared) => Ok(shared),
+            Err(e) => {
+                unsafe { libc::close(fd) };
+                Err(e)
+            }
+        }
+    }
+}
+
+impl AsRawFd for TcpSocket {
+    fn as_raw_fd(&self) -> RawFd {
+        self.inner.as_raw_fd()
+    }
+}
+
+impl IntoRawFd for TcpSocket {
+    fn into_raw_fd(self) -> RawFd {
+        self.inner.into_raw_fd()
+    }
+}
+
+impl FromRawFd for TcpSocket {
+    /// The fd must be a TCP socket, which is nonblocking on legacy driver.
+    unsafe fn from_raw_fd(fd: RawFd) -> Self {
+        Self {
+            inner: socket2::Socket::from_raw_fd(fd),
+        }
+    }
+}
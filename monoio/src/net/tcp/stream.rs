@@ -9,7 +9,7 @@ use std::{
 #[cfg(unix)]
 use {
     libc::{AF_INET, AF_INET6, SOCK_STREAM},
-    std::os::unix::prelude::{AsRawFd, FromRawFd, IntoRawFd, RawFd},
+    std::os::unix::prelude::{AsRawFd, FromRawFd, IntoRawFd, OwnedFd, RawFd},
 };
 #[cfg(windows)]
 use {
@@ -17,6 +17,7 @@ use {
     windows_sys::Win32::Networking::WinSock::{AF_INET, AF_INET6, SOCK_STREAM},
 };
 
+use super::{info, keepalive, latency, KeepAlive, TcpInfo};
 use crate::{
     buf::{IoBuf, IoBufMut, IoVecBuf, IoVecBufMut},
     driver::{op::Op, shared_fd::SharedFd},
@@ -86,6 +87,19 @@ impl TcpStream {
         Self { fd, meta }
     }
 
+    /// Wrap a direct descriptor, which has no fd for the socket options.
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    pub(crate) fn from_direct(fd: SharedFd, peer_addr: SocketAddr) -> Self {
+        let meta = StreamMeta {
+            socket: None,
+            meta: UnsafeCell::new(Meta {
+                local_addr: None,
+                peer_addr: Some(peer_addr),
+            }),
+        };
+        Self { fd, meta }
+    }
+
     /// Open a TCP connection to a remote host.
     /// Note: This function may block the current thread while resolution is
     /// performed.
@@ -141,10 +155,69 @@ impl TcpStream {
             }
         }
         #[cfg(unix)]
-        let op = Op::connect(SharedFd::new::<false>(socket)?, addr, tfo)?;
+        let fd = SharedFd::new::<false>(socket)?;
+        #[cfg(windows)]
+        let fd = SharedFd::new(socket)?;
+        Self::connect_fd(fd, addr, tfo, None).await
+    }
+
+    /// Establish a connection to the specified `addr`, and fail with
+    /// `TimedOut` if it is not established within `timeout`. The socket is
+    /// closed then.
+    ///
+    /// On uring driver the connect is linked to a timeout op which cancels it.
+    /// On legacy driver the timer of the runtime is used, which must be
+    /// enabled.
+    pub async fn connect_timeout(addr: SocketAddr, timeout: Duration) -> io::Result<Self> {
+        let domain = match addr {
+            SocketAddr::V4(_) => AF_INET,
+            SocketAddr::V6(_) => AF_INET6,
+        };
+        #[cfg(unix)]
+        let fd = SharedFd::new::<false>(crate::net::new_socket(domain, SOCK_STREAM)?)?;
         #[cfg(windows)]
-        let op = Op::connect(SharedFd::new(socket)?, addr, tfo)?;
-        let completion = op.await;
+        let fd = SharedFd::new(crate::net::new_socket(domain.into(), SOCK_STREAM)?)?;
+        Self::connect_fd(fd, addr, false, Some(timeout)).await
+    }
+
+    /// Establish a connection to the specified `addr` with TCP fast open and
+    /// send the data of `buf`, returning the stream and how many bytes are
+    /// sent.
+    ///
+    /// The data is carried by the SYN if the kernel has a cookie of the
+    /// server, otherwise it is sent once the connection is established, as
+    /// if by connect and then write. With a cookie the data is accepted
+    /// before the handshake completes, so a failed handshake is reported by
+    /// the next read or write.
+    pub async fn connect_with_data<T: IoBuf>(
+        addr: SocketAddr,
+        buf: T,
+    ) -> BufResult<(Self, usize), T> {
+        const TFO_OPTS: TcpConnectOpts = TcpConnectOpts {
+            tcp_fast_open: true,
+        };
+        let mut stream = match Self::connect_addr_with_config(addr, &TFO_OPTS).await {
+            Ok(stream) => stream,
+            Err(e) => return (Err(e), buf),
+        };
+        // With TCP_FASTOPEN_CONNECT the handshake is deferred to the first
+        // write.
+        let (res, buf) = stream.write(buf).await;
+        (res.map(|n| (stream, n)), buf)
+    }
+
+    /// Connect the socket `fd` to `addr`, within `timeout` if given.
+    pub(crate) async fn connect_fd(
+        fd: SharedFd,
+        addr: SocketAddr,
+        tfo: bool,
+        timeout: Option<Duration>,
+    ) -> io::Result<Self> {
+        let deadline = timeout.map(|timeout| crate::time::Instant::now() + timeout);
+        let completion = match timeout {
+            Some(timeout) => Op::with_timeout(Op::connect_raw(fd, addr, tfo), timeout).await?,
+            None => Op::connect(fd, addr, tfo)?.await,
+        };
         completion.meta.result?;
 
         let stream = TcpStream::from_shared_fd(completion.data.fd);
@@ -152,7 +225,7 @@ impl TcpStream {
         if crate::driver::op::is_legacy() {
             #[cfg(all(any(target_os = "ios", target_os = "macos"), feature = "legacy"))]
             if !tfo {
-                stream.writable(true).await?;
+                stream.connect_writable(deadline).await?;
             } else {
                 // set writable as init state
                 crate::driver::CURRENT.with(|inner| match inner {
@@ -169,7 +242,7 @@ impl TcpStream {
                 })
             }
             #[cfg(not(any(target_os = "ios", target_os = "macos")))]
-            stream.writable(true).await?;
+            stream.connect_writable(deadline).await?;
 
             // getsockopt libc::SO_ERROR
             #[cfg(unix)]
@@ -189,6 +262,16 @@ impl TcpStream {
         Ok(stream)
     }
 
+    // Wait for the connect in progress on legacy driver.
+    async fn connect_writable(&self, deadline: Option<crate::time::Instant>) -> io::Result<()> {
+        match deadline {
+            Some(deadline) => crate::time::timeout_at(deadline, self.writable(true))
+                .await
+                .unwrap_or_else(|_| Err(io::ErrorKind::TimedOut.into())),
+            None => self.writable(true).await,
+        }
+    }
+
     /// Return the local address that this stream is bound to.
     #[inline]
     pub fn local_addr(&self) -> io::Result<SocketAddr> {
@@ -213,6 +296,22 @@ impl TcpStream {
         self.meta.set_no_delay(nodelay)
     }
 
+    /// Set `TCP_QUICKACK`, which sends the ACKs at once instead of delaying
+    /// them. The kernel clears it after some segments, so set it again after
+    /// the reads which need it.
+    ///
+    /// It is Linux only, other platforms return `Unsupported`.
+    #[inline]
+    pub fn set_quickack(&self, quickack: bool) -> io::Result<()> {
+        latency::set_quickack(self.meta.socket()?, quickack)
+    }
+
+    /// Get the value of the `TCP_QUICKACK` option on this socket.
+    #[inline]
+    pub fn quickack(&self) -> io::Result<bool> {
+        latency::quickack(self.meta.socket()?)
+    }
+
     /// Set the value of the `SO_KEEPALIVE` option on this socket.
     #[inline]
     pub fn set_tcp_keepalive(
@@ -224,9 +323,148 @@ impl TcpStream {
         self.meta.set_tcp_keepalive(time, interval, retries)
     }
 
+    /// Enable `SO_KEEPALIVE` with the idle time, interval and retries of the
+    /// probes, or disable it with None.
+    #[inline]
+    pub fn set_keepalive(&self, keepalive: Option<KeepAlive>) -> io::Result<()> {
+        keepalive::set_keepalive(self.meta.socket()?, keepalive)
+    }
+
+    /// Get the keepalive parameters, None if `SO_KEEPALIVE` is disabled.
+    #[inline]
+    pub fn keepalive(&self) -> io::Result<Option<KeepAlive>> {
+        keepalive::keepalive(self.meta.socket()?)
+    }
+
+    /// Get the TCP statistics of the connection from the kernel, like the
+    /// round trip time and the congestion window.
+    #[inline]
+    pub fn tcp_info(&self) -> io::Result<TcpInfo> {
+        info::tcp_info(self.meta.socket()?)
+    }
+
+    /// Set `SO_LINGER`, closing the socket waits for the pending data to be
+    /// sent for the duration, or resets the connection with zero.
+    #[inline]
+    pub fn set_linger(&self, linger: Option<Duration>) -> io::Result<()> {
+        self.meta.socket()?.set_linger(linger)
+    }
+
+    /// Get the value of the `SO_LINGER` option on this socket.
+    #[inline]
+    pub fn linger(&self) -> io::Result<Option<Duration>> {
+        self.meta.socket()?.linger()
+    }
+
+    /// Set the value of the `IP_TTL` option on this socket.
+    #[inline]
+    pub fn set_ttl(&self, ttl: u32) -> io::Result<()> {
+        self.meta.socket()?.set_ttl(ttl)
+    }
+
+    /// Get the value of the `IP_TTL` option on this socket.
+    #[inline]
+    pub fn ttl(&self) -> io::Result<u32> {
+        self.meta.socket()?.ttl()
+    }
+
+    /// Set the traffic class of the packets sent, e.g. the DSCP marking. It
+    /// is `IP_TOS` on IPv4 sockets and `IPV6_TCLASS` on IPv6 sockets.
+    #[cfg(unix)]
+    #[inline]
+    pub fn set_tos(&self, tos: u8) -> io::Result<()> {
+        crate::net::tos::set_tos(self.meta.socket()?, tos)
+    }
+
+    /// Get the value of `IP_TOS` or `IPV6_TCLASS` on this socket.
+    #[cfg(unix)]
+    #[inline]
+    pub fn tos(&self) -> io::Result<u8> {
+        crate::net::tos::tos(self.meta.socket()?)
+    }
+
+    /// Set the size of the send buffer, `SO_SNDBUF`.
+    #[inline]
+    pub fn set_send_buffer_size(&self, size: u32) -> io::Result<()> {
+        self.meta.socket()?.set_send_buffer_size(size as usize)
+    }
+
+    /// Get the size of the send buffer, which may be larger than the one set
+    /// since the kernel doubles it.
+    #[inline]
+    pub fn send_buffer_size(&self) -> io::Result<u32> {
+        self.meta
+            .socket()?
+            .send_buffer_size()
+            .map(|size| size as u32)
+    }
+
+    /// Set the size of the receive buffer, `SO_RCVBUF`.
+    #[inline]
+    pub fn set_recv_buffer_size(&self, size: u32) -> io::Result<()> {
+        self.meta.socket()?.set_recv_buffer_size(size as usize)
+    }
+
+    /// Get the size of the receive buffer, which may be larger than the one
+    /// set since the kernel doubles it.
+    #[inline]
+    pub fn recv_buffer_size(&self) -> io::Result<u32> {
+        self.meta
+            .socket()?
+            .recv_buffer_size()
+            .map(|size| size as u32)
+    }
+
+    /// Get and clear the pending error of the socket, `SO_ERROR`.
+    #[inline]
+    pub fn take_error(&self) -> io::Result<Option<io::Error>> {
+        self.meta.socket()?.take_error()
+    }
+
+    /// Bind the socket to the network interface by name, or remove the
+    /// binding with None. It is `SO_BINDTODEVICE` on Linux, which may fail
+    /// with `EPERM` without `CAP_NET_RAW`, and `IP_BOUND_IF` or
+    /// `IPV6_BOUND_IF` on macOS.
+    #[cfg(unix)]
+    #[inline]
+    pub fn set_bind_device(&self, device: Option<&str>) -> io::Result<()> {
+        crate::net::bind_device::set_bind_device(self.meta.socket()?, device)
+    }
+
+    /// Bind the socket to the network interface by index, or remove the
+    /// binding with 0.
+    #[cfg(unix)]
+    #[inline]
+    pub fn bind_device_by_index(&self, index: u32) -> io::Result<()> {
+        crate::net::bind_device::bind_device_by_index(self.meta.socket()?, index)
+    }
+
+    /// Get the name of the network interface the socket is bound to.
+    #[cfg(unix)]
+    #[inline]
+    pub fn bind_device(&self) -> io::Result<Option<String>> {
+        crate::net::bind_device::bind_device(self.meta.socket()?)
+    }
+
+    /// Creates a new `TcpStream` sharing the same underlying socket, with
+    /// `F_DUPFD_CLOEXEC`. The new fd is registered to the current driver and
+    /// closed independently, the connection is closed once both of them are
+    /// closed, or shut down explicitly.
+    ///
+    /// An error is returned for a stream accepted into a direct descriptor,
+    /// which has no fd.
+    #[cfg(unix)]
+    pub fn try_clone(&self) -> io::Result<Self> {
+        Ok(Self::from_shared_fd(self.fd.try_clone()?))
+    }
+
     /// Creates new `TcpStream` from a `std::net::TcpStream`.
     #[cfg(unix)]
     pub fn from_std(stream: std::net::TcpStream) -> io::Result<Self> {
+        // The io of legacy driver relies on readiness.
+        if crate::driver::op::is_legacy() {
+            stream.set_nonblocking(true)?;
+        }
         match SharedFd::new::<false>(stream.as_raw_fd()) {
             Ok(shared) => {
                 stream.into_raw_fd();
@@ -248,6 +486,45 @@ impl TcpStream {
         }
     }
 
+    /// Converts the stream into a `std::net::TcpStream`. The stream must not
+    /// be split.
+    ///
+    /// For the stream accepted by [`TcpListener::accept_direct`], a fd is
+    /// installed for the direct descriptor first(requires kernel 6.8+).
+    ///
+    /// [`TcpListener::accept_direct`]: crate::net::TcpListener::accept_direct
+    #[cfg(unix)]
+    pub async fn into_std(self) -> io::Result<std::net::TcpStream> {
+        #[cfg(all(target_os = "linux", feature = "iouring"))]
+        let installed = match self.fd.is_direct() {
+            true => {
+                let fd = Op::fixed_fd_install(&self.fd)?.await.meta.result? as RawFd;
+                Some(unsafe { std::net::TcpStream::from_raw_fd(fd) })
+            }
+            false => None,
+        };
+        // The slot of a direct descriptor is given back, and the installed fd
+        // keeps the socket.
+        let fd = self
+            .fd
+            .try_unwrap()
+            .map_err(|_| io::Error::other("unexpected multiple reference to rawfd"))?;
+        #[cfg(all(target_os = "linux", feature = "iouring"))]
+        if let Some(stream) = installed {
+            return Ok(stream);
+        }
+        Ok(unsafe { std::net::TcpStream::from_raw_fd(fd) })
+    }
+
+    /// Closes the stream and waits for the close to complete, so the error of
+    /// closing can be observed. On uring driver it is done with a `Close` op.
+    ///
+    /// If the stream is split or has in-flight ops, the close waits for them.
+    /// Dropping the stream closes it in the background without reporting.
+    pub async fn close(self) -> io::Result<()> {
+        self.fd.close().await
+    }
+
     /// Wait for read readiness.
     /// Note: Do not use it before every io. It is different from other runtimes!
     ///
@@ -277,6 +554,296 @@ impl TcpStream {
         let op = Op::poll_write(&self.fd, relaxed).unwrap();
         op.wait().await
     }
+
+    /// Try to read data into `buf` without waiting, fail with `WouldBlock`
+    /// if there is nothing to read. Returns 0 at EOF.
+    ///
+    /// It is a plain syscall, to be used with [`readable`](Self::readable)
+    /// by code which does not pass the ownership of buffers.
+    #[cfg(unix)]
+    pub fn try_read(&self, buf: &mut [u8]) -> io::Result<usize> {
+        let fd = self.meta.socket()?.as_raw_fd();
+        let res = crate::syscall_u32!(recv(
+            fd,
+            buf.as_mut_ptr() as _,
+            buf.len(),
+            libc::MSG_DONTWAIT
+        ));
+        self.fd.clear_readiness_if_blocked(&res, true);
+        res.map(|n| n as usize)
+    }
+
+    /// Try to write data of `buf` without waiting, fail with `WouldBlock` if
+    /// the send buffer is full.
+    ///
+    /// It is a plain syscall, to be used with [`writable`](Self::writable)
+    /// by code which does not pass the ownership of buffers.
+    #[cfg(unix)]
+    pub fn try_write(&self, buf: &[u8]) -> io::Result<usize> {
+        let fd = self.meta.socket()?.as_raw_fd();
+        #[cfg(target_os = "linux")]
+        let flags = libc::MSG_DONTWAIT | libc::MSG_NOSIGNAL;
+        #[cfg(not(target_os = "linux"))]
+        let flags = libc::MSG_DONTWAIT;
+        let res = crate::syscall_u32!(send(fd, buf.as_ptr() as _, buf.len(), flags));
+        self.fd.clear_readiness_if_blocked(&res, false);
+        res.map(|n| n as usize)
+    }
+
+    /// Read some data without removing it from the receive queue, so the next
+    /// read or peek returns the same data. Returns 0 at EOF.
+    #[cfg(unix)]
+    pub async fn peek<T: IoBufMut>(&self, buf: T) -> BufResult<usize, T> {
+        let op = Op::recv_peek(self.fd.clone(), buf).unwrap();
+        op.read().await
+    }
+
+    /// Write with `MSG_MORE`, which hints the kernel that more data is coming
+    /// so it does not send a small segment, e.g. when the header and the body
+    /// of a response are in different buffers. The data is flushed by the
+    /// next write without the flag.
+    #[cfg(target_os = "linux")]
+    pub async fn write_more<T: IoBuf>(&mut self, buf: T) -> BufResult<usize, T> {
+        let op = Op::send_more(self.fd.clone(), buf).unwrap();
+        op.write().await
+    }
+
+    /// Read some data, and fail with `TimedOut` if nothing is read within
+    /// `timeout`. The buffer is returned either way, and nothing is read into
+    /// it once timed out.
+    ///
+    /// On uring driver the read is linked to a timeout op which cancels it, so
+    /// no timer is allocated. On legacy driver the timer of the runtime is
+    /// used, which must be enabled.
+    pub async fn read_timeout<T: IoBufMut>(
+        &mut self,
+        buf: T,
+        timeout: Duration,
+    ) -> BufResult<usize, T> {
+        Op::recv_timeout(&self.fd, buf, timeout).await
+    }
+
+    /// Write some data, and fail with `TimedOut` if nothing is written within
+    /// `timeout`, e.g. when the peer does not read and the send buffer is
+    /// full. See [`TcpStream::read_timeout`] for how the timeout works.
+    pub async fn write_timeout<T: IoBuf>(
+        &mut self,
+        buf: T,
+        timeout: Duration,
+    ) -> BufResult<usize, T> {
+        Op::send_timeout(&self.fd, buf, timeout).await
+    }
+
+    /// Shut down the read, write, or both halves of this connection.
+    ///
+    /// Shutting down the write half sends a FIN to the peer, and the stream
+    /// can still read the response. A stream which is no longer connected is
+    /// treated as already shut down.
+    ///
+    /// Unlike [`AsyncWriteRent::shutdown`], which always shuts down the write
+    /// half with a direct syscall, this submits a shutdown op(requires kernel
+    /// 5.11+ on uring driver).
+    #[cfg(unix)]
+    pub async fn shutdown_with(&self, how: std::net::Shutdown) -> io::Result<()> {
+        let op = Op::shutdown(&self.fd, how)?;
+        match op.await.meta.result {
+            Err(e) if e.raw_os_error() == Some(libc::ENOTCONN) => Ok(()),
+            res => res.map(|_| ()),
+        }
+    }
+
+    /// Write with zero copy.
+    ///
+    /// On uring driver with kernel 6.0+, the data is sent with `SendZc` and
+    /// the buffer is returned after the kernel notifies it is no longer used.
+    /// Otherwise it degrades to a normal write. Use
+    /// [`TcpStream::is_write_zc_supported`] to check which one is used.
+    ///
+    /// Zero copy only pays off for large writes(around 10 KB or more).
+    pub async fn write_zc<T: IoBuf>(&mut self, buf: T) -> BufResult<usize, T> {
+        #[cfg(all(target_os = "linux", feature = "iouring"))]
+        if Self::is_write_zc_supported() {
+            let op = Op::send_zc(self.fd.clone(), buf).unwrap();
+            return op.wait().await;
+        }
+        self.write(buf).await
+    }
+
+    /// Check if [`TcpStream::write_zc`] sends with zero copy on current
+    /// runtime.
+    pub fn is_write_zc_supported() -> bool {
+        #[cfg(all(target_os = "linux", feature = "iouring"))]
+        return crate::driver::op::is_uring_op_supported(io_uring::opcode::SendZc::CODE);
+        #[cfg(not(all(target_os = "linux", feature = "iouring")))]
+        false
+    }
+
+    /// Register the fd into the file table of io_uring driver, then ops on the
+    /// stream address the file by its slot, which saves looking up the file
+    /// for every op. The slot is given back when the stream is closed.
+    ///
+    /// The table must be enabled with [`RuntimeBuilder::register_files`].
+    /// Return false if the fd is not registered(e.g. on legacy driver or the
+    /// table is full), and the stream keeps working with the fd as is.
+    ///
+    /// [`RuntimeBuilder::register_files`]: crate::RuntimeBuilder::register_files
+    pub fn register(&mut self) -> bool {
+        #[cfg(all(target_os = "linux", feature = "iouring"))]
+        return self.fd.register_fixed();
+        #[cfg(not(all(target_os = "linux", feature = "iouring")))]
+        false
+    }
+
+    /// Write data from a buffer taken from
+    /// [`FixedBufPool`](crate::buf::FixedBufPool).
+    ///
+    /// It works like a normal write, but the kernel does not need to map the
+    /// buffer for every write since it is registered in advance.
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    pub async fn write_fixed(
+        &mut self,
+        buf: crate::buf::FixedBuf,
+    ) -> BufResult<usize, crate::buf::FixedBuf> {
+        let op = Op::write_fixed_at(&self.fd, buf, 0).unwrap();
+        op.write().await
+    }
+
+    /// Receive data into a buffer picked by the kernel from the given
+    /// [`BufRing`](crate::buf::BufRing).
+    ///
+    /// The returned buffer is given back to the ring on drop. If all buffers
+    /// of the ring are in use, an error with `ENOBUFS` is returned, and it can
+    /// be retried after some buffers are dropped.
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    pub async fn recv_provided(
+        &mut self,
+        ring: &crate::buf::BufRing,
+    ) -> io::Result<crate::buf::ProvidedBuf> {
+        let op = Op::recv_provided(self.fd.clone(), ring.inner().clone())?;
+        op.wait().await
+    }
+
+    /// Receive data continuously with a single multishot recv.
+    ///
+    /// On uring driver with kernel 6.0+, one multishot recv backed by a ring
+    /// of provided buffers serves many reads, and it is only re-armed when
+    /// the kernel terminates it. On legacy driver or older kernels, it is
+    /// emulated with repeated recv calls.
+    ///
+    /// EOF is yielded as an empty `Bytes`, and it or an error is the last
+    /// item of the stream.
+    #[cfg(feature = "bytes")]
+    pub fn recv_multi(&mut self) -> RecvMulti<'_> {
+        RecvMulti {
+            stream: self,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            op: None,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            ring: None,
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            fallback: false,
+            done: false,
+        }
+    }
+}
+
+/// Stream of received data created by [`TcpStream::recv_multi`].
+#[cfg(feature = "bytes")]
+pub struct RecvMulti<'a> {
+    stream: &'a mut TcpStream,
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    op: Option<Op<crate::driver::op::RecvMulti>>,
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    ring: Option<std::rc::Rc<crate::driver::BufRing>>,
+    // Set if multishot recv is not supported by the kernel
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    fallback: bool,
+    done: bool,
+}
+
+#[cfg(feature = "bytes")]
+impl RecvMulti<'_> {
+    const RING_ENTRIES: u16 = 64;
+    const BUF_LEN: usize = 4096;
+
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    async fn recv_uring(&mut self) -> Option<io::Result<bytes::Bytes>> {
+        loop {
+            let ring = match self.ring.as_ref() {
+                Some(ring) => ring.clone(),
+                None => match crate::driver::BufRing::new(Self::RING_ENTRIES, Self::BUF_LEN) {
+                    Ok(ring) => self.ring.insert(std::rc::Rc::new(ring)).clone(),
+                    // Provided buffer ring is not supported.
+                    Err(_) => return None,
+                },
+            };
+            let op = match self.op.as_mut() {
+                Some(op) => op,
+                None => match Op::recv_multi(self.stream.fd.clone(), ring.clone()) {
+                    Ok(op) => self.op.insert(op),
+                    Err(e) => return Some(Err(e)),
+                },
+            };
+            let meta = std::future::poll_fn(|cx| op.poll_multishot(cx)).await;
+            if !meta.has_more() {
+                // The multishot is terminated by the kernel, re-arm on next call.
+                self.op = None;
+            }
+            match meta.result {
+                Ok(0) => return Some(Ok(bytes::Bytes::new())),
+                Ok(n) => {
+                    let bid =
+                        io_uring::cqueue::buffer_select(meta.flags).expect("buffer id is expected");
+                    // Safety: the kernel wrote `n` bytes to the buffer.
+                    let data = bytes::Bytes::copy_from_slice(unsafe { ring.buf(bid, n as _) });
+                    ring.recycle(bid);
+                    return Some(Ok(data));
+                }
+                // All buffers are in use, re-arm when they are recycled.
+                Err(e) if e.raw_os_error() == Some(libc::ENOBUFS) => continue,
+                // Multishot recv is not supported.
+                Err(e) if e.raw_os_error() == Some(libc::EINVAL) => return None,
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl crate::io::stream::Stream for RecvMulti<'_> {
+    type Item = io::Result<bytes::Bytes>;
+
+    async fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        #[cfg(all(target_os = "linux", feature = "iouring"))]
+        if !self.fallback && !crate::driver::op::is_legacy() {
+            match self.recv_uring().await {
+                Some(item) => {
+                    self.done = !matches!(item, Ok(ref data) if !data.is_empty());
+                    return Some(item);
+                }
+                None => self.fallback = true,
+            }
+        }
+        let buf = Vec::with_capacity(Self::BUF_LEN);
+        let (res, buf) = self.stream.read(buf).await;
+        self.done = !matches!(res, Ok(n) if n != 0);
+        Some(res.map(|_| buf.into()))
+    }
+}
+
+#[cfg(all(feature = "bytes", target_os = "linux", feature = "iouring"))]
+impl Drop for RecvMulti<'_> {
+    fn drop(&mut self) {
+        // Multishot recv does not finish until EOF, so we must cancel it even
+        // if async-cancel is not enabled.
+        #[cfg(not(feature = "async-cancel"))]
+        if let Some(op) = self.op.take() {
+            unsafe { op.op_canceller().cancel() };
+        }
+    }
 }
 
 impl AsReadFd for TcpStream {
@@ -310,6 +877,16 @@ impl AsRawFd for TcpStream {
     }
 }
 
+#[cfg(unix)]
+impl TryFrom<OwnedFd> for TcpStream {
+    type Error = io::Error;
+
+    /// The fd must be a connected TCP socket, see [`TcpStream::from_std`].
+    fn try_from(fd: OwnedFd) -> io::Result<Self> {
+        Self::from_std(std::net::TcpStream::from(fd))
+    }
+}
+
 #[cfg(windows)]
 impl IntoRawSocket for TcpStream {
     #[inline]
@@ -356,14 +933,30 @@ impl AsyncWriteRent for TcpStream {
 
     #[cfg(unix)]
     fn shutdown(&mut self) -> impl Future<Output = std::io::Result<()>> {
-        // We could use shutdown op here, which requires kernel 5.11+.
-        // However, for simplicity, we just close the socket using direct syscall.
-        let fd = self.as_raw_fd();
-        let res = match unsafe { libc::shutdown(fd, libc::SHUT_WR) } {
-            -1 => Err(io::Error::last_os_error()),
-            _ => Ok(()),
+        // Dropping an `OwnedWriteHalf` only executes the sync part of this
+        // function, so the syscall is issued before the future is returned.
+        // A direct descriptor has no fd for the syscall, the op is submitted
+        // right away instead and runs even if the future is dropped.
+        #[cfg(all(target_os = "linux", feature = "iouring"))]
+        let op = self
+            .fd
+            .fixed_index()
+            .map(|_| Op::shutdown(&self.fd, std::net::Shutdown::Write));
+        #[cfg(all(target_os = "linux", feature = "iouring"))]
+        let direct = op.is_some();
+        #[cfg(not(all(target_os = "linux", feature = "iouring")))]
+        let direct = false;
+        let res = match direct || unsafe { libc::shutdown(self.as_raw_fd(), libc::SHUT_WR) } != -1 {
+            true => Ok(()),
+            false => Err(io::Error::last_os_error()),
         };
-        async move { res }
+        async move {
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            if let Some(op) = op {
+                return op?.await.meta.result.map(|_| ());
+            }
+            res
+        }
     }
 
     #[cfg(windows)]
@@ -415,8 +1008,9 @@ impl CancelableAsyncWriteRent for TcpStream {
 
     #[cfg(unix)]
     fn cancelable_shutdown(&mut self, _c: CancelHandle) -> impl Future<Output = io::Result<()>> {
-        // We could use shutdown op here, which requires kernel 5.11+.
-        // However, for simplicity, we just close the socket using direct syscall.
+        // The shutdown op is not used here: dropping an `OwnedWriteHalf` only
+        // executes the sync part of this function, so the syscall is issued
+        // before the future is returned. Use `shutdown_with` for the op.
         let fd = self.as_raw_fd();
         let res = match unsafe { libc::shutdown(fd, libc::SHUT_WR) } {
             -1 => Err(io::Error::last_os_error()),
@@ -586,6 +1180,16 @@ impl StreamMeta {
         unimplemented!()
     }
 
+    // The socket is None for a direct descriptor.
+    fn socket(&self) -> io::Result<&socket2::Socket> {
+        self.socket.as_ref().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::Unsupported,
+                "direct descriptor has no fd, convert it with into_std",
+            )
+        })
+    }
+
     fn local_addr(&self) -> io::Result<SocketAddr> {
         let meta = unsafe { &mut *self.meta.get() };
         if let Some(addr) = meta.local_addr {
@@ -593,9 +1197,7 @@ impl StreamMeta {
         }
 
         let ret = self
-            .socket
-            .as_ref()
-            .unwrap()
+            .socket()?
             .local_addr()
             .map(|addr| addr.as_socket().expect("tcp socket is expected"));
         if let Ok(addr) = ret {
@@ -611,9 +1213,7 @@ impl StreamMeta {
         }
 
         let ret = self
-            .socket
-            .as_ref()
-            .unwrap()
+            .socket()?
             .peer_addr()
             .map(|addr| addr.as_socket().expect("tcp socket is expected"));
         if let Ok(addr) = ret {
@@ -623,11 +1223,11 @@ impl StreamMeta {
     }
 
     fn no_delay(&self) -> io::Result<bool> {
-        self.socket.as_ref().unwrap().nodelay()
+        self.socket()?.nodelay()
     }
 
     fn set_no_delay(&self, no_delay: bool) -> io::Result<()> {
-        self.socket.as_ref().unwrap().set_nodelay(no_delay)
+        self.socket()?.set_nodelay(no_delay)
     }
 
     #[allow(unused_variables)]
@@ -648,7 +1248,7 @@ impl StreamMeta {
         if let Some(retries) = retries {
             t = t.with_retries(retries)
         }
-        self.socket.as_ref().unwrap().set_tcp_keepalive(&t)
+        self.socket()?.set_tcp_keepalive(&t)
     }
 
     #[cfg(feature = "zero-copy")]
@@ -671,7 +1271,9 @@ impl StreamMeta {
 impl Drop for StreamMeta {
     fn drop(&mut self) {
         #[cfg(unix)]
-        self.socket.take().unwrap().into_raw_fd();
+        if let Some(socket) = self.socket.take() {
+            socket.into_raw_fd();
+        }
         #[cfg(windows)]
         unimplemented!()
     }
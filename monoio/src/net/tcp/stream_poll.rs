@@ -2,7 +2,7 @@
 
 use std::{io, net::SocketAddr, os::fd::AsRawFd, time::Duration};
 
-use super::TcpStream;
+use super::{KeepAlive, TcpStream};
 use crate::driver::op::Op;
 
 /// A TcpStream with poll-io style interface.
@@ -166,6 +166,19 @@ impl TcpStreamPoll {
     ) -> io::Result<()> {
         self.0.set_tcp_keepalive(time, interval, retries)
     }
+
+    /// Enable `SO_KEEPALIVE` with the idle time, interval and retries of the
+    /// probes, or disable it with None.
+    #[inline]
+    pub fn set_keepalive(&self, keepalive: Option<KeepAlive>) -> io::Result<()> {
+        self.0.set_keepalive(keepalive)
+    }
+
+    /// Get the keepalive parameters, None if `SO_KEEPALIVE` is disabled.
+    #[inline]
+    pub fn keepalive(&self) -> io::Result<Option<KeepAlive>> {
+        self.0.keepalive()
+    }
 }
 
 impl AsRawFd for TcpStreamPoll {
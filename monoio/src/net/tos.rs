@@ -0,0 +1,46 @@
+//! The traffic class of the packets sent, `IP_TOS` on IPv4 sockets and
+//! `IPV6_TCLASS` on IPv6 sockets. The family is taken from the socket, so an
+//! IPv6 socket sending to an IPv4-mapped address uses `IPV6_TCLASS` too.
+
+use std::{io, os::unix::io::AsRawFd};
+
+/// The level and name of the option for the family of the socket, which are
+/// also the level and type of the control message.
+pub(crate) fn option(socket: &socket2::Socket) -> io::Result<(libc::c_int, libc::c_int)> {
+    if socket.local_addr()?.is_ipv6() {
+        Ok((libc::IPPROTO_IPV6, libc::IPV6_TCLASS))
+    } else {
+        Ok((libc::IPPROTO_IP, libc::IP_TOS))
+    }
+}
+
+pub(crate) fn set_tos(socket: &socket2::Socket, tos: u8) -> io::Result<()> {
+    let (level, name) = option(socket)?;
+    let value = tos as libc::c_int;
+    crate::syscall!(setsockopt(
+        socket.as_raw_fd(),
+        level,
+        name,
+        &value as *const libc::c_int as *const libc::c_void,
+        std::mem::size_of::<libc::c_int>() as libc::socklen_t
+    ))
+    .map(|_| ())
+}
+
+pub(crate) fn tos(socket: &socket2::Socket) -> io::Result<u8> {
+    let (level, name) = option(socket)?;
+    let mut value: libc::c_int = 0;
+    let mut len = std::mem::size_of::<libc::c_int>() as libc::socklen_t;
+    crate::syscall!(getsockopt(
+        socket.as_raw_fd(),
+        level,
+        name,
+        &mut value as *mut libc::c_int as *mut libc::c_void,
+        &mut len
+    ))?;
+    // IP_TOS may be returned in one byte on some platforms.
+    if len as usize == std::mem::size_of::<u8>() {
+        return Ok(value.to_ne_bytes()[0]);
+    }
+    Ok(value as u8)
+}
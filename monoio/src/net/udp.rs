@@ -1,14 +1,24 @@
 //! UDP impl.
 
-#[cfg(unix)]
-use std::os::unix::prelude::{AsRawFd, FromRawFd, IntoRawFd};
 #[cfg(windows)]
-use std::os::windows::prelude::{AsRawSocket, FromRawSocket, IntoRawSocket, RawSocket};
+use std::os::windows::prelude::{
+    AsRawSocket, AsSocket, BorrowedSocket, FromRawSocket, IntoRawSocket, RawSocket,
+};
 use std::{
     io,
-    net::{SocketAddr, ToSocketAddrs},
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, ToSocketAddrs},
+};
+#[cfg(unix)]
+use {
+    crate::driver::op::{recv_batch, send_batch, RecvBatch, SendBatch},
+    crate::net::tos,
+    std::os::unix::prelude::{AsFd, AsRawFd, BorrowedFd, FromRawFd, IntoRawFd},
 };
 
+use socket2::SockRef;
+
+#[cfg(unix)]
+use crate::buf::{IoVecBuf, IoVecBufMut};
 use crate::{
     buf::{IoBuf, IoBufMut},
     driver::{op::Op, shared_fd::SharedFd},
@@ -29,6 +39,62 @@ pub struct UdpSocket {
     fd: SharedFd,
 }
 
+/// Meta data of a datagram received by [`UdpSocket::recv_from_full`] or
+/// [`UdpSocket::recv_msg_meta`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecvMeta {
+    /// Number of bytes received into the buffer.
+    pub len: usize,
+    /// Real length of the datagram, which is larger than `len` if it is
+    /// truncated.
+    pub datagram_len: usize,
+    /// Size of the datagrams coalesced into the buffer with `UDP_GRO`, the
+    /// last one may be shorter. None if it is a single datagram, and it is
+    /// only reported by [`UdpSocket::recv_msg_meta`].
+    pub segment_size: Option<u16>,
+    /// Destination address of the datagram, i.e. the local address the peer
+    /// sent it to. It is reported by [`UdpSocket::recv_msg_meta`] if
+    /// [`UdpSocket::set_pktinfo`] is enabled.
+    pub dst_addr: Option<IpAddr>,
+    /// Index of the interface the datagram is received on, reported with
+    /// `dst_addr`.
+    pub ifindex: Option<u32>,
+    /// Origin of the datagram.
+    pub addr: SocketAddr,
+}
+
+impl RecvMeta {
+    /// Check if the datagram is truncated.
+    #[inline]
+    pub fn truncated(&self) -> bool {
+        self.datagram_len > self.len
+    }
+}
+
+#[cfg(unix)]
+fn check_flags(flags: libc::c_int, rejected: libc::c_int) -> io::Result<()> {
+    if flags & rejected != 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("unsupported flags {:#x}", flags & rejected),
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(all(unix, not(any(target_os = "linux", target_os = "android"))))]
+fn unsupported(option: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::Unsupported,
+        format!("{option} is not supported on this platform"),
+    )
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn as_bytes<T>(value: &T) -> &[u8] {
+    unsafe { std::slice::from_raw_parts(value as *const T as *const u8, std::mem::size_of::<T>()) }
+}
+
 /// UdpSocket is safe to split to two parts
 unsafe impl Split for UdpSocket {}
 
@@ -47,8 +113,15 @@ impl UdpSocket {
         })
     }
 
-    /// Creates a UDP socket from the given address.
+    /// Creates a UDP socket from the given address. An IPv6 wildcard address
+    /// like `[::]:8080` is dual-stack and receives IPv4 datagrams too.
     pub fn bind<A: ToSocketAddrs>(addr: A) -> io::Result<Self> {
+        Self::bind_with_only_v6(addr, false)
+    }
+
+    /// Creates a UDP socket from the given address with `IPV6_V6ONLY` set to
+    /// `only_v6`, which is ignored for IPv4 addresses.
+    pub fn bind_with_only_v6<A: ToSocketAddrs>(addr: A, only_v6: bool) -> io::Result<Self> {
         let addr = addr
             .to_socket_addrs()?
             .next()
@@ -63,6 +136,9 @@ impl UdpSocket {
         #[cfg(all(unix, feature = "legacy"))]
         Self::set_non_blocking(&socket)?;
 
+        if addr.is_ipv6() {
+            socket.set_only_v6(only_v6)?;
+        }
         let addr = socket2::SockAddr::from(addr);
         socket.bind(&addr)?;
 
@@ -81,6 +157,57 @@ impl UdpSocket {
         op.wait().await
     }
 
+    /// Receives a single datagram message on the socket, and reports if it
+    /// is truncated because the buffer is too small.
+    ///
+    /// The part of the datagram which does not fit in the buffer is
+    /// discarded, and the real length of it is returned in [`RecvMeta`] so
+    /// the caller can enlarge the buffer.
+    #[cfg(unix)]
+    pub async fn recv_from_full<T: IoBufMut>(&self, buf: T) -> crate::BufResult<RecvMeta, T> {
+        let op = Op::recv_msg_trunc(self.fd.clone(), buf).unwrap();
+        op.wait_meta().await
+    }
+
+    /// Receives a single datagram message on the socket like
+    /// [`recv_from_full`], and also reports the meta data in the control
+    /// messages the socket is set to receive:
+    ///
+    /// - The destination address and the interface index with
+    ///   [`set_pktinfo`], so a server bound to the wildcard address can reply
+    ///   from the address the peer sent to with [`send_to_from`].
+    /// - The segment size of the datagrams coalesced with [`set_gro`].
+    ///
+    /// It returns `Unsupported` on the platforms other than Linux.
+    ///
+    /// [`recv_from_full`]: UdpSocket::recv_from_full
+    /// [`set_pktinfo`]: UdpSocket::set_pktinfo
+    /// [`send_to_from`]: UdpSocket::send_to_from
+    /// [`set_gro`]: UdpSocket::set_gro
+    #[cfg(unix)]
+    pub async fn recv_msg_meta<T: IoBufMut>(&self, buf: T) -> crate::BufResult<RecvMeta, T> {
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        {
+            let op = Op::recv_msg_meta(self.fd.clone(), buf).unwrap();
+            op.wait_meta().await
+        }
+        #[cfg(not(any(target_os = "linux", target_os = "android")))]
+        (Err(unsupported("IP_PKTINFO")), buf)
+    }
+
+    /// Receives the datagrams coalesced by the kernel into the buffer if
+    /// `UDP_GRO` is enabled with [`set_gro`], [`RecvMeta::segment_size`] is
+    /// the size to split it. It is the same as [`recv_msg_meta`].
+    ///
+    /// It returns `Unsupported` on the platforms other than Linux.
+    ///
+    /// [`set_gro`]: UdpSocket::set_gro
+    /// [`recv_msg_meta`]: UdpSocket::recv_msg_meta
+    #[cfg(unix)]
+    pub async fn recv_from_gro<T: IoBufMut>(&self, buf: T) -> crate::BufResult<RecvMeta, T> {
+        self.recv_msg_meta(buf).await
+    }
+
     /// Sends data on the socket to the given address. On success, returns the
     /// number of bytes written.
     pub async fn send_to<T: IoBuf>(
@@ -92,6 +219,261 @@ impl UdpSocket {
         op.wait().await
     }
 
+    /// Sends the data gathered from the buffers(e.g. a [`VecBuf`]) on the
+    /// socket to the given address as a single datagram. On success, returns
+    /// the number of bytes written.
+    ///
+    /// [`VecBuf`]: crate::buf::VecBuf
+    #[cfg(unix)]
+    pub async fn send_to_vectored<T: IoVecBuf>(
+        &self,
+        buf_vec: T,
+        socket_addr: SocketAddr,
+    ) -> crate::BufResult<usize, T> {
+        let op = Op::send_msg_vectored(self.fd.clone(), buf_vec, Some(socket_addr)).unwrap();
+        op.wait().await
+    }
+
+    /// Sends data on the socket to the given address with the flags(e.g.
+    /// `MSG_DONTROUTE` or `MSG_CONFIRM`). `MSG_NOSIGNAL` is always set on
+    /// Linux.
+    ///
+    /// Flags which break the completion model(`MSG_DONTWAIT`) are rejected
+    /// with `InvalidInput`.
+    #[cfg(unix)]
+    pub async fn send_to_with_flags<T: IoBuf>(
+        &self,
+        buf: T,
+        socket_addr: SocketAddr,
+        flags: i32,
+    ) -> crate::BufResult<usize, T> {
+        if let Err(e) = check_flags(flags, libc::MSG_DONTWAIT) {
+            return (Err(e), buf);
+        }
+        let op = Op::send_msg_with_flags(self.fd.clone(), buf, Some(socket_addr), flags).unwrap();
+        op.wait().await
+    }
+
+    /// Sends a batch of datagrams, each to its address, in order. It is one
+    /// `sendmmsg` on legacy driver, and the `sendmsg` of the datagrams linked
+    /// and submitted together on uring driver.
+    ///
+    /// Returns how many datagrams from the head of the batch are fully sent,
+    /// and all the buffers. The ones after a failed datagram are not sent, so
+    /// the caller can retry the tail, the error is only returned if the
+    /// first one fails.
+    #[cfg(unix)]
+    pub async fn send_batch<T: IoBuf>(
+        &self,
+        msgs: Vec<(T, SocketAddr)>,
+    ) -> (io::Result<usize>, Vec<T>) {
+        let batch = std::rc::Rc::new(SendBatch::new(self.fd.clone(), msgs));
+        let res = send_batch(&batch).await;
+        // All the operations have completed and released the batch.
+        let bufs = match std::rc::Rc::try_unwrap(batch) {
+            Ok(batch) => batch.into_bufs(),
+            Err(_) => unreachable!("operations of the batch are in flight"),
+        };
+        (res, bufs)
+    }
+
+    /// Sends the buffer to the given address as datagrams of `segment_size`
+    /// bytes, the kernel splits it with the `UDP_SEGMENT` message attached.
+    /// On success, returns the number of bytes written.
+    ///
+    /// The errors are the same as [`set_gso_segment`], and it returns
+    /// `Unsupported` on the platforms other than Linux.
+    ///
+    /// [`set_gso_segment`]: UdpSocket::set_gso_segment
+    #[cfg(unix)]
+    pub async fn send_to_segmented<T: IoBuf>(
+        &self,
+        buf: T,
+        socket_addr: SocketAddr,
+        segment_size: u16,
+    ) -> crate::BufResult<usize, T> {
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        {
+            let op = Op::send_msg_with_cmsg(
+                self.fd.clone(),
+                buf,
+                socket_addr,
+                libc::SOL_UDP,
+                libc::UDP_SEGMENT,
+                &segment_size.to_ne_bytes(),
+            );
+            op.unwrap().wait().await
+        }
+        #[cfg(not(any(target_os = "linux", target_os = "android")))]
+        {
+            let _ = (socket_addr, segment_size);
+            (Err(unsupported("UDP_SEGMENT")), buf)
+        }
+    }
+
+    /// Sends data on the socket to the given address with the traffic class
+    /// of this datagram, which overrides the one set with [`set_tos`]. On
+    /// success, returns the number of bytes written.
+    ///
+    /// It attaches an `IP_TOS` or `IPV6_TCLASS` message by the family of the
+    /// socket, the platforms which do not take it on send return the error.
+    ///
+    /// [`set_tos`]: UdpSocket::set_tos
+    #[cfg(unix)]
+    pub async fn send_to_with_tos<T: IoBuf>(
+        &self,
+        buf: T,
+        socket_addr: SocketAddr,
+        tos: u8,
+    ) -> crate::BufResult<usize, T> {
+        let (level, ty) = match tos::option(&SockRef::from(self)) {
+            Ok(option) => option,
+            Err(e) => return (Err(e), buf),
+        };
+        let data = (tos as libc::c_int).to_ne_bytes();
+        let op = Op::send_msg_with_cmsg(self.fd.clone(), buf, socket_addr, level, ty, &data);
+        op.unwrap().wait().await
+    }
+
+    /// Sends data on the socket to the given address from the local address
+    /// `src`, which is usually the destination address of the datagram being
+    /// replied, see [`recv_msg_meta`]. On success, returns the number of
+    /// bytes written.
+    ///
+    /// It attaches an `IP_PKTINFO` or `IPV6_PKTINFO` message by the family of
+    /// `src`, and returns `Unsupported` on the platforms other than Linux.
+    ///
+    /// [`recv_msg_meta`]: UdpSocket::recv_msg_meta
+    #[cfg(unix)]
+    pub async fn send_to_from<T: IoBuf>(
+        &self,
+        buf: T,
+        socket_addr: SocketAddr,
+        src: IpAddr,
+    ) -> crate::BufResult<usize, T> {
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        {
+            let op = match src {
+                IpAddr::V4(src) => {
+                    let info = libc::in_pktinfo {
+                        ipi_ifindex: 0,
+                        ipi_spec_dst: libc::in_addr {
+                            s_addr: u32::from_ne_bytes(src.octets()),
+                        },
+                        ipi_addr: libc::in_addr { s_addr: 0 },
+                    };
+                    Op::send_msg_with_cmsg(
+                        self.fd.clone(),
+                        buf,
+                        socket_addr,
+                        libc::IPPROTO_IP,
+                        libc::IP_PKTINFO,
+                        as_bytes(&info),
+                    )
+                }
+                IpAddr::V6(src) => {
+                    let info = libc::in6_pktinfo {
+                        ipi6_addr: libc::in6_addr {
+                            s6_addr: src.octets(),
+                        },
+                        ipi6_ifindex: 0,
+                    };
+                    Op::send_msg_with_cmsg(
+                        self.fd.clone(),
+                        buf,
+                        socket_addr,
+                        libc::IPPROTO_IPV6,
+                        libc::IPV6_PKTINFO,
+                        as_bytes(&info),
+                    )
+                }
+            };
+            op.unwrap().wait().await
+        }
+        #[cfg(not(any(target_os = "linux", target_os = "android")))]
+        {
+            let _ = (socket_addr, src);
+            (Err(unsupported("IP_PKTINFO")), buf)
+        }
+    }
+
+    /// Receives a batch of datagrams into the buffers, waiting for the first
+    /// one and then taking the ones already queued. It is one `recvmmsg` with
+    /// `MSG_WAITFORONE` on legacy driver, and the `recvmsg` of the buffers
+    /// linked and submitted together on uring driver.
+    ///
+    /// Returns how many buffers are filled, and all the buffers in order with
+    /// the length and origin of the datagram. The buffers which are not
+    /// filled are untouched, and come back with 0 and the unspecified address.
+    #[cfg(unix)]
+    pub async fn recv_batch<T: IoBufMut>(
+        &self,
+        bufs: Vec<T>,
+    ) -> (io::Result<usize>, Vec<(T, usize, SocketAddr)>) {
+        let batch = std::rc::Rc::new(RecvBatch::new(self.fd.clone(), bufs));
+        let res = recv_batch(&batch).await;
+        // All the operations have completed and released the batch.
+        let bufs = match std::rc::Rc::try_unwrap(batch) {
+            Ok(batch) => batch.into_bufs(*res.as_ref().unwrap_or(&0)),
+            Err(_) => unreachable!("operations of the batch are in flight"),
+        };
+        (res, bufs)
+    }
+
+    /// Receives a single datagram message on the socket, scattering it into
+    /// the buffers(e.g. a [`VecBuf`]) in order. On success, returns the total
+    /// number of bytes read and the origin.
+    ///
+    /// [`VecBuf`]: crate::buf::VecBuf
+    #[cfg(unix)]
+    pub async fn recv_from_vectored<T: IoVecBufMut>(
+        &self,
+        buf_vec: T,
+    ) -> crate::BufResult<(usize, SocketAddr), T> {
+        let op = Op::recv_msg_vectored(self.fd.clone(), buf_vec).unwrap();
+        op.wait().await
+    }
+
+    /// Receives a single datagram message on the socket with the flags(e.g.
+    /// `MSG_OOB`).
+    ///
+    /// Flags which break the completion model or have their own API
+    /// (`MSG_DONTWAIT`, `MSG_PEEK`, `MSG_TRUNC` and `MSG_ERRQUEUE`) are
+    /// rejected with `InvalidInput`.
+    #[cfg(unix)]
+    pub async fn recv_from_with_flags<T: IoBufMut>(
+        &self,
+        buf: T,
+        flags: i32,
+    ) -> crate::BufResult<(usize, SocketAddr), T> {
+        #[cfg(target_os = "linux")]
+        const REJECTED: libc::c_int =
+            libc::MSG_DONTWAIT | libc::MSG_PEEK | libc::MSG_TRUNC | libc::MSG_ERRQUEUE;
+        #[cfg(not(target_os = "linux"))]
+        const REJECTED: libc::c_int = libc::MSG_DONTWAIT | libc::MSG_PEEK | libc::MSG_TRUNC;
+        if let Err(e) = check_flags(flags, REJECTED) {
+            return (Err(e), buf);
+        }
+        let op = Op::recv_msg_with_flags(self.fd.clone(), buf, flags).unwrap();
+        op.wait().await
+    }
+
+    /// Receives a single datagram message on the socket without removing it
+    /// from the queue, so the next receive returns it again. On success,
+    /// returns the number of bytes read and the origin.
+    ///
+    /// Like [`recv_from`], only the part of the datagram which fits in the
+    /// buffer is returned, but the datagram is kept whole in the queue, and
+    /// [`recv_from_full`] reports its real length.
+    ///
+    /// [`recv_from`]: UdpSocket::recv_from
+    /// [`recv_from_full`]: UdpSocket::recv_from_full
+    #[cfg(unix)]
+    pub async fn peek_from<T: IoBufMut>(&self, buf: T) -> crate::BufResult<(usize, SocketAddr), T> {
+        let op = Op::recv_msg_with_flags(self.fd.clone(), buf, libc::MSG_PEEK).unwrap();
+        op.wait().await
+    }
+
     /// Returns the socket address of the remote peer this socket was connected to.
     pub fn peer_addr(&self) -> io::Result<SocketAddr> {
         #[cfg(unix)]
@@ -147,9 +529,38 @@ impl UdpSocket {
         op.read().await
     }
 
+    /// Receives a single datagram message on the socket from the remote
+    /// address to which it is connected, without removing it from the queue.
+    #[cfg(unix)]
+    pub async fn peek<T: IoBufMut>(&self, buf: T) -> crate::BufResult<usize, T> {
+        let op = Op::recv_peek(self.fd.clone(), buf).unwrap();
+        op.read().await
+    }
+
+    /// Creates a new `UdpSocket` sharing the same underlying socket, with
+    /// `F_DUPFD_CLOEXEC`. The new fd is registered to the current driver and
+    /// closed independently, e.g. for one task to receive while another
+    /// sends.
+    #[cfg(unix)]
+    pub fn try_clone(&self) -> io::Result<Self> {
+        Ok(Self::from_shared_fd(self.fd.try_clone()?))
+    }
+
     /// Creates new `UdpSocket` from a `std::net::UdpSocket`.
     #[cfg(unix)]
     pub fn from_std(socket: std::net::UdpSocket) -> io::Result<Self> {
+        Self::from_socket(socket.into())
+    }
+
+    /// Creates new `UdpSocket` from a `socket2::Socket`, which is configured
+    /// and bound by the caller, e.g. with the options monoio does not have.
+    /// The socket must be a UDP socket.
+    #[cfg(unix)]
+    pub fn from_socket(socket: socket2::Socket) -> io::Result<Self> {
+        // The io of legacy driver relies on readiness.
+        if crate::driver::op::is_legacy() {
+            socket.set_nonblocking(true)?;
+        }
         match SharedFd::new::<false>(socket.as_raw_fd()) {
             Ok(shared) => {
                 socket.into_raw_fd();
@@ -159,6 +570,19 @@ impl UdpSocket {
         }
     }
 
+    /// Converts the socket into a `std::net::UdpSocket`. It fails if the
+    /// socket is split or there are ops in flight.
+    ///
+    /// On legacy driver the fd is deregistered and stays non-blocking.
+    #[cfg(unix)]
+    pub fn into_std(self) -> io::Result<std::net::UdpSocket> {
+        let fd = self
+            .fd
+            .try_unwrap()
+            .map_err(|_| io::Error::other("unexpected multiple reference to rawfd"))?;
+        Ok(unsafe { std::net::UdpSocket::from_raw_fd(fd) })
+    }
+
     /// Creates new `UdpSocket` from a `std::net::UdpSocket`.
     #[cfg(windows)]
     pub fn from_std(socket: std::net::UdpSocket) -> io::Result<Self> {
@@ -209,6 +633,331 @@ impl UdpSocket {
         r
     }
 
+    /// Bind the socket to the network interface by name, or remove the
+    /// binding with None. It is `SO_BINDTODEVICE` on Linux, which may fail
+    /// with `EPERM` without `CAP_NET_RAW`, and `IP_BOUND_IF` or
+    /// `IPV6_BOUND_IF` on macOS.
+    #[cfg(unix)]
+    pub fn set_bind_device(&self, device: Option<&str>) -> io::Result<()> {
+        crate::net::bind_device::set_bind_device(&SockRef::from(self), device)
+    }
+
+    /// Bind the socket to the network interface by index, or remove the
+    /// binding with 0.
+    #[cfg(unix)]
+    pub fn bind_device_by_index(&self, index: u32) -> io::Result<()> {
+        crate::net::bind_device::bind_device_by_index(&SockRef::from(self), index)
+    }
+
+    /// Get the name of the network interface the socket is bound to.
+    #[cfg(unix)]
+    pub fn bind_device(&self) -> io::Result<Option<String>> {
+        crate::net::bind_device::bind_device(&SockRef::from(self))
+    }
+
+    /// Set the value of the `IP_TTL` option on this socket.
+    pub fn set_ttl(&self, ttl: u32) -> io::Result<()> {
+        SockRef::from(self).set_ttl(ttl)
+    }
+
+    /// Get the value of the `IP_TTL` option on this socket.
+    pub fn ttl(&self) -> io::Result<u32> {
+        SockRef::from(self).ttl()
+    }
+
+    /// Set `SO_BROADCAST`, which allows sending to a broadcast address like
+    /// `255.255.255.255`.
+    pub fn set_broadcast(&self, broadcast: bool) -> io::Result<()> {
+        SockRef::from(self).set_broadcast(broadcast)
+    }
+
+    /// Get the value of the `SO_BROADCAST` option on this socket.
+    pub fn broadcast(&self) -> io::Result<bool> {
+        SockRef::from(self).broadcast()
+    }
+
+    /// Set `UDP_SEGMENT`, the kernel splits the buffer of each send into
+    /// datagrams of the segment size (GSO), and the last one may be shorter.
+    /// None disables it, [`send_to_segmented`] sets it for one send.
+    ///
+    /// It is only supported on Linux. The send fails with `EIO` if the device
+    /// can not offload the checksum, and `EINVAL` if the segments do not fit
+    /// in the MTU or are too many, so the caller can segment the data itself.
+    ///
+    /// [`send_to_segmented`]: UdpSocket::send_to_segmented
+    #[cfg(unix)]
+    pub fn set_gso_segment(&self, segment_size: Option<u16>) -> io::Result<()> {
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        {
+            let value = segment_size.unwrap_or(0) as libc::c_int;
+            crate::syscall!(setsockopt(
+                self.as_raw_fd(),
+                libc::SOL_UDP,
+                libc::UDP_SEGMENT,
+                &value as *const libc::c_int as *const libc::c_void,
+                std::mem::size_of::<libc::c_int>() as libc::socklen_t
+            ))
+            .map(|_| ())
+        }
+        #[cfg(not(any(target_os = "linux", target_os = "android")))]
+        {
+            let _ = segment_size;
+            Err(unsupported("UDP_SEGMENT"))
+        }
+    }
+
+    /// Get the value of `UDP_SEGMENT`, None if it is disabled.
+    #[cfg(unix)]
+    pub fn gso_segment(&self) -> io::Result<Option<u16>> {
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        {
+            let mut value: libc::c_int = 0;
+            let mut len = std::mem::size_of::<libc::c_int>() as libc::socklen_t;
+            crate::syscall!(getsockopt(
+                self.as_raw_fd(),
+                libc::SOL_UDP,
+                libc::UDP_SEGMENT,
+                &mut value as *mut libc::c_int as *mut libc::c_void,
+                &mut len
+            ))?;
+            Ok((value != 0).then_some(value as u16))
+        }
+        #[cfg(not(any(target_os = "linux", target_os = "android")))]
+        Err(unsupported("UDP_SEGMENT"))
+    }
+
+    /// Set `UDP_GRO`, the kernel coalesces the datagrams of the same size
+    /// from the same origin into one buffer, receive them with
+    /// [`recv_from_gro`] to get the segment size.
+    ///
+    /// It is only supported on Linux. The other receive methods do not tell
+    /// where the datagrams are split, so keep it disabled with them.
+    ///
+    /// [`recv_from_gro`]: UdpSocket::recv_from_gro
+    #[cfg(unix)]
+    pub fn set_gro(&self, gro: bool) -> io::Result<()> {
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        {
+            let value = gro as libc::c_int;
+            crate::syscall!(setsockopt(
+                self.as_raw_fd(),
+                libc::SOL_UDP,
+                libc::UDP_GRO,
+                &value as *const libc::c_int as *const libc::c_void,
+                std::mem::size_of::<libc::c_int>() as libc::socklen_t
+            ))
+            .map(|_| ())
+        }
+        #[cfg(not(any(target_os = "linux", target_os = "android")))]
+        {
+            let _ = gro;
+            Err(unsupported("UDP_GRO"))
+        }
+    }
+
+    /// Get the value of `UDP_GRO`.
+    #[cfg(unix)]
+    pub fn gro(&self) -> io::Result<bool> {
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        {
+            let mut value: libc::c_int = 0;
+            let mut len = std::mem::size_of::<libc::c_int>() as libc::socklen_t;
+            crate::syscall!(getsockopt(
+                self.as_raw_fd(),
+                libc::SOL_UDP,
+                libc::UDP_GRO,
+                &mut value as *mut libc::c_int as *mut libc::c_void,
+                &mut len
+            ))?;
+            Ok(value != 0)
+        }
+        #[cfg(not(any(target_os = "linux", target_os = "android")))]
+        Err(unsupported("UDP_GRO"))
+    }
+
+    /// Set `IP_PKTINFO` on IPv4 sockets, and `IPV6_RECVPKTINFO` with
+    /// `IP_PKTINFO` on IPv6 sockets for the IPv4-mapped ones, so
+    /// [`recv_msg_meta`] reports the destination address and the interface
+    /// of the datagrams.
+    ///
+    /// It is only supported on Linux.
+    ///
+    /// [`recv_msg_meta`]: UdpSocket::recv_msg_meta
+    #[cfg(unix)]
+    pub fn set_pktinfo(&self, pktinfo: bool) -> io::Result<()> {
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        {
+            let set = |level, name| {
+                let value = pktinfo as libc::c_int;
+                crate::syscall!(setsockopt(
+                    self.as_raw_fd(),
+                    level,
+                    name,
+                    &value as *const libc::c_int as *const libc::c_void,
+                    std::mem::size_of::<libc::c_int>() as libc::socklen_t
+                ))
+                .map(|_| ())
+            };
+            if self.local_addr()?.is_ipv6() {
+                set(libc::IPPROTO_IPV6, libc::IPV6_RECVPKTINFO)?;
+            }
+            set(libc::IPPROTO_IP, libc::IP_PKTINFO)
+        }
+        #[cfg(not(any(target_os = "linux", target_os = "android")))]
+        {
+            let _ = pktinfo;
+            Err(unsupported("IP_PKTINFO"))
+        }
+    }
+
+    /// Get the value of `IP_PKTINFO`, or `IPV6_RECVPKTINFO` on IPv6 sockets.
+    #[cfg(unix)]
+    pub fn pktinfo(&self) -> io::Result<bool> {
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        {
+            let (level, name) = if self.local_addr()?.is_ipv6() {
+                (libc::IPPROTO_IPV6, libc::IPV6_RECVPKTINFO)
+            } else {
+                (libc::IPPROTO_IP, libc::IP_PKTINFO)
+            };
+            let mut value: libc::c_int = 0;
+            let mut len = std::mem::size_of::<libc::c_int>() as libc::socklen_t;
+            crate::syscall!(getsockopt(
+                self.as_raw_fd(),
+                level,
+                name,
+                &mut value as *mut libc::c_int as *mut libc::c_void,
+                &mut len
+            ))?;
+            Ok(value != 0)
+        }
+        #[cfg(not(any(target_os = "linux", target_os = "android")))]
+        Err(unsupported("IP_PKTINFO"))
+    }
+
+    /// Set the traffic class of the datagrams sent, e.g. the DSCP marking.
+    /// It is `IP_TOS` on IPv4 sockets and `IPV6_TCLASS` on IPv6 sockets.
+    #[cfg(unix)]
+    pub fn set_tos(&self, tos: u8) -> io::Result<()> {
+        tos::set_tos(&SockRef::from(self), tos)
+    }
+
+    /// Get the value of `IP_TOS` or `IPV6_TCLASS` on this socket.
+    #[cfg(unix)]
+    pub fn tos(&self) -> io::Result<u8> {
+        tos::tos(&SockRef::from(self))
+    }
+
+    /// Set the size of the send buffer, `SO_SNDBUF`.
+    pub fn set_send_buffer_size(&self, size: u32) -> io::Result<()> {
+        SockRef::from(self).set_send_buffer_size(size as usize)
+    }
+
+    /// Get the size of the send buffer, which may be larger than the one set
+    /// since the kernel doubles it.
+    pub fn send_buffer_size(&self) -> io::Result<u32> {
+        SockRef::from(self)
+            .send_buffer_size()
+            .map(|size| size as u32)
+    }
+
+    /// Set the size of the receive buffer, `SO_RCVBUF`.
+    pub fn set_recv_buffer_size(&self, size: u32) -> io::Result<()> {
+        SockRef::from(self).set_recv_buffer_size(size as usize)
+    }
+
+    /// Get the size of the receive buffer, which may be larger than the one
+    /// set since the kernel doubles it.
+    pub fn recv_buffer_size(&self) -> io::Result<u32> {
+        SockRef::from(self)
+            .recv_buffer_size()
+            .map(|size| size as u32)
+    }
+
+    /// Get and clear the pending error of the socket, `SO_ERROR`.
+    pub fn take_error(&self) -> io::Result<Option<io::Error>> {
+        SockRef::from(self).take_error()
+    }
+
+    /// Join the IPv4 multicast group `multiaddr` on the interface with the
+    /// address `interface`, or the default one with `Ipv4Addr::UNSPECIFIED`.
+    pub fn join_multicast_v4(&self, multiaddr: Ipv4Addr, interface: Ipv4Addr) -> io::Result<()> {
+        SockRef::from(self).join_multicast_v4(&multiaddr, &interface)
+    }
+
+    /// Leave the IPv4 multicast group joined by
+    /// [`join_multicast_v4`](Self::join_multicast_v4).
+    pub fn leave_multicast_v4(&self, multiaddr: Ipv4Addr, interface: Ipv4Addr) -> io::Result<()> {
+        SockRef::from(self).leave_multicast_v4(&multiaddr, &interface)
+    }
+
+    /// Join the IPv6 multicast group `multiaddr` on the interface with the
+    /// index `interface`, or the default one with 0.
+    pub fn join_multicast_v6(&self, multiaddr: &Ipv6Addr, interface: u32) -> io::Result<()> {
+        SockRef::from(self).join_multicast_v6(multiaddr, interface)
+    }
+
+    /// Leave the IPv6 multicast group joined by
+    /// [`join_multicast_v6`](Self::join_multicast_v6).
+    pub fn leave_multicast_v6(&self, multiaddr: &Ipv6Addr, interface: u32) -> io::Result<()> {
+        SockRef::from(self).leave_multicast_v6(multiaddr, interface)
+    }
+
+    /// Set `IP_MULTICAST_LOOP`, whether the multicast datagrams sent are
+    /// looped back to the local sockets. It is enabled by default.
+    pub fn set_multicast_loop_v4(&self, multicast_loop: bool) -> io::Result<()> {
+        SockRef::from(self).set_multicast_loop_v4(multicast_loop)
+    }
+
+    /// Get the value of the `IP_MULTICAST_LOOP` option on this socket.
+    pub fn multicast_loop_v4(&self) -> io::Result<bool> {
+        SockRef::from(self).multicast_loop_v4()
+    }
+
+    /// Set `IPV6_MULTICAST_LOOP`, whether the multicast datagrams sent are
+    /// looped back to the local sockets. It is enabled by default.
+    pub fn set_multicast_loop_v6(&self, multicast_loop: bool) -> io::Result<()> {
+        SockRef::from(self).set_multicast_loop_v6(multicast_loop)
+    }
+
+    /// Get the value of the `IPV6_MULTICAST_LOOP` option on this socket.
+    pub fn multicast_loop_v6(&self) -> io::Result<bool> {
+        SockRef::from(self).multicast_loop_v6()
+    }
+
+    /// Set `IP_MULTICAST_TTL`, the number of hops of the multicast datagrams
+    /// sent. It is 1 by default, which keeps them in the local network.
+    pub fn set_multicast_ttl_v4(&self, ttl: u32) -> io::Result<()> {
+        SockRef::from(self).set_multicast_ttl_v4(ttl)
+    }
+
+    /// Get the value of the `IP_MULTICAST_TTL` option on this socket.
+    pub fn multicast_ttl_v4(&self) -> io::Result<u32> {
+        SockRef::from(self).multicast_ttl_v4()
+    }
+
+    /// Set `IP_MULTICAST_IF`, the interface with the address `interface` to
+    /// send the multicast datagrams from.
+    pub fn set_multicast_if_v4(&self, interface: Ipv4Addr) -> io::Result<()> {
+        SockRef::from(self).set_multicast_if_v4(&interface)
+    }
+
+    /// Get the value of the `IP_MULTICAST_IF` option on this socket.
+    pub fn multicast_if_v4(&self) -> io::Result<Ipv4Addr> {
+        SockRef::from(self).multicast_if_v4()
+    }
+
+    /// Set `IPV6_MULTICAST_IF`, the interface with the index `interface` to
+    /// send the multicast datagrams from, 0 for the default one.
+    pub fn set_multicast_if_v6(&self, interface: u32) -> io::Result<()> {
+        SockRef::from(self).set_multicast_if_v6(interface)
+    }
+
+    /// Get the value of the `IPV6_MULTICAST_IF` option on this socket.
+    pub fn multicast_if_v6(&self) -> io::Result<u32> {
+        SockRef::from(self).multicast_if_v6()
+    }
+
     /// Wait for read readiness.
     /// Note: Do not use it before every io. It is different from other runtimes!
     ///
@@ -247,6 +996,13 @@ impl AsRawFd for UdpSocket {
     }
 }
 
+#[cfg(unix)]
+impl AsFd for UdpSocket {
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        unsafe { BorrowedFd::borrow_raw(self.fd.raw_fd()) }
+    }
+}
+
 #[cfg(windows)]
 impl AsRawSocket for UdpSocket {
     fn as_raw_socket(&self) -> RawSocket {
@@ -254,6 +1010,13 @@ impl AsRawSocket for UdpSocket {
     }
 }
 
+#[cfg(windows)]
+impl AsSocket for UdpSocket {
+    fn as_socket(&self) -> BorrowedSocket<'_> {
+        unsafe { BorrowedSocket::borrow_raw(self.fd.raw_socket()) }
+    }
+}
+
 /// Cancelable related methods
 impl UdpSocket {
     /// Receives a single datagram message on the socket. On success, returns the number
@@ -14,7 +14,7 @@ use super::{
     SocketAddr,
 };
 use crate::{
-    buf::{IoBuf, IoBufMut},
+    buf::{IoBuf, IoBufMut, IoVecBuf, IoVecBufMut},
     driver::{op::Op, shared_fd::SharedFd},
     net::new_socket,
 };
@@ -34,12 +34,24 @@ impl UnixDatagram {
         StdUnixDatagram::bind(path).and_then(Self::from_std)
     }
 
+    /// Creates a Unix datagram socket bound to the given address. It may be an
+    /// address in the abstract namespace, see
+    /// [`SocketAddr::from_abstract_name`].
+    pub fn bind_addr(addr: &SocketAddr) -> io::Result<Self> {
+        let socket = socket2::Socket::new(socket2::Domain::UNIX, socket2::Type::DGRAM, None)?;
+        socket.bind(&addr.to_sock_addr())?;
+        Self::from_std(socket.into())
+    }
+
     /// Creates a new `UnixDatagram` which is not bound to any address.
     pub fn unbound() -> io::Result<Self> {
         StdUnixDatagram::unbound().and_then(Self::from_std)
     }
 
     /// Creates an unnamed pair of connected sockets.
+    ///
+    /// Both sockets are registered with the current runtime, an error is
+    /// returned if it is called outside of one.
     pub fn pair() -> io::Result<(Self, Self)> {
         let (a, b) = pair(libc::SOCK_DGRAM)?;
         Ok((Self::from_std(a)?, Self::from_std(b)?))
@@ -72,6 +84,10 @@ impl UnixDatagram {
 
     /// Creates new `UnixDatagram` from a `std::os::unix::net::UnixDatagram`.
     pub fn from_std(datagram: StdUnixDatagram) -> io::Result<Self> {
+        // The io of legacy driver relies on readiness.
+        if crate::driver::op::is_legacy() {
+            datagram.set_nonblocking(true)?;
+        }
         match SharedFd::new::<false>(datagram.as_raw_fd()) {
             Ok(shared) => {
                 datagram.into_raw_fd();
@@ -150,7 +166,14 @@ impl UnixDatagram {
 
     /// Sends data on the socket to the remote address to which it is connected.
     pub async fn send<T: IoBuf>(&self, buf: T) -> crate::BufResult<usize, T> {
-        let op = Op::send_msg_unix(self.fd.clone(), buf, None).unwrap();
+        let op = Op::send(self.fd.clone(), buf).unwrap();
+        op.write().await
+    }
+
+    /// Sends the data gathered from the buffers on the socket to the remote
+    /// address to which it is connected as a single datagram.
+    pub async fn send_vectored<T: IoVecBuf>(&self, buf_vec: T) -> crate::BufResult<usize, T> {
+        let op = Op::send_msg_vectored(self.fd.clone(), buf_vec, None).unwrap();
         op.wait().await
     }
 
@@ -160,6 +183,14 @@ impl UnixDatagram {
         let op = Op::recv(self.fd.clone(), buf).unwrap();
         op.read().await
     }
+
+    /// Receives a single datagram on the socket from the remote address to
+    /// which it is connected, scattered into the buffers in order. The
+    /// buffers after the end of the datagram are left empty.
+    pub async fn recv_vectored<T: IoVecBufMut>(&self, buf_vec: T) -> crate::BufResult<usize, T> {
+        let op = Op::recv_msg_vectored(self.fd.clone(), buf_vec).unwrap();
+        op.read().await
+    }
 }
 
 impl AsRawFd for UnixDatagram {
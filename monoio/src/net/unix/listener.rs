@@ -1,7 +1,7 @@
 use std::{
     io,
     mem::{ManuallyDrop, MaybeUninit},
-    os::unix::prelude::{AsRawFd, FromRawFd, IntoRawFd, RawFd},
+    os::unix::prelude::{AsRawFd, FromRawFd, IntoRawFd, OwnedFd, RawFd},
     path::Path,
 };
 
@@ -33,12 +33,37 @@ impl UnixListener {
         path: P,
         config: &ListenerOpts,
     ) -> io::Result<UnixListener> {
+        Self::bind_sock_addr(&socket2::SockAddr::unix(path)?, config)
+    }
+
+    /// Creates a new `UnixListener` bound to the specified address with
+    /// custom config. It may be an address in the abstract namespace, see
+    /// [`SocketAddr::from_abstract_name`].
+    pub fn bind_addr_with_config(
+        addr: &SocketAddr,
+        config: &ListenerOpts,
+    ) -> io::Result<UnixListener> {
+        Self::bind_sock_addr(&addr.to_sock_addr(), config)
+    }
+
+    /// Creates a new `UnixListener` bound to the specified address with
+    /// default config.
+    pub fn bind_addr(addr: &SocketAddr) -> io::Result<UnixListener> {
+        Self::bind_addr_with_config(addr, &ListenerOpts::default())
+    }
+
+    fn bind_sock_addr(addr: &socket2::SockAddr, config: &ListenerOpts) -> io::Result<UnixListener> {
         let sys_listener =
             socket2::Socket::new(socket2::Domain::UNIX, socket2::Type::STREAM, None)?;
-        let addr = socket2::SockAddr::unix(path)?;
 
+        // Newer Linux kernels reject SO_REUSEPORT on AF_UNIX sockets with
+        // EOPNOTSUPP, the listener is bound without it then.
         if config.reuse_port {
-            sys_listener.set_reuse_port(true)?;
+            if let Err(e) = sys_listener.set_reuse_port(true) {
+                if e.raw_os_error() != Some(libc::EOPNOTSUPP) {
+                    return Err(e);
+                }
+            }
         }
         if config.reuse_addr {
             sys_listener.set_reuse_address(true)?;
@@ -50,7 +75,7 @@ impl UnixListener {
             sys_listener.set_recv_buffer_size(recv_buf_size)?;
         }
 
-        sys_listener.bind(&addr)?;
+        sys_listener.bind(addr)?;
         sys_listener.listen(config.backlog)?;
 
         let fd = SharedFd::new::<false>(sys_listener.into_raw_fd())?;
@@ -133,8 +158,25 @@ impl UnixListener {
         op.wait().await
     }
 
+    /// Returns a [`futures_core::Stream`] of the accepted connections, so it
+    /// can be used with the combinators of `futures_util::StreamExt`.
+    ///
+    /// The pending accept is kept across polls and canceled when the
+    /// [`Incoming`] is dropped, so the listener can be used again.
+    #[cfg(feature = "futures-stream")]
+    pub fn incoming(&self) -> Incoming<'_> {
+        Incoming {
+            listener: self,
+            op: None,
+        }
+    }
+
     /// Creates new `UnixListener` from a `std::os::unix::net::UnixListener`.
     pub fn from_std(sys_listener: std::os::unix::net::UnixListener) -> io::Result<Self> {
+        // The io of legacy driver relies on readiness.
+        if crate::driver::op::is_legacy() {
+            sys_listener.set_nonblocking(true)?;
+        }
         match SharedFd::new::<false>(sys_listener.as_raw_fd()) {
             Ok(shared) => Ok(Self {
                 fd: shared,
@@ -154,6 +196,58 @@ impl Stream for UnixListener {
     }
 }
 
+/// Stream of accepted connections created by [`UnixListener::incoming`].
+#[cfg(feature = "futures-stream")]
+pub struct Incoming<'a> {
+    listener: &'a UnixListener,
+    op: Option<Op<crate::driver::op::Accept>>,
+}
+
+#[cfg(feature = "futures-stream")]
+impl futures_core::Stream for Incoming<'_> {
+    type Item = io::Result<UnixStream>;
+
+    fn poll_next(
+        self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        let op = match this.op.as_mut() {
+            Some(op) => op,
+            None => match Op::accept(&this.listener.fd) {
+                Ok(op) => this.op.insert(op),
+                Err(e) => return std::task::Poll::Ready(Some(Err(e))),
+            },
+        };
+        let completion = std::task::ready!(std::future::Future::poll(std::pin::Pin::new(op), cx));
+        this.op = None;
+        let res = completion
+            .meta
+            .result
+            .and_then(|fd| Ok(UnixStream::from_shared_fd(SharedFd::new::<false>(fd as _)?)));
+        std::task::Poll::Ready(Some(res))
+    }
+}
+
+#[cfg(feature = "futures-stream")]
+impl Drop for Incoming<'_> {
+    fn drop(&mut self) {
+        // A pending accept would take the next connection of the listener, so
+        // we must cancel it even if async-cancel is not enabled. There is
+        // nothing in flight on legacy driver.
+        #[cfg(all(
+            target_os = "linux",
+            feature = "iouring",
+            not(feature = "async-cancel")
+        ))]
+        if let Some(op) = self.op.take() {
+            if !crate::driver::op::is_legacy() {
+                unsafe { op.op_canceller().cancel() };
+            }
+        }
+    }
+}
+
 impl std::fmt::Debug for UnixListener {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("UnixListener")
@@ -190,6 +284,16 @@ impl AsRawFd for UnixListener {
     }
 }
 
+impl TryFrom<OwnedFd> for UnixListener {
+    type Error = io::Error;
+
+    /// The fd must be a listening unix stream socket, see
+    /// [`UnixListener::from_std`].
+    fn try_from(fd: OwnedFd) -> io::Result<Self> {
+        Self::from_std(std::os::unix::net::UnixListener::from(fd))
+    }
+}
+
 impl Drop for UnixListener {
     #[inline]
     fn drop(&mut self) {
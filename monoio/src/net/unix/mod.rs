@@ -12,6 +12,8 @@ mod ucred;
 #[cfg(target_os = "linux")]
 mod seq_packet;
 pub use datagram::UnixDatagram;
+#[cfg(feature = "futures-stream")]
+pub use listener::Incoming;
 pub use listener::UnixListener;
 pub use pipe::{new_pipe, Pipe};
 #[cfg(target_os = "linux")]
@@ -19,6 +21,7 @@ pub use seq_packet::{UnixSeqpacket, UnixSeqpacketListener};
 pub use socket_addr::SocketAddr;
 pub use split::{UnixOwnedReadHalf, UnixOwnedWriteHalf};
 pub use stream::UnixStream;
+pub use ucred::UCred;
 
 #[cfg(feature = "poll-io")]
 pub mod stream_poll;
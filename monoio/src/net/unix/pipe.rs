@@ -13,25 +13,84 @@ impl Pipe {
         Self { fd }
     }
 
-    fn from_raw_fd(fd: RawFd) -> Self {
-        Self::from_shared_fd(SharedFd::new_without_register(fd))
+    fn from_raw_fd(fd: RawFd) -> io::Result<Self> {
+        // Registered so legacy driver can wait for its readiness.
+        match SharedFd::new::<false>(fd) {
+            Ok(fd) => Ok(Self::from_shared_fd(fd)),
+            Err(e) => {
+                let _ = crate::syscall!(close(fd));
+                Err(e)
+            }
+        }
+    }
+}
+
+#[cfg(all(target_os = "linux", feature = "splice"))]
+impl Pipe {
+    /// Splice up to `len` bytes from `src` into the pipe, it must be the write
+    /// end.
+    ///
+    /// Return the number of bytes moved, 0 means `src` reaches EOF.
+    #[inline]
+    pub async fn splice_from<T: crate::io::as_fd::AsReadFd>(
+        &mut self,
+        src: &mut T,
+        len: u32,
+    ) -> io::Result<u32> {
+        use crate::io::splice::SpliceSource;
+        src.splice_to_pipe(self, len).await
+    }
+
+    /// Splice up to `len` bytes from the pipe into `dst`, it must be the read
+    /// end.
+    ///
+    /// Return the number of bytes moved, it may be less than `len`.
+    #[inline]
+    pub async fn splice_to<T: crate::io::as_fd::AsWriteFd>(
+        &mut self,
+        dst: &mut T,
+        len: u32,
+    ) -> io::Result<u32> {
+        use crate::io::splice::SpliceDestination;
+        dst.splice_from_pipe(self, len).await
+    }
+
+    /// Duplicate up to `len` bytes from the pipe to `other` without consuming
+    /// them, the pipe must be the read end and `other` must be the write end.
+    ///
+    /// Return the number of bytes duplicated, 0 means there is no writer of
+    /// the pipe and it is empty.
+    #[inline]
+    pub async fn tee_to(&mut self, other: &mut Pipe, len: u32) -> io::Result<u32> {
+        crate::driver::op::Op::tee_pipe(&self.fd, &other.fd, len)?
+            .tee()
+            .await
     }
 }
 
 /// Create a new pair of pipe.
 pub fn new_pipe() -> io::Result<(Pipe, Pipe)> {
     let mut pipes = [0 as libc::c_int; 2];
+    // Pipe is only non-blocking on legacy driver since it waits for readiness,
+    // uring splice on a non-blocking pipe would fail with EAGAIN instead.
     #[cfg(target_os = "linux")]
     let flag = {
         if crate::driver::op::is_legacy() {
-            libc::O_NONBLOCK
+            libc::O_CLOEXEC | libc::O_NONBLOCK
         } else {
-            0
+            libc::O_CLOEXEC
         }
     };
     #[cfg(target_os = "linux")]
     crate::syscall!(pipe2(pipes.as_mut_ptr() as _, flag))?;
     #[cfg(not(target_os = "linux"))]
     crate::syscall!(pipe(pipes.as_mut_ptr() as _))?;
-    Ok((Pipe::from_raw_fd(pipes[0]), Pipe::from_raw_fd(pipes[1])))
+    let rx = match Pipe::from_raw_fd(pipes[0]) {
+        Ok(rx) => rx,
+        Err(e) => {
+            let _ = crate::syscall!(close(pipes[1]));
+            return Err(e);
+        }
+    };
+    Ok((rx, Pipe::from_raw_fd(pipes[1])?))
 }
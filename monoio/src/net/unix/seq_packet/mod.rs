@@ -20,7 +20,9 @@ use crate::{
 mod listener;
 pub use listener::UnixSeqpacketListener;
 
-/// UnixSeqpacket
+/// A connected Unix socket of `SOCK_SEQPACKET`, which keeps the boundaries
+/// of the records like a datagram socket, and delivers them in order and
+/// reliably like a stream socket.
 pub struct UnixSeqpacket {
     fd: SharedFd,
 }
@@ -131,18 +133,39 @@ impl UnixSeqpacket {
         op.wait().await
     }
 
-    /// Sends data on the socket to the remote address to which it is connected.
+    /// Sends the buffer as one record to the connected peer. On success,
+    /// returns the number of bytes written, which is the whole buffer: a
+    /// record larger than the send buffer fails with `EMSGSIZE` instead of
+    /// being split.
     pub async fn send<T: IoBuf>(&self, buf: T) -> crate::BufResult<usize, T> {
-        let op = Op::send_msg_unix(self.fd.clone(), buf, None).unwrap();
-        op.wait().await
+        let op = Op::send(self.fd.clone(), buf).unwrap();
+        op.write().await
     }
 
-    /// Receives a single datagram message on the socket from the remote address to
-    /// which it is connected. On success, returns the number of bytes read.
+    /// Receives one record from the connected peer. On success, returns the
+    /// number of bytes read, records are never merged or split across
+    /// calls.
+    ///
+    /// The part of a record which does not fit in the buffer is discarded,
+    /// use [`recv_full`] to detect it. 0 is returned once the peer has shut
+    /// down or closed the connection, an empty record can not be told apart
+    /// from it.
+    ///
+    /// [`recv_full`]: UnixSeqpacket::recv_full
     pub async fn recv<T: IoBufMut>(&self, buf: T) -> crate::BufResult<usize, T> {
         let op = Op::recv(self.fd.clone(), buf).unwrap();
         op.read().await
     }
+
+    /// Receives one record like [`recv`], and also returns the real length of
+    /// it, which is larger than the number of bytes read if the record is
+    /// truncated because the buffer is too small.
+    ///
+    /// [`recv`]: UnixSeqpacket::recv
+    pub async fn recv_full<T: IoBufMut>(&self, buf: T) -> crate::BufResult<(usize, usize), T> {
+        let op = Op::recv_trunc(self.fd.clone(), buf).unwrap();
+        op.read_trunc().await
+    }
 }
 
 impl AsRawFd for UnixSeqpacket {
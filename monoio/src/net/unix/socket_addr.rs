@@ -91,6 +91,41 @@ impl SocketAddr {
         (self.sockaddr, self.socklen)
     }
 
+    /// Creates an address in the abstract namespace, the name is used as is
+    /// without the leading null byte and a null terminator. Unlike pathname
+    /// addresses, nothing is created on the filesystem and the name is
+    /// released once the socket is closed.
+    ///
+    /// The name must be shorter than `sun_path`, otherwise an error of
+    /// `InvalidInput` is returned.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub fn from_abstract_name(name: &[u8]) -> io::Result<SocketAddr> {
+        let mut sockaddr = {
+            let sockaddr = mem::MaybeUninit::<libc::sockaddr_un>::zeroed();
+            unsafe { sockaddr.assume_init() }
+        };
+        sockaddr.sun_family = libc::AF_UNIX as libc::sa_family_t;
+
+        if name.len() >= sockaddr.sun_path.len() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "abstract name must be shorter than libc::sockaddr_un.sun_path",
+            ));
+        }
+        // The first byte stays zero.
+        for (dst, src) in sockaddr.sun_path[1..].iter_mut().zip(name.iter()) {
+            *dst = *src as libc::c_char;
+        }
+
+        // The length covers the leading null byte and the name only, the
+        // bytes after it would be taken as a part of the name.
+        let socklen = path_offset(&sockaddr) + 1 + name.len();
+        Ok(SocketAddr {
+            sockaddr,
+            socklen: socklen as libc::socklen_t,
+        })
+    }
+
     /// Returns `true` if the address is unnamed.
     ///
     /// Documentation reflected in [`SocketAddr`]
@@ -137,6 +172,20 @@ impl SocketAddr {
     pub(crate) fn len(&self) -> libc::socklen_t {
         self.socklen
     }
+
+    pub(crate) fn to_sock_addr(&self) -> socket2::SockAddr {
+        let mut storage: libc::sockaddr_storage = unsafe { mem::zeroed() };
+        // Safety: `sockaddr_storage` is large enough for any address, and the
+        // length is the one of the `sockaddr_un`.
+        unsafe {
+            std::ptr::copy_nonoverlapping(
+                self.as_ptr() as *const u8,
+                &mut storage as *mut libc::sockaddr_storage as *mut u8,
+                mem::size_of::<libc::sockaddr_un>(),
+            );
+            socket2::SockAddr::new(storage, self.socklen)
+        }
+    }
 }
 
 impl fmt::Debug for SocketAddr {
@@ -213,6 +262,14 @@ pub(crate) fn pair<T>(flags: libc::c_int) -> io::Result<(T, T)>
 where
     T: FromRawFd,
 {
+    // Both fds are registered with the driver of the current runtime, the
+    // check is done before it panics without one.
+    if !crate::driver::CURRENT.is_set() {
+        return Err(io::Error::other(
+            "socket pair must be created within a monoio runtime",
+        ));
+    }
+
     #[cfg(any(
         target_os = "android",
         target_os = "dragonfly",
@@ -1,10 +1,12 @@
 use std::{
     future::Future,
     io::{self},
-    os::unix::prelude::{AsRawFd, FromRawFd, IntoRawFd, RawFd},
+    os::unix::prelude::{AsFd, AsRawFd, BorrowedFd, FromRawFd, IntoRawFd, OwnedFd, RawFd},
     path::Path,
 };
 
+use socket2::SockRef;
+
 use super::{
     socket_addr::{local_addr, pair, peer_addr, socket_addr, SocketAddr},
     ucred::UCred,
@@ -73,6 +75,9 @@ impl UnixStream {
     /// Creates an unnamed pair of connected sockets.
     ///
     /// Returns two `UnixStream`s which are connected to each other.
+    ///
+    /// Both sockets are registered with the current runtime, an error is
+    /// returned if it is called outside of one.
     pub fn pair() -> io::Result<(Self, Self)> {
         let (a, b) = pair(libc::SOCK_STREAM)?;
         Ok((Self::from_std(a)?, Self::from_std(b)?))
@@ -84,8 +89,43 @@ impl UnixStream {
         super::ucred::get_peer_cred(self)
     }
 
+    /// Set the size of the send buffer, `SO_SNDBUF`.
+    pub fn set_send_buffer_size(&self, size: u32) -> io::Result<()> {
+        SockRef::from(self).set_send_buffer_size(size as usize)
+    }
+
+    /// Get the size of the send buffer, which may be larger than the one set
+    /// since the kernel doubles it.
+    pub fn send_buffer_size(&self) -> io::Result<u32> {
+        SockRef::from(self)
+            .send_buffer_size()
+            .map(|size| size as u32)
+    }
+
+    /// Set the size of the receive buffer, `SO_RCVBUF`.
+    pub fn set_recv_buffer_size(&self, size: u32) -> io::Result<()> {
+        SockRef::from(self).set_recv_buffer_size(size as usize)
+    }
+
+    /// Get the size of the receive buffer, which may be larger than the one
+    /// set since the kernel doubles it.
+    pub fn recv_buffer_size(&self) -> io::Result<u32> {
+        SockRef::from(self)
+            .recv_buffer_size()
+            .map(|size| size as u32)
+    }
+
+    /// Get and clear the pending error of the socket, `SO_ERROR`.
+    pub fn take_error(&self) -> io::Result<Option<io::Error>> {
+        SockRef::from(self).take_error()
+    }
+
     /// Creates new `UnixStream` from a `std::os::unix::net::UnixStream`.
     pub fn from_std(stream: std::os::unix::net::UnixStream) -> io::Result<Self> {
+        // The io of legacy driver relies on readiness.
+        if crate::driver::op::is_legacy() {
+            stream.set_nonblocking(true)?;
+        }
         match SharedFd::new::<false>(stream.as_raw_fd()) {
             Ok(shared) => {
                 stream.into_raw_fd();
@@ -95,6 +135,18 @@ impl UnixStream {
         }
     }
 
+    /// Converts the stream into a `std::os::unix::net::UnixStream`. It fails
+    /// if the stream is split or there are ops in flight.
+    ///
+    /// On legacy driver the fd is deregistered and stays non-blocking.
+    pub fn into_std(self) -> io::Result<std::os::unix::net::UnixStream> {
+        let fd = self
+            .fd
+            .try_unwrap()
+            .map_err(|_| io::Error::other("unexpected multiple reference to rawfd"))?;
+        Ok(unsafe { std::os::unix::net::UnixStream::from_raw_fd(fd) })
+    }
+
     /// Returns the socket address of the local half of this connection.
     pub fn local_addr(&self) -> io::Result<SocketAddr> {
         local_addr(self.as_raw_fd())
@@ -105,6 +157,116 @@ impl UnixStream {
         peer_addr(self.as_raw_fd())
     }
 
+    /// Receive data with the file descriptors sent with `SCM_RIGHTS`, which
+    /// are put into the slots of `fds` in order. Returns the number of bytes
+    /// and fds received.
+    ///
+    /// The fds are received as close-on-exec. If there are more fds than the
+    /// slots, the data is consumed and an error is returned, and the fds
+    /// which did fit are closed.
+    pub async fn recv_with_fds<T: IoBufMut>(
+        &mut self,
+        buf: T,
+        fds: &mut [Option<OwnedFd>],
+    ) -> BufResult<(usize, usize), T> {
+        let op = Op::recv_msg_unix_with_fds(self.fd.clone(), buf, fds.len()).unwrap();
+        op.wait_with_fds(fds).await
+    }
+
+    /// Send data with the file descriptors attached in a `SCM_RIGHTS`
+    /// message. Returns the number of bytes sent.
+    ///
+    /// The fds are looked up when the kernel executes the op, so they must
+    /// be kept open until the future completes.
+    pub async fn send_with_fds<T: IoBuf>(&mut self, buf: T, fds: &[RawFd]) -> BufResult<usize, T> {
+        let op = Op::send_msg_unix_with_fds(self.fd.clone(), buf, None, fds).unwrap();
+        op.wait().await
+    }
+
+    /// Receive data with the credentials of the sender in the
+    /// `SCM_CREDENTIALS` message. Returns the number of bytes received, and
+    /// the credentials if [`set_passcred`] is enabled.
+    ///
+    /// The credentials are the ones of the process which sent the data,
+    /// rather than the one which connected like [`peer_cred`]. Without
+    /// [`send_with_creds`], the kernel fills them with the sender's.
+    ///
+    /// [`set_passcred`]: UnixStream::set_passcred
+    /// [`peer_cred`]: UnixStream::peer_cred
+    /// [`send_with_creds`]: UnixStream::send_with_creds
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub async fn recv_with_creds<T: IoBufMut>(
+        &mut self,
+        buf: T,
+    ) -> BufResult<(usize, Option<UCred>), T> {
+        let op = Op::recv_msg_unix_with_ancillary(self.fd.clone(), buf, 0, true).unwrap();
+        let (res, buf) = op.wait_with_ancillary(&mut []).await;
+        (res.map(|(n, _, creds)| (n, creds)), buf)
+    }
+
+    /// Send data with the credentials of the current process attached in a
+    /// `SCM_CREDENTIALS` message. Returns the number of bytes sent.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub async fn send_with_creds<T: IoBuf>(&mut self, buf: T) -> BufResult<usize, T> {
+        let ucred = unsafe {
+            libc::ucred {
+                pid: libc::getpid(),
+                uid: libc::getuid(),
+                gid: libc::getgid(),
+            }
+        };
+        // Safety: ucred is plain data.
+        let data = unsafe {
+            std::slice::from_raw_parts(
+                &ucred as *const libc::ucred as *const u8,
+                std::mem::size_of::<libc::ucred>(),
+            )
+        };
+        let op = Op::send_msg_unix_with_cmsg(
+            self.fd.clone(),
+            buf,
+            None,
+            libc::SOL_SOCKET,
+            libc::SCM_CREDENTIALS,
+            data,
+        )
+        .unwrap();
+        op.wait().await
+    }
+
+    /// Set the value of the `SO_PASSCRED` option, the credentials of the
+    /// sender are received with each message by [`recv_with_creds`] if it is
+    /// enabled.
+    ///
+    /// [`recv_with_creds`]: UnixStream::recv_with_creds
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub fn set_passcred(&self, passcred: bool) -> io::Result<()> {
+        let value = passcred as libc::c_int;
+        crate::syscall!(setsockopt(
+            self.as_raw_fd(),
+            libc::SOL_SOCKET,
+            libc::SO_PASSCRED,
+            &value as *const libc::c_int as *const libc::c_void,
+            std::mem::size_of::<libc::c_int>() as libc::socklen_t
+        ))
+        .map(|_| ())
+    }
+
+    /// Get the value of the `SO_PASSCRED` option.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub fn passcred(&self) -> io::Result<bool> {
+        let mut value: libc::c_int = 0;
+        let mut len = std::mem::size_of::<libc::c_int>() as libc::socklen_t;
+        crate::syscall!(getsockopt(
+            self.as_raw_fd(),
+            libc::SOL_SOCKET,
+            libc::SO_PASSCRED,
+            &mut value as *mut libc::c_int as *mut libc::c_void,
+            &mut len
+        ))?;
+        Ok(value != 0)
+    }
+
     /// Wait for read readiness.
     /// Note: Do not use it before every io. It is different from other runtimes!
     ///
@@ -134,6 +296,37 @@ impl UnixStream {
         let op = Op::poll_write(&self.fd, relaxed).unwrap();
         op.wait().await
     }
+
+    /// Try to read data into `buf` without waiting, fail with `WouldBlock`
+    /// if there is nothing to read. Returns 0 at EOF.
+    ///
+    /// It is a plain syscall, to be used with [`readable`](Self::readable)
+    /// by code which does not pass the ownership of buffers.
+    pub fn try_read(&self, buf: &mut [u8]) -> io::Result<usize> {
+        let res = crate::syscall_u32!(recv(
+            self.fd.raw_fd(),
+            buf.as_mut_ptr() as _,
+            buf.len(),
+            libc::MSG_DONTWAIT
+        ));
+        self.fd.clear_readiness_if_blocked(&res, true);
+        res.map(|n| n as usize)
+    }
+
+    /// Try to write data of `buf` without waiting, fail with `WouldBlock` if
+    /// the send buffer is full.
+    ///
+    /// It is a plain syscall, to be used with [`writable`](Self::writable)
+    /// by code which does not pass the ownership of buffers.
+    pub fn try_write(&self, buf: &[u8]) -> io::Result<usize> {
+        #[cfg(target_os = "linux")]
+        let flags = libc::MSG_DONTWAIT | libc::MSG_NOSIGNAL;
+        #[cfg(not(target_os = "linux"))]
+        let flags = libc::MSG_DONTWAIT;
+        let res = crate::syscall_u32!(send(self.fd.raw_fd(), buf.as_ptr() as _, buf.len(), flags));
+        self.fd.clear_readiness_if_blocked(&res, false);
+        res.map(|n| n as usize)
+    }
 }
 
 impl AsReadFd for UnixStream {
@@ -166,6 +359,23 @@ impl AsRawFd for UnixStream {
     }
 }
 
+impl AsFd for UnixStream {
+    #[inline]
+    fn as_fd(&self) -> BorrowedFd<'_> {
+        unsafe { BorrowedFd::borrow_raw(self.fd.raw_fd()) }
+    }
+}
+
+impl TryFrom<OwnedFd> for UnixStream {
+    type Error = io::Error;
+
+    /// The fd must be a connected unix stream socket, see
+    /// [`UnixStream::from_std`].
+    fn try_from(fd: OwnedFd) -> io::Result<Self> {
+        Self::from_std(std::os::unix::net::UnixStream::from(fd))
+    }
+}
+
 impl std::fmt::Debug for UnixStream {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("UnixStream").field("fd", &self.fd).finish()
@@ -0,0 +1,12 @@
+//! Synchronization primitives which can be shared between runtimes on
+//! different threads.
+//!
+//! On uring driver with kernel 6.7+, the waiters wait with futex ops, and
+//! releasing wakes them with a futex syscall. Otherwise the wakers are sent
+//! back to the runtimes of the waiters.
+
+mod mutex;
+mod semaphore;
+
+pub use mutex::{Mutex, MutexGuard};
+pub use semaphore::{Semaphore, SemaphorePermit};
@@ -0,0 +1,96 @@
+use std::{
+    cell::UnsafeCell,
+    fmt,
+    ops::{Deref, DerefMut},
+};
+
+use super::{Semaphore, SemaphorePermit};
+
+/// An async mutex which can be shared between runtimes.
+pub struct Mutex<T: ?Sized> {
+    sem: Semaphore,
+    data: UnsafeCell<T>,
+}
+
+unsafe impl<T: ?Sized + Send> Send for Mutex<T> {}
+unsafe impl<T: ?Sized + Send> Sync for Mutex<T> {}
+
+impl<T> Mutex<T> {
+    /// Create a new unlocked mutex.
+    pub const fn new(data: T) -> Self {
+        Self {
+            sem: Semaphore::new(1),
+            data: UnsafeCell::new(data),
+        }
+    }
+
+    /// Consume the mutex and return the data.
+    pub fn into_inner(self) -> T {
+        self.data.into_inner()
+    }
+}
+
+impl<T: ?Sized> Mutex<T> {
+    /// Lock the mutex, wait if it is locked.
+    pub async fn lock(&self) -> MutexGuard<'_, T> {
+        let permit = self.sem.acquire().await;
+        MutexGuard {
+            lock: self,
+            _permit: permit,
+        }
+    }
+
+    /// Try to lock the mutex without waiting.
+    pub fn try_lock(&self) -> Option<MutexGuard<'_, T>> {
+        self.sem.try_acquire().map(|permit| MutexGuard {
+            lock: self,
+            _permit: permit,
+        })
+    }
+
+    /// Get a mutable reference to the data, no locking is needed since the
+    /// mutex is borrowed mutably.
+    pub fn get_mut(&mut self) -> &mut T {
+        self.data.get_mut()
+    }
+}
+
+impl<T: ?Sized + fmt::Debug> fmt::Debug for Mutex<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut d = f.debug_struct("Mutex");
+        match self.try_lock() {
+            Some(guard) => d.field("data", &&*guard),
+            None => d.field("data", &format_args!("<locked>")),
+        };
+        d.finish()
+    }
+}
+
+/// A guard of the locked [`Mutex`], which unlocks it on drop.
+#[must_use = "the mutex is unlocked at once if unused"]
+pub struct MutexGuard<'a, T: ?Sized> {
+    lock: &'a Mutex<T>,
+    _permit: SemaphorePermit<'a>,
+}
+
+unsafe impl<T: ?Sized + Sync> Sync for MutexGuard<'_, T> {}
+
+impl<T: ?Sized> Deref for MutexGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        unsafe { &*self.lock.data.get() }
+    }
+}
+
+impl<T: ?Sized> DerefMut for MutexGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { &mut *self.lock.data.get() }
+    }
+}
+
+impl<T: ?Sized + fmt::Debug> fmt::Debug for MutexGuard<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
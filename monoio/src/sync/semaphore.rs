@@ -0,0 +1,164 @@
+use std::{
+    fmt,
+    sync::atomic::{AtomicU32, Ordering},
+    task::{Poll, Waker},
+};
+
+/// A counting semaphore which can be shared between runtimes.
+///
+/// All the waiters are woken when permits are released, and they race for
+/// the permits.
+pub struct Semaphore {
+    // Available permits, which is also the futex word.
+    permits: AtomicU32,
+    // Number of the waiters waiting with futex ops.
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    futex_waiters: AtomicU32,
+    // Wakers of the waiters on drivers without futex ops.
+    wakers: std::sync::Mutex<Vec<Waker>>,
+}
+
+impl Semaphore {
+    /// Create a semaphore with the given number of permits.
+    pub const fn new(permits: u32) -> Self {
+        Self {
+            permits: AtomicU32::new(permits),
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            futex_waiters: AtomicU32::new(0),
+            wakers: std::sync::Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Returns the number of available permits.
+    #[inline]
+    pub fn available_permits(&self) -> u32 {
+        self.permits.load(Ordering::Acquire)
+    }
+
+    /// Acquire a permit, wait if there is none.
+    pub async fn acquire(&self) -> SemaphorePermit<'_> {
+        loop {
+            if self.try_acquire_raw() {
+                return SemaphorePermit { sem: self };
+            }
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            if futex_supported() {
+                self.futex_wait().await;
+                continue;
+            }
+            self.waker_wait().await;
+        }
+    }
+
+    /// Try to acquire a permit without waiting.
+    pub fn try_acquire(&self) -> Option<SemaphorePermit<'_>> {
+        self.try_acquire_raw()
+            .then(|| SemaphorePermit { sem: self })
+    }
+
+    /// Add `n` permits and wake the waiters.
+    pub fn add_permits(&self, n: u32) {
+        self.permits.fetch_add(n, Ordering::SeqCst);
+        #[cfg(all(target_os = "linux", feature = "iouring"))]
+        if self.futex_waiters.load(Ordering::SeqCst) != 0 {
+            crate::driver::op::futex_wake_all(&self.permits);
+        }
+        let wakers = std::mem::take(&mut *self.wakers.lock().unwrap());
+        for waker in wakers {
+            waker.wake();
+        }
+    }
+
+    fn try_acquire_raw(&self) -> bool {
+        let mut permits = self.permits.load(Ordering::Acquire);
+        while permits != 0 {
+            match self.permits.compare_exchange_weak(
+                permits,
+                permits - 1,
+                Ordering::Acquire,
+                Ordering::Acquire,
+            ) {
+                Ok(_) => return true,
+                Err(actual) => permits = actual,
+            }
+        }
+        false
+    }
+
+    /// Wait until the permits may be available.
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    async fn futex_wait(&self) {
+        struct Waiting<'a>(&'a AtomicU32);
+        impl Drop for Waiting<'_> {
+            fn drop(&mut self) {
+                self.0.fetch_sub(1, Ordering::SeqCst);
+            }
+        }
+
+        self.futex_waiters.fetch_add(1, Ordering::SeqCst);
+        let _waiting = Waiting(&self.futex_waiters);
+        // Releasing after the check changes the word, so the wait completes
+        // at once with EAGAIN.
+        if self.permits.load(Ordering::SeqCst) == 0 {
+            if let Ok(op) = crate::driver::op::Op::futex_wait(&self.permits, 0) {
+                let _ = op.await;
+            }
+        }
+    }
+
+    /// Wait until the permits may be available.
+    async fn waker_wait(&self) {
+        let mut registered = false;
+        crate::macros::support::poll_fn(|cx| {
+            if registered {
+                return Poll::Ready(());
+            }
+            let mut wakers = self.wakers.lock().unwrap();
+            // Permits are added before the wakers are taken.
+            if self.permits.load(Ordering::Acquire) != 0 {
+                return Poll::Ready(());
+            }
+            wakers.push(cx.waker().clone());
+            registered = true;
+            Poll::Pending
+        })
+        .await
+    }
+}
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+fn futex_supported() -> bool {
+    use crate::driver::op::{is_legacy, is_uring_op_supported};
+
+    crate::driver::CURRENT.is_set()
+        && !is_legacy()
+        && is_uring_op_supported(io_uring::opcode::FutexWait::CODE)
+}
+
+impl fmt::Debug for Semaphore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Semaphore")
+            .field("permits", &self.available_permits())
+            .finish()
+    }
+}
+
+/// A permit acquired from a [`Semaphore`], which is given back on drop.
+#[must_use = "the permit is released at once if unused"]
+#[derive(Debug)]
+pub struct SemaphorePermit<'a> {
+    sem: &'a Semaphore,
+}
+
+impl SemaphorePermit<'_> {
+    /// Forget the permit without giving it back.
+    pub fn forget(self) {
+        std::mem::forget(self);
+    }
+}
+
+impl Drop for SemaphorePermit<'_> {
+    fn drop(&mut self) {
+        self.sem.add_permits(1);
+    }
+}
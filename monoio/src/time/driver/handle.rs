@@ -25,6 +25,15 @@ impl Handle {
     pub(super) fn get(&self) -> &super::Inner {
         &self.inner
     }
+
+    /// Whether sleeps prefer uring timeout ops over the wheel.
+    pub(crate) fn precise_timers(&self) -> bool {
+        self.inner.precise.get()
+    }
+
+    pub(crate) fn set_precise_timers(&self, precise: bool) {
+        self.inner.precise.set(precise);
+    }
 }
 
 impl Handle {
@@ -16,7 +16,13 @@ mod wheel;
 
 pub(super) mod sleep;
 
-use std::{cell::RefCell, fmt, io, num::NonZeroU64, ptr::NonNull, rc::Rc};
+use std::{
+    cell::{Cell, RefCell},
+    fmt, io,
+    num::NonZeroU64,
+    ptr::NonNull,
+    rc::Rc,
+};
 
 use crate::{
     driver::Driver,
@@ -134,6 +140,9 @@ impl ClockTime {
 struct Inner {
     // The state is split like this so `Handle` can access `is_shutdown` without locking the mutex
     pub(super) state: RefCell<InnerState>,
+
+    /// Whether sleeps are fired by uring timeout ops instead of the wheel
+    pub(super) precise: Cell<bool>,
 }
 
 /// Time state shared which must be protected by a `Mutex`
@@ -367,6 +376,7 @@ impl Inner {
                 next_wake: None,
                 wheel: wheel::Wheel::new(),
             }),
+            precise: Cell::new(false),
         }
     }
 }
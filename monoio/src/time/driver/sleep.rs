@@ -6,6 +6,8 @@ use std::{
 
 use pin_project_lite::pin_project;
 
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+use crate::driver::op::{is_legacy, is_uring_op_supported, Op, Timeout};
 use crate::time::{
     driver::{Handle, TimerEntry},
     error::Error,
@@ -15,8 +17,9 @@ use crate::time::{
 /// Waits until `deadline` is reached.
 ///
 /// No work is performed while awaiting on the sleep future to complete. `Sleep`
-/// operates at millisecond granularity and should not be used for tasks that
-/// require high-resolution timers.
+/// operates at millisecond granularity, except on uring driver where sleeps
+/// shorter than a millisecond, or all sleeps if
+/// [`RuntimeBuilder::precise_timers`] is set, are fired by the kernel.
 ///
 /// To run something regularly on a schedule, see [`interval`].
 ///
@@ -43,6 +46,7 @@ use crate::time::{
 ///
 /// [`Sleep`]: struct@crate::time::Sleep
 /// [`interval`]: crate::time::interval()
+/// [`RuntimeBuilder::precise_timers`]: crate::RuntimeBuilder::precise_timers
 // Alias for old name in 0.x
 #[cfg_attr(docsrs, doc(alias = "delay_until"))]
 pub fn sleep_until(deadline: Instant) -> Sleep {
@@ -55,8 +59,9 @@ pub fn sleep_until(deadline: Instant) -> Sleep {
 /// analog to `std::thread::sleep`.
 ///
 /// No work is performed while awaiting on the sleep future to complete. `Sleep`
-/// operates at millisecond granularity and should not be used for tasks that
-/// require high-resolution timers.
+/// operates at millisecond granularity, except on uring driver where sleeps
+/// shorter than a millisecond, or all sleeps if
+/// [`RuntimeBuilder::precise_timers`] is set, are fired by the kernel.
 ///
 /// To run something regularly on a schedule, see [`interval`].
 ///
@@ -86,6 +91,7 @@ pub fn sleep_until(deadline: Instant) -> Sleep {
 ///
 /// [`Sleep`]: struct@crate::time::Sleep
 /// [`interval`]: crate::time::interval()
+/// [`RuntimeBuilder::precise_timers`]: crate::RuntimeBuilder::precise_timers
 // Alias for old name in 0.x
 #[cfg_attr(docsrs, doc(alias = "delay_for"))]
 #[cfg_attr(docsrs, doc(alias = "wait"))]
@@ -193,15 +199,37 @@ pin_project! {
         // The link between the `Sleep` instance and the timer that drives it.
         #[pin]
         entry: TimerEntry,
+
+        // Uring timeout op used instead of the wheel entry.
+        precise: Precise,
     }
 }
 
 impl Sleep {
     pub(crate) fn new_timeout(deadline: Instant) -> Sleep {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        Self::new_timeout_with(deadline, !remaining.is_zero() && remaining < TICK)
+    }
+
+    /// Create a sleep which is fired by a uring timeout op if `sub_tick` is
+    /// set or the runtime prefers precise timers.
+    #[allow(unused_variables)]
+    pub(crate) fn new_timeout_with(deadline: Instant, sub_tick: bool) -> Sleep {
         let handle = Handle::current();
+        #[cfg(all(target_os = "linux", feature = "iouring"))]
+        let precise = match handle.precise_timers() || sub_tick {
+            true => PreciseTimer::new(deadline),
+            false => None,
+        };
+        #[cfg(not(all(target_os = "linux", feature = "iouring")))]
+        let precise = ();
         let entry = TimerEntry::new(&handle, deadline);
 
-        Sleep { deadline, entry }
+        Sleep {
+            deadline,
+            entry,
+            precise,
+        }
     }
 
     pub(crate) fn far_future() -> Sleep {
@@ -217,6 +245,10 @@ impl Sleep {
     ///
     /// A `Sleep` instance is elapsed when the requested duration has elapsed.
     pub fn is_elapsed(&self) -> bool {
+        #[cfg(all(target_os = "linux", feature = "iouring"))]
+        if let Some(precise) = &self.precise {
+            return precise.is_elapsed();
+        }
         self.entry.is_elapsed()
     }
 
@@ -253,16 +285,95 @@ impl Sleep {
     /// [`Pin::as_mut`]: fn@std::pin::Pin::as_mut
     pub fn reset(self: Pin<&mut Self>, deadline: Instant) {
         let me = self.project();
-        me.entry.reset(deadline);
         *me.deadline = deadline;
+        #[cfg(all(target_os = "linux", feature = "iouring"))]
+        if let Some(precise) = me.precise {
+            match precise.reset(deadline) {
+                Ok(()) => return,
+                // The wheel fires it instead, like when the timeout op is not
+                // available at creation.
+                Err(_) => *me.precise = None,
+            }
+        }
+        me.entry.reset(deadline);
     }
 
     fn poll_elapsed(self: Pin<&mut Self>, cx: &mut task::Context<'_>) -> Poll<Result<(), Error>> {
         let me = self.project();
+        #[cfg(all(target_os = "linux", feature = "iouring"))]
+        if let Some(precise) = me.precise {
+            return precise.poll_elapsed(cx).map(Ok);
+        }
         me.entry.poll_elapsed(cx)
     }
 }
 
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+type Precise = Option<PreciseTimer>;
+#[cfg(not(all(target_os = "linux", feature = "iouring")))]
+type Precise = ();
+
+/// Resolution of the timer wheel.
+pub(crate) const TICK: Duration = Duration::from_millis(1);
+
+/// A timer fired by the kernel at the deadline.
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+struct PreciseTimer {
+    // None if elapsed.
+    op: Option<Op<Timeout>>,
+}
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+impl PreciseTimer {
+    /// Return None if timeout ops are not available on current driver.
+    fn new(deadline: Instant) -> Option<Self> {
+        if is_legacy() || !is_uring_op_supported(io_uring::opcode::Timeout::CODE) {
+            return None;
+        }
+        Op::timeout(deadline).ok().map(|op| Self { op: Some(op) })
+    }
+
+    fn is_elapsed(&self) -> bool {
+        self.op.is_none()
+    }
+
+    fn reset(&mut self, deadline: Instant) -> std::io::Result<()> {
+        if let Some(op) = self.op.take() {
+            op.remove();
+        }
+        self.op = Some(Op::timeout(deadline)?);
+        Ok(())
+    }
+
+    fn poll_elapsed(&mut self, cx: &mut task::Context<'_>) -> Poll<()> {
+        let Some(op) = self.op.as_mut() else {
+            return Poll::Ready(());
+        };
+        // The timeout completes with ETIME when it fires.
+        let _ = ready!(Pin::new(op).poll(cx));
+        self.op = None;
+        Poll::Ready(())
+    }
+}
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+impl Drop for PreciseTimer {
+    fn drop(&mut self) {
+        if let Some(op) = self.op.take() {
+            op.remove();
+        }
+    }
+}
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+impl std::fmt::Debug for PreciseTimer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PreciseTimer")
+            .field("elapsed", &self.is_elapsed())
+            .finish()
+    }
+}
+
 impl Future for Sleep {
     type Output = ();
 
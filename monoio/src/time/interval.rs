@@ -6,7 +6,7 @@ use std::{
 
 use crate::{
     macros::support::poll_fn,
-    time::{sleep_until, Duration, Instant, Sleep},
+    time::{driver::sleep::TICK, Duration, Instant, Sleep},
 };
 
 /// Creates new [`Interval`] that yields with interval of `period`. The first
@@ -111,7 +111,8 @@ pub fn interval_at(start: Instant, period: Duration) -> Interval {
     assert!(period > Duration::new(0, 0), "`period` must be non-zero.");
 
     Interval {
-        delay: Box::pin(sleep_until(start)),
+        // Sub-millisecond periods are fired by the kernel on uring driver.
+        delay: Box::pin(Sleep::new_timeout_with(start, period < TICK)),
         period,
         missed_tick_behavior: Default::default(),
     }
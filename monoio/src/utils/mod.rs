@@ -9,10 +9,17 @@ pub(crate) mod thread_id;
 pub(crate) mod uring_detect;
 
 mod rand;
+mod ring_ping;
 pub use rand::thread_rng_n;
+pub use ring_ping::ring_ping;
 pub use uring_detect::detect_uring;
 
 pub use crate::driver::op::is_legacy;
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+pub use crate::driver::op::{
+    is_uring_op_supported, register_personality, unregister_personality, uring_cq_overflow_events,
+    uring_entries, uring_sq_full_events, PersonalityId,
+};
 
 #[cfg(feature = "signal")]
 mod ctrlc;
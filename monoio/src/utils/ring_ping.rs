@@ -0,0 +1,30 @@
+use std::{
+    io,
+    time::{Duration, Instant},
+};
+
+use crate::driver::op::Op;
+
+/// Measure the round trip of current driver, which is useful as a health
+/// check or a latency probe without touching any fd.
+///
+/// On uring driver, a `Nop` op is submitted and the time from submission to
+/// completion(including scheduling the task again) is returned. On legacy
+/// driver there is no op to submit, so the task waits for the next poll of
+/// the driver instead, which is done without blocking.
+///
+/// # Errors
+///
+/// The error of the submission or of the completion of the `Nop` op, e.g.
+/// when the op is not allowed by the restrictions of the ring.
+pub async fn ring_ping() -> io::Result<Duration> {
+    let start = Instant::now();
+    #[cfg(feature = "legacy")]
+    if crate::utils::is_legacy() {
+        let mut round = None;
+        std::future::poll_fn(|cx| crate::driver::op::poll_legacy_round(&mut round, cx)).await;
+        return Ok(start.elapsed());
+    }
+    Op::nop()?.await.meta.result?;
+    Ok(start.elapsed())
+}
@@ -0,0 +1,56 @@
+#![cfg(unix)]
+
+use std::{
+    io::{Read, Write},
+    os::unix::net::UnixStream,
+    time::Duration,
+};
+
+use monoio::io::{AsyncFd, Interest};
+
+#[monoio::test_all(timer_enabled = true)]
+async fn readiness() {
+    let (a, mut b) = UnixStream::pair().unwrap();
+    a.set_nonblocking(true).unwrap();
+    let a = AsyncFd::new(a).unwrap();
+
+    a.writable().await.unwrap();
+    monoio::select! {
+        _ = monoio::time::sleep(Duration::from_millis(50)) => {},
+        _ = a.readable() => panic!("unexpected readable"),
+    }
+
+    let peer = monoio::spawn(async move {
+        monoio::time::sleep(Duration::from_millis(10)).await;
+        b.write_all(b"hello").unwrap();
+        b
+    });
+    let mut buf = [0; 16];
+    let n = a
+        .try_io(Interest::Readable, |mut s| s.read(&mut buf))
+        .await
+        .unwrap();
+    assert_eq!(&buf[..n], b"hello");
+
+    // The inner fd is still usable after the AsyncFd is gone.
+    let mut b = peer.await;
+    let mut a = a.into_inner();
+    a.write_all(b"world").unwrap();
+    b.read_exact(&mut buf[..5]).unwrap();
+    assert_eq!(&buf[..5], b"world");
+}
+
+#[monoio::test_all(timer_enabled = true)]
+async fn drop_pending() {
+    let (a, mut b) = UnixStream::pair().unwrap();
+    a.set_nonblocking(true).unwrap();
+    let a = AsyncFd::new(a).unwrap();
+    monoio::select! {
+        _ = monoio::time::sleep(Duration::from_millis(10)) => {},
+        _ = a.readable() => panic!("unexpected readable"),
+    }
+    let a = AsyncFd::new(a.into_inner()).unwrap();
+
+    b.write_all(b"hello").unwrap();
+    a.readable().await.unwrap();
+}
@@ -0,0 +1,79 @@
+use std::{future::Future, io::Write};
+
+use monoio::{buf::BufPool, fs::File};
+
+#[monoio::test_all]
+async fn read_into_pooled() {
+    let mut tempfile = tempfile::NamedTempFile::new().unwrap();
+    tempfile.write_all(b"hello world").unwrap();
+    let file = File::open(tempfile.path()).await.unwrap();
+
+    let pool = BufPool::new(1024, 2);
+    let mut buf = pool.get().await;
+    buf.put_slice(b"stale");
+    buf.clear();
+    let (res, buf) = file.read_at(buf, 6).await;
+    assert_eq!(res.unwrap(), 5);
+    assert_eq!(&buf[..], b"world");
+    assert_eq!(pool.available(), 1);
+    drop(buf);
+    assert_eq!(pool.available(), 2);
+}
+
+#[monoio::test_all(timer_enabled = true)]
+async fn wait_for_recycle() {
+    let pool = BufPool::new(16, 1);
+    let buf = pool.try_get().unwrap();
+    assert!(pool.try_get().is_none());
+
+    let waiter = monoio::spawn({
+        let pool = pool.clone();
+        async move { pool.get().await.capacity() }
+    });
+    monoio::time::sleep(std::time::Duration::from_millis(10)).await;
+    drop(buf);
+    assert_eq!(waiter.await, 16);
+    assert_eq!(pool.available(), 1);
+}
+
+#[monoio::test_all(timer_enabled = true)]
+async fn dropped_waiter_passes_on() {
+    let pool = BufPool::new(16, 1);
+    let buf = pool.try_get().unwrap();
+
+    let mut first = Box::pin(pool.get());
+    std::future::poll_fn(|cx| {
+        assert!(first.as_mut().poll(cx).is_pending());
+        std::task::Poll::Ready(())
+    })
+    .await;
+    let second = monoio::spawn({
+        let pool = pool.clone();
+        async move { pool.get().await.capacity() }
+    });
+    monoio::time::sleep(std::time::Duration::from_millis(10)).await;
+    // The buffer is handed to the first waiter, which is gone before taking
+    // it.
+    drop(buf);
+    drop(first);
+    assert_eq!(second.await, 16);
+}
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+#[monoio::test(driver = "uring")]
+async fn registered_on_uring() {
+    let pool = BufPool::new(64, 4);
+    assert!(pool.is_registered());
+    // The ring holds one registered set at a time, the second pool is plain
+    // memory.
+    let other = BufPool::new(64, 4);
+    assert!(!other.is_registered());
+    drop(pool);
+    assert!(BufPool::new(64, 4).is_registered());
+}
+
+#[cfg(feature = "legacy")]
+#[monoio::test(driver = "legacy")]
+async fn unregistered_on_legacy() {
+    assert!(!BufPool::new(64, 4).is_registered());
+}
@@ -0,0 +1,50 @@
+#![cfg(all(feature = "sync", target_os = "linux", feature = "iouring"))]
+
+use std::{sync::mpsc, thread, time::Duration};
+
+use futures::channel::oneshot;
+use monoio::{IoUringDriver, RuntimeBuilder};
+
+/// Spawn a uring runtime waiting for the value in a task.
+fn spawn_receiver() -> (oneshot::Sender<u32>, thread::JoinHandle<u32>) {
+    let (tx, rx) = oneshot::channel();
+    let (ready_tx, ready_rx) = mpsc::channel();
+    let handle = thread::spawn(move || {
+        let mut rt = RuntimeBuilder::<IoUringDriver>::new().build().unwrap();
+        rt.block_on(async move {
+            let task = monoio::spawn(async move { rx.await.unwrap() });
+            ready_tx.send(()).unwrap();
+            task.await
+        })
+    });
+    ready_rx.recv().unwrap();
+    // Let the receiver park.
+    thread::sleep(Duration::from_millis(10));
+    (tx, handle)
+}
+
+#[test]
+fn wake_from_uring() {
+    // Both sides are uring drivers, the wakeup is posted with msg_ring.
+    let (tx, handle) = spawn_receiver();
+    let mut rt = RuntimeBuilder::<IoUringDriver>::new().build().unwrap();
+    rt.block_on(async move { tx.send(1).unwrap() });
+    assert_eq!(handle.join().unwrap(), 1);
+}
+
+#[test]
+fn wake_from_legacy() {
+    let (tx, handle) = spawn_receiver();
+    let mut rt = RuntimeBuilder::<monoio::LegacyDriver>::new()
+        .build()
+        .unwrap();
+    rt.block_on(async move { tx.send(2).unwrap() });
+    assert_eq!(handle.join().unwrap(), 2);
+}
+
+#[test]
+fn wake_from_thread() {
+    let (tx, handle) = spawn_receiver();
+    tx.send(3).unwrap();
+    assert_eq!(handle.join().unwrap(), 3);
+}
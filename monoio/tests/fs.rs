@@ -0,0 +1,440 @@
+use std::io::ErrorKind;
+
+#[monoio::test_all]
+async fn remove_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("file");
+    std::fs::write(&path, b"hello").unwrap();
+
+    monoio::fs::remove_file(&path).await.unwrap();
+    assert!(!path.exists());
+
+    let err = monoio::fs::remove_file(&path).await.unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::NotFound);
+    assert!(monoio::fs::remove_file(dir.path()).await.is_err());
+}
+
+#[monoio::test_all]
+async fn remove_dir() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("dir");
+    std::fs::create_dir(&path).unwrap();
+    std::fs::write(path.join("file"), b"hello").unwrap();
+
+    let err = monoio::fs::remove_dir(&path).await.unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::DirectoryNotEmpty);
+    std::fs::remove_file(path.join("file")).unwrap();
+    monoio::fs::remove_dir(&path).await.unwrap();
+    assert!(!path.exists());
+
+    let err = monoio::fs::remove_dir(&path).await.unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::NotFound);
+}
+
+#[monoio::test_all]
+async fn rename() {
+    let dir = tempfile::tempdir().unwrap();
+    let from = dir.path().join("from");
+    let to = dir.path().join("to");
+    std::fs::write(&from, b"hello").unwrap();
+    std::fs::write(&to, b"world").unwrap();
+
+    monoio::fs::rename(&from, &to).await.unwrap();
+    assert!(!from.exists());
+    assert_eq!(std::fs::read(&to).unwrap(), b"hello");
+
+    let err = monoio::fs::rename(&from, &to).await.unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::NotFound);
+}
+
+#[cfg(target_os = "linux")]
+#[monoio::test_all]
+async fn rename_noreplace() {
+    let dir = tempfile::tempdir().unwrap();
+    let from = dir.path().join("from");
+    let to = dir.path().join("to");
+    std::fs::write(&from, b"hello").unwrap();
+    std::fs::write(&to, b"world").unwrap();
+
+    let err = monoio::fs::rename_noreplace(&from, &to).await.unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::AlreadyExists);
+    assert_eq!(std::fs::read(&to).unwrap(), b"world");
+
+    std::fs::remove_file(&to).unwrap();
+    monoio::fs::rename_noreplace(&from, &to).await.unwrap();
+    assert_eq!(std::fs::read(&to).unwrap(), b"hello");
+}
+
+#[monoio::test_all]
+async fn create_dir() {
+    use std::os::unix::fs::{DirBuilderExt, PermissionsExt};
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("dir");
+    monoio::fs::create_dir(&path).await.unwrap();
+    assert!(path.is_dir());
+
+    let err = monoio::fs::create_dir(&path).await.unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::AlreadyExists);
+    let err = monoio::fs::create_dir(dir.path().join("a/b"))
+        .await
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::NotFound);
+
+    let path = dir.path().join("private");
+    monoio::fs::DirBuilder::new()
+        .mode(0o700)
+        .create(&path)
+        .await
+        .unwrap();
+    let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+    assert_eq!(mode & 0o777, 0o700);
+}
+
+#[monoio::test_all]
+async fn create_dir_all() {
+    use std::os::unix::fs::{DirBuilderExt, PermissionsExt};
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("a/b/c");
+    monoio::fs::create_dir_all(&path).await.unwrap();
+    assert!(path.is_dir());
+    // Existing directories are fine.
+    monoio::fs::create_dir_all(&path).await.unwrap();
+    monoio::fs::create_dir_all(dir.path().join("a/b"))
+        .await
+        .unwrap();
+    monoio::fs::create_dir_all("").await.unwrap();
+
+    // Racing creations of the same ancestors.
+    let paths = ["x/y/1", "x/y/2", "x/3", "x/y/1/4"].map(|p| dir.path().join(p));
+    let (r1, r2, r3, r4) = monoio::join!(
+        monoio::fs::create_dir_all(&paths[0]),
+        monoio::fs::create_dir_all(&paths[1]),
+        monoio::fs::create_dir_all(&paths[2]),
+        monoio::fs::create_dir_all(&paths[3]),
+    );
+    for res in [r1, r2, r3, r4] {
+        res.unwrap();
+    }
+    assert!(paths.iter().all(|p| p.is_dir()));
+
+    // A component exists and is not a directory.
+    let file = dir.path().join("file");
+    std::fs::write(&file, b"hello").unwrap();
+    let err = monoio::fs::create_dir_all(&file).await.unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::AlreadyExists);
+    let err = monoio::fs::create_dir_all(file.join("a/b"))
+        .await
+        .unwrap_err();
+    assert_eq!(err.raw_os_error(), Some(libc::ENOTDIR));
+
+    let path = dir.path().join("private/dir");
+    monoio::fs::DirBuilder::new()
+        .recursive(true)
+        .mode(0o700)
+        .create(&path)
+        .await
+        .unwrap();
+    for path in [&path, &dir.path().join("private")] {
+        let mode = std::fs::metadata(path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o700);
+    }
+}
+
+#[monoio::test_all]
+async fn remove_dir_all() {
+    let dir = tempfile::tempdir().unwrap();
+    let outside = dir.path().join("outside");
+    std::fs::create_dir(&outside).unwrap();
+    std::fs::write(outside.join("keep"), b"hello").unwrap();
+
+    let root = dir.path().join("root");
+    std::fs::create_dir_all(root.join("a/b/c")).unwrap();
+    std::fs::create_dir_all(root.join("empty")).unwrap();
+    for i in 0..200 {
+        std::fs::write(root.join(format!("a/{i}")), b"hello").unwrap();
+    }
+    std::fs::write(root.join("a/b/c/file"), b"hello").unwrap();
+    // The links are removed instead of followed.
+    std::os::unix::fs::symlink(&outside, root.join("a/b/link")).unwrap();
+    std::os::unix::fs::symlink(outside.join("keep"), root.join("file_link")).unwrap();
+    std::os::unix::fs::symlink(dir.path().join("missing"), root.join("dangling")).unwrap();
+
+    monoio::fs::remove_dir_all(&root).await.unwrap();
+    assert!(!root.exists());
+    assert_eq!(std::fs::read(outside.join("keep")).unwrap(), b"hello");
+
+    // The link itself is removed if the path is a link.
+    let link = dir.path().join("link");
+    std::os::unix::fs::symlink(&outside, &link).unwrap();
+    monoio::fs::remove_dir_all(&link).await.unwrap();
+    assert!(std::fs::symlink_metadata(&link).is_err());
+    assert!(outside.join("keep").exists());
+
+    let err = monoio::fs::remove_dir_all(&root).await.unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::NotFound);
+    let err = monoio::fs::remove_dir_all(outside.join("keep"))
+        .await
+        .unwrap_err();
+    assert_eq!(err.raw_os_error(), Some(libc::ENOTDIR));
+}
+
+#[monoio::test_all]
+async fn copy() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = tempfile::tempdir().unwrap();
+    let from = dir.path().join("from");
+    let contents: Vec<u8> = (0..300_000).map(|i| i as u8).collect();
+    std::fs::write(&from, &contents).unwrap();
+    std::fs::set_permissions(&from, std::fs::Permissions::from_mode(0o640)).unwrap();
+
+    // The destination is truncated.
+    let to = dir.path().join("to");
+    std::fs::write(&to, vec![0; 400_000]).unwrap();
+    let n = monoio::fs::copy(&from, &to).await.unwrap();
+    assert_eq!(n, contents.len() as u64);
+    assert_eq!(std::fs::read(&to).unwrap(), contents);
+    let mode = std::fs::metadata(&to).unwrap().permissions().mode();
+    assert_eq!(mode & 0o777, 0o640);
+
+    // The fallback of another file system.
+    #[cfg(target_os = "linux")]
+    if let Ok(other) = tempfile::tempdir_in("/dev/shm") {
+        let to = other.path().join("to");
+        let n = monoio::fs::copy(&from, &to).await.unwrap();
+        assert_eq!(n, contents.len() as u64);
+        assert_eq!(std::fs::read(&to).unwrap(), contents);
+    }
+    // The fallback of a file without size.
+    #[cfg(target_os = "linux")]
+    {
+        let status = dir.path().join("status");
+        let n = monoio::fs::copy("/proc/self/status", &status)
+            .await
+            .unwrap();
+        assert!(n > 0);
+        assert_eq!(std::fs::metadata(&status).unwrap().len(), n);
+    }
+
+    let empty = dir.path().join("empty");
+    std::fs::write(&empty, b"").unwrap();
+    assert_eq!(monoio::fs::copy(&empty, &to).await.unwrap(), 0);
+    assert_eq!(std::fs::metadata(&to).unwrap().len(), 0);
+
+    let err = monoio::fs::copy(dir.path(), &to).await.unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    let err = monoio::fs::copy(dir.path().join("missing"), &to)
+        .await
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::NotFound);
+}
+
+#[monoio::test_all]
+async fn read_link() {
+    let dir = tempfile::tempdir().unwrap();
+    let link = dir.path().join("link");
+    std::os::unix::fs::symlink("target", &link).unwrap();
+    assert_eq!(
+        monoio::fs::read_link(&link).await.unwrap(),
+        std::path::Path::new("target")
+    );
+
+    // Longer than the first buffer, the target does not need to exist.
+    let target = "x/".repeat(1000);
+    let long = dir.path().join("long");
+    std::os::unix::fs::symlink(&target, &long).unwrap();
+    assert_eq!(
+        monoio::fs::read_link(&long).await.unwrap().as_os_str(),
+        target.as_str()
+    );
+
+    let file = dir.path().join("file");
+    std::fs::write(&file, b"hello").unwrap();
+    let err = monoio::fs::read_link(&file).await.unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    let err = monoio::fs::read_link(dir.path().join("missing"))
+        .await
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::NotFound);
+}
+
+#[monoio::test_all]
+async fn canonicalize() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = std::fs::canonicalize(dir.path()).unwrap();
+    std::fs::create_dir(dir.path().join("sub")).unwrap();
+    std::fs::write(dir.path().join("file"), b"hello").unwrap();
+    std::os::unix::fs::symlink("sub/..", dir.path().join("up")).unwrap();
+
+    let path = dir.path().join("sub/../up/./file");
+    assert_eq!(
+        monoio::fs::canonicalize(&path).await.unwrap(),
+        root.join("file")
+    );
+    let err = monoio::fs::canonicalize(dir.path().join("sub/missing/.."))
+        .await
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::NotFound);
+
+    // A loop of links.
+    let a = dir.path().join("a");
+    let b = dir.path().join("b");
+    std::os::unix::fs::symlink(&b, &a).unwrap();
+    std::os::unix::fs::symlink(&a, &b).unwrap();
+    assert_eq!(monoio::fs::read_link(&a).await.unwrap(), b);
+    let err = monoio::fs::canonicalize(&a).await.unwrap_err();
+    assert_eq!(err.raw_os_error(), Some(libc::ELOOP));
+    let err = monoio::fs::canonicalize(a.join("file")).await.unwrap_err();
+    assert_eq!(err.raw_os_error(), Some(libc::ELOOP));
+}
+
+#[monoio::test_all]
+async fn symlink() {
+    let dir = tempfile::tempdir().unwrap();
+    let original = dir.path().join("original");
+    let link = dir.path().join("link");
+    std::fs::write(&original, b"hello").unwrap();
+
+    monoio::fs::symlink(&original, &link).await.unwrap();
+    assert_eq!(std::fs::read_link(&link).unwrap(), original);
+    assert_eq!(std::fs::read(&link).unwrap(), b"hello");
+
+    let err = monoio::fs::symlink_file(&original, &link)
+        .await
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::AlreadyExists);
+
+    let link = dir.path().join("dir_link");
+    monoio::fs::symlink_dir(dir.path(), &link).await.unwrap();
+    assert!(link.join("original").exists());
+}
+
+#[monoio::test_all]
+async fn hard_link() {
+    use std::os::unix::fs::MetadataExt;
+
+    let dir = tempfile::tempdir().unwrap();
+    let original = dir.path().join("original");
+    std::fs::write(&original, b"hello").unwrap();
+
+    let link = dir.path().join("link");
+    monoio::fs::hard_link(&original, &link).await.unwrap();
+    let original_meta = monoio::fs::metadata(&original).await.unwrap();
+    let link_meta = monoio::fs::metadata(&link).await.unwrap();
+    assert_eq!(original_meta.ino(), link_meta.ino());
+    assert_eq!(original_meta.dev(), link_meta.dev());
+    assert_eq!(link_meta.nlink(), 2);
+
+    let err = monoio::fs::hard_link(&original, &link).await.unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::AlreadyExists);
+}
+
+#[monoio::test_all]
+async fn set_permissions() {
+    use std::{fs::Permissions, os::unix::fs::PermissionsExt};
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("file");
+    std::fs::write(&path, b"hello").unwrap();
+
+    monoio::fs::set_permissions(&path, Permissions::from_mode(0o600))
+        .await
+        .unwrap();
+    let meta = monoio::fs::metadata(&path).await.unwrap();
+    assert_eq!(meta.permissions().mode() & 0o777, 0o600);
+
+    // The link is followed.
+    let link = dir.path().join("link");
+    monoio::fs::symlink(&path, &link).await.unwrap();
+    monoio::fs::set_permissions(&link, Permissions::from_mode(0o644))
+        .await
+        .unwrap();
+    let meta = monoio::fs::metadata(&path).await.unwrap();
+    assert_eq!(meta.permissions().mode() & 0o777, 0o644);
+
+    let err = monoio::fs::set_permissions(dir.path().join("missing"), meta.permissions())
+        .await
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::NotFound);
+}
+
+#[monoio::test_all]
+async fn set_times() {
+    use std::time::{Duration, SystemTime};
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("file");
+    std::fs::write(&path, b"hello").unwrap();
+
+    let accessed = SystemTime::UNIX_EPOCH + Duration::new(1_000_000_000, 123_456_789);
+    let modified = SystemTime::UNIX_EPOCH + Duration::new(1_500_000_000, 987_654_321);
+    monoio::fs::set_times(&path, Some(accessed), Some(modified))
+        .await
+        .unwrap();
+    let meta = monoio::fs::metadata(&path).await.unwrap();
+    assert_eq!(meta.accessed().unwrap(), accessed);
+    assert_eq!(meta.modified().unwrap(), modified);
+
+    // The link is followed, and the access time is kept for `None`.
+    let link = dir.path().join("link");
+    monoio::fs::symlink(&path, &link).await.unwrap();
+    let before_epoch = SystemTime::UNIX_EPOCH - Duration::new(1, 500_000_000);
+    monoio::fs::set_times(&link, None, Some(before_epoch))
+        .await
+        .unwrap();
+    let meta = monoio::fs::metadata(&path).await.unwrap();
+    assert_eq!(meta.accessed().unwrap(), accessed);
+    assert_eq!(meta.modified().unwrap(), before_epoch);
+
+    // Only the link itself is changed.
+    monoio::fs::set_symlink_times(&link, None, Some(modified))
+        .await
+        .unwrap();
+    let link_meta = monoio::fs::symlink_metadata(&link).await.unwrap();
+    assert_eq!(link_meta.modified().unwrap(), modified);
+    let meta = monoio::fs::metadata(&path).await.unwrap();
+    assert_eq!(meta.modified().unwrap(), before_epoch);
+
+    let err = monoio::fs::set_times(dir.path().join("missing"), None, Some(modified))
+        .await
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::NotFound);
+}
+
+#[monoio::test_all]
+async fn read_write() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("file");
+    std::fs::write(&path, vec![b'x'; 100]).unwrap();
+
+    // The file is truncated.
+    let contents: Vec<u8> = (0..100_000).map(|i| i as u8).collect();
+    let (res, contents) = monoio::fs::write(&path, contents).await;
+    res.unwrap();
+    assert_eq!(std::fs::read(&path).unwrap(), contents);
+    assert_eq!(monoio::fs::read(&path).await.unwrap(), contents);
+
+    let (res, _) = monoio::fs::write(&path, "hello").await;
+    res.unwrap();
+    assert_eq!(monoio::fs::read_to_string(&path).await.unwrap(), "hello");
+    let (res, _) = monoio::fs::write(&path, vec![0xff, 0xfe]).await;
+    res.unwrap();
+    let err = monoio::fs::read_to_string(&path).await.unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+
+    // The size of the file is not the size of the contents.
+    #[cfg(target_os = "linux")]
+    {
+        let status = monoio::fs::read_to_string("/proc/self/status")
+            .await
+            .unwrap();
+        assert!(status.contains("Pid:"));
+    }
+
+    let err = monoio::fs::read(dir.path().join("missing"))
+        .await
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::NotFound);
+}
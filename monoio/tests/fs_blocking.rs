@@ -0,0 +1,259 @@
+//! The blocking fs calls run on the thread pool attached to the runtime.
+#![cfg(all(unix, feature = "sync"))]
+
+use std::future::Future;
+
+use monoio::fs::File;
+
+fn block_on_pool<F: Future>(fut: F) -> F::Output {
+    monoio::RuntimeBuilder::<monoio::FusionDriver>::new()
+        .attach_thread_pool(Box::new(monoio::blocking::DefaultThreadPool::new(2)))
+        .build()
+        .unwrap()
+        .block_on(fut)
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn sync_range() {
+    use monoio::fs::SyncRangeFlags;
+
+    let tempfile = tempfile::NamedTempFile::new().unwrap();
+    block_on_pool(async {
+        let file = File::create(tempfile.path()).await.unwrap();
+        file.write_all_at(&b"hello"[..], 0).await.0.unwrap();
+        file.sync_range(0, 5, SyncRangeFlags::WRITE).await.unwrap();
+        file.sync_range(
+            0,
+            5 << 30,
+            SyncRangeFlags::WRITE | SyncRangeFlags::WAIT_AFTER,
+        )
+        .await
+        .unwrap();
+        // The duplicate of the pool is closed.
+        file.close().await.unwrap();
+    });
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn fallocate() {
+    let tempfile = tempfile::NamedTempFile::new().unwrap();
+    std::fs::write(tempfile.path(), [1; 8192]).unwrap();
+    block_on_pool(async {
+        let file = monoio::fs::OpenOptions::new()
+            .write(true)
+            .open(tempfile.path())
+            .await
+            .unwrap();
+        file.punch_hole(0, 4096).await.unwrap();
+        file.allocate(0, 16384).await.unwrap();
+        file.close().await.unwrap();
+    });
+    let content = std::fs::read(tempfile.path()).unwrap();
+    assert_eq!(content.len(), 16384);
+    assert!(content[..4096].iter().all(|&b| b == 0));
+    assert!(content[4096..8192].iter().all(|&b| b == 1));
+}
+
+#[test]
+fn set_len() {
+    let tempfile = tempfile::NamedTempFile::new().unwrap();
+    block_on_pool(async {
+        let file = File::create(tempfile.path()).await.unwrap();
+        file.set_len(10).await.unwrap();
+        file.close().await.unwrap();
+    });
+    assert_eq!(tempfile.as_file().metadata().unwrap().len(), 10);
+}
+
+#[test]
+fn remove() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("file");
+    let sub = dir.path().join("sub");
+    std::fs::write(&file, b"hello").unwrap();
+    std::fs::create_dir(&sub).unwrap();
+    block_on_pool(async {
+        monoio::fs::remove_file(&file).await.unwrap();
+        monoio::fs::remove_dir(&sub).await.unwrap();
+        let err = monoio::fs::remove_file(&file).await.unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+    });
+    assert!(!file.exists() && !sub.exists());
+}
+
+#[test]
+fn rename() {
+    let dir = tempfile::tempdir().unwrap();
+    let from = dir.path().join("from");
+    let to = dir.path().join("to");
+    std::fs::write(&from, b"hello").unwrap();
+    block_on_pool(async {
+        monoio::fs::rename(&from, &to).await.unwrap();
+        let err = monoio::fs::rename(&from, &to).await.unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+    });
+    assert_eq!(std::fs::read(&to).unwrap(), b"hello");
+}
+
+#[test]
+fn create_dir() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("a/b/c");
+    block_on_pool(async {
+        monoio::fs::create_dir_all(&path).await.unwrap();
+        let err = monoio::fs::create_dir(&path).await.unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::AlreadyExists);
+    });
+    assert!(path.is_dir());
+}
+
+#[test]
+fn symlink() {
+    let dir = tempfile::tempdir().unwrap();
+    let link = dir.path().join("link");
+    block_on_pool(async {
+        monoio::fs::symlink("target", &link).await.unwrap();
+        let err = monoio::fs::symlink("target", &link).await.unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::AlreadyExists);
+    });
+    assert_eq!(
+        std::fs::read_link(&link).unwrap(),
+        std::path::Path::new("target")
+    );
+}
+
+#[test]
+fn hard_link() {
+    let dir = tempfile::tempdir().unwrap();
+    let original = dir.path().join("original");
+    let link = dir.path().join("link");
+    std::fs::write(&original, b"hello").unwrap();
+    block_on_pool(async {
+        monoio::fs::hard_link(&original, &link).await.unwrap();
+        let err = monoio::fs::hard_link(&original, &link).await.unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::AlreadyExists);
+    });
+    assert_eq!(std::fs::read(&link).unwrap(), b"hello");
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn persist() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("persisted");
+    block_on_pool(async {
+        let mut file = monoio::fs::tempfile_in(dir.path()).await.unwrap();
+        file.write_all_at(&b"hello"[..], 0).await.0.unwrap();
+        file.persist(&path).await.unwrap();
+    });
+    assert_eq!(std::fs::read(&path).unwrap(), b"hello");
+}
+
+#[test]
+fn set_permissions() {
+    use std::{fs::Permissions, os::unix::fs::PermissionsExt};
+
+    let tempfile = tempfile::NamedTempFile::new().unwrap();
+    block_on_pool(async {
+        let file = File::open(tempfile.path()).await.unwrap();
+        file.set_permissions(Permissions::from_mode(0o600))
+            .await
+            .unwrap();
+        file.close().await.unwrap();
+        assert_eq!(
+            tempfile.as_file().metadata().unwrap().permissions().mode() & 0o777,
+            0o600
+        );
+        monoio::fs::set_permissions(tempfile.path(), Permissions::from_mode(0o640))
+            .await
+            .unwrap();
+    });
+    assert_eq!(
+        tempfile.as_file().metadata().unwrap().permissions().mode() & 0o777,
+        0o640
+    );
+}
+
+#[test]
+fn remove_dir_all() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path().join("root");
+    std::fs::create_dir_all(root.join("a/b")).unwrap();
+    for i in 0..100 {
+        std::fs::write(root.join(format!("a/{i}")), b"hello").unwrap();
+    }
+    std::fs::write(root.join("a/b/c"), b"hello").unwrap();
+    block_on_pool(async {
+        monoio::fs::remove_dir_all(&root).await.unwrap();
+        let err = monoio::fs::remove_dir_all(&root).await.unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+    });
+    assert!(!root.exists());
+}
+
+#[test]
+fn copy() {
+    let dir = tempfile::tempdir().unwrap();
+    let from = dir.path().join("from");
+    let to = dir.path().join("to");
+    std::fs::write(&from, vec![7; 1 << 20]).unwrap();
+    block_on_pool(async {
+        assert_eq!(monoio::fs::copy(&from, &to).await.unwrap(), 1 << 20);
+    });
+    assert_eq!(std::fs::read(&to).unwrap(), vec![7; 1 << 20]);
+}
+
+#[test]
+fn read_link() {
+    let dir = tempfile::tempdir().unwrap();
+    let target = dir.path().join("target");
+    let link = dir.path().join("link");
+    std::fs::write(&target, b"hello").unwrap();
+    std::os::unix::fs::symlink(&target, &link).unwrap();
+    block_on_pool(async {
+        assert_eq!(monoio::fs::read_link(&link).await.unwrap(), target);
+        assert_eq!(
+            monoio::fs::canonicalize(&link).await.unwrap(),
+            std::fs::canonicalize(&target).unwrap()
+        );
+    });
+}
+
+#[test]
+fn set_times() {
+    use std::time::{Duration, SystemTime};
+
+    let tempfile = tempfile::NamedTempFile::new().unwrap();
+    let accessed = SystemTime::UNIX_EPOCH + Duration::from_secs(1 << 20);
+    let modified = SystemTime::UNIX_EPOCH + Duration::from_secs(1 << 30);
+    block_on_pool(async {
+        let file = File::open(tempfile.path()).await.unwrap();
+        file.set_times(Some(accessed), None).await.unwrap();
+        file.close().await.unwrap();
+        monoio::fs::set_times(tempfile.path(), None, Some(modified))
+            .await
+            .unwrap();
+    });
+    let metadata = tempfile.as_file().metadata().unwrap();
+    assert_eq!(metadata.accessed().unwrap(), accessed);
+    assert_eq!(metadata.modified().unwrap(), modified);
+}
+
+#[test]
+fn metadata() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("file");
+    let link = dir.path().join("link");
+    std::fs::write(&path, b"hello").unwrap();
+    std::os::unix::fs::symlink(&path, &link).unwrap();
+    block_on_pool(async {
+        let file = File::open(&path).await.unwrap();
+        assert_eq!(file.metadata().await.unwrap().len(), 5);
+        file.close().await.unwrap();
+        assert_eq!(monoio::fs::metadata(&link).await.unwrap().len(), 5);
+        let metadata = monoio::fs::symlink_metadata(&link).await.unwrap();
+        assert!(metadata.file_type().is_symlink());
+    });
+}
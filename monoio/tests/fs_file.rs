@@ -64,6 +64,41 @@ async fn basic_write_all() {
     assert_eq!(file, HELLO);
 }
 
+#[monoio::test_all]
+async fn write_all_at_sync() {
+    let tempfile = tempfile();
+
+    let file = File::create(tempfile.path()).await.unwrap();
+    file.write_all_at_sync(HELLO, 0).await.0.unwrap();
+    file.write_all_at_sync(HELLO, HELLO.len() as u64)
+        .await
+        .0
+        .unwrap();
+
+    let file = std::fs::read(tempfile.path()).unwrap();
+    assert_eq!(file, HELLO.repeat(2));
+
+    // The write fails on a read-only file, and its error is returned instead
+    // of the one of the cancelled fsync.
+    let file = File::open(tempfile.path()).await.unwrap();
+    let err = file.write_all_at_sync(HELLO, 0).await.0.unwrap_err();
+    assert_eq!(err.raw_os_error(), Some(libc::EBADF));
+}
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+#[monoio::test(driver = "uring")]
+async fn read_fixed_at() {
+    let mut tempfile = tempfile();
+    tempfile.write_all(HELLO).unwrap();
+
+    let pool = monoio::buf::FixedBufPool::new(2, 1024).unwrap();
+    let file = File::open(tempfile.path()).await.unwrap();
+    let buf = pool.get().await;
+    let (res, buf) = file.read_fixed_at(buf, 6).await;
+    assert_eq!(res.unwrap(), HELLO.len() - 6);
+    assert_eq!(&buf[..], &HELLO[6..]);
+}
+
 #[monoio::test(driver = "uring")]
 async fn cancel_read() {
     let mut tempfile = tempfile();
@@ -103,6 +138,102 @@ async fn drop_open() {
     drop(file_w);
 }
 
+#[monoio::test_all]
+async fn stream_read_write() {
+    use monoio::io::{
+        AsyncBufReadExt, AsyncReadRent, AsyncReadRentExt, AsyncWriteRentExt, BufReader,
+    };
+
+    let tempfile = tempfile();
+    let file = File::create(tempfile.path()).await.unwrap();
+    let mut stream = file.into_stream();
+    stream.write_all(&b"hello\n"[..]).await.0.unwrap();
+    stream.write_all(&b"world\n"[..]).await.0.unwrap();
+    assert_eq!(stream.position(), 12);
+    stream.into_inner().close().await.unwrap();
+
+    let file = File::open(tempfile.path()).await.unwrap();
+    let mut reader = BufReader::new(file.into_stream());
+    let mut line = String::new();
+    reader.read_line(&mut line).await.unwrap();
+    assert_eq!(line, "hello\n");
+    let mut stream = reader.into_inner();
+    assert_eq!(stream.position(), 12);
+
+    // EOF is reported as Ok(0).
+    let (res, _) = stream.read(vec![0; 8]).await;
+    assert_eq!(res.unwrap(), 0);
+
+    stream.set_position(6);
+    let (res, buf) = stream.read_exact(vec![0; 6]).await;
+    res.unwrap();
+    assert_eq!(buf, b"world\n");
+}
+
+#[monoio::test_all]
+async fn stream_seek() {
+    use std::io::SeekFrom;
+
+    use monoio::io::{
+        AsyncBufRead, AsyncReadRentExt, AsyncSeekRent, AsyncWriteRent, AsyncWriteRentExt,
+        BufReader, BufWriter,
+    };
+
+    let tempfile = tempfile();
+    let file = monoio::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(tempfile.path())
+        .await
+        .unwrap();
+    let mut writer = BufWriter::with_capacity(64, file.into_stream());
+    writer.write_all(&b"0123456789"[..]).await.0.unwrap();
+    // The buffered data is written before seeking.
+    assert_eq!(writer.seek(SeekFrom::Start(2)).await.unwrap(), 2);
+    assert!(writer.buffer().is_empty());
+    writer.write_all(&b"ab"[..]).await.0.unwrap();
+    assert_eq!(writer.seek(SeekFrom::End(1)).await.unwrap(), 11);
+    writer.write_all(&b"z"[..]).await.0.unwrap();
+    writer.flush().await.unwrap();
+    assert_eq!(std::fs::read(tempfile.path()).unwrap(), b"01ab456789\0z");
+
+    let mut stream = writer.into_inner();
+    let err = stream.seek(SeekFrom::Current(-13)).await.unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+    assert_eq!(stream.position(), 12);
+    assert_eq!(stream.seek(SeekFrom::End(-12)).await.unwrap(), 0);
+
+    // A buffer of 4 bytes is filled with "01ab", then "4567".
+    let mut reader = BufReader::with_capacity(4, stream);
+    for expected in [b"01", b"ab", b"45"] {
+        let (res, buf) = reader.read_exact(vec![0; 2]).await;
+        res.unwrap();
+        assert_eq!(buf, expected);
+    }
+    assert_eq!(reader.buffer(), b"67");
+    assert_eq!(reader.get_ref().position(), 8);
+
+    // Backwards within the buffer keeps it.
+    reader.seek_relative(-1).await.unwrap();
+    assert_eq!(reader.buffer(), b"567");
+    // Backwards across the start of the buffer discards it.
+    assert_eq!(reader.seek(SeekFrom::Current(-3)).await.unwrap(), 2);
+    assert!(reader.buffer().is_empty());
+    assert_eq!(reader.fill_buf().await.unwrap(), b"ab45");
+    reader.consume(1);
+    reader.seek_relative(-3).await.unwrap();
+    assert_eq!(reader.get_ref().position(), 0);
+    let (res, buf) = reader.read_exact(vec![0; 3]).await;
+    res.unwrap();
+    assert_eq!(buf, b"01a");
+    // Forwards beyond the buffer.
+    reader.seek_relative(5).await.unwrap();
+    let (res, buf) = reader.read_exact(vec![0; 2]).await;
+    res.unwrap();
+    assert_eq!(buf, b"89");
+    assert_eq!(reader.seek(SeekFrom::Current(0)).await.unwrap(), 10);
+}
+
 #[test]
 fn drop_off_runtime() {
     let tempfile = tempfile();
@@ -133,6 +264,452 @@ async fn sync_doesnt_kill_anything() {
     file.sync_data().await.unwrap();
 }
 
+#[monoio::test_all]
+async fn metadata() {
+    let mut tempfile = tempfile();
+    tempfile.write_all(HELLO).unwrap();
+    let std_meta = std::fs::metadata(tempfile.path()).unwrap();
+
+    let file = File::open(tempfile.path()).await.unwrap();
+    let meta = file.metadata().await.unwrap();
+    assert!(meta.is_file());
+    assert_eq!(meta.len(), HELLO.len() as u64);
+    assert_eq!(meta.permissions(), std_meta.permissions());
+    assert_eq!(meta.modified().unwrap(), std_meta.modified().unwrap());
+
+    let dir = tempfile.path().parent().unwrap();
+    assert!(monoio::fs::metadata(dir).await.unwrap().is_dir());
+    let link = tempfile::tempdir().unwrap();
+    let link = link.path().join("link");
+    std::os::unix::fs::symlink(tempfile.path(), &link).unwrap();
+    assert!(monoio::fs::metadata(&link).await.unwrap().is_file());
+    assert!(monoio::fs::symlink_metadata(&link)
+        .await
+        .unwrap()
+        .is_symlink());
+
+    let err = monoio::fs::metadata(dir.join("not-exist"))
+        .await
+        .unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+}
+
+#[monoio::test_all]
+async fn set_len() {
+    let tempfile = tempfile();
+
+    let file = File::create(tempfile.path()).await.unwrap();
+    file.write_all_at(HELLO, 0).await.0.unwrap();
+    file.set_len(HELLO.len() as u64 + 4).await.unwrap();
+    let content = std::fs::read(tempfile.path()).unwrap();
+    assert_eq!(&content[..HELLO.len()], HELLO);
+    assert_eq!(&content[HELLO.len()..], &[0; 4]);
+
+    // Appending writes continue from the new end.
+    let file = monoio::fs::OpenOptions::new()
+        .append(true)
+        .open(tempfile.path())
+        .await
+        .unwrap();
+    file.set_len(5).await.unwrap();
+    file.write_all_at(&b" world"[..], 0).await.0.unwrap();
+    assert_eq!(std::fs::read(tempfile.path()).unwrap(), b"hello world");
+    // A sparse file larger than 32-bit.
+    file.set_len(5 << 30).await.unwrap();
+    assert_eq!(std::fs::metadata(tempfile.path()).unwrap().len(), 5 << 30);
+    file.set_len(0).await.unwrap();
+
+    let file = File::open(tempfile.path()).await.unwrap();
+    assert!(file.set_len(0).await.is_err());
+    let err = File::create(tempfile.path())
+        .await
+        .unwrap()
+        .set_len(u64::MAX)
+        .await
+        .unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+}
+
+#[monoio::test_all]
+async fn open_options_ext() {
+    use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("file");
+
+    // The mode is masked by the umask only.
+    let file = monoio::fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .mode(0o600)
+        .open(&path)
+        .await
+        .unwrap();
+    let mode = file.metadata().await.unwrap().permissions().mode();
+    assert_eq!(mode & 0o777, 0o600);
+
+    // The custom flags are OR'd, and the access mode in them is ignored.
+    let link = dir.path().join("link");
+    std::os::unix::fs::symlink(&path, &link).unwrap();
+    let err = monoio::fs::OpenOptions::new()
+        .read(true)
+        .custom_flags(libc::O_NOFOLLOW | libc::O_WRONLY)
+        .open(&link)
+        .await
+        .unwrap_err();
+    assert_eq!(err.raw_os_error(), Some(libc::ELOOP));
+    let file = monoio::fs::OpenOptions::new()
+        .read(true)
+        .custom_flags(libc::O_NOFOLLOW | libc::O_WRONLY)
+        .open(&path)
+        .await
+        .unwrap();
+    assert!(file.write_at(&b"hello"[..], 0).await.0.is_err());
+
+    // Conflicting options fail like std.
+    #[allow(clippy::nonsensical_open_options)]
+    let err = monoio::fs::OpenOptions::new()
+        .append(true)
+        .truncate(true)
+        .open(&path)
+        .await
+        .unwrap_err();
+    #[allow(clippy::nonsensical_open_options)]
+    let std_err = std::fs::OpenOptions::new()
+        .append(true)
+        .truncate(true)
+        .open(&path)
+        .unwrap_err();
+    assert_eq!(err.kind(), std_err.kind());
+}
+
+#[cfg(target_os = "linux")]
+#[monoio::test_all]
+async fn direct_io() {
+    use monoio::buf::{AlignedBuf, IoBuf};
+
+    let tempfile = tempfile();
+    let file = monoio::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .direct(true)
+        .open(tempfile.path())
+        .await
+        .unwrap();
+
+    let mut buf = AlignedBuf::new(8192, 4096);
+    buf.put_slice(&[b'a'; 4096]);
+    buf.put_slice(&[b'b'; 4096]);
+    let (res, _) = file.write_at(buf, 4096).await;
+    assert_eq!(res.unwrap(), 8192);
+
+    let (res, buf) = file.read_at(AlignedBuf::new(4096, 4096), 8192).await;
+    assert_eq!(res.unwrap(), 4096);
+    assert!(buf.iter().all(|&b| b == b'b'));
+    // A short read at the end of the file.
+    let (res, _) = file.read_at(AlignedBuf::new(8192, 4096), 8192).await;
+    assert_eq!(res.unwrap(), 4096);
+
+    // ext4 and most file systems report the alignment, the misaligned IO is
+    // rejected before it is submitted.
+    let (res, _) = file.write_at(vec![0; 4097].slice(1..), 0).await;
+    assert_eq!(res.unwrap_err().kind(), std::io::ErrorKind::InvalidInput);
+    let (res, _) = file.read_at(AlignedBuf::new(4096, 4096), 1).await;
+    assert_eq!(res.unwrap_err().kind(), std::io::ErrorKind::InvalidInput);
+    let mut buf = AlignedBuf::new(4096, 4096);
+    buf.put_slice(&[0; 100]);
+    let (res, _) = file.write_at(buf, 0).await;
+    assert_eq!(res.unwrap_err().kind(), std::io::ErrorKind::InvalidInput);
+}
+
+#[monoio::test_all]
+async fn set_permissions() {
+    use std::{fs::Permissions, os::unix::fs::PermissionsExt};
+
+    let tempfile = tempfile();
+
+    let file = File::open(tempfile.path()).await.unwrap();
+    file.set_permissions(Permissions::from_mode(0o640))
+        .await
+        .unwrap();
+    let perm = file.metadata().await.unwrap().permissions();
+    assert_eq!(perm.mode() & 0o777, 0o640);
+
+    let mut perm = perm;
+    perm.set_readonly(true);
+    file.set_permissions(perm).await.unwrap();
+    let perm = file.metadata().await.unwrap().permissions();
+    assert!(perm.readonly());
+    assert_eq!(perm.mode() & 0o777, 0o440);
+}
+
+#[cfg(target_os = "linux")]
+#[monoio::test_all]
+async fn sync_range() {
+    use monoio::fs::SyncRangeFlags;
+
+    // tmpfs has no write-back, the call succeeds without doing anything.
+    let mut tempfiles = vec![tempfile()];
+    if let Ok(tmpfs) = NamedTempFile::new_in("/dev/shm") {
+        tempfiles.push(tmpfs);
+    }
+    for tempfile in tempfiles {
+        let file = File::create(tempfile.path()).await.unwrap();
+        file.write_all_at(HELLO, 0).await.0.unwrap();
+        file.sync_range(0, HELLO.len() as u64, SyncRangeFlags::WRITE)
+            .await
+            .unwrap();
+        // Larger than the 32-bit length of the opcode.
+        file.sync_range(0, 5 << 30, SyncRangeFlags::WRITE)
+            .await
+            .unwrap();
+        let err = file
+            .sync_range(u64::MAX, 1, SyncRangeFlags::WRITE)
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+        file.sync_range(
+            0,
+            0,
+            SyncRangeFlags::WAIT_BEFORE | SyncRangeFlags::WRITE | SyncRangeFlags::WAIT_AFTER,
+        )
+        .await
+        .unwrap();
+        assert_eq!(std::fs::read(tempfile.path()).unwrap(), HELLO);
+    }
+}
+
+#[cfg(target_os = "linux")]
+#[monoio::test_all]
+async fn fallocate() {
+    let mut tempfile = tempfile();
+    tempfile.write_all(&[1; 8192]).unwrap();
+
+    let file = monoio::fs::OpenOptions::new()
+        .write(true)
+        .open(tempfile.path())
+        .await
+        .unwrap();
+    file.punch_hole(0, 4096).await.unwrap();
+    file.zero_range(6000, 100).await.unwrap();
+    let content = std::fs::read(tempfile.path()).unwrap();
+    assert_eq!(content.len(), 8192);
+    assert!(content[..4096].iter().all(|&b| b == 0));
+    assert!(content[4096..6000].iter().all(|&b| b == 1));
+    assert!(content[6000..6100].iter().all(|&b| b == 0));
+    assert!(content[6100..].iter().all(|&b| b == 1));
+
+    file.allocate(0, 16384).await.unwrap();
+    assert_eq!(file.metadata().await.unwrap().len(), 16384);
+
+    let err = file.allocate(u64::MAX, 1).await.unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+}
+
+#[monoio::test_all]
+async fn advise() {
+    use monoio::fs::Advice;
+
+    let mut tempfile = tempfile();
+    tempfile.write_all(HELLO).unwrap();
+
+    let file = File::open(tempfile.path()).await.unwrap();
+    file.advise(0, 0, Advice::Sequential).await.unwrap();
+    read_hello(&file).await;
+    let advise = monoio::spawn(file.advise(0, HELLO.len() as u64, Advice::DontNeed));
+    drop(file);
+    advise.await.unwrap();
+
+    // Longer than the 32-bit length of io_uring.
+    let file = File::open(tempfile.path()).await.unwrap();
+    file.advise(0, 5 << 30, Advice::WillNeed).await.unwrap();
+    file.advise(1, u64::MAX - 1, Advice::DontNeed)
+        .await
+        .unwrap();
+    // Split into chunks inside a sparse file.
+    tempfile.as_file().set_len(8 << 30).unwrap();
+    file.advise(4096, 5 << 30, Advice::DontNeed).await.unwrap();
+    #[cfg(target_os = "linux")]
+    {
+        let err = file.advise(u64::MAX, 1, Advice::Normal).await.unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+    }
+}
+
+#[cfg(target_os = "linux")]
+#[monoio::test_all]
+async fn advise_memory() {
+    use monoio::fs::Advice;
+
+    struct Mmap(*mut u8, usize);
+
+    unsafe impl monoio::buf::IoBuf for Mmap {
+        fn read_ptr(&self) -> *const u8 {
+            self.0
+        }
+
+        fn bytes_init(&self) -> usize {
+            self.1
+        }
+    }
+
+    impl Drop for Mmap {
+        fn drop(&mut self) {
+            unsafe { libc::munmap(self.0 as _, self.1) };
+        }
+    }
+
+    let len = 2 * 4096;
+    let ptr = unsafe {
+        libc::mmap(
+            std::ptr::null_mut(),
+            len,
+            libc::PROT_READ | libc::PROT_WRITE,
+            libc::MAP_PRIVATE | libc::MAP_ANONYMOUS,
+            -1,
+            0,
+        )
+    };
+    assert_ne!(ptr, libc::MAP_FAILED);
+    let mmap = Mmap(ptr as *mut u8, len);
+    unsafe { std::ptr::write_bytes(mmap.0, 1, len) };
+
+    let (res, mmap) = monoio::fs::advise_memory(mmap, Advice::WillNeed).await;
+    res.unwrap();
+    let (res, mmap) = monoio::fs::advise_memory(mmap, Advice::NoReuse).await;
+    assert_eq!(res.unwrap_err().kind(), std::io::ErrorKind::InvalidInput);
+
+    let (res, mmap) = monoio::fs::advise_memory(mmap, Advice::DontNeed).await;
+    assert_eq!(res.unwrap_err().kind(), std::io::ErrorKind::InvalidInput);
+
+    // Private anonymous pages are zero-filled after they are dropped.
+    let (res, mmap) = unsafe { monoio::fs::advise_memory_unchecked(mmap, Advice::DontNeed) }.await;
+    res.unwrap();
+    let data = unsafe { std::slice::from_raw_parts(mmap.0, len) };
+    assert!(data.iter().all(|&b| b == 0));
+}
+
+#[cfg(target_os = "linux")]
+#[monoio::test_all]
+async fn open_at_resolve_beneath() {
+    use monoio::fs::OpenOptions;
+
+    let outside = tempfile();
+    let root = tempfile::tempdir().unwrap();
+    std::fs::write(root.path().join("hello"), HELLO).unwrap();
+    std::os::unix::fs::symlink(outside.path(), root.path().join("escape")).unwrap();
+    let dir = File::open(root.path()).await.unwrap();
+
+    // Without resolve flags the symlink is followed.
+    let file = OpenOptions::new()
+        .read(true)
+        .open_at(&dir, "escape")
+        .await
+        .unwrap();
+    drop(file);
+
+    let mut opts = OpenOptions::new();
+    opts.read(true).resolve_flags(libc::RESOLVE_BENEATH);
+    let file = opts.open_at(&dir, "hello").await.unwrap();
+    read_hello(&file).await;
+    for path in ["escape", "../hello"] {
+        let err = opts.open_at(&dir, path).await.unwrap_err();
+        assert_eq!(err.raw_os_error(), Some(libc::EXDEV));
+    }
+}
+
+#[monoio::test_all(timer_enabled = true)]
+async fn try_clone() {
+    let tempfile = tempfile();
+    let file = monoio::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(tempfile.path())
+        .await
+        .unwrap();
+    let clone = file.try_clone().unwrap();
+    assert_ne!(clone.as_raw_fd(), file.as_raw_fd());
+
+    // Writes at different offsets in flight at once.
+    let (a, b) = monoio::join!(
+        file.write_at(&b"hello"[..], 0),
+        clone.write_at(&b"world"[..], 5)
+    );
+    assert_eq!(a.0.unwrap(), 5);
+    assert_eq!(b.0.unwrap(), 5);
+
+    // Dropping one closes only its own fd.
+    drop(file);
+    monoio::time::sleep(std::time::Duration::from_millis(10)).await;
+    let (res, buf) = clone.read_at(vec![0; 16], 0).await;
+    assert_eq!(res.unwrap(), 10);
+    assert_eq!(&buf, b"helloworld");
+    clone.close().await.unwrap();
+}
+
+#[monoio::test_all]
+async fn set_times() {
+    use std::time::{Duration, SystemTime};
+
+    let tempfile = tempfile();
+    let file = File::open(tempfile.path()).await.unwrap();
+    let accessed = SystemTime::UNIX_EPOCH + Duration::new(1_000_000_000, 1);
+    let modified = SystemTime::UNIX_EPOCH - Duration::new(86_400, 999_999_999);
+    file.set_times(Some(accessed), Some(modified))
+        .await
+        .unwrap();
+    let meta = file.metadata().await.unwrap();
+    assert_eq!(meta.accessed().unwrap(), accessed);
+    assert_eq!(meta.modified().unwrap(), modified);
+
+    // `None` keeps the time.
+    let now = SystemTime::now();
+    file.set_times(None, Some(now)).await.unwrap();
+    let meta = file.metadata().await.unwrap();
+    assert_eq!(meta.accessed().unwrap(), accessed);
+    assert_eq!(meta.modified().unwrap(), now);
+    file.close().await.unwrap();
+}
+
+#[cfg(target_os = "linux")]
+#[monoio::test_all]
+async fn tempfile_persist() {
+    let dir = tempfile::tempdir().unwrap();
+    let entries = || {
+        std::fs::read_dir(dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect::<Vec<_>>()
+    };
+
+    let mut file = monoio::fs::tempfile_in(dir.path()).await.unwrap();
+    file.write_all_at(&b"hello"[..], 0).await.0.unwrap();
+    file.sync_all().await.unwrap();
+    // Unnamed until persisted.
+    assert!(entries().is_empty());
+    let path = dir.path().join("hello");
+    file.persist(&path).await.unwrap();
+    assert_eq!(std::fs::read(&path).unwrap(), b"hello");
+
+    // The existing file is replaced, no other name is left behind.
+    let mut file = monoio::fs::tempfile_in(dir.path()).await.unwrap();
+    file.write_all_at(&b"world"[..], 0).await.0.unwrap();
+    file.persist(&path).await.unwrap();
+    assert_eq!(std::fs::read(&path).unwrap(), b"world");
+    assert_eq!(entries(), ["hello"]);
+    // It is still open, and can be linked again.
+    file.write_all_at(&b"!"[..], 5).await.0.unwrap();
+    file.persist(dir.path().join("other")).await.unwrap();
+    assert_eq!(std::fs::read(&path).unwrap(), b"world!");
+    assert_eq!(std::fs::read(dir.path().join("other")).unwrap(), b"world!");
+    drop(file);
+
+    let err = monoio::fs::tempfile_in(dir.path().join("missing"))
+        .await
+        .unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+}
+
 fn tempfile() -> NamedTempFile {
     NamedTempFile::new().expect("unable to create tempfile")
 }
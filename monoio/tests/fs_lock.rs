@@ -0,0 +1,91 @@
+#![cfg(unix)]
+
+use std::io::ErrorKind;
+#[cfg(feature = "sync")]
+use std::time::{Duration, Instant};
+
+use monoio::fs::File;
+
+#[monoio::test_all]
+async fn try_lock() {
+    let tempfile = tempfile::NamedTempFile::new().unwrap();
+    // Two open file descriptions, whose locks conflict.
+    let a = File::open(tempfile.path()).await.unwrap();
+    let b = File::open(tempfile.path()).await.unwrap();
+
+    let lock = a.try_lock_exclusive().unwrap();
+    let err = b.try_lock_shared().unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::WouldBlock);
+    drop(lock);
+
+    let _lock = b.try_lock_shared().unwrap();
+    let lock = a.try_lock_shared().unwrap();
+    let err = b.try_lock_exclusive().unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::WouldBlock);
+    lock.unlock().unwrap();
+    b.try_lock_exclusive().unwrap().unlock().unwrap();
+}
+
+// A `flock` command of util-linux in another process, `None` if there is no
+// such command.
+fn flock_command(args: &[&str]) -> Option<std::process::Child> {
+    match std::process::Command::new("flock").args(args).spawn() {
+        Ok(child) => Some(child),
+        Err(e) if e.kind() == ErrorKind::NotFound => None,
+        Err(e) => panic!("failed to run flock: {e}"),
+    }
+}
+
+#[monoio::test_all]
+async fn lock_other_process() {
+    let tempfile = tempfile::NamedTempFile::new().unwrap();
+    let path = tempfile.path().to_str().unwrap();
+    let file = File::open(path).await.unwrap();
+
+    let lock = file.try_lock_exclusive().unwrap();
+    let Some(mut child) = flock_command(&["-n", path, "true"]) else {
+        return;
+    };
+    assert!(!child.wait().unwrap().success());
+    drop(lock);
+    let mut child = flock_command(&["-n", path, "true"]).unwrap();
+    assert!(child.wait().unwrap().success());
+}
+
+#[cfg(feature = "sync")]
+#[test]
+fn lock_blocking() {
+    let tempfile = tempfile::NamedTempFile::new().unwrap();
+    let path = tempfile.path().to_str().unwrap().to_string();
+
+    let mut rt = monoio::RuntimeBuilder::<monoio::FusionDriver>::new()
+        .attach_thread_pool(Box::new(monoio::blocking::DefaultThreadPool::new(1)))
+        .enable_timer()
+        .build()
+        .unwrap();
+    rt.block_on(async move {
+        let file = File::open(&path).await.unwrap();
+        let Some(mut child) = flock_command(&[&path, "sleep", "0.3"]) else {
+            return;
+        };
+        // Wait for the child to take the lock.
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while let Ok(lock) = file.try_lock_shared() {
+            lock.unlock().unwrap();
+            assert!(Instant::now() < deadline, "flock did not take the lock");
+            monoio::time::sleep(Duration::from_millis(10)).await;
+        }
+
+        let lock = file.lock_exclusive().await.unwrap();
+        // The lock is released when the child exits.
+        assert!(child.wait().unwrap().success());
+        drop(lock);
+
+        let other = File::open(&path).await.unwrap();
+        let _lock = other.lock_shared().await.unwrap();
+        assert_eq!(
+            file.try_lock_exclusive().unwrap_err().kind(),
+            ErrorKind::WouldBlock
+        );
+    });
+}
@@ -0,0 +1,96 @@
+#![cfg(unix)]
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use monoio::net::IcmpSocket;
+
+fn checksum(data: &[u8]) -> u16 {
+    let mut sum = data
+        .chunks(2)
+        .map(|c| u32::from(u16::from_be_bytes([c[0], *c.get(1).unwrap_or(&0)])))
+        .sum::<u32>();
+    while sum > 0xffff {
+        sum = (sum & 0xffff) + (sum >> 16);
+    }
+    !(sum as u16)
+}
+
+fn echo_request(ty: u8, seq: u16) -> Vec<u8> {
+    let mut msg = vec![ty, 0, 0, 0, 0x12, 0x34];
+    msg.extend_from_slice(&seq.to_be_bytes());
+    msg.extend_from_slice(b"monoio");
+    let sum = checksum(&msg);
+    msg[2..4].copy_from_slice(&sum.to_be_bytes());
+    msg
+}
+
+#[monoio::test_all]
+async fn raw_echo_v4() {
+    let socket = match IcmpSocket::new_raw_v4() {
+        Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => return,
+        socket => socket.unwrap(),
+    };
+    socket.set_ttl(3).unwrap();
+    assert_eq!(socket.ttl().unwrap(), 3);
+
+    let localhost = IpAddr::from(Ipv4Addr::LOCALHOST);
+    let request = echo_request(8, 1);
+    let n = socket.send_to(request.clone(), localhost).await.0.unwrap();
+    assert_eq!(n, request.len());
+    // The request is received too, skip it.
+    loop {
+        let (res, buf) = socket.recv_from(vec![0; 128]).await;
+        let (n, from) = res.unwrap();
+        assert_eq!(from, localhost);
+        let header_len = usize::from(buf[0] & 0x0f) * 4;
+        let msg = &buf[header_len..n];
+        if msg[0] == 0 {
+            assert_eq!(&msg[4..], &request[4..]);
+            break;
+        }
+    }
+}
+
+#[monoio::test_all]
+async fn raw_echo_v6() {
+    let socket = match IcmpSocket::new_raw_v6() {
+        Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => return,
+        socket => socket.unwrap(),
+    };
+    socket.set_ttl(3).unwrap();
+    assert_eq!(socket.ttl().unwrap(), 3);
+
+    let localhost = IpAddr::from(Ipv6Addr::LOCALHOST);
+    socket.bind(localhost).unwrap();
+    assert_eq!(socket.local_addr().unwrap(), localhost);
+    // The kernel fills the checksum of ICMPv6.
+    let mut request = echo_request(128, 1);
+    request[2..4].copy_from_slice(&[0, 0]);
+    socket.send_to(request.clone(), localhost).await.0.unwrap();
+    loop {
+        let (res, buf) = socket.recv_from(vec![0; 128]).await;
+        let (n, from) = res.unwrap();
+        assert_eq!(from, localhost);
+        if buf[0] == 129 {
+            assert_eq!(&buf[4..n], &request[4..]);
+            break;
+        }
+    }
+}
+
+// It fails without the group of the process in `net.ipv4.ping_group_range`.
+#[monoio::test_all]
+async fn dgram_echo_v4() {
+    let socket = match IcmpSocket::new_v4() {
+        Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => return,
+        socket => socket.unwrap(),
+    };
+    let localhost = IpAddr::from(Ipv4Addr::LOCALHOST);
+    let request = echo_request(8, 2);
+    socket.send_to(request.clone(), localhost).await.0.unwrap();
+    let (res, buf) = socket.recv_from(vec![0; 128]).await;
+    let (n, from) = res.unwrap();
+    assert_eq!(from, localhost);
+    assert_eq!(buf[0], 0);
+    assert_eq!(&buf[6..n], &request[6..]);
+}
@@ -0,0 +1,80 @@
+#![cfg(all(target_os = "linux", feature = "iouring"))]
+
+use std::{io::ErrorKind, os::unix::fs::PermissionsExt};
+
+use monoio::{fs::OpenOptions, utils::register_personality, IoUringDriver, RuntimeBuilder};
+
+#[monoio::test(driver = "uring")]
+async fn unregistered_personality() {
+    let personality = register_personality().unwrap();
+    monoio::utils::unregister_personality(personality).unwrap();
+
+    let err = OpenOptions::new()
+        .read(true)
+        .personality(personality)
+        .open("/dev/null")
+        .await
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    let err = monoio::fs::metadata_as("/dev/null", personality)
+        .await
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    let err = monoio::utils::unregister_personality(personality).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+}
+
+#[cfg(feature = "legacy")]
+#[monoio::test(driver = "legacy")]
+async fn personality_on_legacy() {
+    let err = register_personality().unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Unsupported);
+}
+
+#[test]
+fn personality_credentials() {
+    if unsafe { libc::geteuid() } != 0 {
+        return;
+    }
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::set_permissions(dir.path(), std::fs::Permissions::from_mode(0o700)).unwrap();
+    let path = dir.path().join("key");
+    std::fs::write(&path, b"secret").unwrap();
+
+    // Changing the euid with the syscall only affects this thread.
+    std::thread::spawn(move || {
+        let mut rt = RuntimeBuilder::<IoUringDriver>::new().build().unwrap();
+        rt.block_on(async move {
+            let root = register_personality().unwrap();
+            assert_eq!(
+                unsafe { libc::syscall(libc::SYS_setresuid, -1, 65534, -1) },
+                0
+            );
+
+            let err = OpenOptions::new().read(true).open(&path).await.unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+            let file = OpenOptions::new()
+                .read(true)
+                .personality(root)
+                .open(&path)
+                .await
+                .unwrap();
+            let (res, buf) = file.read_at(vec![0; 6], 0).await;
+            res.unwrap();
+            assert_eq!(buf, b"secret");
+            file.close().await.unwrap();
+
+            let err = monoio::fs::metadata(&path).await.unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+            assert_eq!(monoio::fs::metadata_as(&path, root).await.unwrap().len(), 6);
+            let err = monoio::fs::remove_file(&path).await.unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+            monoio::fs::remove_file_as(&path, root).await.unwrap();
+
+            assert_eq!(unsafe { libc::syscall(libc::SYS_setresuid, -1, 0, -1) }, 0);
+            assert!(!path.exists());
+        });
+    })
+    .join()
+    .unwrap();
+}
@@ -0,0 +1,7 @@
+#[monoio::test_all]
+async fn ring_ping() {
+    for _ in 0..3 {
+        let rtt = monoio::utils::ring_ping().await.unwrap();
+        assert!(rtt < std::time::Duration::from_secs(1));
+    }
+}
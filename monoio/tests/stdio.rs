@@ -0,0 +1,113 @@
+#![cfg(target_os = "linux")]
+
+use std::{
+    io::Write,
+    process::{Command, Stdio},
+};
+
+use monoio::io::AsyncWriteRentExt;
+
+// Set for the test binary run by `stdio`, whose stdio is redirected.
+const CHILD: &str = "MONOIO_STDIO_CHILD";
+
+/// Copies stdin to stderr, and reports the size to stdout.
+async fn copy_stdin(legacy: bool) {
+    use std::os::fd::AsRawFd;
+
+    if std::env::var_os(CHILD).is_none() {
+        return;
+    }
+
+    let mut stdin = monoio::io::stdin().unwrap();
+    let nonblocking = |fd| unsafe { libc::fcntl(fd, libc::F_GETFL) } & libc::O_NONBLOCK != 0;
+    // Only the pipe of the handle waits for readiness, the shared one is not
+    // changed. Other fds are read on the blocking thread pool.
+    use std::os::unix::fs::FileTypeExt;
+    let is_pipe = std::fs::metadata("/proc/self/fd/0")
+        .unwrap()
+        .file_type()
+        .is_fifo();
+    assert_eq!(nonblocking(stdin.as_raw_fd()), legacy && is_pipe);
+    assert!(!nonblocking(libc::STDIN_FILENO));
+    let mut stderr = monoio::io::stderr().unwrap();
+    let n = monoio::io::copy(&mut stdin, &mut stderr).await.unwrap();
+
+    let mut stdout = monoio::io::stdout().unwrap();
+    #[cfg(feature = "sync")]
+    let mut stdout = stdout.lock().await;
+    let (res, _) = stdout
+        .write_all(format!("copied {n} bytes\n").into_bytes())
+        .await;
+    res.unwrap();
+}
+
+#[cfg(feature = "legacy")]
+#[monoio::test(driver = "legacy")]
+async fn copy_stdin_legacy() {
+    copy_stdin(true).await;
+}
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+#[monoio::test(driver = "uring")]
+async fn copy_stdin_uring() {
+    copy_stdin(false).await;
+}
+
+fn run_child(name: &str, stdin: Stdio, input: Option<&[u8]>) -> std::process::Output {
+    let mut child = Command::new(std::env::current_exe().unwrap())
+        .args([name, "--exact", "--test-threads=1"])
+        .env(CHILD, "1")
+        .stdin(stdin)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    // More than the buffer of a pipe, so the child waits for the pipe.
+    if let Some(input) = input {
+        let mut pipe = child.stdin.take().unwrap();
+        let input = input.to_vec();
+        std::thread::spawn(move || pipe.write_all(&input).unwrap());
+    }
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success(), "{output:?}");
+    output
+}
+
+#[test]
+fn stdio() {
+    let data: Vec<u8> = (0..256 * 1024).map(|i| (i % 251) as u8).collect();
+    let mut file = tempfile::tempfile().unwrap();
+    file.write_all(&data).unwrap();
+
+    let mut children = vec![];
+    #[cfg(feature = "legacy")]
+    children.push("copy_stdin_legacy");
+    #[cfg(all(target_os = "linux", feature = "iouring"))]
+    children.push("copy_stdin_uring");
+    for name in children {
+        let output = run_child(name, Stdio::piped(), Some(&data));
+        assert!(output.stderr.windows(data.len()).any(|w| w == data));
+        let report = format!("copied {} bytes\n", data.len());
+        assert!(String::from_utf8_lossy(&output.stdout).contains(&report));
+
+        // A regular file is read from its position.
+        use std::io::{Seek, SeekFrom};
+        file.seek(SeekFrom::Start(1024)).unwrap();
+        let output = run_child(name, file.try_clone().unwrap().into(), None);
+        assert!(output
+            .stderr
+            .windows(data.len() - 1024)
+            .any(|w| w == &data[1024..]));
+        let report = format!("copied {} bytes\n", data.len() - 1024);
+        assert!(String::from_utf8_lossy(&output.stdout).contains(&report));
+
+        // A socket can not be reopened, it is read on the blocking thread pool
+        // by legacy driver.
+        let (mut tx, rx) = std::os::unix::net::UnixStream::pair().unwrap();
+        let input = data.clone();
+        let writer = std::thread::spawn(move || tx.write_all(&input).unwrap());
+        let output = run_child(name, std::os::fd::OwnedFd::from(rx).into(), None);
+        writer.join().unwrap();
+        assert!(output.stderr.windows(data.len()).any(|w| w == data));
+    }
+}
@@ -0,0 +1,87 @@
+#![cfg(feature = "sync")]
+
+use std::{future::poll_fn, sync::Arc, task::Poll, thread};
+
+use monoio::{
+    sync::{Mutex, Semaphore},
+    LegacyDriver, RuntimeBuilder,
+};
+
+const ROUNDS: u64 = 1000;
+
+async fn yield_now() {
+    let mut yielded = false;
+    poll_fn(|cx| {
+        if yielded {
+            return Poll::Ready(());
+        }
+        yielded = true;
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    })
+    .await
+}
+
+async fn increase(lock: &Mutex<u64>) {
+    for _ in 0..ROUNDS {
+        let mut guard = lock.lock().await;
+        let v = *guard;
+        // Hold the lock across a yield point.
+        yield_now().await;
+        *guard = v + 1;
+    }
+}
+
+#[test]
+fn mutex_between_runtimes() {
+    let lock = Arc::new(Mutex::new(0));
+    let mut handles = Vec::new();
+    for _ in 0..2 {
+        let lock = lock.clone();
+        handles.push(thread::spawn(move || {
+            #[cfg(all(target_os = "linux", feature = "iouring"))]
+            let mut rt = RuntimeBuilder::<monoio::IoUringDriver>::new()
+                .build()
+                .unwrap();
+            #[cfg(not(all(target_os = "linux", feature = "iouring")))]
+            let mut rt = RuntimeBuilder::<LegacyDriver>::new().build().unwrap();
+            rt.block_on(increase(&lock));
+        }));
+    }
+    for _ in 0..2 {
+        let lock = lock.clone();
+        handles.push(thread::spawn(move || {
+            let mut rt = RuntimeBuilder::<LegacyDriver>::new().build().unwrap();
+            rt.block_on(increase(&lock));
+        }));
+    }
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    assert_eq!(Arc::try_unwrap(lock).unwrap().into_inner(), 4 * ROUNDS);
+}
+
+#[monoio::test_all]
+async fn semaphore_permits() {
+    let sem = Semaphore::new(2);
+    let a = sem.acquire().await;
+    let b = sem.try_acquire().unwrap();
+    assert!(sem.try_acquire().is_none());
+    drop(a);
+    assert_eq!(sem.available_permits(), 1);
+    b.forget();
+    let _c = sem.acquire().await;
+    assert_eq!(sem.available_permits(), 0);
+    sem.add_permits(1);
+    assert!(sem.try_acquire().is_some());
+}
+
+#[monoio::test_all]
+async fn mutex_try_lock() {
+    let lock = Mutex::new(1);
+    let guard = lock.lock().await;
+    assert!(lock.try_lock().is_none());
+    drop(guard);
+    *lock.try_lock().unwrap() += 1;
+    assert_eq!(*lock.lock().await, 2);
+}
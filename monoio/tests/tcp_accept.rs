@@ -29,3 +29,344 @@ test_accept! {
     (str_port_tuple, ("127.0.0.1", 0)),
     (ip_port_tuple, ("127.0.0.1".parse::<IpAddr>().unwrap(), 0)),
 }
+
+#[cfg(unix)]
+#[monoio::test_all]
+async fn accept_multi() {
+    use monoio::io::stream::Stream;
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let (tx, mut rx) = local_sync::mpsc::unbounded::channel();
+    monoio::spawn(async move {
+        let mut incoming = listener.accept_multi();
+        for _ in 0..3 {
+            let (socket, peer) = incoming.next().await.unwrap().unwrap();
+            assert_eq!(socket.peer_addr().unwrap(), peer);
+            tx.send(socket).unwrap();
+        }
+    });
+    for _ in 0..3 {
+        let cli = TcpStream::connect(&addr).await.unwrap();
+        let srv = rx.recv().await.unwrap();
+        assert_eq!(cli.local_addr().unwrap(), srv.peer_addr().unwrap());
+    }
+}
+
+#[cfg(unix)]
+#[monoio::test_all(timer_enabled = true)]
+async fn accept_multi_drop() {
+    use std::time::Duration;
+
+    use monoio::io::{stream::Stream, AsyncReadRent};
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let mut clients = vec![];
+    for _ in 0..3 {
+        clients.push(TcpStream::connect(&addr).await.unwrap());
+    }
+    let mut incoming = listener.accept_multi();
+    let _first = incoming.next().await.unwrap().unwrap();
+    // The rest are queued in the multishot op on uring driver.
+    monoio::time::sleep(Duration::from_millis(50)).await;
+    drop(incoming);
+    drop(listener);
+
+    // The queued connections are closed instead of leaked.
+    for mut cli in clients.into_iter().skip(1) {
+        let (res, _) = monoio::time::timeout(Duration::from_secs(5), cli.read(vec![0; 8]))
+            .await
+            .expect("the accepted connection is leaked");
+        assert!(matches!(res, Ok(0) | Err(_)));
+    }
+}
+
+#[cfg(unix)]
+#[monoio::test_all]
+async fn reuse_port() {
+    use monoio::net::ListenerOpts;
+
+    let opts = ListenerOpts::new().reuse_port(true);
+    let first = TcpListener::bind_with_config("127.0.0.1:0", &opts).unwrap();
+    let addr = first.local_addr().unwrap();
+    let second = TcpListener::bind_with_config(addr, &opts).unwrap();
+    let err = TcpListener::bind_with_config(addr, &opts.reuse_port(false)).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::AddrInUse);
+
+    // The kernel shards the connections across the listeners.
+    let (tx, mut rx) = local_sync::mpsc::unbounded::channel();
+    for (i, listener) in [first, second].into_iter().enumerate() {
+        let tx = tx.clone();
+        monoio::spawn(async move {
+            while listener.accept().await.is_ok() {
+                tx.send(i).unwrap();
+            }
+        });
+    }
+    let mut clients = Vec::new();
+    for _ in 0..64 {
+        clients.push(TcpStream::connect(addr).await.unwrap());
+    }
+    let mut accepted = [0; 2];
+    for _ in 0..64 {
+        let i = rx.recv().await.unwrap();
+        accepted[i] += 1;
+    }
+    assert!(accepted[0] > 0 && accepted[1] > 0, "{accepted:?}");
+}
+
+#[cfg(unix)]
+#[monoio::test_all]
+async fn std_conversions() {
+    use std::{
+        io::{Read, Write},
+        os::unix::io::OwnedFd,
+    };
+
+    use monoio::io::{AsyncReadRentExt, AsyncWriteRentExt};
+
+    // Blocking sockets, e.g. inherited with socket activation.
+    let std_listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = std_listener.local_addr().unwrap();
+    let listener = TcpListener::try_from(OwnedFd::from(std_listener)).unwrap();
+    let mut std_client = std::net::TcpStream::connect(addr).unwrap();
+    let (stream, _) = listener.accept().await.unwrap();
+
+    let mut stream = TcpStream::try_from(OwnedFd::from(stream.into_std().await.unwrap())).unwrap();
+    std_client.write_all(b"hello").unwrap();
+    let (res, buf) = stream.read_exact(vec![0; 5]).await;
+    res.unwrap();
+    assert_eq!(buf, b"hello");
+    stream.write_all("world").await.0.unwrap();
+    let mut buf = [0; 5];
+    std_client.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"world");
+
+    let std_listener = listener.into_std().unwrap();
+    assert_eq!(std_listener.local_addr().unwrap(), addr);
+    let _client = std::net::TcpStream::connect(addr).unwrap();
+    std_listener.set_nonblocking(false).unwrap();
+    std_listener.accept().unwrap();
+}
+
+// A dropped accept is in flight on uring driver until it is canceled.
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+#[monoio::test(driver = "uring", timer_enabled = true)]
+async fn into_std_with_op_in_flight() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    monoio::select! {
+        _ = listener.accept() => unreachable!(),
+        _ = monoio::time::sleep(std::time::Duration::from_millis(10)) => {}
+    }
+    assert!(listener.into_std().is_err());
+}
+
+// The io of legacy driver relies on readiness.
+#[cfg(all(unix, feature = "legacy"))]
+#[monoio::test(driver = "legacy")]
+async fn from_std_nonblocking() {
+    use std::os::unix::io::AsRawFd;
+
+    let is_nonblocking =
+        |fd| unsafe { libc::fcntl(fd, libc::F_GETFL) } & libc::O_NONBLOCK == libc::O_NONBLOCK;
+    let std_listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = std_listener.local_addr().unwrap();
+    assert!(!is_nonblocking(std_listener.as_raw_fd()));
+    let listener = TcpListener::from_std(std_listener).unwrap();
+    assert!(is_nonblocking(listener.as_raw_fd()));
+
+    let std_stream = std::net::TcpStream::connect(addr).unwrap();
+    let stream = TcpStream::from_std(std_stream).unwrap();
+    assert!(is_nonblocking(stream.as_raw_fd()));
+}
+
+#[monoio::test_all(timer_enabled = true)]
+async fn accept_after_cancel() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    // Drop a pending accept, it must not take the next connection.
+    monoio::select! {
+        _ = monoio::time::sleep(std::time::Duration::from_millis(10)) => {},
+        _ = listener.accept() => panic!("unexpected accept"),
+    }
+
+    let stream = TcpStream::connect(&addr).await.unwrap();
+    let (_, peer) = listener.accept().await.unwrap();
+    assert_eq!(peer, stream.local_addr().unwrap());
+}
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+#[test]
+fn accept_direct() {
+    use std::{io::Read, os::unix::io::AsRawFd};
+
+    use monoio::io::{AsyncReadRent, AsyncReadRentExt, AsyncWriteRent, AsyncWriteRentExt};
+
+    let mut rt = monoio::RuntimeBuilder::<monoio::IoUringDriver>::new()
+        .register_files(1)
+        .build()
+        .unwrap();
+    rt.block_on(async {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let mut cli = TcpStream::connect(&addr).await.unwrap();
+        let (mut srv, peer) = listener.accept_direct().await.unwrap();
+        assert_eq!(srv.as_raw_fd(), -1);
+        assert_eq!(cli.local_addr().unwrap(), peer);
+        assert_eq!(srv.peer_addr().unwrap(), peer);
+
+        cli.write_all("foo").await.0.unwrap();
+        let (res, buf) = srv.read_exact(vec![0; 3]).await;
+        res.unwrap();
+        assert_eq!(buf, b"foo");
+        srv.write_all("bar").await.0.unwrap();
+        let (res, buf) = cli.read_exact(vec![0; 3]).await;
+        res.unwrap();
+        assert_eq!(buf, b"bar");
+
+        // The table is full, the accepted stream has its fd.
+        let _cli2 = TcpStream::connect(&addr).await.unwrap();
+        let (fallback, _) = listener.accept_direct().await.unwrap();
+        assert!(fallback.as_raw_fd() >= 0);
+        drop(fallback);
+
+        // A fd is installed, and the slot is given back.
+        let mut std_srv = srv.into_std().await.unwrap();
+        cli.write_all("baz").await.0.unwrap();
+        let mut buf = [0; 3];
+        std_srv.read_exact(&mut buf).unwrap();
+        assert_eq!(&buf, b"baz");
+
+        let mut cli3 = TcpStream::connect(&addr).await.unwrap();
+        let (mut srv, _) = listener.accept_direct().await.unwrap();
+        assert_eq!(srv.as_raw_fd(), -1);
+        // The direct descriptor is shut down with the op.
+        srv.shutdown().await.unwrap();
+        let (res, _) = cli3.read(vec![0; 1]).await;
+        assert_eq!(res.unwrap(), 0);
+    });
+}
+
+#[monoio::test_all]
+async fn dual_stack() {
+    use monoio::net::ListenerOpts;
+
+    // Skip if IPv6 is disabled in the kernel.
+    let Ok(listener) = TcpListener::bind("[::]:0") else {
+        return;
+    };
+    let port = listener.local_addr().unwrap().port();
+    let (stream, accepted) =
+        monoio::join!(TcpStream::connect(("127.0.0.1", port)), listener.accept());
+    stream.unwrap();
+    let (_, peer) = accepted.unwrap();
+    assert_eq!(peer.ip().to_canonical(), IpAddr::from([127, 0, 0, 1]));
+
+    let opts = ListenerOpts::new().only_v6(true);
+    let listener = TcpListener::bind_with_config("[::]:0", &opts).unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let err = TcpStream::connect(("127.0.0.1", port)).await.unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::ConnectionRefused);
+    TcpStream::connect(("::1", port)).await.unwrap();
+}
+
+#[cfg(unix)]
+#[monoio::test_all]
+async fn socket_only_v6() {
+    use monoio::net::TcpSocket;
+
+    let Ok(socket) = TcpSocket::new_v6() else {
+        return;
+    };
+    socket.set_only_v6(true).unwrap();
+    assert!(socket.only_v6().unwrap());
+    socket.set_only_v6(false).unwrap();
+    assert!(!socket.only_v6().unwrap());
+    socket.bind("[::]:0".parse().unwrap()).unwrap();
+    let listener = socket.listen(16).unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let (stream, accepted) =
+        monoio::join!(TcpStream::connect(("127.0.0.1", port)), listener.accept());
+    stream.unwrap();
+    accepted.unwrap();
+}
+
+#[cfg(all(unix, feature = "futures-stream"))]
+#[monoio::test_all(timer_enabled = true)]
+async fn incoming() {
+    use std::time::Duration;
+
+    use futures::StreamExt;
+    use monoio::io::{AsyncReadRentExt, AsyncWriteRentExt};
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    // Dropping the incoming cancels the pending accept, which would take the
+    // next connection otherwise.
+    let mut incoming = listener.incoming();
+    monoio::select! {
+        _ = incoming.next() => panic!("accepted without a client"),
+        _ = monoio::time::sleep(Duration::from_millis(20)) => {}
+    }
+    drop(incoming);
+    let (client, accepted) = monoio::join!(TcpStream::connect(addr), listener.accept());
+    client.unwrap();
+    accepted.unwrap();
+
+    monoio::spawn(async move {
+        for _ in 0..4 {
+            let mut client = TcpStream::connect(addr).await.unwrap();
+            client.write_all("ping").await.0.unwrap();
+        }
+    });
+    listener
+        .incoming()
+        .take(4)
+        .for_each_concurrent(None, |stream| async move {
+            let (res, buf) = stream.unwrap().read_exact(vec![0; 4]).await;
+            res.unwrap();
+            assert_eq!(buf, b"ping");
+        })
+        .await;
+}
+
+#[cfg(target_os = "linux")]
+#[monoio::test_all(timer_enabled = true)]
+async fn defer_accept() {
+    use std::{os::unix::io::AsRawFd, time::Duration};
+
+    use monoio::{io::AsyncWriteRentExt, net::ListenerOpts};
+
+    let opts = ListenerOpts::new().defer_accept(Duration::from_millis(1500));
+    let listener = TcpListener::bind_with_config("127.0.0.1:0", &opts).unwrap();
+    let mut secs: libc::c_int = 0;
+    let mut len = std::mem::size_of::<libc::c_int>() as libc::socklen_t;
+    let res = unsafe {
+        libc::getsockopt(
+            listener.as_raw_fd(),
+            libc::IPPROTO_TCP,
+            libc::TCP_DEFER_ACCEPT,
+            &mut secs as *mut libc::c_int as *mut libc::c_void,
+            &mut len,
+        )
+    };
+    assert_eq!(res, 0);
+    // Rounded up to seconds, the kernel may report more since it counts
+    // retransmissions of the SYN-ACK.
+    assert!(secs >= 2, "{secs}");
+
+    // The handshake is done but accept waits for the data.
+    let mut client = TcpStream::connect(listener.local_addr().unwrap())
+        .await
+        .unwrap();
+    monoio::select! {
+        _ = listener.accept() => panic!("accepted before the data"),
+        _ = monoio::time::sleep(Duration::from_millis(100)) => {}
+    }
+    client.write_all("hello").await.0.unwrap();
+    listener.accept().await.unwrap();
+}
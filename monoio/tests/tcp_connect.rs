@@ -1,4 +1,7 @@
-use std::net::{IpAddr, SocketAddr};
+use std::{
+    net::{IpAddr, SocketAddr},
+    time::Duration,
+};
 
 use monoio::net::{TcpListener, TcpStream};
 
@@ -113,6 +116,31 @@ async fn connect_timeout_dst() {
     drop_flag.assert_dropped();
 }
 
+#[cfg(target_os = "linux")]
+#[monoio::test_all(timer_enabled = true)]
+async fn connect_timeout() {
+    // SYNs are dropped once the accept queue of the listener is full.
+    let socket = monoio::net::TcpSocket::new_v4().unwrap();
+    socket.bind("127.0.0.1:0".parse().unwrap()).unwrap();
+    let listener = socket.listen(0).unwrap();
+    let addr = listener.local_addr().unwrap();
+    let _queued = TcpStream::connect_timeout(addr, Duration::from_secs(5))
+        .await
+        .unwrap();
+
+    let open_fds = || std::fs::read_dir("/proc/self/fd").unwrap().count();
+    let before = open_fds();
+    for _ in 0..8 {
+        let err = TcpStream::connect_timeout(addr, Duration::from_millis(20))
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::TimedOut);
+    }
+    // The sockets are closed by ops on uring driver.
+    monoio::time::sleep(Duration::from_millis(10)).await;
+    assert_eq!(open_fds(), before);
+}
+
 #[monoio::test_all]
 async fn connect_invalid_dst() {
     assert!(TcpStream::connect("127.0.0.1:1").await.is_err());
@@ -176,3 +204,253 @@ impl DropFlag {
         assert!(*self.0.borrow());
     }
 }
+
+#[cfg(unix)]
+#[monoio::test_all]
+async fn connect_with_socket() {
+    use monoio::net::TcpSocket;
+
+    let socket = TcpSocket::new_v4().unwrap();
+    socket.set_reuseaddr(true).unwrap();
+    socket.set_reuseport(true).unwrap();
+    socket.set_recv_buffer_size(64 * 1024).unwrap();
+    socket.bind("127.0.0.1:0".parse().unwrap()).unwrap();
+    assert!(socket.reuseaddr().unwrap());
+    assert!(socket.reuseport().unwrap());
+    assert!(socket.recv_buffer_size().unwrap() >= 64 * 1024);
+    let listener = socket.listen(128).unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let socket = TcpSocket::new_v4().unwrap();
+    socket.set_nodelay(true).unwrap();
+    socket.set_keepalive(Some(KEEPALIVE)).unwrap();
+    socket.bind("127.0.0.1:0".parse().unwrap()).unwrap();
+    let local = socket.local_addr().unwrap();
+    let (mine, theirs) = monoio::join!(socket.connect(addr), listener.accept());
+    let mine = mine.unwrap();
+    let (theirs, peer) = theirs.unwrap();
+
+    assert_eq!(mine.local_addr().unwrap(), local);
+    assert_eq!(peer, local);
+    assert_eq!(theirs.peer_addr().unwrap(), local);
+    assert!(mine.nodelay().unwrap());
+    assert_eq!(mine.keepalive().unwrap(), Some(KEEPALIVE));
+}
+
+#[cfg(target_os = "linux")]
+#[monoio::test_all]
+async fn connect_with_bind_device() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let socket = monoio::net::TcpSocket::new_v4().unwrap();
+    assert_eq!(socket.bind_device().unwrap(), None);
+    let err = socket.set_bind_device(Some("nonexistent0")).unwrap_err();
+    assert_eq!(err.raw_os_error(), Some(libc::ENODEV));
+    socket.set_bind_device(Some("lo")).unwrap();
+    assert_eq!(socket.bind_device().unwrap().as_deref(), Some("lo"));
+    let (stream, accepted) = monoio::join!(socket.connect(addr), listener.accept());
+    let stream = stream.unwrap();
+    accepted.unwrap();
+    assert_eq!(stream.bind_device().unwrap().as_deref(), Some("lo"));
+
+    stream.set_bind_device(None).unwrap();
+    assert_eq!(stream.bind_device().unwrap(), None);
+    let index = unsafe { libc::if_nametoindex(c"lo".as_ptr()) };
+    stream.bind_device_by_index(index).unwrap();
+    assert_eq!(stream.bind_device().unwrap().as_deref(), Some("lo"));
+}
+
+#[cfg(unix)]
+const KEEPALIVE: monoio::net::KeepAlive = monoio::net::KeepAlive {
+    time: Duration::from_secs(30),
+    interval: Duration::from_secs(5),
+    retries: 4,
+};
+
+// Read the options with socket2 instead of the getters.
+#[cfg(unix)]
+fn assert_keepalive(fd: std::os::unix::io::RawFd, expected: Option<monoio::net::KeepAlive>) {
+    use std::os::unix::io::FromRawFd;
+    let socket = std::mem::ManuallyDrop::new(unsafe { socket2::Socket::from_raw_fd(fd) });
+    let Some(expected) = expected else {
+        assert!(!socket.keepalive().unwrap());
+        return;
+    };
+    assert!(socket.keepalive().unwrap());
+    assert_eq!(socket.keepalive_time().unwrap(), expected.time);
+    assert_eq!(socket.keepalive_interval().unwrap(), expected.interval);
+    assert_eq!(socket.keepalive_retries().unwrap(), expected.retries);
+}
+
+#[cfg(unix)]
+#[monoio::test_all]
+async fn keepalive() {
+    use std::os::unix::io::AsRawFd;
+
+    // Accepted streams inherit the options of the listener.
+    let socket = monoio::net::TcpSocket::new_v4().unwrap();
+    socket.set_keepalive(Some(KEEPALIVE)).unwrap();
+    socket.bind("127.0.0.1:0".parse().unwrap()).unwrap();
+    assert_keepalive(socket.as_raw_fd(), Some(KEEPALIVE));
+    let listener = socket.listen(128).unwrap();
+    let addr = listener.local_addr().unwrap();
+    let (mine, theirs) = monoio::join!(TcpStream::connect(addr), listener.accept());
+    let mine = mine.unwrap();
+    let (theirs, _) = theirs.unwrap();
+    assert_keepalive(theirs.as_raw_fd(), Some(KEEPALIVE));
+    assert_eq!(theirs.keepalive().unwrap(), Some(KEEPALIVE));
+
+    assert_eq!(mine.keepalive().unwrap(), None);
+    let other = monoio::net::KeepAlive {
+        time: Duration::from_secs(60),
+        interval: Duration::from_secs(10),
+        retries: 2,
+    };
+    mine.set_keepalive(Some(other)).unwrap();
+    assert_keepalive(mine.as_raw_fd(), Some(other));
+    assert_eq!(mine.keepalive().unwrap(), Some(other));
+    theirs.set_keepalive(None).unwrap();
+    assert_keepalive(theirs.as_raw_fd(), None);
+    assert_eq!(theirs.keepalive().unwrap(), None);
+}
+
+#[cfg(unix)]
+#[monoio::test_all]
+async fn connect_with_socket_refused() {
+    // Closed at once, unlike the listener of uring driver closed by an op.
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    drop(listener);
+
+    let socket = monoio::net::TcpSocket::new_v4().unwrap();
+    let err = socket.connect(addr).await.unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::ConnectionRefused);
+}
+
+#[monoio::test_all]
+async fn socket_options() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    listener.set_ttl(42).unwrap();
+    assert_eq!(listener.ttl().unwrap(), 42);
+    assert!(listener.take_error().unwrap().is_none());
+
+    let addr = listener.local_addr().unwrap();
+    let (stream, accepted) = monoio::join!(TcpStream::connect(addr), listener.accept());
+    let stream = stream.unwrap();
+    let (accepted, _) = accepted.unwrap();
+    assert_eq!(accepted.ttl().unwrap(), 42);
+
+    stream.set_ttl(7).unwrap();
+    assert_eq!(stream.ttl().unwrap(), 7);
+    assert_eq!(stream.linger().unwrap(), None);
+    stream.set_linger(Some(Duration::from_secs(3))).unwrap();
+    assert_eq!(stream.linger().unwrap(), Some(Duration::from_secs(3)));
+    stream.set_linger(None).unwrap();
+    assert_eq!(stream.linger().unwrap(), None);
+    // The kernel doubles the sizes for its bookkeeping.
+    stream.set_send_buffer_size(64 * 1024).unwrap();
+    assert!(stream.send_buffer_size().unwrap() >= 64 * 1024);
+    stream.set_recv_buffer_size(64 * 1024).unwrap();
+    assert!(stream.recv_buffer_size().unwrap() >= 64 * 1024);
+    assert!(stream.take_error().unwrap().is_none());
+}
+
+#[cfg(target_os = "linux")]
+#[monoio::test_all]
+async fn tcp_info() {
+    use monoio::io::{AsyncReadRentExt, AsyncWriteRentExt};
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let (stream, accepted) = monoio::join!(TcpStream::connect(addr), listener.accept());
+    let mut stream = stream.unwrap();
+    let (mut accepted, _) = accepted.unwrap();
+
+    stream.write_all(vec![1; 1000]).await.0.unwrap();
+    let (res, buf) = accepted.read_exact(vec![0; 1000]).await;
+    res.unwrap();
+    accepted.write_all(buf).await.0.unwrap();
+    stream.read_exact(vec![0; 1000]).await.0.unwrap();
+
+    // The reply acknowledges the request, so the counters are exact.
+    let info = stream.tcp_info().unwrap();
+    assert!(info.rtt > Duration::ZERO, "{info:?}");
+    assert!(info.rto >= info.rtt, "{info:?}");
+    assert!(info.min_rtt.unwrap() > Duration::ZERO, "{info:?}");
+    assert!(info.snd_cwnd >= 1, "{info:?}");
+    assert!(info.snd_mss > 0, "{info:?}");
+    assert_eq!(info.retrans, 0);
+    assert_eq!(info.bytes_acked, Some(1001), "the SYN is counted");
+    assert_eq!(info.bytes_received, Some(1000));
+    assert!(info.delivery_rate.unwrap() > 0, "{info:?}");
+}
+
+#[cfg(target_os = "linux")]
+#[monoio::test_all]
+async fn quickack() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let (stream, accepted) = monoio::join!(TcpStream::connect(addr), listener.accept());
+    let stream = stream.unwrap();
+    accepted.unwrap();
+
+    stream.set_quickack(false).unwrap();
+    assert!(!stream.quickack().unwrap());
+    stream.set_quickack(true).unwrap();
+    assert!(stream.quickack().unwrap());
+}
+
+#[cfg(unix)]
+#[monoio::test_all]
+async fn tos() {
+    for addr in ["127.0.0.1:0", "[::1]:0"] {
+        let listener = TcpListener::bind(addr).unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (stream, accepted) = monoio::join!(TcpStream::connect(addr), listener.accept());
+        let stream = stream.unwrap();
+        accepted.unwrap();
+
+        stream.set_tos(0xb8).unwrap();
+        assert_eq!(stream.tos().unwrap(), 0xb8);
+        stream.set_tos(0).unwrap();
+        assert_eq!(stream.tos().unwrap(), 0);
+    }
+}
+
+#[cfg(unix)]
+#[monoio::test_all]
+async fn try_clone() {
+    use std::os::unix::io::{AsRawFd, FromRawFd, IntoRawFd};
+
+    use monoio::io::{AsyncReadRent, AsyncWriteRentExt};
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let (stream, accepted) = monoio::join!(TcpStream::connect(addr), listener.accept());
+    let stream = stream.unwrap();
+    let (mut accepted, _) = accepted.unwrap();
+
+    let mut reader = stream.try_clone().unwrap();
+    assert_ne!(reader.as_raw_fd(), stream.as_raw_fd());
+    assert_eq!(reader.local_addr().unwrap(), stream.local_addr().unwrap());
+
+    // One handle reads while the other writes.
+    let mut writer = stream;
+    let (read, written) = monoio::join!(reader.read(vec![0; 16]), async {
+        let (res, _) = accepted.write_all(b"ping").await;
+        res.unwrap();
+        writer.write_all(b"pong").await
+    });
+    let (res, buf) = read;
+    assert_eq!(&buf[..res.unwrap()], b"ping");
+    written.0.unwrap();
+
+    // Taking the fd of one leaves the other open.
+    let fd = writer.into_raw_fd();
+    drop(unsafe { std::net::TcpStream::from_raw_fd(fd) });
+    let (res, _) = reader.write_all(b"still open").await;
+    res.unwrap();
+    let (res, buf) = accepted.read(vec![0; 32]).await;
+    assert_eq!(&buf[..res.unwrap()], b"pongstill open");
+}
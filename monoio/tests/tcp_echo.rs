@@ -1,5 +1,5 @@
 use monoio::{
-    io::{self, AsyncReadRentExt, AsyncWriteRentExt, Splitable},
+    io::{self, AsyncReadRent, AsyncReadRentExt, AsyncWriteRentExt, Splitable},
     net::{TcpListener, TcpStream},
 };
 
@@ -118,3 +118,434 @@ async fn echo_tfo() {
     let active_addr = rx.await.unwrap();
     assert_eq!(active.local_addr().unwrap(), active_addr);
 }
+
+#[cfg(unix)]
+#[monoio::test_all(timer_enabled = true)]
+async fn try_read_write() {
+    use std::{io::ErrorKind, time::Duration};
+
+    let srv = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = srv.local_addr().unwrap();
+    let client = TcpStream::connect(addr).await.unwrap();
+    let (mut server, _) = srv.accept().await.unwrap();
+
+    let mut buf = [0; 16];
+    let err = client.try_read(&mut buf).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::WouldBlock);
+    monoio::select! {
+        _ = client.readable(true) => panic!("readable without data"),
+        _ = monoio::time::sleep(Duration::from_millis(20)) => {}
+    }
+    server.write_all("hello").await.0.unwrap();
+    client.readable(true).await.unwrap();
+    assert_eq!(client.try_read(&mut buf).unwrap(), 5);
+    assert_eq!(&buf[..5], b"hello");
+
+    // Fill the send buffer since the server does not read.
+    let chunk = vec![1; 64 * 1024];
+    let mut written = 0;
+    let err = loop {
+        match client.try_write(&chunk) {
+            Ok(n) => written += n,
+            Err(e) => break e,
+        }
+    };
+    assert_eq!(err.kind(), ErrorKind::WouldBlock);
+    monoio::select! {
+        _ = client.writable(true) => panic!("writable with full send buffer"),
+        _ = monoio::time::sleep(Duration::from_millis(20)) => {}
+    }
+    let (res, _) = server.read_exact(vec![0; written]).await;
+    res.unwrap();
+    client.writable(true).await.unwrap();
+    assert_eq!(client.try_write(b"world").unwrap(), 5);
+    let (res, _) = server.read_exact(vec![0; 5]).await;
+    res.unwrap();
+
+    drop(server);
+    client.readable(true).await.unwrap();
+    assert_eq!(client.try_read(&mut buf).unwrap(), 0);
+}
+
+#[monoio::test_all]
+async fn connect_with_data() {
+    let opts = monoio::net::ListenerOpts::new().fastopen(16);
+    let listener = TcpListener::bind_with_config("127.0.0.1:0", &opts).unwrap();
+    let addr = listener.local_addr().unwrap();
+    let server = monoio::spawn(async move {
+        let mut received = Vec::new();
+        // The first one gets the cookie, the second one may carry the data in
+        // the SYN.
+        for _ in 0..2 {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let (res, buf) = stream.read_exact(vec![0; 5]).await;
+            res.unwrap();
+            stream.write_all(buf.clone()).await.0.unwrap();
+            received.push(buf);
+        }
+        received
+    });
+
+    for _ in 0..2 {
+        let (res, buf) = TcpStream::connect_with_data(addr, "hello").await;
+        let (mut stream, n) = res.unwrap();
+        assert_eq!(n, 5);
+        assert_eq!(buf, "hello");
+        let (res, buf) = stream.read_exact(vec![0; 5]).await;
+        res.unwrap();
+        assert_eq!(buf, b"hello");
+    }
+    assert_eq!(server.await, [b"hello"; 2]);
+
+    // Nothing listens on the port after the listener is dropped. The data is
+    // accepted before the handshake with a cookie, and the refusal is
+    // reported by the next read.
+    let (res, buf) = TcpStream::connect_with_data(addr, "hello").await;
+    assert_eq!(buf, "hello");
+    if let Ok((mut stream, _)) = res {
+        assert!(stream.read(vec![0; 5]).await.0.is_err());
+    }
+}
+
+#[cfg(feature = "bytes")]
+#[monoio::test_all]
+async fn recv_multi() {
+    use monoio::io::stream::Stream;
+
+    const ITER: usize = 256;
+    let msg = "foo bar baz";
+
+    let srv = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = srv.local_addr().unwrap();
+    monoio::spawn(async move {
+        let mut stream = TcpStream::connect(&addr).await.unwrap();
+        for _ in 0..ITER {
+            assert!(stream.write_all(msg).await.0.is_ok());
+        }
+    });
+
+    let (mut stream, _) = srv.accept().await.unwrap();
+    let mut incoming = stream.recv_multi();
+    let mut received = Vec::new();
+    loop {
+        let data = incoming.next().await.unwrap().unwrap();
+        if data.is_empty() {
+            break;
+        }
+        received.extend_from_slice(&data);
+    }
+    assert!(incoming.next().await.is_none());
+    assert_eq!(received, msg.repeat(ITER).as_bytes());
+}
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+#[monoio::test(driver = "uring")]
+async fn recv_provided() {
+    use monoio::buf::BufRing;
+
+    const ITER: usize = 64;
+    let msg = "foo bar baz";
+
+    let srv = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = srv.local_addr().unwrap();
+    monoio::spawn(async move {
+        let mut stream = TcpStream::connect(&addr).await.unwrap();
+        for _ in 0..ITER {
+            assert!(stream.write_all(msg).await.0.is_ok());
+        }
+    });
+
+    let ring = BufRing::new(4, 16).unwrap();
+    let (mut stream, _) = srv.accept().await.unwrap();
+    let mut received = Vec::new();
+    loop {
+        let buf = stream.recv_provided(&ring).await.unwrap();
+        if buf.is_empty() {
+            break;
+        }
+        assert!(buf.len() <= ring.buf_len());
+        received.extend_from_slice(&buf);
+    }
+    assert_eq!(received, msg.repeat(ITER).as_bytes());
+
+    // Hold all buffers of the ring, then the next recv finds it exhausted.
+    let mut client = TcpStream::connect(&addr).await.unwrap();
+    let (mut stream, _) = srv.accept().await.unwrap();
+    let mut held = Vec::new();
+    for _ in 0..4 {
+        assert!(client.write_all(msg).await.0.is_ok());
+        held.push(stream.recv_provided(&ring).await.unwrap());
+    }
+    assert!(client.write_all(msg).await.0.is_ok());
+    let err = stream.recv_provided(&ring).await.unwrap_err();
+    assert_eq!(err.raw_os_error(), Some(libc::ENOBUFS));
+    drop(held);
+    let buf = stream.recv_provided(&ring).await.unwrap();
+    assert_eq!(&buf[..], msg.as_bytes());
+}
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+#[monoio::test(driver = "uring", timer_enabled = true)]
+async fn recv_provided_dropped() {
+    use std::time::Duration;
+
+    use monoio::buf::BufRing;
+
+    let msg = "foo bar baz";
+    let srv = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = srv.local_addr().unwrap();
+    let mut client = TcpStream::connect(&addr).await.unwrap();
+    let (mut stream, _) = srv.accept().await.unwrap();
+
+    // The recvs pick buffers of the ring, but they are dropped either before
+    // or after the completion is reaped. The buffers are given back anyway,
+    // or the ring is exhausted after 4 of them.
+    let ring = BufRing::new(4, 16).unwrap();
+    for i in 0..16 {
+        let mut recv = std::pin::pin!(stream.recv_provided(&ring));
+        let _ = futures::poll!(recv.as_mut());
+        assert!(client.write_all(msg).await.0.is_ok());
+        if i % 2 == 0 {
+            monoio::time::sleep(Duration::from_millis(10)).await;
+        }
+    }
+    monoio::time::sleep(Duration::from_millis(10)).await;
+
+    // The recvs canceled in time may leave data in the old connection.
+    let mut client = TcpStream::connect(&addr).await.unwrap();
+    let (mut stream, _) = srv.accept().await.unwrap();
+    assert!(client.write_all(msg).await.0.is_ok());
+    let buf = stream.recv_provided(&ring).await.unwrap();
+    assert_eq!(&buf[..], msg.as_bytes());
+}
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+#[monoio::test(driver = "uring", timer_enabled = true)]
+async fn write_fixed() {
+    use std::time::Duration;
+
+    use monoio::buf::FixedBufPool;
+
+    let msg = "foo bar baz";
+    let srv = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = srv.local_addr().unwrap();
+    let (tx, rx) = local_sync::oneshot::channel();
+    monoio::spawn(async move {
+        let (mut stream, _) = srv.accept().await.unwrap();
+        let (res, buf) = stream.read_exact(vec![0; msg.len() * 2]).await;
+        res.unwrap();
+        assert!(tx.send(buf).is_ok());
+    });
+
+    let pool = FixedBufPool::new(1, 64).unwrap();
+    let mut stream = TcpStream::connect(&addr).await.unwrap();
+    let mut buf = pool.get().await;
+    buf.put_slice(msg.as_bytes());
+    let (res, buf) = stream.write_fixed(buf).await;
+    assert_eq!(res.unwrap(), msg.len());
+
+    // The pool is exhausted, get waits until the buffer is given back.
+    assert!(pool.try_get().is_none());
+    let pool_ = pool.clone();
+    let waiter = monoio::spawn(async move { pool_.get().await.capacity() });
+    monoio::time::sleep(Duration::from_millis(10)).await;
+    drop(buf);
+    assert_eq!(waiter.await, 64);
+
+    let mut buf = pool.get().await;
+    buf.put_slice(msg.as_bytes());
+    let (res, _) = stream.write_fixed(buf).await;
+    assert_eq!(res.unwrap(), msg.len());
+    assert_eq!(rx.await.unwrap(), msg.repeat(2).as_bytes());
+}
+
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+#[test]
+fn register_files() {
+    let mut rt = monoio::RuntimeBuilder::<monoio::IoUringDriver>::new()
+        .register_files(1)
+        .build()
+        .unwrap();
+    rt.block_on(async {
+        let msg = "foo bar baz";
+        let srv = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = srv.local_addr().unwrap();
+        monoio::spawn(async move {
+            loop {
+                let (stream, _) = srv.accept().await.unwrap();
+                monoio::spawn(async move {
+                    let (mut rd, mut wr) = stream.into_split();
+                    let _ = io::copy(&mut rd, &mut wr).await;
+                });
+            }
+        });
+
+        async fn echo(stream: &mut TcpStream, msg: &'static str) {
+            assert!(stream.write_all(msg).await.0.is_ok());
+            let (res, buf) = stream.read_exact(vec![0; msg.len()]).await;
+            res.unwrap();
+            assert_eq!(buf, msg.as_bytes());
+        }
+
+        let mut registered = TcpStream::connect(&addr).await.unwrap();
+        assert!(registered.register());
+        echo(&mut registered, msg).await;
+
+        // The table is full, the stream keeps using its fd.
+        let mut fallback = TcpStream::connect(&addr).await.unwrap();
+        assert!(!fallback.register());
+        echo(&mut fallback, msg).await;
+
+        // The slot is recycled after close.
+        drop(registered);
+        let mut stream = TcpStream::connect(&addr).await.unwrap();
+        assert!(stream.register());
+        echo(&mut stream, msg).await;
+    });
+}
+
+#[monoio::test_all]
+async fn close() {
+    let srv = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = srv.local_addr().unwrap();
+    let (tx, rx) = local_sync::oneshot::channel();
+    monoio::spawn(async move {
+        let (mut stream, _) = srv.accept().await.unwrap();
+        let (res, _) = stream.read(vec![0; 8]).await;
+        tx.send(res.unwrap()).unwrap();
+    });
+
+    let stream = TcpStream::connect(&addr).await.unwrap();
+    stream.close().await.unwrap();
+    // The peer reads EOF once the stream is closed.
+    assert_eq!(rx.await.unwrap(), 0);
+}
+
+#[monoio::test_all]
+async fn half_close() {
+    use std::net::Shutdown;
+
+    let msg = "foo bar baz";
+    let srv = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = srv.local_addr().unwrap();
+    monoio::spawn(async move {
+        let (mut stream, _) = srv.accept().await.unwrap();
+        let mut request = Vec::new();
+        loop {
+            let (res, buf) = stream.read(Vec::with_capacity(64)).await;
+            if res.unwrap() == 0 {
+                break;
+            }
+            request.extend_from_slice(&buf);
+        }
+        stream.write_all(request).await.0.unwrap();
+    });
+
+    // The peer sees EOF after the write half is shut down, and the response
+    // can still be read.
+    let mut stream = TcpStream::connect(&addr).await.unwrap();
+    stream.write_all(msg).await.0.unwrap();
+    stream.shutdown_with(Shutdown::Write).await.unwrap();
+    let (res, buf) = stream.read_exact(vec![0; msg.len()]).await;
+    res.unwrap();
+    assert_eq!(buf, msg.as_bytes());
+    let (res, _) = stream.read(vec![0; 1]).await;
+    assert_eq!(res.unwrap(), 0);
+
+    // The peer has closed, shutting down again is not an error.
+    stream.shutdown_with(Shutdown::Both).await.unwrap();
+    stream.shutdown_with(Shutdown::Both).await.unwrap();
+}
+
+#[cfg(target_os = "linux")]
+#[monoio::test_all]
+async fn write_more() {
+    let srv = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = srv.local_addr().unwrap();
+    let server = monoio::spawn(async move {
+        let (mut stream, _) = srv.accept().await.unwrap();
+        let (res, buf) = stream.read_exact(vec![0; 11]).await;
+        res.unwrap();
+        buf
+    });
+
+    // The header is held by the kernel and flushed with the body.
+    let mut stream = TcpStream::connect(&addr).await.unwrap();
+    let (res, _) = stream.write_more("header\n").await;
+    assert_eq!(res.unwrap(), 7);
+    stream.write_all("body").await.0.unwrap();
+    assert_eq!(server.await, b"header\nbody");
+}
+
+#[monoio::test_all]
+async fn peek() {
+    let srv = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = srv.local_addr().unwrap();
+    monoio::spawn(async move {
+        let (mut stream, _) = srv.accept().await.unwrap();
+        stream.write_all("hello").await.0.unwrap();
+    });
+
+    let mut stream = TcpStream::connect(&addr).await.unwrap();
+    let (res, buf) = stream.peek(vec![0; 5]).await;
+    assert_eq!(res.unwrap(), 5);
+    assert_eq!(buf, b"hello");
+    let (res, buf) = stream.peek(vec![0; 5]).await;
+    assert_eq!(res.unwrap(), 5);
+    assert_eq!(buf, b"hello");
+    let (res, buf) = stream.read_exact(vec![0; 5]).await;
+    res.unwrap();
+    assert_eq!(buf, b"hello");
+
+    // The server has dropped the stream.
+    let (res, _) = stream.peek(vec![0; 5]).await;
+    assert_eq!(res.unwrap(), 0);
+}
+
+#[monoio::test_all(timer_enabled = true)]
+async fn read_write_timeout() {
+    use std::time::Duration;
+
+    let srv = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = srv.local_addr().unwrap();
+    let mut client = TcpStream::connect(&addr).await.unwrap();
+    let (mut server, _) = srv.accept().await.unwrap();
+
+    let (res, buf) = client
+        .read_timeout(vec![0; 16], Duration::from_millis(20))
+        .await;
+    assert_eq!(res.unwrap_err().kind(), std::io::ErrorKind::TimedOut);
+    assert_eq!(buf.capacity(), 16);
+
+    // The timed out read is cancelled and does not take the data.
+    server.write_all("hello").await.0.unwrap();
+    let (res, buf) = client.read_timeout(buf, Duration::from_secs(5)).await;
+    assert_eq!(res.unwrap(), 5);
+    assert_eq!(buf, b"hello");
+
+    // Fill the send buffer since the server never reads.
+    let mut chunk = vec![0; 64 * 1024];
+    loop {
+        let (res, buf) = client.write_timeout(chunk, Duration::from_millis(20)).await;
+        chunk = buf;
+        if let Err(e) = res {
+            assert_eq!(e.kind(), std::io::ErrorKind::TimedOut);
+            break;
+        }
+    }
+    chunk.truncate(1);
+    let (res, _) = client.write_timeout(chunk, Duration::from_millis(20)).await;
+    assert_eq!(res.unwrap_err().kind(), std::io::ErrorKind::TimedOut);
+}
+
+// The timeout is a linked op on uring driver, the timer is not needed.
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+#[monoio::test(driver = "uring", timer_enabled = false)]
+async fn read_timeout_without_timer() {
+    let srv = TcpListener::bind("127.0.0.1:0").unwrap();
+    let mut client = TcpStream::connect(srv.local_addr().unwrap()).await.unwrap();
+    let (res, _) = client
+        .read_timeout(vec![0; 16], std::time::Duration::from_millis(10))
+        .await;
+    assert_eq!(res.unwrap_err().kind(), std::io::ErrorKind::TimedOut);
+}
@@ -0,0 +1,65 @@
+#![cfg(all(target_os = "linux", feature = "iouring"))]
+
+use std::time::{Duration, Instant};
+
+use monoio::{time, IoUringDriver, RuntimeBuilder};
+
+fn precise_rt<F: std::future::Future>(f: F) -> F::Output {
+    RuntimeBuilder::<IoUringDriver>::new()
+        .enable_timer()
+        .precise_timers(true)
+        .build()
+        .unwrap()
+        .block_on(f)
+}
+
+#[monoio::test(driver = "uring", timer_enabled = true)]
+async fn sub_tick_sleep() {
+    // The wheel would round each sleep up to at least a millisecond.
+    let begin = Instant::now();
+    for _ in 0..10 {
+        time::sleep(Duration::from_micros(100)).await;
+    }
+    let elapsed = begin.elapsed();
+    assert!(elapsed >= Duration::from_micros(1000));
+    assert!(elapsed < Duration::from_millis(10), "{elapsed:?}");
+
+    let begin = Instant::now();
+    let mut interval = time::interval(Duration::from_micros(200));
+    for _ in 0..5 {
+        interval.tick().await;
+    }
+    assert!(begin.elapsed() < Duration::from_millis(5));
+}
+
+#[test]
+fn precise_timers() {
+    precise_rt(async {
+        let begin = Instant::now();
+        time::sleep(Duration::from_millis(2)).await;
+        assert!(begin.elapsed() >= Duration::from_millis(2));
+
+        // Dropping the sleep removes the timeout from the kernel.
+        let res = time::timeout(
+            Duration::from_millis(1),
+            time::sleep(Duration::from_secs(60)),
+        )
+        .await;
+        assert!(res.is_err());
+        let res = time::timeout(
+            Duration::from_secs(60),
+            time::sleep(Duration::from_micros(100)),
+        )
+        .await;
+        assert!(res.is_ok());
+
+        let sleep = time::sleep(Duration::from_secs(60));
+        let mut sleep = std::pin::pin!(sleep);
+        assert!(!sleep.is_elapsed());
+        sleep
+            .as_mut()
+            .reset(time::Instant::now() + Duration::from_micros(100));
+        sleep.as_mut().await;
+        assert!(sleep.is_elapsed());
+    });
+}
@@ -57,6 +57,111 @@ async fn send_to() {
     must_success!(passive3.recv_from(vec![0; 20]).await, active_addr);
 }
 
+#[monoio::test_all]
+async fn recv_from_full() {
+    const MSG: &str = "foo bar baz";
+
+    let passive = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let passive_addr = passive.local_addr().unwrap();
+    let active = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let active_addr = active.local_addr().unwrap();
+
+    active.send_to(MSG, passive_addr).await.0.unwrap();
+    let (res, buf) = passive.recv_from_full(vec![0; 20]).await;
+    let meta = res.unwrap();
+    assert_eq!(meta.len, MSG.len());
+    assert_eq!(meta.addr, active_addr);
+    assert!(!meta.truncated());
+    assert_eq!(buf, MSG.as_bytes());
+
+    active.send_to(MSG, passive_addr).await.0.unwrap();
+    let (res, buf) = passive.recv_from_full(vec![0; 3]).await;
+    let meta = res.unwrap();
+    assert_eq!((meta.len, meta.datagram_len), (3, MSG.len()));
+    assert!(meta.truncated());
+    assert_eq!(buf, &MSG.as_bytes()[..3]);
+}
+
+#[cfg(unix)]
+#[monoio::test_all]
+async fn send_to_vectored() {
+    let passive = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let passive_addr = passive.local_addr().unwrap();
+    let active = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let active_addr = active.local_addr().unwrap();
+
+    let buf_vec: monoio::buf::VecBuf = vec![b"foo ".to_vec(), b"bar baz".to_vec()].into();
+    let (res, buf_vec) = active.send_to_vectored(buf_vec, passive_addr).await;
+    assert_eq!(res.unwrap(), 11);
+    let raw: Vec<Vec<u8>> = buf_vec.into();
+    assert_eq!(raw.len(), 2);
+
+    let (res, buf) = passive.recv_from(vec![0; 20]).await;
+    let (n, addr) = res.unwrap();
+    assert_eq!(addr, active_addr);
+    assert_eq!(&buf[..n], b"foo bar baz");
+}
+
+#[cfg(unix)]
+#[monoio::test_all]
+async fn recv_from_vectored() {
+    let passive = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let passive_addr = passive.local_addr().unwrap();
+    let active = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let active_addr = active.local_addr().unwrap();
+
+    active.send_to("foo bar baz", passive_addr).await.0.unwrap();
+    let buf_vec: monoio::buf::VecBuf = vec![vec![0; 4], vec![0; 5], vec![0; 8]].into();
+    let (res, buf_vec) = passive.recv_from_vectored(buf_vec).await;
+    let (n, addr) = res.unwrap();
+    assert_eq!(n, 11);
+    assert_eq!(addr, active_addr);
+    let raw: Vec<Vec<u8>> = buf_vec.into();
+    assert_eq!(
+        raw,
+        vec![b"foo ".to_vec(), b"bar b".to_vec(), b"az".to_vec()]
+    );
+
+    // The buffers after the datagram end hold no data.
+    active.send_to("foo", passive_addr).await.0.unwrap();
+    let buf_vec: monoio::buf::VecBuf = vec![vec![0; 4], vec![0; 5]].into();
+    let (res, buf_vec) = passive.recv_from_vectored(buf_vec).await;
+    assert_eq!(res.unwrap().0, 3);
+    let raw: Vec<Vec<u8>> = buf_vec.into();
+    assert_eq!(raw, vec![b"foo".to_vec(), vec![]]);
+}
+
+#[cfg(unix)]
+#[monoio::test_all]
+async fn send_recv_with_flags() {
+    const MSG: &str = "foo bar baz";
+
+    let passive = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let passive_addr = passive.local_addr().unwrap();
+    let active = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let active_addr = active.local_addr().unwrap();
+
+    active
+        .send_to_with_flags(MSG, passive_addr, libc::MSG_DONTROUTE)
+        .await
+        .0
+        .unwrap();
+    let (res, buf) = passive.recv_from_with_flags(vec![0; 20], 0).await;
+    let (n, addr) = res.unwrap();
+    assert_eq!(n, MSG.len());
+    assert_eq!(addr, active_addr);
+    assert_eq!(&buf[..n], MSG.as_bytes());
+
+    let (res, _) = active
+        .send_to_with_flags(MSG, passive_addr, libc::MSG_DONTWAIT)
+        .await;
+    assert_eq!(res.unwrap_err().kind(), std::io::ErrorKind::InvalidInput);
+    let (res, _) = passive
+        .recv_from_with_flags(vec![0; 20], libc::MSG_PEEK)
+        .await;
+    assert_eq!(res.unwrap_err().kind(), std::io::ErrorKind::InvalidInput);
+}
+
 #[monoio::test_all(timer_enabled = true)]
 async fn rw_able() {
     const MSG: &str = "foo bar baz";
@@ -96,3 +201,523 @@ async fn cancel_recv_from() {
         }
     }
 }
+
+#[cfg(unix)]
+#[monoio::test_all]
+async fn peek() {
+    let passive = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let passive_addr = passive.local_addr().unwrap();
+    let active = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let active_addr = active.local_addr().unwrap();
+    active.connect(passive_addr).await.unwrap();
+    passive.connect(active_addr).await.unwrap();
+
+    active.send("foo").await.0.unwrap();
+    let (res, buf) = passive.peek_from(vec![0; 8]).await;
+    assert_eq!(res.unwrap(), (3, active_addr));
+    assert_eq!(buf, b"foo");
+    let (res, buf) = passive.peek(vec![0; 8]).await;
+    assert_eq!(res.unwrap(), 3);
+    assert_eq!(buf, b"foo");
+    let (res, buf) = passive.recv_from(vec![0; 8]).await;
+    assert_eq!(res.unwrap(), (3, active_addr));
+    assert_eq!(buf, b"foo");
+
+    // Peeking with a small buffer does not truncate the queued datagram.
+    active.send("foobar").await.0.unwrap();
+    let (res, buf) = passive.peek_from(vec![0; 3]).await;
+    assert_eq!(res.unwrap(), (3, active_addr));
+    assert_eq!(buf, b"foo");
+    let (res, buf) = passive.recv_from_full(vec![0; 8]).await;
+    let meta = res.unwrap();
+    assert_eq!((meta.len, meta.datagram_len), (6, 6));
+    assert_eq!(&buf[..meta.len], b"foobar");
+}
+
+#[cfg(target_os = "linux")]
+#[monoio::test_all]
+async fn bind_device() {
+    let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let addr = socket.local_addr().unwrap();
+    socket.set_bind_device(Some("lo")).unwrap();
+    assert_eq!(socket.bind_device().unwrap().as_deref(), Some("lo"));
+
+    let active = UdpSocket::bind("127.0.0.1:0").unwrap();
+    active.send_to("foo", addr).await.0.unwrap();
+    let (res, buf) = socket.recv_from(vec![0; 8]).await;
+    assert_eq!(res.unwrap(), (3, active.local_addr().unwrap()));
+    assert_eq!(buf, b"foo");
+
+    socket.set_bind_device(None).unwrap();
+    assert_eq!(socket.bind_device().unwrap(), None);
+    let index = unsafe { libc::if_nametoindex(c"lo".as_ptr()) };
+    socket.bind_device_by_index(index).unwrap();
+    assert_eq!(socket.bind_device().unwrap().as_deref(), Some("lo"));
+    socket.bind_device_by_index(0).unwrap();
+    assert_eq!(socket.bind_device().unwrap(), None);
+}
+
+#[monoio::test_all]
+async fn socket_options() {
+    let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+    socket.set_ttl(42).unwrap();
+    assert_eq!(socket.ttl().unwrap(), 42);
+    socket.set_send_buffer_size(64 * 1024).unwrap();
+    assert!(socket.send_buffer_size().unwrap() >= 64 * 1024);
+    socket.set_recv_buffer_size(64 * 1024).unwrap();
+    assert!(socket.recv_buffer_size().unwrap() >= 64 * 1024);
+    assert!(socket.take_error().unwrap().is_none());
+
+    // Sending to a closed port leaves ECONNREFUSED pending on the connected
+    // socket, and take_error clears it.
+    #[cfg(unix)]
+    {
+        let closed = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let addr = closed.local_addr().unwrap();
+        drop(closed);
+        socket.connect(addr).await.unwrap();
+        socket.send("foo").await.0.unwrap();
+        let err = socket.take_error().unwrap().unwrap();
+        assert_eq!(err.kind(), std::io::ErrorKind::ConnectionRefused);
+        assert!(socket.take_error().unwrap().is_none());
+    }
+}
+
+#[monoio::test_all]
+async fn dual_stack() {
+    use std::net::{Ipv6Addr, SocketAddr};
+
+    // Skip if IPv6 is disabled in the kernel.
+    let Ok(socket) = UdpSocket::bind("[::]:0") else {
+        return;
+    };
+    let port = socket.local_addr().unwrap().port();
+    let active = UdpSocket::bind("127.0.0.1:0").unwrap();
+    active
+        .send_to("foo", SocketAddr::from(([127, 0, 0, 1], port)))
+        .await
+        .0
+        .unwrap();
+    let (res, buf) = socket.recv_from(vec![0; 8]).await;
+    let (n, peer) = res.unwrap();
+    assert_eq!(&buf[..n], b"foo");
+    assert_eq!(peer.ip().to_canonical(), active.local_addr().unwrap().ip());
+
+    let socket = UdpSocket::bind_with_only_v6("[::]:0", true).unwrap();
+    let port = socket.local_addr().unwrap().port();
+    let active = UdpSocket::bind("[::1]:0").unwrap();
+    active
+        .send_to("bar", SocketAddr::from((Ipv6Addr::LOCALHOST, port)))
+        .await
+        .0
+        .unwrap();
+    let (res, buf) = socket.recv_from(vec![0; 8]).await;
+    assert_eq!(&buf[..res.unwrap().0], b"bar");
+    // The IPv4 port is not taken by the IPv6 only socket.
+    UdpSocket::bind(("127.0.0.1", port)).unwrap();
+}
+
+#[cfg(unix)]
+#[monoio::test_all]
+async fn multicast_v4() {
+    use std::net::{Ipv4Addr, SocketAddr};
+
+    const GROUP: Ipv4Addr = Ipv4Addr::new(239, 255, 0, 1);
+
+    let socket = UdpSocket::bind("0.0.0.0:0").unwrap();
+    let port = socket.local_addr().unwrap().port();
+    socket
+        .join_multicast_v4(GROUP, Ipv4Addr::LOCALHOST)
+        .unwrap();
+    socket.set_multicast_if_v4(Ipv4Addr::LOCALHOST).unwrap();
+    assert_eq!(socket.multicast_if_v4().unwrap(), Ipv4Addr::LOCALHOST);
+    socket.set_multicast_ttl_v4(4).unwrap();
+    assert_eq!(socket.multicast_ttl_v4().unwrap(), 4);
+    assert!(socket.multicast_loop_v4().unwrap());
+
+    // The datagram sent to the group is looped back to the sender.
+    let group = SocketAddr::from((GROUP, port));
+    socket.send_to("foo", group).await.0.unwrap();
+    let (res, buf) = socket.recv_from(vec![0; 8]).await;
+    let (n, _) = res.unwrap();
+    assert_eq!(&buf[..n], b"foo");
+
+    socket
+        .leave_multicast_v4(GROUP, Ipv4Addr::LOCALHOST)
+        .unwrap();
+    let err = socket
+        .leave_multicast_v4(GROUP, Ipv4Addr::LOCALHOST)
+        .unwrap_err();
+    assert_eq!(err.raw_os_error(), Some(libc::EADDRNOTAVAIL));
+    socket.set_multicast_loop_v4(false).unwrap();
+    assert!(!socket.multicast_loop_v4().unwrap());
+}
+
+#[cfg(unix)]
+#[monoio::test_all]
+async fn multicast_v6() {
+    use std::net::Ipv6Addr;
+
+    const GROUP: Ipv6Addr = Ipv6Addr::new(0xff01, 0, 0, 0, 0, 0, 0, 0x1234);
+
+    // Skip if IPv6 is disabled in the kernel.
+    let Ok(socket) = UdpSocket::bind("[::]:0") else {
+        return;
+    };
+    let index = unsafe { libc::if_nametoindex(c"lo".as_ptr()) };
+    socket.join_multicast_v6(&GROUP, index).unwrap();
+    socket.set_multicast_if_v6(index).unwrap();
+    assert_eq!(socket.multicast_if_v6().unwrap(), index);
+    assert!(socket.multicast_loop_v6().unwrap());
+
+    // There is no IPv6 multicast route on the loopback interface, so only the
+    // membership is checked.
+    socket.leave_multicast_v6(&GROUP, index).unwrap();
+    let err = socket.leave_multicast_v6(&GROUP, index).unwrap_err();
+    assert_eq!(err.raw_os_error(), Some(libc::EADDRNOTAVAIL));
+    socket.set_multicast_loop_v6(false).unwrap();
+    assert!(!socket.multicast_loop_v6().unwrap());
+}
+
+#[monoio::test_all]
+async fn broadcast() {
+    use std::net::{Ipv4Addr, SocketAddr};
+
+    let receiver = UdpSocket::bind("0.0.0.0:0").unwrap();
+    let port = receiver.local_addr().unwrap().port();
+    let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+    assert!(!socket.broadcast().unwrap());
+    // The broadcast address of the loopback network on Linux.
+    let addr = SocketAddr::from((Ipv4Addr::new(127, 255, 255, 255), port));
+    #[cfg(target_os = "linux")]
+    {
+        let err = socket.send_to("foo", addr).await.0.unwrap_err();
+        assert_eq!(err.raw_os_error(), Some(libc::EACCES));
+    }
+
+    socket.set_broadcast(true).unwrap();
+    assert!(socket.broadcast().unwrap());
+    #[cfg(target_os = "linux")]
+    {
+        socket.send_to("foo", addr).await.0.unwrap();
+        let (res, buf) = receiver.recv_from(vec![0; 8]).await;
+        let (n, peer) = res.unwrap();
+        assert_eq!(&buf[..n], b"foo");
+        assert_eq!(peer, socket.local_addr().unwrap());
+    }
+    socket.set_broadcast(false).unwrap();
+    assert!(!socket.broadcast().unwrap());
+
+    let std_socket = std::net::UdpSocket::bind("127.0.0.1:0").unwrap();
+    let socket = UdpSocket::from_std(std_socket).unwrap();
+    socket.set_broadcast(true).unwrap();
+    assert!(socket.broadcast().unwrap());
+    socket.set_ttl(9).unwrap();
+    assert_eq!(socket.ttl().unwrap(), 9);
+}
+
+#[cfg(unix)]
+#[monoio::test_all]
+async fn send_batch() {
+    let receiver = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let addr = receiver.local_addr().unwrap();
+    let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+
+    let msgs = (0..8u8).map(|i| (vec![i; i as usize + 1], addr)).collect();
+    let (res, bufs) = socket.send_batch(msgs).await;
+    assert_eq!(res.unwrap(), 8);
+    assert_eq!(bufs.len(), 8);
+    for i in 0..8u8 {
+        let (res, buf) = receiver.recv_from(vec![0; 16]).await;
+        let (n, peer) = res.unwrap();
+        assert_eq!(&buf[..n], &bufs[i as usize][..]);
+        assert_eq!(peer, socket.local_addr().unwrap());
+    }
+
+    // The one too large for a datagram stops the batch.
+    let mut msgs: Vec<_> = (0..4u8).map(|i| (vec![i; 4], addr)).collect();
+    msgs.insert(2, (vec![0; 70000], addr));
+    let (res, bufs) = socket.send_batch(msgs).await;
+    assert_eq!(res.unwrap(), 2);
+    assert_eq!(bufs.len(), 5);
+    for i in 0..2u8 {
+        let (res, buf) = receiver.recv_from(vec![0; 16]).await;
+        assert_eq!(&buf[..res.unwrap().0], &[i; 4]);
+    }
+    let tail = bufs.into_iter().skip(2).map(|buf| (buf, addr)).collect();
+    let (res, bufs) = socket.send_batch(tail).await;
+    assert_eq!(res.unwrap_err().raw_os_error(), Some(libc::EMSGSIZE));
+    assert_eq!(bufs.len(), 3);
+
+    let (res, bufs) = socket.send_batch(Vec::<(Vec<u8>, _)>::new()).await;
+    assert_eq!(res.unwrap(), 0);
+    assert!(bufs.is_empty());
+
+    // Larger than the SQ and the limit of sendmmsg.
+    let msgs = (0..3000).map(|_| (b"x".as_slice(), addr)).collect();
+    let (res, bufs) = socket.send_batch(msgs).await;
+    assert_eq!(res.unwrap(), 3000);
+    assert_eq!(bufs.len(), 3000);
+}
+
+#[cfg(unix)]
+#[monoio::test_all(timer_enabled = true)]
+async fn recv_batch() {
+    let receiver = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let addr = receiver.local_addr().unwrap();
+    let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+
+    for i in 0..3u8 {
+        socket
+            .send_to(vec![i; i as usize + 1], addr)
+            .await
+            .0
+            .unwrap();
+    }
+    let bufs = (0..5).map(|_| Vec::with_capacity(8)).collect();
+    let (res, bufs) = receiver.recv_batch(bufs).await;
+    assert_eq!(res.unwrap(), 3);
+    assert_eq!(bufs.len(), 5);
+    for (i, (buf, n, peer)) in bufs.iter().enumerate().take(3) {
+        assert_eq!(*n, i + 1);
+        assert_eq!(&buf[..], &vec![i as u8; i + 1][..]);
+        assert_eq!(*peer, socket.local_addr().unwrap());
+    }
+    for (buf, n, peer) in &bufs[3..] {
+        assert!(buf.is_empty());
+        assert_eq!(*n, 0);
+        assert!(peer.ip().is_unspecified());
+    }
+
+    // Waits for the first datagram.
+    let (res, bufs) = monoio::join!(receiver.recv_batch(vec![vec![0; 8]; 2]), async {
+        monoio::time::sleep(std::time::Duration::from_millis(10)).await;
+        socket.send_to("foo", addr).await.0.unwrap();
+    })
+    .0;
+    assert_eq!(res.unwrap(), 1);
+    assert_eq!(&bufs[0].0[..bufs[0].1], b"foo");
+
+    let (res, bufs) = receiver.recv_batch(Vec::<Vec<u8>>::new()).await;
+    assert_eq!(res.unwrap(), 0);
+    assert!(bufs.is_empty());
+}
+
+#[cfg(target_os = "linux")]
+#[monoio::test_all]
+async fn gso_segment() {
+    let receiver = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let addr = receiver.local_addr().unwrap();
+    let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+
+    let data: Vec<u8> = (0..2500).map(|i| i as u8).collect();
+    let (res, _) = socket.send_to_segmented(data.clone(), addr, 1000).await;
+    assert_eq!(res.unwrap(), 2500);
+    for chunk in data.chunks(1000) {
+        let (res, buf) = receiver.recv_from(vec![0; 4096]).await;
+        assert_eq!(&buf[..res.unwrap().0], chunk);
+    }
+
+    assert_eq!(socket.gso_segment().unwrap(), None);
+    socket.set_gso_segment(Some(1200)).unwrap();
+    assert_eq!(socket.gso_segment().unwrap(), Some(1200));
+    socket.send_to(data.clone(), addr).await.0.unwrap();
+    for len in [1200, 1200, 100] {
+        let (res, _) = receiver.recv_from(vec![0; 4096]).await;
+        assert_eq!(res.unwrap().0, len);
+    }
+    socket.set_gso_segment(None).unwrap();
+    assert_eq!(socket.gso_segment().unwrap(), None);
+
+    // Too many segments, the caller has to split it.
+    let (res, _) = socket.send_to_segmented(vec![0; 60000], addr, 10).await;
+    assert_eq!(res.unwrap_err().raw_os_error(), Some(libc::EINVAL));
+}
+
+#[cfg(target_os = "linux")]
+#[monoio::test_all]
+async fn gro() {
+    let receiver = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let addr = receiver.local_addr().unwrap();
+    let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+    assert!(!receiver.gro().unwrap());
+    receiver.set_gro(true).unwrap();
+    assert!(receiver.gro().unwrap());
+
+    // The segments sent with GSO over loopback are kept together.
+    let data: Vec<u8> = (0..2500).map(|i| i as u8).collect();
+    socket
+        .send_to_segmented(data.clone(), addr, 1000)
+        .await
+        .0
+        .unwrap();
+    let (res, buf) = receiver.recv_from_gro(vec![0; 4096]).await;
+    let meta = res.unwrap();
+    assert_eq!(meta.len, 2500);
+    assert_eq!(meta.segment_size, Some(1000));
+    assert_eq!(meta.addr, socket.local_addr().unwrap());
+    assert_eq!(&buf[..meta.len], &data[..]);
+
+    socket.send_to("foo", addr).await.0.unwrap();
+    let (res, _) = receiver.recv_from_gro(vec![0; 4096]).await;
+    let meta = res.unwrap();
+    assert_eq!(meta.len, 3);
+    assert!(!meta.truncated());
+
+    receiver.set_gro(false).unwrap();
+    socket.send_to_segmented(data, addr, 1000).await.0.unwrap();
+    let (res, _) = receiver.recv_from_gro(vec![0; 4096]).await;
+    let meta = res.unwrap();
+    assert_eq!(meta.len, 1000);
+    assert_eq!(meta.segment_size, None);
+}
+
+#[cfg(unix)]
+#[monoio::test_all]
+async fn std_conversions() {
+    let socket = socket2::Socket::new(socket2::Domain::IPV4, socket2::Type::DGRAM, None).unwrap();
+    #[cfg(target_os = "linux")]
+    socket.set_freebind(true).unwrap();
+    socket
+        .bind(
+            &"127.0.0.1:0"
+                .parse::<std::net::SocketAddr>()
+                .unwrap()
+                .into(),
+        )
+        .unwrap();
+    let socket = UdpSocket::from_socket(socket).unwrap();
+    let addr = socket.local_addr().unwrap();
+    let peer = std::net::UdpSocket::bind("127.0.0.1:0").unwrap();
+    peer.send_to(b"foo", addr).unwrap();
+    let (res, buf) = socket.recv_from(vec![0; 8]).await;
+    assert_eq!(res.unwrap(), (3, peer.local_addr().unwrap()));
+    assert_eq!(buf, b"foo");
+
+    let std_socket = socket.into_std().unwrap();
+    assert_eq!(std_socket.local_addr().unwrap(), addr);
+    std_socket.set_nonblocking(false).unwrap();
+    peer.send_to(b"bar", addr).unwrap();
+    let mut buf = [0; 8];
+    assert_eq!(std_socket.recv(&mut buf).unwrap(), 3);
+    assert_eq!(&buf[..3], b"bar");
+
+    let socket = UdpSocket::from_std(std_socket).unwrap();
+    peer.send_to(b"baz", addr).unwrap();
+    let (res, buf) = socket.recv(vec![0; 8]).await;
+    assert_eq!(res.unwrap(), 3);
+    assert_eq!(buf, b"baz");
+}
+
+// A dropped recv is in flight on uring driver until it is canceled.
+#[cfg(all(target_os = "linux", feature = "iouring"))]
+#[monoio::test(driver = "uring", timer_enabled = true)]
+async fn into_std_with_op_in_flight() {
+    let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+    monoio::select! {
+        _ = socket.recv_from(vec![0; 8]) => unreachable!(),
+        _ = monoio::time::sleep(std::time::Duration::from_millis(10)) => {}
+    }
+    assert!(socket.into_std().is_err());
+}
+
+#[cfg(unix)]
+#[monoio::test_all]
+async fn tos() {
+    let receiver = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let addr = receiver.local_addr().unwrap();
+    let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+    socket.set_tos(0xb8).unwrap();
+    assert_eq!(socket.tos().unwrap(), 0xb8);
+
+    socket.send_to_with_tos("foo", addr, 0x28).await.0.unwrap();
+    let (res, buf) = receiver.recv_from(vec![0; 8]).await;
+    assert_eq!(&buf[..res.unwrap().0], b"foo");
+    assert_eq!(socket.tos().unwrap(), 0xb8);
+
+    let socket = UdpSocket::bind("[::1]:0").unwrap();
+    socket.set_tos(0x28).unwrap();
+    assert_eq!(socket.tos().unwrap(), 0x28);
+    let receiver = UdpSocket::bind("[::1]:0").unwrap();
+    let addr = receiver.local_addr().unwrap();
+    socket.send_to_with_tos("bar", addr, 0xb8).await.0.unwrap();
+    let (res, buf) = receiver.recv_from(vec![0; 8]).await;
+    assert_eq!(&buf[..res.unwrap().0], b"bar");
+}
+
+#[cfg(target_os = "linux")]
+#[monoio::test_all]
+async fn pktinfo() {
+    use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+    let lo = unsafe { libc::if_nametoindex(c"lo".as_ptr()) };
+    for (wildcard, dst, src) in [
+        (
+            IpAddr::from(Ipv4Addr::UNSPECIFIED),
+            IpAddr::from(Ipv4Addr::LOCALHOST),
+            IpAddr::from(Ipv4Addr::new(127, 0, 0, 2)),
+        ),
+        (
+            IpAddr::from(Ipv6Addr::UNSPECIFIED),
+            IpAddr::from(Ipv6Addr::LOCALHOST),
+            IpAddr::from(Ipv6Addr::LOCALHOST),
+        ),
+    ] {
+        let server = UdpSocket::bind(SocketAddr::new(wildcard, 0)).unwrap();
+        assert!(!server.pktinfo().unwrap());
+        server.set_pktinfo(true).unwrap();
+        assert!(server.pktinfo().unwrap());
+        let port = server.local_addr().unwrap().port();
+        let client = UdpSocket::bind(SocketAddr::new(dst, 0)).unwrap();
+
+        client
+            .send_to("foo", SocketAddr::new(dst, port))
+            .await
+            .0
+            .unwrap();
+        let (res, buf) = server.recv_msg_meta(vec![0; 8]).await;
+        let meta = res.unwrap();
+        assert_eq!(&buf[..meta.len], b"foo");
+        assert_eq!(meta.addr, client.local_addr().unwrap());
+        assert_eq!(meta.dst_addr, Some(dst));
+        assert_eq!(meta.ifindex, Some(lo));
+
+        server.send_to_from("bar", meta.addr, src).await.0.unwrap();
+        let (res, buf) = client.recv_from(vec![0; 8]).await;
+        let (n, peer) = res.unwrap();
+        assert_eq!(&buf[..n], b"bar");
+        assert_eq!(peer, SocketAddr::new(src, port));
+    }
+
+    // Not reported without the option.
+    let server = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let client = UdpSocket::bind("127.0.0.1:0").unwrap();
+    client
+        .send_to("foo", server.local_addr().unwrap())
+        .await
+        .0
+        .unwrap();
+    let (res, _) = server.recv_msg_meta(vec![0; 8]).await;
+    let meta = res.unwrap();
+    assert_eq!((meta.dst_addr, meta.ifindex), (None, None));
+}
+
+#[cfg(unix)]
+#[monoio::test_all]
+async fn try_clone() {
+    use std::os::unix::io::AsRawFd;
+
+    let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let addr = socket.local_addr().unwrap();
+    let clone = socket.try_clone().unwrap();
+    assert_ne!(clone.as_raw_fd(), socket.as_raw_fd());
+    assert_eq!(clone.local_addr().unwrap(), addr);
+
+    // The clone still receives after the original is dropped.
+    drop(socket);
+    let peer = UdpSocket::bind("127.0.0.1:0").unwrap();
+    peer.send_to("hello", addr).await.0.unwrap();
+    let (res, buf) = clone.recv_from(vec![0; 16]).await;
+    assert_eq!(res.unwrap().1, peer.local_addr().unwrap());
+    assert_eq!(buf, b"hello");
+}
@@ -1,5 +1,5 @@
 use libc::{getegid, geteuid};
-use monoio::net::UnixStream;
+use monoio::net::{UnixListener, UnixStream};
 
 #[monoio::test_all]
 async fn test_socket_pair() {
@@ -13,4 +13,70 @@ async fn test_socket_pair() {
 
     assert_eq!(cred_a.uid(), uid);
     assert_eq!(cred_a.gid(), gid);
+    #[cfg(any(target_os = "linux", target_os = "android", target_os = "macos"))]
+    assert_eq!(cred_a.pid(), Some(std::process::id() as i32));
+}
+
+#[monoio::test_all]
+async fn test_connect_accept() {
+    let dir = tempfile::Builder::new()
+        .prefix("monoio-uds-cred-tests")
+        .tempdir()
+        .unwrap();
+    let sock_path = dir.path().join("cred.sock");
+    let listener = UnixListener::bind(&sock_path).unwrap();
+
+    let accept = listener.accept();
+    let connect = UnixStream::connect(&sock_path);
+    let ((server, _), client) = futures::future::try_join(accept, connect).await.unwrap();
+    // Both are the credentials of this process, captured at connect and
+    // listen time.
+    assert_eq!(server.peer_cred().unwrap(), client.peer_cred().unwrap());
+    assert_eq!(server.peer_cred().unwrap().uid(), unsafe { geteuid() });
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[monoio::test_all]
+async fn test_scm_credentials() {
+    let (mut a, mut b) = UnixStream::pair().unwrap();
+    assert!(!b.passcred().unwrap());
+
+    // Nothing is received without SO_PASSCRED.
+    a.send_with_creds(b"hello").await.0.unwrap();
+    let (res, buf) = b.recv_with_creds(vec![0; 16]).await;
+    assert_eq!(res.unwrap(), (5, None));
+    assert_eq!(buf, b"hello");
+
+    b.set_passcred(true).unwrap();
+    assert!(b.passcred().unwrap());
+    a.send_with_creds(b"world").await.0.unwrap();
+    let (res, buf) = b.recv_with_creds(vec![0; 16]).await;
+    let (n, cred) = res.unwrap();
+    assert_eq!(n, 5);
+    assert_eq!(buf, b"world");
+    let cred = cred.unwrap();
+    assert_eq!(cred.pid(), Some(std::process::id() as i32));
+    assert_eq!(cred.uid(), unsafe { libc::getuid() });
+    assert_eq!(cred.gid(), unsafe { libc::getgid() });
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[monoio::test_all]
+async fn test_scm_credentials_with_fds() {
+    use std::os::unix::io::{AsRawFd, OwnedFd};
+
+    let (mut a, mut b) = UnixStream::pair().unwrap();
+    b.set_passcred(true).unwrap();
+
+    // The credentials arrive with the fds in one message, which must not
+    // truncate the fds.
+    let file = tempfile::tempfile().unwrap();
+    a.send_with_fds(b"hello", &[file.as_raw_fd()])
+        .await
+        .0
+        .unwrap();
+    let mut fds: [Option<OwnedFd>; 1] = Default::default();
+    let (res, _) = b.recv_with_fds(vec![0; 16], &mut fds).await;
+    assert_eq!(res.unwrap(), (5, 1));
+    assert!(fds[0].is_some());
 }
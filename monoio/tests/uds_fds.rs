@@ -0,0 +1,83 @@
+use std::{
+    fs::File,
+    io::{Read, Seek, Write},
+    os::unix::io::{AsRawFd, OwnedFd},
+};
+
+use monoio::net::UnixStream;
+
+fn temp_file(content: &[u8]) -> File {
+    let mut file = tempfile::tempfile().unwrap();
+    file.write_all(content).unwrap();
+    file
+}
+
+#[monoio::test_all]
+async fn recv_fds() {
+    let (mut a, mut b) = UnixStream::pair().unwrap();
+    let (f1, f2) = (temp_file(b"one"), temp_file(b"two"));
+    let (res, _) = a
+        .send_with_fds(b"hello", &[f1.as_raw_fd(), f2.as_raw_fd()])
+        .await;
+    assert_eq!(res.unwrap(), 5);
+    // The received fds refer to the files even if the sent ones are closed.
+    drop((f1, f2));
+
+    let mut fds: [Option<OwnedFd>; 4] = Default::default();
+    let (res, buf) = b.recv_with_fds(vec![0; 16], &mut fds).await;
+    assert_eq!(res.unwrap(), (5, 2));
+    assert_eq!(buf, b"hello");
+    assert!(fds[2].is_none());
+
+    for (fd, expected) in fds.into_iter().flatten().zip([b"one", b"two"]) {
+        let mut file = File::from(fd);
+        let mut content = Vec::new();
+        file.rewind().unwrap();
+        file.read_to_end(&mut content).unwrap();
+        assert_eq!(content, expected);
+    }
+}
+
+#[monoio::test_all]
+async fn recv_fds_truncated() {
+    let (mut a, mut b) = UnixStream::pair().unwrap();
+    let (f1, f2) = (temp_file(b"one"), temp_file(b"two"));
+    let (res, _) = a
+        .send_with_fds(b"hello", &[f1.as_raw_fd(), f2.as_raw_fd()])
+        .await;
+    assert_eq!(res.unwrap(), 5);
+    // The received fds refer to the files even if the sent ones are closed.
+    drop((f1, f2));
+
+    let mut fds: [Option<OwnedFd>; 1] = Default::default();
+    let (res, _) = b.recv_with_fds(vec![0; 16], &mut fds).await;
+    assert!(res.is_err());
+    assert!(fds[0].is_none());
+}
+
+#[monoio::test_all(timer_enabled = true)]
+async fn recv_fds_dropped() {
+    use std::time::Duration;
+
+    let (r, w) = std::io::pipe().unwrap();
+    let (mut a, mut b) = UnixStream::pair().unwrap();
+    for i in 0..8 {
+        let mut fds: [Option<OwnedFd>; 1] = Default::default();
+        let mut recv = std::pin::pin!(b.recv_with_fds(vec![0; 16], &mut fds));
+        let _ = futures::poll!(recv.as_mut());
+        let (res, _) = a.send_with_fds(b"hello", &[w.as_raw_fd()]).await;
+        assert_eq!(res.unwrap(), 5);
+        // Dropped either before or after the completion is reaped.
+        if i % 2 == 0 {
+            monoio::time::sleep(Duration::from_millis(10)).await;
+        }
+    }
+    drop((w, a, b));
+    monoio::time::sleep(Duration::from_millis(10)).await;
+
+    // All the write ends are closed, including the received ones, or the
+    // read would block.
+    unsafe { libc::fcntl(r.as_raw_fd(), libc::F_SETFL, libc::O_NONBLOCK) };
+    let mut buf = [0; 1];
+    assert_eq!((&r).read(&mut buf).unwrap(), 0);
+}
@@ -62,3 +62,186 @@ async fn shutdown() -> std::io::Result<()> {
     assert_eq!(n, 0);
     Ok(())
 }
+
+#[monoio::test_all]
+async fn std_conversions() {
+    use std::{io::Read, os::unix::io::OwnedFd};
+
+    let (std_stream, mut peer) = std::os::unix::net::UnixStream::pair().unwrap();
+    let mut stream = UnixStream::try_from(OwnedFd::from(std_stream)).unwrap();
+    stream.write_all("hello").await.0.unwrap();
+    let mut buf = [0; 5];
+    peer.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"hello");
+
+    let std_stream = stream.into_std().unwrap();
+    drop(peer);
+    let mut stream = UnixStream::from_std(std_stream).unwrap();
+    let (res, _) = stream.read(vec![0; 5]).await;
+    assert_eq!(res.unwrap(), 0);
+}
+
+#[monoio::test_all]
+async fn socket_options() {
+    let (stream, _peer) = UnixStream::pair().unwrap();
+    stream.set_send_buffer_size(64 * 1024).unwrap();
+    assert!(stream.send_buffer_size().unwrap() >= 64 * 1024);
+    stream.set_recv_buffer_size(64 * 1024).unwrap();
+    assert!(stream.recv_buffer_size().unwrap() >= 64 * 1024);
+    assert!(stream.take_error().unwrap().is_none());
+}
+
+#[monoio::test_all(timer_enabled = true)]
+async fn try_read_write() {
+    use std::{io::ErrorKind, time::Duration};
+
+    let (client, mut server) = UnixStream::pair().unwrap();
+    let mut buf = [0; 16];
+    let err = client.try_read(&mut buf).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::WouldBlock);
+    monoio::select! {
+        _ = client.readable(true) => panic!("readable without data"),
+        _ = monoio::time::sleep(Duration::from_millis(20)) => {}
+    }
+    server.write_all("hello").await.0.unwrap();
+    client.readable(true).await.unwrap();
+    assert_eq!(client.try_read(&mut buf).unwrap(), 5);
+    assert_eq!(&buf[..5], b"hello");
+
+    // Fill the send buffer since the server does not read.
+    let chunk = vec![1; 64 * 1024];
+    let mut written = 0;
+    let err = loop {
+        match client.try_write(&chunk) {
+            Ok(n) => written += n,
+            Err(e) => break e,
+        }
+    };
+    assert_eq!(err.kind(), ErrorKind::WouldBlock);
+    let (res, _) = server.read_exact(vec![0; written]).await;
+    res.unwrap();
+    client.writable(true).await.unwrap();
+    assert_eq!(client.try_write(b"world").unwrap(), 5);
+    let (res, _) = server.read_exact(vec![0; 5]).await;
+    res.unwrap();
+
+    // No SIGPIPE once the peer is gone.
+    drop(server);
+    client.readable(true).await.unwrap();
+    assert_eq!(client.try_read(&mut buf).unwrap(), 0);
+    let err = client.try_write(b"world").unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::BrokenPipe);
+}
+
+#[cfg(feature = "futures-stream")]
+#[monoio::test_all(timer_enabled = true)]
+async fn incoming() {
+    use std::time::Duration;
+
+    use futures::StreamExt;
+
+    let dir = tempfile::Builder::new()
+        .prefix("monoio-uds-tests")
+        .tempdir()
+        .unwrap();
+    let sock_path = dir.path().join("incoming.sock");
+    // SO_REUSEPORT is rejected for unix sockets by newer kernels.
+    let opts = monoio::net::ListenerOpts::new().reuse_port(false);
+    let listener = UnixListener::bind_with_config(&sock_path, &opts).unwrap();
+
+    let mut incoming = listener.incoming();
+    monoio::select! {
+        _ = incoming.next() => panic!("accepted without a client"),
+        _ = monoio::time::sleep(Duration::from_millis(20)) => {}
+    }
+    drop(incoming);
+    let (client, accepted) = monoio::join!(UnixStream::connect(&sock_path), listener.accept());
+    client.unwrap();
+    accepted.unwrap();
+
+    let mut incoming = listener.incoming();
+    let (client, accepted) = monoio::join!(UnixStream::connect(&sock_path), incoming.next());
+    let mut client = client.unwrap();
+    let mut accepted = accepted.unwrap().unwrap();
+    client.write_all("ping").await.0.unwrap();
+    let (res, buf) = accepted.read_exact(vec![0; 4]).await;
+    res.unwrap();
+    assert_eq!(buf, b"ping");
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[monoio::test_all]
+async fn abstract_namespace() {
+    use monoio::net::unix::SocketAddr;
+
+    let name = format!(
+        "monoio-uds-tests-{}-{:?}",
+        std::process::id(),
+        std::thread::current().id()
+    );
+    let addr = SocketAddr::from_abstract_name(name.as_bytes()).unwrap();
+    assert_eq!(addr.as_abstract_namespace(), Some(name.as_bytes()));
+    assert!(addr.as_pathname().is_none());
+
+    let listener = UnixListener::bind_addr(&addr).unwrap();
+    // The name is taken until the listener is closed.
+    assert_eq!(
+        UnixListener::bind_addr(&addr).err().unwrap().kind(),
+        std::io::ErrorKind::AddrInUse
+    );
+
+    let accept = listener.accept();
+    let connect = UnixStream::connect_addr(addr.clone());
+    let ((mut server, peer), mut client) = try_join(accept, connect).await.unwrap();
+    assert!(peer.is_unnamed());
+    assert_eq!(
+        client.peer_addr().unwrap().as_abstract_namespace(),
+        Some(name.as_bytes())
+    );
+    assert_eq!(
+        server.local_addr().unwrap().as_abstract_namespace(),
+        Some(name.as_bytes())
+    );
+
+    client.write_all(b"hello").await.0.unwrap();
+    let (res, buf) = server.read_exact(vec![0; 5]).await;
+    res.unwrap();
+    assert_eq!(buf, b"hello");
+
+    let long = vec![b'a'; 108];
+    assert_eq!(
+        SocketAddr::from_abstract_name(&long).err().unwrap().kind(),
+        std::io::ErrorKind::InvalidInput
+    );
+}
+
+#[monoio::test_all]
+async fn pair() {
+    let (mut a, mut b) = UnixStream::pair().unwrap();
+    assert!(a.local_addr().unwrap().is_unnamed());
+    assert!(b.peer_addr().unwrap().is_unnamed());
+
+    // Each half is driven by its own task.
+    let echo = monoio::spawn(async move {
+        let (res, buf) = b.read_exact(vec![0; 4]).await;
+        res.unwrap();
+        b.write_all(buf).await.0.unwrap();
+        b
+    });
+    a.write_all(b"ping").await.0.unwrap();
+    let (res, buf) = a.read_exact(vec![0; 4]).await;
+    res.unwrap();
+    assert_eq!(buf, b"ping");
+
+    drop(echo.await);
+    let (res, _) = a.read(vec![0; 4]).await;
+    assert_eq!(res.unwrap(), 0);
+}
+
+#[test]
+fn pair_outside_runtime() {
+    let err = UnixStream::pair().err().unwrap();
+    assert_eq!(err.kind(), std::io::ErrorKind::Other);
+    let err = monoio::net::UnixDatagram::pair().err().unwrap();
+    assert_eq!(err.kind(), std::io::ErrorKind::Other);
+}
@@ -42,3 +42,129 @@ async fn addr_type() -> std::io::Result<()> {
     assert_eq!(_res.unwrap().1.as_pathname(), Some(sock_path1.as_path()));
     Ok(())
 }
+
+#[monoio::test_all]
+async fn send_vectored() -> std::io::Result<()> {
+    let (dgram1, dgram2) = UnixDatagram::pair()?;
+
+    let buf_vec: monoio::buf::VecBuf = vec![b"hello ".to_vec(), b"world".to_vec()].into();
+    let (res, _) = dgram1.send_vectored(buf_vec).await;
+    assert_eq!(res.unwrap(), 11);
+    let (res, buf) = dgram2.recv(vec![0; 100]).await;
+    assert_eq!(res.unwrap(), 11);
+    assert_eq!(buf, b"hello world");
+    Ok(())
+}
+
+#[monoio::test_all]
+async fn recv_vectored() {
+    let (sock1, sock2) = UnixDatagram::pair().unwrap();
+
+    // A QUIC-like packet with the header and the payload in separate buffers.
+    sock1.send("headpayload").await.0.unwrap();
+    let buf_vec: monoio::buf::VecBuf = vec![vec![0; 4], vec![0; 16]].into();
+    let (res, buf_vec) = sock2.recv_vectored(buf_vec).await;
+    assert_eq!(res.unwrap(), 11);
+    let raw: Vec<Vec<u8>> = buf_vec.into();
+    assert_eq!(raw, vec![b"head".to_vec(), b"payload".to_vec()]);
+
+    sock1.send("foo").await.0.unwrap();
+    let buf_vec: monoio::buf::VecBuf = vec![vec![0; 4], vec![0; 16]].into();
+    let (res, buf_vec) = sock2.recv_vectored(buf_vec).await;
+    assert_eq!(res.unwrap(), 3);
+    let raw: Vec<Vec<u8>> = buf_vec.into();
+    assert_eq!(raw, vec![b"foo".to_vec(), vec![]]);
+}
+
+#[monoio::test_all]
+async fn pair_boundaries() {
+    let (sock1, sock2) = UnixDatagram::pair().unwrap();
+    assert!(sock1.local_addr().unwrap().is_unnamed());
+    assert!(sock1.peer_addr().unwrap().is_unnamed());
+
+    for msg in ["foo", "", "barbaz"] {
+        sock1.send(msg).await.0.unwrap();
+    }
+    for msg in ["foo", "", "barbaz"] {
+        let (res, buf) = sock2.recv(vec![0; 16]).await;
+        assert_eq!(res.unwrap(), msg.len());
+        assert_eq!(&buf[..], msg.as_bytes());
+    }
+    // The rest of a datagram larger than the buffer is discarded.
+    sock2.send("hello world").await.0.unwrap();
+    sock2.send("next").await.0.unwrap();
+    let (res, buf) = sock1.recv(vec![0; 5]).await;
+    assert_eq!(res.unwrap(), 5);
+    assert_eq!(buf, b"hello");
+    let (res, buf) = sock1.recv(vec![0; 16]).await;
+    assert_eq!(res.unwrap(), 4);
+    assert_eq!(buf, b"next");
+}
+
+#[monoio::test_all(timer_enabled = true)]
+async fn send_to_dropped_peer() {
+    // The fd is closed asynchronously on uring driver.
+    let close = || monoio::time::sleep(std::time::Duration::from_millis(10));
+
+    let (sock1, sock2) = UnixDatagram::pair().unwrap();
+    drop(sock2);
+    close().await;
+    let (res, _) = sock1.send("foo").await;
+    assert_eq!(res.unwrap_err().raw_os_error(), Some(libc::ECONNREFUSED));
+
+    let dir = tempfile::Builder::new()
+        .prefix("monoio-unix-datagram-tests")
+        .tempdir()
+        .unwrap();
+    let sock_path = dir.path().join("dropped.sock");
+    let server = UnixDatagram::bind(&sock_path).unwrap();
+    let client = UnixDatagram::connect(&sock_path).await.unwrap();
+    client.send("foo").await.0.unwrap();
+    drop(server);
+    close().await;
+    let (res, _) = client.send("bar").await;
+    assert_eq!(res.unwrap_err().raw_os_error(), Some(libc::ECONNREFUSED));
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[monoio::test_all]
+async fn abstract_namespace() {
+    use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+
+    use monoio::net::unix::SocketAddr;
+
+    let name = |side: &str| {
+        format!(
+            "monoio-unix-datagram-tests-{}-{:?}-{side}",
+            std::process::id(),
+            std::thread::current().id()
+        )
+    };
+    let (server_name, client_name) = (name("server"), name("client"));
+    let server_addr = SocketAddr::from_abstract_name(server_name.as_bytes()).unwrap();
+    let client_addr = SocketAddr::from_abstract_name(client_name.as_bytes()).unwrap();
+    let server = UnixDatagram::bind_addr(&server_addr).unwrap();
+    let client = UnixDatagram::bind_addr(&client_addr).unwrap();
+
+    // The leading null byte of a path is taken as an abstract name too.
+    let mut path = vec![0];
+    path.extend_from_slice(server_name.as_bytes());
+    client
+        .send_to("hello", OsStr::from_bytes(&path))
+        .await
+        .0
+        .unwrap();
+    let (res, buf) = server.recv_from(vec![0; 16]).await;
+    let (n, from) = res.unwrap();
+    assert_eq!(&buf[..n], b"hello");
+    assert_eq!(from.as_abstract_namespace(), Some(client_name.as_bytes()));
+
+    let connected = UnixDatagram::connect_addr(server_addr).await.unwrap();
+    assert_eq!(
+        connected.peer_addr().unwrap().as_abstract_namespace(),
+        Some(server_name.as_bytes())
+    );
+    connected.send("world").await.0.unwrap();
+    let (res, buf) = server.recv(vec![0; 16]).await;
+    assert_eq!(&buf[..res.unwrap()], b"world");
+}
@@ -20,3 +20,31 @@ async fn test_seqpacket() -> std::io::Result<()> {
     conn.send(b"hello").await.0.unwrap();
     Ok(())
 }
+
+#[cfg(target_os = "linux")]
+#[monoio::test_all]
+async fn test_seqpacket_records() {
+    use monoio::net::unix::UnixSeqpacket;
+
+    let (a, b) = UnixSeqpacket::pair().unwrap();
+    for msg in ["foo", "barbaz", "qux"] {
+        assert_eq!(a.send(msg).await.0.unwrap(), msg.len());
+    }
+    // Each recv returns exactly one record.
+    let (res, buf) = b.recv(vec![0; 16]).await;
+    assert_eq!(res.unwrap(), 3);
+    assert_eq!(buf, b"foo");
+
+    // The rest of a truncated record is discarded.
+    let (res, buf) = b.recv_full(vec![0; 3]).await;
+    assert_eq!(res.unwrap(), (3, 6));
+    assert_eq!(buf, b"bar");
+    let (res, buf) = b.recv_full(vec![0; 16]).await;
+    assert_eq!(res.unwrap(), (3, 3));
+    assert_eq!(buf, b"qux");
+
+    // EOF is reported as 0 once the peer is closed.
+    drop(a);
+    let (res, _) = b.recv(vec![0; 16]).await;
+    assert_eq!(res.unwrap(), 0);
+}
@@ -0,0 +1,356 @@
+#![cfg(all(target_os = "linux", feature = "iouring"))]
+
+use monoio::{
+    io::{AsyncReadRentExt, AsyncWriteRentExt},
+    net::{TcpListener, TcpStream},
+    IoUringDriver, RuntimeBuilder,
+};
+
+async fn echo() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let server = monoio::spawn(async move {
+        let (mut conn, _) = listener.accept().await.unwrap();
+        let (res, buf) = conn.read_exact(vec![0; 5]).await;
+        res.unwrap();
+        let (res, _) = conn.write_all(buf).await;
+        res.unwrap();
+    });
+
+    let mut conn = TcpStream::connect(addr).await.unwrap();
+    // Let the driver park, and with SQPOLL the polling thread fall asleep.
+    monoio::time::sleep(std::time::Duration::from_millis(50)).await;
+    let (res, _) = conn.write_all(b"hello").await;
+    res.unwrap();
+    let (res, buf) = conn.read_exact(vec![0; 5]).await;
+    res.unwrap();
+    assert_eq!(buf, b"hello");
+    server.await;
+}
+
+#[test]
+fn sqpoll() {
+    let mut rt = match RuntimeBuilder::<IoUringDriver>::new()
+        .uring_sqpoll(10)
+        .enable_timer()
+        .build()
+    {
+        Ok(rt) => rt,
+        // SQPOLL is not permitted in this environment.
+        Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => return,
+        Err(e) => panic!("{e}"),
+    };
+    rt.block_on(echo());
+}
+
+#[test]
+fn without_taskrun_flags() {
+    let mut rt = RuntimeBuilder::<IoUringDriver>::new()
+        .uring_taskrun_flags(false)
+        .enable_timer()
+        .build()
+        .unwrap();
+    rt.block_on(echo());
+}
+
+#[test]
+fn cq_entries() {
+    let mut rt = RuntimeBuilder::<IoUringDriver>::new()
+        .with_entries(300)
+        .uring_cq_entries(4096)
+        .build()
+        .unwrap();
+    rt.block_on(async {
+        // The sq entries are rounded up to a power of two.
+        assert_eq!(monoio::utils::uring_entries(), Some((512, 4096)));
+    });
+    assert_eq!(monoio::utils::uring_entries(), None);
+
+    let err = RuntimeBuilder::<IoUringDriver>::new()
+        .uring_cq_entries(1000)
+        .build()
+        .err()
+        .unwrap();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+    let err = RuntimeBuilder::<IoUringDriver>::new()
+        .uring_cq_entries(512)
+        .build()
+        .err()
+        .unwrap();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+}
+
+#[test]
+fn disabled_opcodes() {
+    use io_uring::opcode;
+
+    let disabled = [
+        opcode::OpenAt::CODE,
+        opcode::Write::CODE,
+        opcode::Read::CODE,
+        opcode::Statx::CODE,
+        opcode::UnlinkAt::CODE,
+    ];
+    let mut rt = RuntimeBuilder::<IoUringDriver>::new()
+        .disable_uring_opcodes(&disabled)
+        .build()
+        .unwrap();
+    rt.block_on(async move {
+        for code in disabled {
+            assert!(!monoio::utils::is_uring_op_supported(code));
+        }
+        assert!(monoio::utils::is_uring_op_supported(opcode::Nop::CODE));
+
+        // The file ops run in the legacy way.
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("file");
+        let (res, _) = monoio::fs::write(&path, b"hello").await;
+        res.unwrap();
+        assert_eq!(monoio::fs::read(&path).await.unwrap(), b"hello");
+        assert_eq!(monoio::fs::metadata(&path).await.unwrap().len(), 5);
+        monoio::fs::remove_file(&path).await.unwrap();
+        assert!(!path.exists());
+    });
+}
+
+// Without `poll-io` the ops of unsupported opcodes which wait for readiness
+// are still submitted to the ring.
+#[cfg(feature = "poll-io")]
+#[test]
+fn disabled_msg_opcodes() {
+    use io_uring::opcode;
+
+    let disabled = [opcode::SendMsg::CODE, opcode::RecvMsg::CODE];
+    let mut rt = RuntimeBuilder::<IoUringDriver>::new()
+        .disable_uring_opcodes(&disabled)
+        .build()
+        .unwrap();
+    rt.block_on(async {
+        // The batches are not linked in the ring, they are sent and received
+        // with the poller.
+        let receiver = monoio::net::udp::UdpSocket::bind("127.0.0.1:0").unwrap();
+        let addr = receiver.local_addr().unwrap();
+        let socket = monoio::net::udp::UdpSocket::bind("127.0.0.1:0").unwrap();
+        let msgs = (0..4u8).map(|i| (vec![i; 4], addr)).collect();
+        let (res, _) = socket.send_batch(msgs).await;
+        assert_eq!(res.unwrap(), 4);
+        let (res, bufs) = receiver.recv_batch(vec![vec![0; 8]; 4]).await;
+        assert_eq!(res.unwrap(), 4);
+        for (i, (buf, n, _)) in bufs.iter().enumerate() {
+            assert_eq!(&buf[..*n], &[i as u8; 4]);
+        }
+    });
+}
+
+#[test]
+fn sq_full() {
+    let mut rt = RuntimeBuilder::<IoUringDriver>::new()
+        .with_entries(256)
+        .build()
+        .unwrap();
+    rt.block_on(async {
+        // All the tasks submit in one tick, most of them wait for the space
+        // of the submission queue.
+        let tasks: Vec<_> = (0..1024)
+            .map(|_| monoio::spawn(monoio::utils::ring_ping()))
+            .collect();
+        for task in tasks {
+            task.await.unwrap();
+        }
+        assert!(monoio::utils::uring_sq_full_events().unwrap() > 0);
+        echo_without_timer().await;
+    });
+}
+
+#[test]
+fn sq_full_links() {
+    let mut rt = RuntimeBuilder::<IoUringDriver>::new()
+        .with_entries(256)
+        .build()
+        .unwrap();
+    rt.block_on(async {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        // Each connect is linked to its timeout, the chains wait for the
+        // space of the submission queue along with the nops.
+        let connects: Vec<_> = (0..256)
+            .map(|_| {
+                monoio::spawn(TcpStream::connect_timeout(
+                    addr,
+                    std::time::Duration::from_secs(5),
+                ))
+            })
+            .collect();
+        let pings: Vec<_> = (0..1024)
+            .map(|_| monoio::spawn(monoio::utils::ring_ping()))
+            .collect();
+        for task in connects {
+            task.await.unwrap();
+        }
+        for task in pings {
+            task.await.unwrap();
+        }
+        assert!(monoio::utils::uring_sq_full_events().unwrap() > 0);
+        drop(listener);
+        echo_without_timer().await;
+    });
+}
+
+async fn echo_without_timer() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let server = monoio::spawn(async move {
+        let (mut conn, _) = listener.accept().await.unwrap();
+        let (res, buf) = conn.read_exact(vec![0; 5]).await;
+        res.unwrap();
+        conn.write_all(buf).await.0.unwrap();
+    });
+    let mut conn = TcpStream::connect(addr).await.unwrap();
+    conn.write_all(b"hello").await.0.unwrap();
+    let (res, buf) = conn.read_exact(vec![0; 5]).await;
+    res.unwrap();
+    assert_eq!(buf, b"hello");
+    server.await;
+}
+
+#[test]
+fn cq_overflow() {
+    let mut rt = RuntimeBuilder::<IoUringDriver>::new()
+        .with_entries(256)
+        .uring_cq_entries(512)
+        .build()
+        .unwrap();
+    rt.block_on(async {
+        // The nops are submitted in one park without reaping, so most of the
+        // completions overflow the queue.
+        let tasks: Vec<_> = (0..8192)
+            .map(|_| monoio::spawn(monoio::utils::ring_ping()))
+            .collect();
+        for task in tasks {
+            task.await.unwrap();
+        }
+        assert!(monoio::utils::uring_cq_overflow_events().unwrap() > 0);
+        echo_without_timer().await;
+    });
+}
+
+#[test]
+fn attach_wq_group() {
+    let group = monoio::RuntimeGroup::new();
+    assert_eq!(group.ring_fd(), None);
+    let threads: Vec<_> = (0..3)
+        .map(|_| {
+            let group = group.clone();
+            std::thread::spawn(move || {
+                let mut rt = RuntimeBuilder::<IoUringDriver>::new()
+                    .attach_wq_group(&group)
+                    .build()
+                    .unwrap();
+                rt.block_on(echo_without_timer());
+            })
+        })
+        .collect();
+    for thread in threads {
+        thread.join().unwrap();
+    }
+    // The ring is kept by the group after the runtimes are dropped.
+    let fd = group.ring_fd().unwrap();
+    let mut rt = RuntimeBuilder::<IoUringDriver>::new()
+        .attach_wq(fd)
+        .build()
+        .unwrap();
+    rt.block_on(echo_without_timer());
+}
+
+#[test]
+fn attach_wq_fallback() {
+    // Not a ring, the runtime is built with its own workqueue.
+    let file = std::fs::File::open("/dev/null").unwrap();
+    let mut rt = RuntimeBuilder::<IoUringDriver>::new()
+        .attach_wq(std::os::unix::io::AsRawFd::as_raw_fd(&file))
+        .build()
+        .unwrap();
+    rt.block_on(echo_without_timer());
+}
+
+const NET_OPS: &[monoio::AllowedOp] = &[
+    monoio::AllowedOp::Accept,
+    monoio::AllowedOp::Connect,
+    monoio::AllowedOp::Read,
+    monoio::AllowedOp::Write,
+    monoio::AllowedOp::Recv,
+    monoio::AllowedOp::Send,
+    monoio::AllowedOp::Close,
+];
+
+#[test]
+fn uring_restrictions() {
+    let mut rt = RuntimeBuilder::<IoUringDriver>::new()
+        .uring_restrictions(NET_OPS)
+        .build()
+        .unwrap();
+    rt.block_on(async move {
+        echo_without_timer().await;
+
+        let err = monoio::fs::File::open("/dev/null").await.unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::PermissionDenied);
+        assert!(!monoio::utils::is_uring_op_supported(
+            io_uring::opcode::OpenAt::CODE
+        ));
+        let err = monoio::utils::ring_ping().await.unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::PermissionDenied);
+    });
+}
+
+#[test]
+fn uring_restricted_fallback() {
+    let mut rt = RuntimeBuilder::<IoUringDriver>::new()
+        .uring_restrictions(NET_OPS)
+        .uring_restricted_fallback(&[monoio::AllowedOp::Open, monoio::AllowedOp::Statx])
+        .build()
+        .unwrap();
+    rt.block_on(async move {
+        echo_without_timer().await;
+
+        // Open and statx are syscalls, reading and closing go through the
+        // ring.
+        let mut tempfile = tempfile::NamedTempFile::new().unwrap();
+        std::io::Write::write_all(&mut tempfile, b"hello").unwrap();
+        assert_eq!(monoio::fs::read(tempfile.path()).await.unwrap(), b"hello");
+        let err = monoio::fs::remove_file(tempfile.path()).await.unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::PermissionDenied);
+    });
+}
+
+#[test]
+fn uring_napi() {
+    let mut rt = RuntimeBuilder::<IoUringDriver>::new()
+        .uring_napi(50, false)
+        .build()
+        .unwrap();
+    rt.block_on(echo_without_timer());
+
+    // The runtime spins on the completion queue while the polling thread
+    // busy polls.
+    let mut rt = match RuntimeBuilder::<IoUringDriver>::new()
+        .uring_sqpoll(1000)
+        .uring_napi(50, true)
+        .enable_timer()
+        .build()
+    {
+        Ok(rt) => rt,
+        Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => return,
+        Err(e) => panic!("{e}"),
+    };
+    rt.block_on(echo());
+}
+
+#[test]
+fn without_registered_ring_fd() {
+    let mut rt = RuntimeBuilder::<IoUringDriver>::new()
+        .uring_register_ring_fd(false)
+        .enable_timer()
+        .build()
+        .unwrap();
+    rt.block_on(echo());
+}
@@ -61,3 +61,171 @@ async fn zero_copy_for_uds() {
     assert_eq!(zero_copy(&mut rx, &mut tx).await.unwrap(), MSG.len() as u64);
     c_tx.closed().await;
 }
+
+#[monoio::test_all]
+async fn write_zc_for_tcp() {
+    use monoio::{
+        buf::IoBuf,
+        io::AsyncReadRentExt,
+        net::{TcpListener, TcpStream},
+    };
+
+    const LEN: usize = 64 * 1024;
+    let srv = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = srv.local_addr().unwrap();
+    monoio::spawn(async move {
+        let mut stream = TcpStream::connect(&addr).await.unwrap();
+        let mut buf = vec![0xAB; LEN];
+        let mut written = 0;
+        while written < LEN {
+            let (res, slice) = stream.write_zc(buf.slice(written..)).await;
+            written += res.unwrap();
+            buf = slice.into_inner();
+        }
+    });
+    let (mut conn, _) = srv.accept().await.unwrap();
+    let (res, buf) = conn.read_exact(vec![0; LEN]).await;
+    assert_eq!(res.unwrap(), LEN);
+    assert!(buf.iter().all(|b| *b == 0xAB));
+}
+
+#[cfg(all(target_os = "linux", feature = "splice"))]
+#[monoio::test_all]
+async fn splice_for_tcp() {
+    use monoio::{
+        io::{new_pipe, splice, AsyncReadRentExt, AsyncWriteRent, AsyncWriteRentExt, Splitable},
+        net::{TcpListener, TcpStream},
+    };
+
+    const MSG: &[u8] = b"splice through pipe";
+    let srv = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = srv.local_addr().unwrap();
+    let (tx, rx) = local_sync::oneshot::channel();
+    monoio::spawn(async move {
+        let mut stream = TcpStream::connect(&addr).await.unwrap();
+        for _ in 0..2 {
+            stream.write_all(MSG).await.0.unwrap();
+            let (res, buf) = stream.read_exact(vec![0; MSG.len()]).await;
+            res.unwrap();
+            assert_eq!(buf, MSG);
+        }
+        stream.shutdown().await.unwrap();
+        assert!(tx.send(()).is_ok());
+    });
+    let (mut conn, _) = srv.accept().await.unwrap();
+
+    // Echo with a pipe explicitly, splices may be partial.
+    let (mut pr, mut pw) = new_pipe().unwrap();
+    let mut moved = 0;
+    while moved < MSG.len() {
+        let n = pw
+            .splice_from(&mut conn, (MSG.len() - moved) as u32)
+            .await
+            .unwrap();
+        assert!(n > 0);
+        let mut left = n;
+        while left > 0 {
+            left -= pr.splice_to(&mut conn, left).await.unwrap();
+        }
+        moved += n as usize;
+    }
+
+    // Echo with splice until EOF, which is reported with 0.
+    let (mut rd, mut wr) = conn.into_split();
+    let mut moved = 0;
+    loop {
+        let n = splice(&mut rd, &mut wr, 1024).await.unwrap();
+        if n == 0 {
+            break;
+        }
+        moved += n as usize;
+    }
+    assert_eq!(moved, MSG.len());
+    rx.await.unwrap();
+}
+
+#[cfg(all(target_os = "linux", feature = "splice"))]
+#[monoio::test_all]
+async fn tee_for_pipe() {
+    use monoio::{
+        io::{new_pipe, AsyncReadRentExt, AsyncWriteRentExt},
+        net::{TcpListener, TcpStream},
+    };
+
+    const MSG: &[u8] = b"tee through pipe";
+    let srv = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = srv.local_addr().unwrap();
+    monoio::spawn(async move {
+        let mut stream = TcpStream::connect(&addr).await.unwrap();
+        stream.write_all(MSG).await.0.unwrap();
+        let (res, buf) = stream.read_exact(vec![0; MSG.len() * 2]).await;
+        res.unwrap();
+        assert_eq!(buf, MSG.repeat(2));
+    });
+    let (mut conn, _) = srv.accept().await.unwrap();
+
+    let (mut ar, mut aw) = new_pipe().unwrap();
+    let (mut br, mut bw) = new_pipe().unwrap();
+    let mut moved = 0;
+    while moved < MSG.len() {
+        moved += aw.splice_from(&mut conn, MSG.len() as u32).await.unwrap() as usize;
+    }
+    // The data is duplicated to the other pipe and kept in the source one.
+    assert_eq!(
+        ar.tee_to(&mut bw, moved as u32).await.unwrap(),
+        moved as u32
+    );
+    for pipe in [&mut ar, &mut br] {
+        let mut left = moved as u32;
+        while left > 0 {
+            left -= pipe.splice_to(&mut conn, left).await.unwrap();
+        }
+    }
+}
+
+#[cfg(all(target_os = "linux", feature = "splice"))]
+#[monoio::test_all]
+async fn send_file_to_tcp() {
+    use std::io::Write;
+
+    use monoio::{fs::File, io::AsyncReadRentExt, net::TcpStream};
+
+    let content = (0..256 * 1024).map(|i| i as u8).collect::<Vec<_>>();
+    let mut tempfile = tempfile::NamedTempFile::new().unwrap();
+    tempfile.write_all(&content).unwrap();
+    let file = File::open(tempfile.path()).await.unwrap();
+
+    let srv = monoio::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = srv.local_addr().unwrap();
+    let mut client = TcpStream::connect(&addr).await.unwrap();
+    let (mut conn, _) = srv.accept().await.unwrap();
+
+    let offset = 100;
+    let expected = content.len() - offset;
+    let reader = monoio::spawn(async move {
+        let (res, buf) = conn.read_exact(vec![0; expected]).await;
+        res.unwrap();
+        buf
+    });
+    // The file is shorter than requested, it stops at the end of the file.
+    let n = file
+        .send_to(&mut client, offset as u64, content.len())
+        .await
+        .unwrap();
+    assert_eq!(n, expected);
+    assert_eq!(reader.await, &content[offset..]);
+
+    // The peer has closed, the error is propagated.
+    drop(client);
+    let mut client = TcpStream::connect(&addr).await.unwrap();
+    drop(srv.accept().await.unwrap());
+    let err = loop {
+        if let Err(e) = file.send_to(&mut client, 0, content.len()).await {
+            break e;
+        }
+    };
+    assert!(matches!(
+        err.kind(),
+        std::io::ErrorKind::BrokenPipe | std::io::ErrorKind::ConnectionReset
+    ));
+}